@@ -0,0 +1,12 @@
+schema: '1.2'
+contents:
+  - components/model_version.fga
+  - components/conditions.fga
+  - components/user.fga
+  - components/role.fga
+  - components/server.fga
+  - components/project.fga
+  - components/warehouse.fga
+  - components/namespace.fga
+  - components/lakekeeper_table.fga
+  - components/lakekeeper_view.fga
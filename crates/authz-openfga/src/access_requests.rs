@@ -0,0 +1,764 @@
+//! Just-in-time access via access requests.
+//!
+//! A user requests a relation on a warehouse, namespace, table or view for a limited duration.
+//! Anyone who may grant the requested relation can approve the request, which writes
+//! a time-bound grant for the requester that expires after the requested duration.
+use std::str::FromStr as _;
+
+use chrono::{DateTime, Utc};
+use http::StatusCode;
+use lakekeeper::{
+    api::{
+        iceberg::{types::PageToken, v1::PaginationQuery},
+        ApiContext, RequestMetadata,
+    },
+    axum::{
+        extract::{Path, Query, State as AxumState},
+        Extension, Json,
+    },
+    service::{
+        AccessRequest, AccessRequestId, AccessRequestStatus, Actor, CatalogNamespaceOps,
+        CatalogStore, CatalogTabularOps, CatalogWarehouseOps, ErrorModel, NamespaceId,
+        NewAccessRequest, Result, SecretStore, State, TableId, TabularListFlags, Transaction,
+        UserId, ViewId,
+    },
+    WarehouseId,
+};
+use openfga_client::client::{CheckRequestTupleKey, ReadRequestTupleKey};
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+
+use super::{
+    api::checked_write,
+    relations::{
+        APINamespaceRelation as NamespaceRelation, APITableRelation as TableRelation,
+        APIViewRelation as ViewRelation, APIWarehouseRelation as WarehouseRelation, Assignment,
+        GrantableRelation, NamespaceAssignment, ReducedRelation, TableAssignment, ViewAssignment,
+        WarehouseAssignment, WarehouseRelation as AllWarehouseRelation,
+    },
+    OpenFGAAuthorizer, OpenFGAError, OpenFGAResult,
+};
+use crate::{conditions::grant_expires_at, entities::OpenFgaEntity, FgaType, AUTH_CONFIG};
+
+#[derive(Debug, Clone, Deserialize, PartialEq, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+/// Object and relation access is requested for
+pub(super) enum AccessRequestTarget {
+    Warehouse {
+        relation: WarehouseRelation,
+    },
+    #[serde(rename_all = "kebab-case")]
+    Namespace {
+        #[schema(value_type = uuid::Uuid)]
+        namespace_id: NamespaceId,
+        relation: NamespaceRelation,
+    },
+    #[serde(rename_all = "kebab-case")]
+    Table {
+        #[schema(value_type = uuid::Uuid)]
+        table_id: TableId,
+        relation: TableRelation,
+    },
+    #[serde(rename_all = "kebab-case")]
+    View {
+        #[schema(value_type = uuid::Uuid)]
+        view_id: ViewId,
+        relation: ViewRelation,
+    },
+}
+
+impl AccessRequestTarget {
+    fn to_openfga(&self, warehouse_id: WarehouseId) -> (String, String) {
+        match self {
+            AccessRequestTarget::Warehouse { relation } => {
+                (warehouse_id.to_openfga(), relation.to_openfga().to_string())
+            }
+            AccessRequestTarget::Namespace {
+                namespace_id,
+                relation,
+            } => (namespace_id.to_openfga(), relation.to_openfga().to_string()),
+            AccessRequestTarget::Table { table_id, relation } => (
+                (warehouse_id, *table_id).to_openfga(),
+                relation.to_openfga().to_string(),
+            ),
+            AccessRequestTarget::View { view_id, relation } => (
+                (warehouse_id, *view_id).to_openfga(),
+                relation.to_openfga().to_string(),
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub(super) struct CreateAccessRequestRequest {
+    /// Object and relation to request
+    target: AccessRequestTarget,
+    /// Reason for the request, shown to approvers
+    #[serde(default)]
+    justification: Option<String>,
+    /// Number of hours the grant is valid once approved
+    duration_hours: u32,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub(super) struct AccessRequestResponse {
+    #[schema(value_type = uuid::Uuid)]
+    id: AccessRequestId,
+    #[schema(value_type = uuid::Uuid)]
+    warehouse_id: WarehouseId,
+    /// `OpenFGA` object access is requested for
+    object: String,
+    /// Requested relation on `object`
+    relation: String,
+    #[schema(value_type = String)]
+    requested_by: UserId,
+    justification: Option<String>,
+    /// Duration of the grant in seconds
+    duration_seconds: i64,
+    status: AccessRequestStatus,
+    #[schema(value_type = Option<String>)]
+    decided_by: Option<UserId>,
+    decided_at: Option<DateTime<Utc>>,
+    /// Expiry of the grant. Only set for approved requests.
+    expires_at: Option<DateTime<Utc>>,
+    created_at: DateTime<Utc>,
+}
+
+impl From<AccessRequest> for AccessRequestResponse {
+    fn from(request: AccessRequest) -> Self {
+        Self {
+            id: request.access_request_id,
+            warehouse_id: request.warehouse_id,
+            object: request.object,
+            relation: request.relation,
+            requested_by: request.requested_by,
+            justification: request.justification,
+            duration_seconds: request.duration.num_seconds(),
+            status: request.status,
+            decided_by: request.decided_by,
+            decided_at: request.decided_at,
+            expires_at: request.expires_at,
+            created_at: request.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub(super) struct ListAccessRequestsResponse {
+    access_requests: Vec<AccessRequestResponse>,
+    next_page_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct ListAccessRequestsQuery {
+    /// Only return requests with this status
+    #[serde(default)]
+    status: Option<AccessRequestStatus>,
+    /// Next page token
+    #[serde(default)]
+    page_token: Option<String>,
+    /// Signals an upper bound of the number of results that a client will receive.
+    #[serde(default)]
+    page_size: Option<i64>,
+}
+
+impl ListAccessRequestsQuery {
+    fn pagination_query(&self) -> PaginationQuery {
+        PaginationQuery {
+            page_token: self
+                .page_token
+                .clone()
+                .map_or(PageToken::Empty, PageToken::Present),
+            page_size: self.page_size,
+        }
+    }
+}
+
+/// Request temporary access to an object in a warehouse
+#[utoipa::path(
+    post,
+    tag = "permissions",
+    path = "/management/v1/permissions/warehouse/{warehouse_id}/access-requests",
+    request_body = CreateAccessRequestRequest,
+    params(
+        ("warehouse_id" = Uuid, Path, description = "Warehouse ID"),
+    ),
+    responses(
+            (status = 201, body = AccessRequestResponse),
+    )
+)]
+pub(super) async fn create_access_request<C: CatalogStore, S: SecretStore>(
+    Path(warehouse_id): Path<WarehouseId>,
+    AxumState(api_context): AxumState<ApiContext<State<OpenFGAAuthorizer, C, S>>>,
+    Extension(metadata): Extension<RequestMetadata>,
+    Json(request): Json<CreateAccessRequestRequest>,
+) -> Result<(StatusCode, Json<AccessRequestResponse>)> {
+    let requested_by = principal(metadata.actor())?;
+    let CreateAccessRequestRequest {
+        target,
+        justification,
+        duration_hours,
+    } = request;
+
+    let max_duration_hours = AUTH_CONFIG.access_request_max_duration_hours;
+    if duration_hours == 0 || duration_hours > max_duration_hours {
+        return Err(ErrorModel::bad_request(
+            format!("Duration of access requests must be between 1 and {max_duration_hours} hours. Got {duration_hours}."),
+            "InvalidAccessRequestDuration",
+            None,
+        )
+        .into());
+    }
+
+    let catalog_state = api_context.v1_state.catalog;
+    ensure_target_in_warehouse::<C>(&target, warehouse_id, catalog_state.clone()).await?;
+
+    let (object, relation) = target.to_openfga(warehouse_id);
+    let mut t = C::Transaction::begin_write(catalog_state).await?;
+    let access_request = C::create_access_request(
+        NewAccessRequest {
+            warehouse_id,
+            object: &object,
+            relation: &relation,
+            requested_by,
+            justification: justification.as_deref(),
+            duration: chrono::Duration::hours(i64::from(duration_hours)),
+        },
+        t.transaction(),
+    )
+    .await?;
+    t.commit().await?;
+
+    Ok((StatusCode::CREATED, Json(access_request.into())))
+}
+
+/// Ensure the target of an access request is an active object of `warehouse_id`.
+async fn ensure_target_in_warehouse<C: CatalogStore>(
+    target: &AccessRequestTarget,
+    warehouse_id: WarehouseId,
+    catalog_state: C::State,
+) -> Result<()> {
+    let (exists, object) = match target {
+        AccessRequestTarget::Warehouse { .. } => (
+            C::get_warehouse_by_id(warehouse_id, catalog_state)
+                .await?
+                .is_some(),
+            format!("Warehouse {warehouse_id}"),
+        ),
+        AccessRequestTarget::Namespace { namespace_id, .. } => (
+            C::get_namespace(warehouse_id, *namespace_id, catalog_state)
+                .await?
+                .is_some(),
+            format!("Namespace {namespace_id}"),
+        ),
+        AccessRequestTarget::Table { table_id, .. } => (
+            C::get_table_info(
+                warehouse_id,
+                *table_id,
+                TabularListFlags::active(),
+                catalog_state,
+            )
+            .await?
+            .is_some(),
+            format!("Table {table_id}"),
+        ),
+        AccessRequestTarget::View { view_id, .. } => (
+            C::get_view_info(
+                warehouse_id,
+                *view_id,
+                TabularListFlags::active(),
+                catalog_state,
+            )
+            .await?
+            .is_some(),
+            format!("View {view_id}"),
+        ),
+    };
+    if exists {
+        Ok(())
+    } else {
+        Err(ErrorModel::not_found(
+            format!("{object} not found in warehouse {warehouse_id}"),
+            "AccessRequestTargetNotFound",
+            None,
+        )
+        .into())
+    }
+}
+
+/// List access requests of a warehouse
+///
+/// Principals that can read the assignments of the warehouse see all requests,
+/// all other principals only see their own requests.
+#[utoipa::path(
+    get,
+    tag = "permissions",
+    path = "/management/v1/permissions/warehouse/{warehouse_id}/access-requests",
+    params(
+        ("warehouse_id" = Uuid, Path, description = "Warehouse ID"),
+        ListAccessRequestsQuery,
+    ),
+    responses(
+            (status = 200, body = ListAccessRequestsResponse),
+    )
+)]
+pub(super) async fn list_access_requests<C: CatalogStore, S: SecretStore>(
+    Path(warehouse_id): Path<WarehouseId>,
+    Query(query): Query<ListAccessRequestsQuery>,
+    AxumState(api_context): AxumState<ApiContext<State<OpenFGAAuthorizer, C, S>>>,
+    Extension(metadata): Extension<RequestMetadata>,
+) -> Result<(StatusCode, Json<ListAccessRequestsResponse>)> {
+    let authorizer = api_context.v1_state.authz;
+    let user_id = principal(metadata.actor())?;
+
+    let can_read_all = authorizer
+        .check(CheckRequestTupleKey {
+            user: metadata.actor().to_openfga(),
+            relation: AllWarehouseRelation::CanReadAssignments.to_string(),
+            object: warehouse_id.to_openfga(),
+        })
+        .await?;
+
+    let response = C::list_access_requests(
+        warehouse_id,
+        query.status,
+        (!can_read_all).then_some(user_id),
+        query.pagination_query(),
+        api_context.v1_state.catalog,
+    )
+    .await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(ListAccessRequestsResponse {
+            access_requests: response
+                .access_requests
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            next_page_token: response.next_page_token,
+        }),
+    ))
+}
+
+/// Approve an access request
+///
+/// Requires the permission to grant the requested relation on the object.
+/// The requester receives a grant that expires after the requested duration.
+#[utoipa::path(
+    post,
+    tag = "permissions",
+    path = "/management/v1/permissions/access-requests/{access_request_id}/approve",
+    params(
+        ("access_request_id" = Uuid, Path, description = "Access Request ID"),
+    ),
+    responses(
+            (status = 200, body = AccessRequestResponse),
+    )
+)]
+pub(super) async fn approve_access_request<C: CatalogStore, S: SecretStore>(
+    Path(access_request_id): Path<AccessRequestId>,
+    AxumState(api_context): AxumState<ApiContext<State<OpenFGAAuthorizer, C, S>>>,
+    Extension(metadata): Extension<RequestMetadata>,
+) -> Result<(StatusCode, Json<AccessRequestResponse>)> {
+    let decided = decide_access_request(api_context, &metadata, access_request_id, true).await?;
+    Ok((StatusCode::OK, Json(decided.into())))
+}
+
+/// Reject an access request
+///
+/// Requires the permission to grant the requested relation on the object.
+#[utoipa::path(
+    post,
+    tag = "permissions",
+    path = "/management/v1/permissions/access-requests/{access_request_id}/reject",
+    params(
+        ("access_request_id" = Uuid, Path, description = "Access Request ID"),
+    ),
+    responses(
+            (status = 200, body = AccessRequestResponse),
+    )
+)]
+pub(super) async fn reject_access_request<C: CatalogStore, S: SecretStore>(
+    Path(access_request_id): Path<AccessRequestId>,
+    AxumState(api_context): AxumState<ApiContext<State<OpenFGAAuthorizer, C, S>>>,
+    Extension(metadata): Extension<RequestMetadata>,
+) -> Result<(StatusCode, Json<AccessRequestResponse>)> {
+    let decided = decide_access_request(api_context, &metadata, access_request_id, false).await?;
+    Ok((StatusCode::OK, Json(decided.into())))
+}
+
+async fn decide_access_request<C: CatalogStore, S: SecretStore>(
+    api_context: ApiContext<State<OpenFGAAuthorizer, C, S>>,
+    metadata: &RequestMetadata,
+    access_request_id: AccessRequestId,
+    approve: bool,
+) -> Result<AccessRequest> {
+    let authorizer = api_context.v1_state.authz;
+    let catalog_state = api_context.v1_state.catalog;
+    let decided_by = principal(metadata.actor())?;
+
+    let access_request = C::get_access_request(access_request_id, catalog_state.clone())
+        .await?
+        .ok_or_else(|| access_request_not_found(access_request_id))?;
+    if access_request.status != AccessRequestStatus::Pending {
+        return Err(access_request_already_decided(access_request_id).into());
+    }
+    if &access_request.requested_by == decided_by {
+        return Err(ErrorModel::forbidden(
+            "Access requests cannot be decided by the requester",
+            "SelfApproval",
+            None,
+        )
+        .into());
+    }
+
+    let (status, expires_at) = if approve {
+        (
+            AccessRequestStatus::Approved,
+            Some(Utc::now() + access_request.duration),
+        )
+    } else {
+        (AccessRequestStatus::Rejected, None)
+    };
+
+    let mut t = C::Transaction::begin_write(catalog_state).await?;
+    let decided = C::decide_access_request(
+        access_request_id,
+        status,
+        decided_by,
+        expires_at,
+        t.transaction(),
+    )
+    .await?
+    .ok_or_else(|| access_request_already_decided(access_request_id))?;
+
+    // Write the grant before committing, so that the request stays pending if the
+    // approver is not allowed to grant the relation.
+    match object_type(&access_request.object)? {
+        FgaType::Warehouse => {
+            apply_decision::<WarehouseAssignment>(&authorizer, metadata.actor(), &decided).await
+        }
+        FgaType::Namespace => {
+            apply_decision::<NamespaceAssignment>(&authorizer, metadata.actor(), &decided).await
+        }
+        FgaType::Table => {
+            apply_decision::<TableAssignment>(&authorizer, metadata.actor(), &decided).await
+        }
+        FgaType::View => {
+            apply_decision::<ViewAssignment>(&authorizer, metadata.actor(), &decided).await
+        }
+        _ => Err(OpenFGAError::unexpected_entity(
+            vec![
+                FgaType::Warehouse,
+                FgaType::Namespace,
+                FgaType::Table,
+                FgaType::View,
+            ],
+            access_request.object.clone(),
+            "Access requests are only supported for warehouses, namespaces, tables and views"
+                .to_string(),
+        )),
+    }?;
+    t.commit().await?;
+
+    Ok(decided)
+}
+
+/// Check that `actor` may grant the requested relation and write
+/// the time-bound grant if the request was approved.
+///
+/// If the requester already holds the relation at least until the new expiry,
+/// the request counts as granted and nothing is written. A grant that expires
+/// earlier is replaced by the new one.
+async fn apply_decision<RA: Assignment>(
+    authorizer: &OpenFGAAuthorizer,
+    actor: &Actor,
+    access_request: &AccessRequest,
+) -> OpenFGAResult<()> {
+    let relation = RA::Relation::iter()
+        .find(|r| r.to_openfga().to_string() == access_request.relation)
+        .ok_or_else(|| {
+            OpenFGAError::InvalidQuery(format!(
+                "Relation `{}` cannot be requested for `{}`",
+                access_request.relation, access_request.object
+            ))
+        })?;
+    let requester = access_request.requested_by.to_openfga();
+    let assignment = || RA::try_from_user(&requester, &relation);
+    let object = &access_request.object;
+
+    let Some(expires_at) = access_request.expires_at else {
+        return ensure_may_grant(authorizer, actor, relation, object).await;
+    };
+
+    let existing = authorizer
+        .read(
+            1,
+            ReadRequestTupleKey {
+                user: assignment()?.openfga_user(),
+                relation: access_request.relation.clone(),
+                object: object.clone(),
+            },
+            None,
+        )
+        .await?
+        .tuples
+        .into_iter()
+        .next();
+    match existing.as_ref().map(grant_expires_at) {
+        Some(existing_expiry) if existing_expiry.is_none_or(|e| e >= expires_at) => {
+            return ensure_may_grant(authorizer, actor, relation, object).await;
+        }
+        // `OpenFGA` does not overwrite existing tuples
+        Some(_) => {
+            checked_write(
+                authorizer.clone(),
+                actor,
+                vec![],
+                vec![assignment()?],
+                None,
+                object,
+            )
+            .await?;
+        }
+        None => {}
+    }
+    checked_write(
+        authorizer.clone(),
+        actor,
+        vec![assignment()?],
+        vec![],
+        Some(expires_at),
+        object,
+    )
+    .await
+}
+
+async fn ensure_may_grant<R: GrantableRelation>(
+    authorizer: &OpenFGAAuthorizer,
+    actor: &Actor,
+    relation: R,
+    object: &str,
+) -> OpenFGAResult<()> {
+    let grant_relation = relation.grant_relation().to_string();
+    let allowed = authorizer
+        .check(CheckRequestTupleKey {
+            user: actor.to_openfga(),
+            relation: grant_relation.clone(),
+            object: object.to_string(),
+        })
+        .await?;
+    if allowed {
+        Ok(())
+    } else {
+        Err(OpenFGAError::Unauthorized {
+            user: actor.to_openfga(),
+            relation: grant_relation,
+            object: object.to_string(),
+        })
+    }
+}
+
+fn object_type(object: &str) -> OpenFGAResult<FgaType> {
+    let (r#type, _) = object
+        .split_once(':')
+        .ok_or_else(|| OpenFGAError::InvalidEntity(object.to_string()))?;
+    FgaType::from_str(r#type).map_err(|e| OpenFGAError::UnknownType(e.to_string()))
+}
+
+fn principal(actor: &Actor) -> OpenFGAResult<&UserId> {
+    match actor {
+        Actor::Principal(user_id)
+        | Actor::Role {
+            principal: user_id, ..
+        } => Ok(user_id),
+        Actor::Anonymous => Err(OpenFGAError::AuthenticationRequired),
+    }
+}
+
+fn access_request_not_found(access_request_id: AccessRequestId) -> ErrorModel {
+    ErrorModel::not_found(
+        format!("Access request {access_request_id} not found"),
+        "AccessRequestNotFound",
+        None,
+    )
+}
+
+fn access_request_already_decided(access_request_id: AccessRequestId) -> ErrorModel {
+    ErrorModel::conflict(
+        format!("Access request {access_request_id} has already been decided"),
+        "AccessRequestAlreadyDecided",
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_access_request_target_deserialization() {
+        let target: AccessRequestTarget = serde_json::from_value(serde_json::json!({
+            "table": {
+                "table-id": "00000000-0000-0000-0000-000000000001",
+                "relation": "modify"
+            }
+        }))
+        .unwrap();
+        let warehouse_id = WarehouseId::from(uuid::Uuid::nil());
+        let (object, relation) = target.to_openfga(warehouse_id);
+        assert_eq!(
+            object,
+            format!("lakekeeper_table:{warehouse_id}/00000000-0000-0000-0000-000000000001")
+        );
+        assert_eq!(relation, "modify");
+        assert_eq!(object_type(&object).unwrap(), FgaType::Table);
+    }
+
+    mod openfga_integration_tests {
+        use lakekeeper::{
+            implementations::postgres::PostgresBackend, sqlx, tests::SetupTestCatalog,
+        };
+        use openfga_client::client::TupleKey;
+
+        use super::*;
+        use crate::{
+            conditions::not_expired_condition, migration::tests::authorizer_for_empty_store,
+        };
+
+        #[sqlx::test]
+        async fn test_access_request_target_must_belong_to_warehouse(pool: sqlx::PgPool) {
+            let authorizer = authorizer_for_empty_store().await.1;
+            let (ctx, warehouse) = SetupTestCatalog::builder()
+                .pool(pool)
+                .authorizer(authorizer)
+                .build()
+                .setup()
+                .await;
+            let catalog_state = ctx.v1_state.catalog;
+
+            ensure_target_in_warehouse::<PostgresBackend>(
+                &AccessRequestTarget::Warehouse {
+                    relation: WarehouseRelation::Select,
+                },
+                warehouse.warehouse_id,
+                catalog_state.clone(),
+            )
+            .await
+            .unwrap();
+
+            let err = ensure_target_in_warehouse::<PostgresBackend>(
+                &AccessRequestTarget::Namespace {
+                    namespace_id: NamespaceId::from(uuid::Uuid::now_v7()),
+                    relation: NamespaceRelation::Select,
+                },
+                warehouse.warehouse_id,
+                catalog_state.clone(),
+            )
+            .await
+            .unwrap_err();
+            assert_eq!(err.error.code, StatusCode::NOT_FOUND);
+            assert_eq!(err.error.r#type, "AccessRequestTargetNotFound");
+
+            let err = ensure_target_in_warehouse::<PostgresBackend>(
+                &AccessRequestTarget::Warehouse {
+                    relation: WarehouseRelation::Select,
+                },
+                WarehouseId::new_random(),
+                catalog_state,
+            )
+            .await
+            .unwrap_err();
+            assert_eq!(err.error.code, StatusCode::NOT_FOUND);
+        }
+
+        #[sqlx::test]
+        async fn test_approval_keeps_existing_grant(pool: sqlx::PgPool) {
+            let admin = UserId::new_unchecked("oidc", &uuid::Uuid::now_v7().to_string());
+            let requester = UserId::new_unchecked("oidc", &uuid::Uuid::now_v7().to_string());
+            let authorizer = authorizer_for_empty_store().await.1;
+            let (_ctx, warehouse) = SetupTestCatalog::builder()
+                .pool(pool)
+                .authorizer(authorizer.clone())
+                .user_id(Some(admin.clone()))
+                .build()
+                .setup()
+                .await;
+            let object = warehouse.warehouse_id.to_openfga();
+            let relation = AllWarehouseRelation::Select.to_string();
+            let now = Utc::now();
+            let access_request = |expires_at| AccessRequest {
+                access_request_id: AccessRequestId::from(uuid::Uuid::now_v7()),
+                warehouse_id: warehouse.warehouse_id,
+                object: object.clone(),
+                relation: relation.clone(),
+                requested_by: requester.clone(),
+                justification: None,
+                duration: chrono::Duration::hours(1),
+                status: AccessRequestStatus::Approved,
+                decided_by: Some(admin.clone()),
+                decided_at: Some(now),
+                expires_at: Some(expires_at),
+                created_at: now,
+            };
+            let grant_expiry = || async {
+                authorizer
+                    .read(
+                        1,
+                        ReadRequestTupleKey {
+                            user: requester.to_openfga(),
+                            relation: relation.clone(),
+                            object: object.clone(),
+                        },
+                        None,
+                    )
+                    .await
+                    .unwrap()
+                    .tuples
+                    .first()
+                    .map(grant_expires_at)
+            };
+            let actor = Actor::Principal(admin.clone());
+
+            authorizer
+                .write(
+                    Some(vec![TupleKey {
+                        user: requester.to_openfga(),
+                        relation: relation.clone(),
+                        object: object.clone(),
+                        condition: Some(not_expired_condition(now + chrono::Duration::hours(2))),
+                    }]),
+                    None,
+                )
+                .await
+                .unwrap();
+
+            // Already granted for longer
+            apply_decision::<WarehouseAssignment>(
+                &authorizer,
+                &actor,
+                &access_request(now + chrono::Duration::hours(1)),
+            )
+            .await
+            .unwrap();
+            let expiry = grant_expiry().await.flatten().unwrap();
+            assert!(expiry > now + chrono::Duration::hours(1));
+
+            // Extended
+            apply_decision::<WarehouseAssignment>(
+                &authorizer,
+                &actor,
+                &access_request(now + chrono::Duration::hours(3)),
+            )
+            .await
+            .unwrap();
+            let expiry = grant_expiry().await.flatten().unwrap();
+            assert!(expiry > now + chrono::Duration::hours(2));
+        }
+    }
+}
@@ -2,6 +2,7 @@
 
 use std::collections::HashSet;
 
+use chrono::{DateTime, Utc};
 use http::StatusCode;
 use lakekeeper::{
     api::{ApiContext, RequestMetadata},
@@ -24,7 +25,18 @@ use strum::IntoEnumIterator;
 use utoipa::OpenApi;
 
 use super::{
+    access_requests::{
+        __path_approve_access_request, __path_create_access_request, __path_list_access_requests,
+        __path_reject_access_request, approve_access_request, create_access_request,
+        list_access_requests, reject_access_request,
+    },
     check::{__path_check, check},
+    import_export::{
+        __path_export_project_permissions, __path_export_warehouse_permissions,
+        __path_import_project_permissions, __path_import_warehouse_permissions,
+        export_project_permissions, export_warehouse_permissions, import_project_permissions,
+        import_warehouse_permissions,
+    },
     relations::{
         APINamespaceAction as NamespaceAction, APINamespaceRelation as NamespaceRelation,
         APIProjectAction as ProjectAction, APIProjectRelation as ProjectRelation,
@@ -43,7 +55,10 @@ use super::{
     },
 };
 use crate::{
-    entities::OpenFgaEntity, models::RoleIdExt as _, OpenFGAAuthorizer, OpenFGAError, OpenFGAResult,
+    conditions::{is_expired, not_expired_condition},
+    entities::OpenFgaEntity,
+    models::RoleIdExt as _,
+    OpenFGAAuthorizer, OpenFGAError, OpenFGAResult,
 };
 
 const _MAX_ASSIGNMENTS_PER_RELATION: i32 = 200;
@@ -239,6 +254,10 @@ struct UpdateServerAssignmentsRequest {
     writes: Vec<ServerAssignment>,
     #[serde(default)]
     deletes: Vec<ServerAssignment>,
+    /// Expiry of the assignments in `writes`.
+    /// If not set, the assignments are permanent until deleted.
+    #[serde(default)]
+    expires_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, utoipa::ToSchema)]
@@ -248,6 +267,10 @@ struct UpdateProjectAssignmentsRequest {
     writes: Vec<ProjectAssignment>,
     #[serde(default)]
     deletes: Vec<ProjectAssignment>,
+    /// Expiry of the assignments in `writes`.
+    /// If not set, the assignments are permanent until deleted.
+    #[serde(default)]
+    expires_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, utoipa::ToSchema)]
@@ -257,6 +280,10 @@ struct UpdateWarehouseAssignmentsRequest {
     writes: Vec<WarehouseAssignment>,
     #[serde(default)]
     deletes: Vec<WarehouseAssignment>,
+    /// Expiry of the assignments in `writes`.
+    /// If not set, the assignments are permanent until deleted.
+    #[serde(default)]
+    expires_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, utoipa::ToSchema)]
@@ -266,6 +293,10 @@ struct UpdateNamespaceAssignmentsRequest {
     writes: Vec<NamespaceAssignment>,
     #[serde(default)]
     deletes: Vec<NamespaceAssignment>,
+    /// Expiry of the assignments in `writes`.
+    /// If not set, the assignments are permanent until deleted.
+    #[serde(default)]
+    expires_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, utoipa::ToSchema)]
@@ -275,6 +306,10 @@ struct UpdateTableAssignmentsRequest {
     writes: Vec<TableAssignment>,
     #[serde(default)]
     deletes: Vec<TableAssignment>,
+    /// Expiry of the assignments in `writes`.
+    /// If not set, the assignments are permanent until deleted.
+    #[serde(default)]
+    expires_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, utoipa::ToSchema)]
@@ -284,6 +319,10 @@ struct UpdateViewAssignmentsRequest {
     writes: Vec<ViewAssignment>,
     #[serde(default)]
     deletes: Vec<ViewAssignment>,
+    /// Expiry of the assignments in `writes`.
+    /// If not set, the assignments are permanent until deleted.
+    #[serde(default)]
+    expires_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, utoipa::ToSchema)]
@@ -293,6 +332,10 @@ struct UpdateRoleAssignmentsRequest {
     writes: Vec<RoleAssignment>,
     #[serde(default)]
     deletes: Vec<RoleAssignment>,
+    /// Expiry of the assignments in `writes`.
+    /// If not set, the assignments are permanent until deleted.
+    #[serde(default)]
+    expires_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, utoipa::ToSchema)]
@@ -1044,6 +1087,7 @@ async fn update_server_assignments<C: CatalogStore, S: SecretStore>(
         metadata.actor(),
         request.writes,
         request.deletes,
+        request.expires_at,
         &server_id,
     )
     .await?;
@@ -1075,6 +1119,7 @@ async fn update_project_assignments<C: CatalogStore, S: SecretStore>(
         metadata.actor(),
         request.writes,
         request.deletes,
+        request.expires_at,
         &project_id.to_openfga(),
     )
     .await?;
@@ -1107,6 +1152,7 @@ async fn update_project_assignments_by_id<C: CatalogStore, S: SecretStore>(
         metadata.actor(),
         request.writes,
         request.deletes,
+        request.expires_at,
         &project_id.to_openfga(),
     )
     .await?;
@@ -1139,6 +1185,7 @@ async fn update_warehouse_assignments_by_id<C: CatalogStore, S: SecretStore>(
         metadata.actor(),
         request.writes,
         request.deletes,
+        request.expires_at,
         &warehouse_id.to_openfga(),
     )
     .await?;
@@ -1171,6 +1218,7 @@ async fn update_namespace_assignments_by_id<C: CatalogStore, S: SecretStore>(
         metadata.actor(),
         request.writes,
         request.deletes,
+        request.expires_at,
         &namespace_id.to_openfga(),
     )
     .await?;
@@ -1204,6 +1252,7 @@ async fn update_table_assignments_by_id<C: CatalogStore, S: SecretStore>(
         metadata.actor(),
         request.writes,
         request.deletes,
+        request.expires_at,
         &(warehouse_id, table_id).to_openfga(),
     )
     .await?;
@@ -1237,6 +1286,7 @@ async fn update_view_assignments_by_id<C: CatalogStore, S: SecretStore>(
         metadata.actor(),
         request.writes,
         request.deletes,
+        request.expires_at,
         &(warehouse_id, view_id).to_openfga(),
     )
     .await?;
@@ -1278,6 +1328,7 @@ async fn update_role_assignments_by_id<C: CatalogStore, S: SecretStore>(
         metadata.actor(),
         request.writes,
         request.deletes,
+        request.expires_at,
         &role_id.to_openfga(),
     )
     .await?;
@@ -1302,7 +1353,11 @@ async fn update_role_assignments_by_id<C: CatalogStore, S: SecretStore>(
         (name = "permissions", description = "Manage Permissions"),
     ),
     paths(
+        approve_access_request,
         check,
+        create_access_request,
+        export_project_permissions,
+        export_warehouse_permissions,
         get_namespace_access_by_id,
         get_namespace_assignments_by_id,
         get_namespace_by_id,
@@ -1321,6 +1376,10 @@ async fn update_role_assignments_by_id<C: CatalogStore, S: SecretStore>(
         get_warehouse_access_by_id,
         get_warehouse_assignments_by_id,
         get_warehouse_by_id,
+        import_project_permissions,
+        import_warehouse_permissions,
+        list_access_requests,
+        reject_access_request,
         set_namespace_managed_access,
         set_warehouse_managed_access,
         update_namespace_assignments_by_id,
@@ -1420,6 +1479,34 @@ pub(super) fn new_v1_router<C: CatalogStore, S: SecretStore>(
             "/permissions/warehouse/{warehouse_id}/view/{view_id}/assignments",
             get(get_view_assignments_by_id).post(update_view_assignments_by_id),
         )
+        .route(
+            "/permissions/warehouse/{warehouse_id}/access-requests",
+            get(list_access_requests).post(create_access_request),
+        )
+        .route(
+            "/permissions/access-requests/{access_request_id}/approve",
+            post(approve_access_request),
+        )
+        .route(
+            "/permissions/access-requests/{access_request_id}/reject",
+            post(reject_access_request),
+        )
+        .route(
+            "/permissions/project/{project_id}/export",
+            get(export_project_permissions),
+        )
+        .route(
+            "/permissions/project/{project_id}/import",
+            post(import_project_permissions),
+        )
+        .route(
+            "/permissions/warehouse/{warehouse_id}/export",
+            get(export_warehouse_permissions),
+        )
+        .route(
+            "/permissions/warehouse/{warehouse_id}/import",
+            post(import_warehouse_permissions),
+        )
         .route("/permissions/check", post(check))
 }
 
@@ -1429,6 +1516,7 @@ async fn get_relations<RA: Assignment>(
     object: &str,
 ) -> Result<Vec<RA>> {
     let relations = query_relations.unwrap_or_else(|| RA::Relation::iter().collect());
+    let now = Utc::now();
 
     let relations = relations.iter().map(|relation| async {
         authorizer
@@ -1440,6 +1528,8 @@ async fn get_relations<RA: Assignment>(
             }))
             .await?
             .into_iter()
+            // Expired grants have no effect and are purged eventually
+            .filter(|t| !is_expired(t, now))
             .filter_map(|t| t.key)
             .map(|t| RA::try_from_user(&t.user, relation))
             .collect::<OpenFGAResult<Vec<RA>>>()
@@ -1507,17 +1597,21 @@ async fn get_allowed_actions<A: ReducedRelation + IntoEnumIterator>(
     Ok(actions)
 }
 
-async fn checked_write<RA: Assignment>(
+pub(super) async fn checked_write<RA: Assignment>(
     authorizer: OpenFGAAuthorizer,
     actor: &Actor,
     writes: Vec<RA>,
     deletes: Vec<RA>,
+    expires_at: Option<DateTime<Utc>>,
     object: &str,
 ) -> OpenFGAResult<()> {
     // Fail fast
     if actor == &Actor::Anonymous {
         return Err(OpenFGAError::AuthenticationRequired);
     }
+    if let Some(expires_at) = expires_at.filter(|e| *e <= Utc::now()) {
+        return Err(OpenFGAError::GrantExpiryInPast(expires_at));
+    }
     let all_modifications = writes.iter().chain(deletes.iter()).collect::<Vec<_>>();
     // ---------------------------- AUTHZ CHECKS ----------------------------
     let openfga_actor = actor.to_openfga();
@@ -1554,9 +1648,9 @@ async fn checked_write<RA: Assignment>(
             user: ra.openfga_user(),
             relation: ra.relation().to_openfga().to_string(),
             object: object.to_string(),
-            condition: None,
+            condition: expires_at.map(not_expired_condition),
         })
-        .collect();
+        .collect::<Vec<_>>();
     let deletes = deletes
         .into_iter()
         .map(|ra| TupleKeyWithoutCondition {
@@ -1565,7 +1659,22 @@ async fn checked_write<RA: Assignment>(
             object: object.to_string(),
         })
         .collect();
-    authorizer.write(Some(writes), Some(deletes)).await
+    let expiring = expires_at.map(|expires_at| {
+        let keys = writes
+            .iter()
+            .map(|k| TupleKeyWithoutCondition {
+                user: k.user.clone(),
+                relation: k.relation.clone(),
+                object: k.object.clone(),
+            })
+            .collect::<Vec<_>>();
+        (expires_at, keys)
+    });
+    authorizer.write(Some(writes), Some(deletes)).await?;
+    if let Some((expires_at, keys)) = expiring {
+        authorizer.expiring_grants.track(expires_at, keys);
+    }
+    Ok(())
 }
 
 async fn get_managed_access<T: OpenFgaEntity>(
@@ -1693,6 +1802,7 @@ mod tests {
                 &Actor::Principal(user_id.clone()),
                 vec![RoleAssignment::Assignee(role_id.into())],
                 vec![],
+                None,
                 &role_id.to_openfga(),
             )
             .await;
@@ -1994,6 +2104,7 @@ mod tests {
                 &Actor::Principal(user1_id.clone()),
                 vec![ServerAssignment::Admin(user2_id.into())],
                 vec![],
+                None,
                 &openfga_server,
             )
             .await
@@ -2006,6 +2117,73 @@ mod tests {
             assert_eq!(relations.len(), 2);
         }
 
+        #[tokio::test]
+        async fn test_checked_write_with_expiry() {
+            let (_, authorizer) = authorizer_for_empty_store().await;
+
+            let owner_id = UserId::new_unchecked("oidc", &Uuid::now_v7().to_string());
+            let user_id = UserId::new_unchecked("oidc", &Uuid::now_v7().to_string());
+            let warehouse_id = WarehouseId::new_random();
+
+            authorizer
+                .write(
+                    Some(vec![TupleKey {
+                        user: owner_id.to_openfga(),
+                        relation: WarehouseRelation::Ownership.to_openfga().to_string(),
+                        object: warehouse_id.to_openfga(),
+                        condition: None,
+                    }]),
+                    None,
+                )
+                .await
+                .unwrap();
+
+            let err = checked_write(
+                authorizer.clone(),
+                &Actor::Principal(owner_id.clone()),
+                vec![WarehouseAssignment::Modify(user_id.clone().into())],
+                vec![],
+                Some(Utc::now() - chrono::Duration::hours(1)),
+                &warehouse_id.to_openfga(),
+            )
+            .await
+            .unwrap_err();
+            assert!(matches!(err, OpenFGAError::GrantExpiryInPast(_)));
+
+            checked_write(
+                authorizer.clone(),
+                &Actor::Principal(owner_id.clone()),
+                vec![WarehouseAssignment::Modify(user_id.clone().into())],
+                vec![],
+                Some(Utc::now() + chrono::Duration::hours(1)),
+                &warehouse_id.to_openfga(),
+            )
+            .await
+            .unwrap();
+
+            let relations: Vec<WarehouseAssignment> = get_relations(
+                authorizer.clone(),
+                Some(vec![WarehouseRelation::Modify]),
+                &warehouse_id.to_openfga(),
+            )
+            .await
+            .unwrap();
+            assert_eq!(
+                relations,
+                vec![WarehouseAssignment::Modify(user_id.clone().into())]
+            );
+
+            let allowed = authorizer
+                .check(CheckRequestTupleKey {
+                    user: user_id.to_openfga(),
+                    relation: AllWarehouseRelation::Modify.to_string(),
+                    object: warehouse_id.to_openfga(),
+                })
+                .await
+                .unwrap();
+            assert!(allowed);
+        }
+
         #[tokio::test]
         async fn test_assign_to_role() {
             let (_, authorizer) = authorizer_for_empty_store().await;
@@ -2035,6 +2213,7 @@ mod tests {
                     RoleAssignment::Assignee(role_id_2.into()),
                 ],
                 vec![],
+                None,
                 &role_id_1.to_openfga(),
             )
             .await
@@ -2078,6 +2257,7 @@ mod tests {
                     ProjectAssignment::DataAdmin(UserOrRole::User(user_id_assignee.clone())),
                 ],
                 vec![],
+                None,
                 &project_id.to_openfga(),
             )
             .await
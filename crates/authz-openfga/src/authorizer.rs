@@ -1,5 +1,6 @@
 use std::{collections::HashSet, sync::Arc};
 
+use axum_prometheus::metrics;
 use futures::future::try_join_all;
 use lakekeeper::{
     api::{ApiContext, IcebergErrorResponse, RequestMetadata},
@@ -28,6 +29,7 @@ use openfga_client::{
 use utoipa::OpenApi as _;
 
 use crate::{
+    conditions::{check_context, ExpiringGrants},
     entities::{OpenFgaEntity, ParseOpenFgaEntity},
     error::{
         BatchCheckError, MissingItemInBatchCheck, OpenFGABackendUnavailable, OpenFGAError,
@@ -43,11 +45,23 @@ use crate::{
 
 type AuthorizerResult<T> = std::result::Result<T, IcebergErrorResponse>;
 
+const METRIC_REQUEST_DURATION: &str = "lakekeeper_openfga_request_duration_seconds";
+
+fn record_request_duration(operation: &'static str, started_at: std::time::Instant, success: bool) {
+    metrics::histogram!(
+        METRIC_REQUEST_DURATION,
+        "operation" => operation,
+        "status" => if success { "success" } else { "failure" },
+    )
+    .record(started_at.elapsed());
+}
+
 #[derive(Clone, Debug)]
 pub struct OpenFGAAuthorizer {
     pub(crate) client: BasicOpenFgaClient,
     client_higher_consistency: BasicOpenFgaClient,
     pub(crate) health: Arc<RwLock<Vec<Health>>>,
+    pub(crate) expiring_grants: Arc<ExpiringGrants>,
     server_id: ServerId,
 }
 
@@ -60,6 +74,7 @@ impl OpenFGAAuthorizer {
             client,
             client_higher_consistency,
             health: Arc::new(RwLock::new(vec![])),
+            expiring_grants: Arc::new(ExpiringGrants::default()),
             server_id,
         }
     }
@@ -824,9 +839,13 @@ impl OpenFGAAuthorizer {
         &self,
         tuple_key: impl Into<CheckRequestTupleKey>,
     ) -> Result<bool, OpenFGABackendUnavailable> {
-        self.client
-            .check(tuple_key, None, None, false)
-            .await
+        let started_at = std::time::Instant::now();
+        let result = self
+            .client
+            .check(tuple_key, None, check_context(), false)
+            .await;
+        record_request_duration("check", started_at, result.is_ok());
+        result
             .inspect_err(|e| {
                 tracing::error!("Failed to check with OpenFGA: {e}");
             })
@@ -840,20 +859,24 @@ impl OpenFGAAuthorizer {
     ) -> Result<Vec<bool>, OpenFGABackendUnavailable> {
         // Using index into tuple_keys as correlation_id.
         let num_tuples = tuple_keys.len();
+        let context = check_context();
         let items: Vec<BatchCheckItem> = tuple_keys
             .into_iter()
             .enumerate()
             .map(|(i, tuple_key)| BatchCheckItem {
                 tuple_key: Some(tuple_key.into()),
                 contextual_tuples: None,
-                context: None,
+                context: Some(context.clone()),
                 correlation_id: i.to_string(),
             })
             .collect();
 
         let chunks: Vec<_> = items.chunks(AUTH_CONFIG.max_batch_check_size).collect();
+        let started_at = std::time::Instant::now();
         let chunked_raw_results =
-            try_join_all(chunks.iter().map(|&c| self.client.batch_check(c.to_vec()))).await?;
+            try_join_all(chunks.iter().map(|&c| self.client.batch_check(c.to_vec()))).await;
+        record_request_duration("batch-check", started_at, chunked_raw_results.is_ok());
+        let chunked_raw_results = chunked_raw_results?;
 
         let mut results = vec![false; num_tuples];
         let mut idxs_seen = vec![false; num_tuples];
@@ -1069,7 +1092,7 @@ impl OpenFGAAuthorizer {
     ) -> OpenFGAResult<Vec<String>> {
         let user = user.into();
         self.client
-            .list_objects(r#type, relation, user, None, None)
+            .list_objects(r#type, relation, user, None, check_context())
             .await
             .map_err(OpenFGAError::from)
             .map(|response| response.into_inner().objects)
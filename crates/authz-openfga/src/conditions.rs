@@ -0,0 +1,291 @@
+//! Support for time-bound grants.
+//!
+//! Grants can be written with the `not_expired` condition of the authorization model.
+//! Such tuples carry a `grant_expires_at` parameter and are only considered by `OpenFGA`
+//! if the `current_time` passed with every check is before the expiry.
+//! Expired tuples have no effect, but are removed periodically by [`purge_expired_grants`].
+use std::{
+    collections::{BTreeSet, HashMap},
+    sync::{Mutex, PoisonError},
+};
+
+use chrono::{DateTime, Utc};
+use lakekeeper::{tokio, CancellationToken};
+use openfga_client::{
+    client::{
+        ReadRequestTupleKey, RelationshipCondition, Tuple, TupleKey, TupleKeyWithoutCondition,
+    },
+    prost_wkt_types::{value::Kind, Struct, Value},
+};
+
+use crate::{OpenFGAAuthorizer, OpenFGAResult, MAX_TUPLES_PER_WRITE};
+
+pub(crate) const NOT_EXPIRED_CONDITION: &str = "not_expired";
+const CURRENT_TIME_PARAM: &str = "current_time";
+const GRANT_EXPIRES_AT_PARAM: &str = "grant_expires_at";
+/// Minimum time between two scans of the whole store for expired grants.
+const FULL_SCAN_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60 * 60);
+
+fn timestamp_struct(key: &str, time: DateTime<Utc>) -> Struct {
+    Struct {
+        fields: HashMap::from([(
+            key.to_string(),
+            Value {
+                kind: Some(Kind::StringValue(time.to_rfc3339())),
+            },
+        )]),
+    }
+}
+
+/// Context that must be passed with every check and list-objects request
+/// so that conditional tuples can be evaluated.
+pub(crate) fn check_context() -> Struct {
+    timestamp_struct(CURRENT_TIME_PARAM, Utc::now())
+}
+
+/// Condition to attach to a tuple that should stop granting access at `expires_at`.
+pub(crate) fn not_expired_condition(expires_at: DateTime<Utc>) -> RelationshipCondition {
+    RelationshipCondition {
+        name: NOT_EXPIRED_CONDITION.to_string(),
+        context: Some(timestamp_struct(GRANT_EXPIRES_AT_PARAM, expires_at)),
+    }
+}
+
+/// Expiry of a tuple written with the `not_expired` condition.
+/// Returns `None` for unconditional tuples.
+pub(crate) fn grant_expires_at(tuple: &Tuple) -> Option<DateTime<Utc>> {
+    let condition = tuple.key.as_ref()?.condition.as_ref()?;
+    if condition.name != NOT_EXPIRED_CONDITION {
+        return None;
+    }
+    let value = condition
+        .context
+        .as_ref()?
+        .fields
+        .get(GRANT_EXPIRES_AT_PARAM)?;
+    match &value.kind {
+        Some(Kind::StringValue(s)) => DateTime::parse_from_rfc3339(s)
+            .ok()
+            .map(|t| t.with_timezone(&Utc)),
+        _ => None,
+    }
+}
+
+pub(crate) fn is_expired(tuple: &Tuple, now: DateTime<Utc>) -> bool {
+    grant_expires_at(tuple).is_some_and(|expires_at| expires_at <= now)
+}
+
+/// Time-bound grants written by this process that have not been purged yet,
+/// ordered by expiry.
+///
+/// Tracking them avoids reading the whole store on every purge. Grants written by
+/// other replicas are picked up by the full scan that runs every [`FULL_SCAN_INTERVAL`].
+#[derive(Debug, Default)]
+pub(crate) struct ExpiringGrants(Mutex<BTreeSet<(DateTime<Utc>, GrantKey)>>);
+
+/// `(user, relation, object)` of a tuple.
+type GrantKey = (String, String, String);
+
+impl ExpiringGrants {
+    pub(crate) fn track(
+        &self,
+        expires_at: DateTime<Utc>,
+        keys: impl IntoIterator<Item = TupleKeyWithoutCondition>,
+    ) {
+        let mut grants = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        grants.extend(
+            keys.into_iter()
+                .map(|k| (expires_at, (k.user, k.relation, k.object))),
+        );
+    }
+
+    /// Remove and return all grants that expired before `now`.
+    fn take_due(&self, now: DateTime<Utc>) -> Vec<TupleKeyWithoutCondition> {
+        let mut grants = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut due = Vec::new();
+        while let Some(entry) = grants.first().filter(|(expires_at, _)| *expires_at <= now) {
+            let (_, (user, relation, object)) = entry.clone();
+            grants.pop_first();
+            due.push(TupleKeyWithoutCondition {
+                user,
+                relation,
+                object,
+            });
+        }
+        due
+    }
+}
+
+fn tuple_key_without_condition(key: TupleKey) -> TupleKeyWithoutCondition {
+    TupleKeyWithoutCondition {
+        user: key.user,
+        relation: key.relation,
+        object: key.object,
+    }
+}
+
+/// Read the whole store once, delete all expired grants and track the remaining
+/// time-bound grants. Returns the number of deleted tuples.
+async fn delete_all_expired_grants(authorizer: &OpenFGAAuthorizer) -> OpenFGAResult<usize> {
+    let now = Utc::now();
+    let mut expired = Vec::new();
+    for tuple in authorizer.read_all(None::<ReadRequestTupleKey>).await? {
+        let Some(expires_at) = grant_expires_at(&tuple) else {
+            continue;
+        };
+        let Some(key) = tuple.key.map(tuple_key_without_condition) else {
+            continue;
+        };
+        if expires_at <= now {
+            expired.push(key);
+        } else {
+            authorizer.expiring_grants.track(expires_at, [key]);
+        }
+    }
+    delete_grants(authorizer, &expired).await?;
+    Ok(expired.len())
+}
+
+/// Delete the tracked grants that are due, if they still exist and have not been
+/// re-written with a later expiry. Returns the number of deleted tuples.
+async fn delete_tracked_expired_grants(authorizer: &OpenFGAAuthorizer) -> OpenFGAResult<usize> {
+    let now = Utc::now();
+    let mut expired = Vec::new();
+    for key in authorizer.expiring_grants.take_due(now) {
+        let tuple = authorizer
+            .read(
+                1,
+                ReadRequestTupleKey {
+                    user: key.user.clone(),
+                    relation: key.relation.clone(),
+                    object: key.object.clone(),
+                },
+                None,
+            )
+            .await?
+            .tuples
+            .into_iter()
+            .next();
+        match tuple.as_ref().and_then(grant_expires_at) {
+            Some(expires_at) if expires_at <= now => expired.push(key),
+            Some(expires_at) => authorizer.expiring_grants.track(expires_at, [key]),
+            // Deleted or re-written without expiry in the meantime
+            None => {}
+        }
+    }
+    delete_grants(authorizer, &expired).await?;
+    Ok(expired.len())
+}
+
+async fn delete_grants(
+    authorizer: &OpenFGAAuthorizer,
+    keys: &[TupleKeyWithoutCondition],
+) -> OpenFGAResult<()> {
+    #[allow(clippy::cast_sign_loss)]
+    for chunk in keys.chunks(MAX_TUPLES_PER_WRITE as usize) {
+        authorizer.write(None, Some(chunk.to_vec())).await?;
+    }
+    Ok(())
+}
+
+/// Periodically remove expired time-bound grants from `OpenFGA` until `cancellation_token` is cancelled.
+///
+/// The whole store is scanned on the first run and then at most every [`FULL_SCAN_INTERVAL`],
+/// so that grants written by other replicas are purged as well. Runs in between only check
+/// the grants tracked by this process. Expired grants are never evaluated as allowed,
+/// purging only keeps the store and the permission APIs clean.
+pub async fn purge_expired_grants(
+    authorizer: OpenFGAAuthorizer,
+    interval: std::time::Duration,
+    cancellation_token: CancellationToken,
+) {
+    let mut ticker = tokio::time::interval(interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    let mut last_full_scan: Option<tokio::time::Instant> = None;
+
+    loop {
+        tokio::select! {
+            () = cancellation_token.cancelled() => {
+                tracing::info!("Stopping purge of expired OpenFGA grants");
+                return;
+            }
+            _ = ticker.tick() => {}
+        }
+
+        let full_scan_due =
+            last_full_scan.is_none_or(|scanned_at| scanned_at.elapsed() >= FULL_SCAN_INTERVAL);
+        let result = if full_scan_due {
+            let started_at = tokio::time::Instant::now();
+            let result = delete_all_expired_grants(&authorizer).await;
+            if result.is_ok() {
+                last_full_scan = Some(started_at);
+            }
+            result
+        } else {
+            delete_tracked_expired_grants(&authorizer).await
+        };
+        match result {
+            Ok(0) => tracing::debug!("No expired OpenFGA grants to purge"),
+            Ok(n) => tracing::info!("Purged {n} expired OpenFGA grants"),
+            Err(e) => tracing::error!("Failed to purge expired OpenFGA grants: {e}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tuple(condition: Option<RelationshipCondition>) -> Tuple {
+        Tuple {
+            key: Some(TupleKey {
+                user: "user:foo".to_string(),
+                relation: "modify".to_string(),
+                object: "warehouse:bar".to_string(),
+                condition,
+            }),
+            timestamp: None,
+        }
+    }
+
+    #[test]
+    fn test_expiry_roundtrip() {
+        let expires_at = DateTime::parse_from_rfc3339("2030-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let t = tuple(Some(not_expired_condition(expires_at)));
+        assert_eq!(grant_expires_at(&t), Some(expires_at));
+        assert!(!is_expired(&t, expires_at - chrono::Duration::seconds(1)));
+        assert!(is_expired(&t, expires_at));
+    }
+
+    #[test]
+    fn test_unconditional_tuple_never_expires() {
+        let t = tuple(None);
+        assert_eq!(grant_expires_at(&t), None);
+        assert!(!is_expired(&t, Utc::now()));
+    }
+
+    fn key(user: &str) -> TupleKeyWithoutCondition {
+        TupleKeyWithoutCondition {
+            user: user.to_string(),
+            relation: "modify".to_string(),
+            object: "warehouse:bar".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_take_due_returns_only_expired_grants() {
+        let now = Utc::now();
+        let grants = ExpiringGrants::default();
+        grants.track(now + chrono::Duration::hours(1), [key("user:later")]);
+        grants.track(now - chrono::Duration::seconds(1), [key("user:due")]);
+
+        assert_eq!(grants.take_due(now), vec![key("user:due")]);
+        assert!(grants.take_due(now).is_empty());
+        assert_eq!(
+            grants.take_due(now + chrono::Duration::hours(1)),
+            vec![key("user:later")]
+        );
+    }
+}
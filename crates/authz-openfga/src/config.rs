@@ -80,6 +80,15 @@ pub struct OpenFGAConfig {
     /// [configuration option]: https://openfga.dev/docs/getting-started/setup-openfga/configuration#OPENFGA_MAX_CHECKS_PER_BATCH_CHECK
     #[serde(default = "default_openfga_max_batch_check_size")]
     pub max_batch_check_size: usize,
+    /// Interval in seconds in which expired time-bound grants are removed from `OpenFGA`.
+    /// Expired grants are never effective, purging only keeps the store clean.
+    /// Set to 0 to disable purging. Defaults to 300.
+    #[serde(default = "default_openfga_purge_expired_grants_interval_seconds")]
+    pub purge_expired_grants_interval_seconds: u64,
+    /// Maximum duration in hours that can be requested for a just-in-time
+    /// grant via an access request. Defaults to 24.
+    #[serde(default = "default_openfga_access_request_max_duration_hours")]
+    pub access_request_max_duration_hours: u32,
 }
 
 #[derive(Clone, Serialize, Deserialize, PartialEq, veil::Redact)]
@@ -118,6 +127,8 @@ where
         authorization_model_prefix,
         authorization_model_version,
         max_batch_check_size,
+        purge_expired_grants_interval_seconds,
+        access_request_max_duration_hours,
     }) = Option::<OpenFGAConfigSerde>::deserialize(deserializer)?
     else {
         return Ok(None);
@@ -150,6 +161,12 @@ where
         ));
     }
 
+    if access_request_max_duration_hours == 0 {
+        return Err(serde::de::Error::custom(
+            "openfga access_request_max_duration_hours must be greater than zero",
+        ));
+    }
+
     Ok(Some(OpenFGAConfig {
         endpoint,
         store_name,
@@ -157,6 +174,8 @@ where
         authorization_model_prefix,
         authorization_model_version,
         max_batch_check_size,
+        purge_expired_grants_interval_seconds,
+        access_request_max_duration_hours,
     }))
 }
 
@@ -200,6 +219,8 @@ where
         authorization_model_prefix: value.authorization_model_prefix.clone(),
         authorization_model_version: value.authorization_model_version.clone(),
         max_batch_check_size: value.max_batch_check_size,
+        purge_expired_grants_interval_seconds: value.purge_expired_grants_interval_seconds,
+        access_request_max_duration_hours: value.access_request_max_duration_hours,
     }
     .serialize(serializer)
 }
@@ -227,6 +248,10 @@ struct OpenFGAConfigSerde {
     token_endpoint: Option<Url>,
     #[serde(default = "default_openfga_max_batch_check_size")]
     max_batch_check_size: usize,
+    #[serde(default = "default_openfga_purge_expired_grants_interval_seconds")]
+    purge_expired_grants_interval_seconds: u64,
+    #[serde(default = "default_openfga_access_request_max_duration_hours")]
+    access_request_max_duration_hours: u32,
 }
 
 fn default_openfga_store_name() -> String {
@@ -241,6 +266,14 @@ fn default_openfga_max_batch_check_size() -> usize {
     50
 }
 
+fn default_openfga_purge_expired_grants_interval_seconds() -> u64 {
+    300
+}
+
+fn default_openfga_access_request_max_duration_hours() -> u32 {
+    24
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -351,4 +384,28 @@ mod test {
             Ok(())
         });
     }
+
+    #[test]
+    fn test_openfga_time_bound_grant_settings() {
+        figment::Jail::expect_with(|jail| {
+            jail.set_env("LAKEKEEPER_TEST__AUTHZ_BACKEND", "openfga");
+            jail.set_env("LAKEKEEPER_TEST__OPENFGA__ENDPOINT", "http://localhost");
+            let authz_config = get_config().openfga.unwrap();
+            assert_eq!(authz_config.purge_expired_grants_interval_seconds, 300);
+            assert_eq!(authz_config.access_request_max_duration_hours, 24);
+
+            jail.set_env(
+                "LAKEKEEPER_TEST__OPENFGA__PURGE_EXPIRED_GRANTS_INTERVAL_SECONDS",
+                "0",
+            );
+            jail.set_env(
+                "LAKEKEEPER_TEST__OPENFGA__ACCESS_REQUEST_MAX_DURATION_HOURS",
+                "8",
+            );
+            let authz_config = get_config().openfga.unwrap();
+            assert_eq!(authz_config.purge_expired_grants_interval_seconds, 0);
+            assert_eq!(authz_config.access_request_max_duration_hours, 8);
+            Ok(())
+        });
+    }
 }
@@ -111,6 +111,8 @@ pub enum OpenFGAError {
     },
     #[error("Cannot assign {0} to itself")]
     SelfAssignment(String),
+    #[error("Grants must expire in the future. Got expiry `{0}`")]
+    GrantExpiryInPast(chrono::DateTime<chrono::Utc>),
     #[error("Invalid OpenFGA query: {0}")]
     InvalidQuery(String),
 }
@@ -161,6 +163,9 @@ impl From<OpenFGAError> for ErrorModel {
             e @ OpenFGAError::SelfAssignment { .. } => {
                 ErrorModel::bad_request(err_msg, "SelfAssignment", Some(Box::new(e)))
             }
+            e @ OpenFGAError::GrantExpiryInPast(_) => {
+                ErrorModel::bad_request(err_msg, "GrantExpiryInPast", Some(Box::new(e)))
+            }
             OpenFGAError::CannotWriteTupleAlreadyExists(e) => {
                 ErrorModel::conflict(err_msg, "TupleAlreadyExistsError", Some(Box::new(e)))
             }
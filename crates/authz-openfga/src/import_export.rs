@@ -0,0 +1,1329 @@
+//! Bulk export and import of permissions.
+//!
+//! Exports contain all assignments of a project or warehouse and of the objects below it.
+//! Objects are referenced by id and by name, so that a document exported from one
+//! environment can be imported into another environment where ids differ.
+//! Imports are idempotent: for every object listed in the document, the assignments
+//! are made to match the document exactly. Objects not listed are not modified.
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use http::StatusCode;
+use lakekeeper::{
+    api::{
+        iceberg::{types::PageToken, v1::PaginationQuery},
+        ApiContext, RequestMetadata,
+    },
+    axum::{
+        extract::{Path, Query, State as AxumState},
+        Extension, Json,
+    },
+    iceberg::{NamespaceIdent, TableIdent},
+    service::{
+        Actor, CatalogNamespaceOps, CatalogStore, CatalogTabularOps, CatalogWarehouseOps,
+        ErrorModel, NamespaceId, Result, RoleId, SecretStore, State, TableId, TabularListFlags,
+        ViewId,
+    },
+    ProjectId, WarehouseId,
+};
+use openfga_client::client::{CheckRequestTupleKey, ReadRequestTupleKey};
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+
+use super::{
+    api::checked_write,
+    relations::{
+        Assignment, NamespaceAssignment, NamespaceRelation, ProjectAssignment, ProjectRelation,
+        ReducedRelation, RoleAssignment, TableAssignment, UserOrRole, ViewAssignment,
+        WarehouseAssignment, WarehouseRelation,
+    },
+    OpenFGAAuthorizer, OpenFGAError, OpenFGAResult,
+};
+use crate::{
+    conditions::{grant_expires_at, is_expired},
+    entities::{OpenFgaEntity, ParseOpenFgaEntity},
+    models::RoleIdExt as _,
+    FgaType,
+};
+
+const PERMISSIONS_DOCUMENT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+/// Portable document of all assignments of a project or warehouse
+pub(super) struct PermissionsDocument {
+    /// Version of the document format
+    version: u32,
+    /// Roles of the project. Used to map role principals by name on import.
+    roles: Vec<RoleReference>,
+    /// Objects and their assignments
+    objects: Vec<PermissionObject>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub(super) struct RoleReference {
+    #[schema(value_type = uuid::Uuid)]
+    role_id: RoleId,
+    name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub(super) struct ExpiringAssignment<RA> {
+    #[serde(flatten)]
+    assignment: RA,
+    /// Expiry of the assignment. Permanent if not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, utoipa::ToSchema)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+/// An object with all its assignments
+pub(super) enum PermissionObject {
+    #[serde(rename_all = "kebab-case")]
+    Project {
+        #[schema(value_type = String)]
+        project_id: ProjectId,
+        assignments: Vec<ExpiringAssignment<ProjectAssignment>>,
+    },
+    #[serde(rename_all = "kebab-case")]
+    Role {
+        #[schema(value_type = uuid::Uuid)]
+        role_id: RoleId,
+        assignments: Vec<ExpiringAssignment<RoleAssignment>>,
+    },
+    #[serde(rename_all = "kebab-case")]
+    Warehouse {
+        #[schema(value_type = uuid::Uuid)]
+        warehouse_id: WarehouseId,
+        name: String,
+        assignments: Vec<ExpiringAssignment<WarehouseAssignment>>,
+    },
+    #[serde(rename_all = "kebab-case")]
+    Namespace {
+        #[schema(value_type = uuid::Uuid)]
+        namespace_id: NamespaceId,
+        #[schema(value_type = uuid::Uuid)]
+        warehouse_id: WarehouseId,
+        name: Vec<String>,
+        assignments: Vec<ExpiringAssignment<NamespaceAssignment>>,
+    },
+    #[serde(rename_all = "kebab-case")]
+    Table {
+        #[schema(value_type = uuid::Uuid)]
+        table_id: TableId,
+        #[schema(value_type = uuid::Uuid)]
+        warehouse_id: WarehouseId,
+        namespace: Vec<String>,
+        name: String,
+        assignments: Vec<ExpiringAssignment<TableAssignment>>,
+    },
+    #[serde(rename_all = "kebab-case")]
+    View {
+        #[schema(value_type = uuid::Uuid)]
+        view_id: ViewId,
+        #[schema(value_type = uuid::Uuid)]
+        warehouse_id: WarehouseId,
+        namespace: Vec<String>,
+        name: String,
+        assignments: Vec<ExpiringAssignment<ViewAssignment>>,
+    },
+}
+
+impl PermissionObject {
+    /// Key used to order objects in a document deterministically
+    fn sort_key(&self) -> (u8, String) {
+        match self {
+            PermissionObject::Project { project_id, .. } => (0, project_id.to_string()),
+            PermissionObject::Role { role_id, .. } => (1, role_id.to_string()),
+            PermissionObject::Warehouse { name, .. } => (2, name.clone()),
+            PermissionObject::Namespace {
+                warehouse_id, name, ..
+            } => (3, format!("{warehouse_id}/{}", name.join("."))),
+            PermissionObject::Table {
+                warehouse_id,
+                namespace,
+                name,
+                ..
+            } => (4, format!("{warehouse_id}/{}/{name}", namespace.join("."))),
+            PermissionObject::View {
+                warehouse_id,
+                namespace,
+                name,
+                ..
+            } => (5, format!("{warehouse_id}/{}/{name}", namespace.join("."))),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct ImportPermissionsQuery {
+    /// Only compute the changes, do not apply them
+    #[serde(default)]
+    dry_run: bool,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub(super) struct ImportPermissionsResponse {
+    /// Whether the changes were only computed and not applied
+    dry_run: bool,
+    /// Changes per object. Objects without changes are omitted.
+    changes: Vec<ObjectChanges>,
+    /// Objects or roles of the document that could not be found.
+    /// Imports are only applied if all references can be resolved.
+    unresolved: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub(super) struct ObjectChanges {
+    /// `OpenFGA` object in the target
+    object: String,
+    writes: Vec<TupleChange>,
+    deletes: Vec<TupleChange>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub(super) struct TupleChange {
+    relation: String,
+    /// `OpenFGA` user or role assignee
+    principal: String,
+    expires_at: Option<DateTime<Utc>>,
+}
+
+/// Export all permissions of a project
+#[utoipa::path(
+    get,
+    tag = "permissions",
+    path = "/management/v1/permissions/project/{project_id}/export",
+    params(
+        ("project_id" = String, Path, description = "Project ID"),
+    ),
+    responses(
+            (status = 200, body = PermissionsDocument),
+    )
+)]
+pub(super) async fn export_project_permissions<C: CatalogStore, S: SecretStore>(
+    Path(project_id): Path<ProjectId>,
+    AxumState(api_context): AxumState<ApiContext<State<OpenFGAAuthorizer, C, S>>>,
+    Extension(metadata): Extension<RequestMetadata>,
+) -> Result<(StatusCode, Json<PermissionsDocument>)> {
+    let authorizer = api_context.v1_state.authz;
+    let catalog_state = api_context.v1_state.catalog;
+    let project_object = project_id.to_openfga();
+    require_read_assignments(
+        &authorizer,
+        metadata.actor(),
+        &ProjectRelation::CanReadAssignments.to_string(),
+        &project_object,
+    )
+    .await?;
+
+    let roles = list_project_roles::<C>(&project_id, catalog_state.clone()).await?;
+    let mut objects = vec![PermissionObject::Project {
+        project_id: project_id.clone(),
+        assignments: read_assignments(&authorizer, &project_object, false).await?,
+    }];
+    for role in &roles {
+        objects.push(PermissionObject::Role {
+            role_id: role.role_id,
+            assignments: read_assignments(&authorizer, &role.role_id.to_openfga(), false).await?,
+        });
+    }
+    for warehouse_id in related_ids::<WarehouseId>(
+        &authorizer,
+        &project_object,
+        &ProjectRelation::Warehouse.to_string(),
+    )
+    .await?
+    {
+        objects.extend(
+            export_warehouse_objects::<C>(&authorizer, warehouse_id, catalog_state.clone()).await?,
+        );
+    }
+
+    Ok((StatusCode::OK, Json(new_document(roles, objects))))
+}
+
+/// Export all permissions of a warehouse
+///
+/// Contains the warehouse and all namespaces, tables and views in it.
+#[utoipa::path(
+    get,
+    tag = "permissions",
+    path = "/management/v1/permissions/warehouse/{warehouse_id}/export",
+    params(
+        ("warehouse_id" = Uuid, Path, description = "Warehouse ID"),
+    ),
+    responses(
+            (status = 200, body = PermissionsDocument),
+    )
+)]
+pub(super) async fn export_warehouse_permissions<C: CatalogStore, S: SecretStore>(
+    Path(warehouse_id): Path<WarehouseId>,
+    AxumState(api_context): AxumState<ApiContext<State<OpenFGAAuthorizer, C, S>>>,
+    Extension(metadata): Extension<RequestMetadata>,
+) -> Result<(StatusCode, Json<PermissionsDocument>)> {
+    let authorizer = api_context.v1_state.authz;
+    let catalog_state = api_context.v1_state.catalog;
+    require_read_assignments(
+        &authorizer,
+        metadata.actor(),
+        &WarehouseRelation::CanReadAssignments.to_string(),
+        &warehouse_id.to_openfga(),
+    )
+    .await?;
+
+    let warehouse = C::require_warehouse_by_id(warehouse_id, catalog_state.clone()).await?;
+    let roles = list_project_roles::<C>(&warehouse.project_id, catalog_state.clone()).await?;
+    let objects = export_warehouse_objects::<C>(&authorizer, warehouse_id, catalog_state).await?;
+
+    Ok((StatusCode::OK, Json(new_document(roles, objects))))
+}
+
+/// Import permissions into a project
+///
+/// Objects are resolved by name. Assignments of every object in the document
+/// are replaced by the assignments of the document. Requires the permission
+/// to grant every changed relation.
+#[utoipa::path(
+    post,
+    tag = "permissions",
+    path = "/management/v1/permissions/project/{project_id}/import",
+    request_body = PermissionsDocument,
+    params(
+        ("project_id" = String, Path, description = "Project ID"),
+        ImportPermissionsQuery,
+    ),
+    responses(
+            (status = 200, body = ImportPermissionsResponse),
+    )
+)]
+pub(super) async fn import_project_permissions<C: CatalogStore, S: SecretStore>(
+    Path(project_id): Path<ProjectId>,
+    Query(query): Query<ImportPermissionsQuery>,
+    AxumState(api_context): AxumState<ApiContext<State<OpenFGAAuthorizer, C, S>>>,
+    Extension(metadata): Extension<RequestMetadata>,
+    Json(document): Json<PermissionsDocument>,
+) -> Result<(StatusCode, Json<ImportPermissionsResponse>)> {
+    let authorizer = api_context.v1_state.authz.clone();
+    require_read_assignments(
+        &authorizer,
+        metadata.actor(),
+        &ProjectRelation::CanReadAssignments.to_string(),
+        &project_id.to_openfga(),
+    )
+    .await?;
+
+    let response = import_permissions(
+        api_context,
+        metadata.actor(),
+        &project_id,
+        None,
+        document,
+        query.dry_run,
+    )
+    .await?;
+    Ok((StatusCode::OK, Json(response)))
+}
+
+/// Import permissions into a warehouse
+///
+/// The document must only contain a single warehouse and objects within it.
+/// Namespaces, tables and views are resolved by name. Assignments of every object
+/// in the document are replaced by the assignments of the document. Requires the
+/// permission to grant every changed relation.
+#[utoipa::path(
+    post,
+    tag = "permissions",
+    path = "/management/v1/permissions/warehouse/{warehouse_id}/import",
+    request_body = PermissionsDocument,
+    params(
+        ("warehouse_id" = Uuid, Path, description = "Warehouse ID"),
+        ImportPermissionsQuery,
+    ),
+    responses(
+            (status = 200, body = ImportPermissionsResponse),
+    )
+)]
+pub(super) async fn import_warehouse_permissions<C: CatalogStore, S: SecretStore>(
+    Path(warehouse_id): Path<WarehouseId>,
+    Query(query): Query<ImportPermissionsQuery>,
+    AxumState(api_context): AxumState<ApiContext<State<OpenFGAAuthorizer, C, S>>>,
+    Extension(metadata): Extension<RequestMetadata>,
+    Json(document): Json<PermissionsDocument>,
+) -> Result<(StatusCode, Json<ImportPermissionsResponse>)> {
+    let authorizer = api_context.v1_state.authz.clone();
+    require_read_assignments(
+        &authorizer,
+        metadata.actor(),
+        &WarehouseRelation::CanReadAssignments.to_string(),
+        &warehouse_id.to_openfga(),
+    )
+    .await?;
+
+    let warehouse =
+        C::require_warehouse_by_id(warehouse_id, api_context.v1_state.catalog.clone()).await?;
+    let response = import_permissions(
+        api_context,
+        metadata.actor(),
+        &warehouse.project_id,
+        Some(warehouse_id),
+        document,
+        query.dry_run,
+    )
+    .await?;
+    Ok((StatusCode::OK, Json(response)))
+}
+
+fn new_document(
+    mut roles: Vec<RoleReference>,
+    mut objects: Vec<PermissionObject>,
+) -> PermissionsDocument {
+    roles.sort_by(|a, b| a.name.cmp(&b.name));
+    objects.sort_by_key(PermissionObject::sort_key);
+    PermissionsDocument {
+        version: PERMISSIONS_DOCUMENT_VERSION,
+        roles,
+        objects,
+    }
+}
+
+async fn require_read_assignments(
+    authorizer: &OpenFGAAuthorizer,
+    actor: &Actor,
+    relation: &str,
+    object: &str,
+) -> OpenFGAResult<()> {
+    if actor == &Actor::Anonymous {
+        return Err(OpenFGAError::AuthenticationRequired);
+    }
+    let allowed = authorizer
+        .check(CheckRequestTupleKey {
+            user: actor.to_openfga(),
+            relation: relation.to_string(),
+            object: object.to_string(),
+        })
+        .await?;
+    if allowed {
+        Ok(())
+    } else {
+        Err(OpenFGAError::Unauthorized {
+            user: actor.to_openfga(),
+            relation: relation.to_string(),
+            object: object.to_string(),
+        })
+    }
+}
+
+async fn list_project_roles<C: CatalogStore>(
+    project_id: &ProjectId,
+    catalog_state: C::State,
+) -> Result<Vec<RoleReference>> {
+    let mut roles = Vec::new();
+    let mut page_token = None;
+    loop {
+        let response = C::list_roles(
+            Some(project_id.clone()),
+            None,
+            None,
+            PaginationQuery {
+                page_token: page_token.map_or(PageToken::Empty, PageToken::Present),
+                page_size: None,
+            },
+            catalog_state.clone(),
+        )
+        .await?;
+        if response.roles.is_empty() {
+            break;
+        }
+        roles.extend(response.roles.into_iter().map(|r| RoleReference {
+            role_id: r.id,
+            name: r.name,
+        }));
+        page_token = response.next_page_token;
+        if page_token.is_none() {
+            break;
+        }
+    }
+    Ok(roles)
+}
+
+/// Ids of all objects related to `object` via `relation`, i.e. the users of the tuples.
+async fn related_ids<T: From<uuid::Uuid>>(
+    authorizer: &OpenFGAAuthorizer,
+    object: &str,
+    relation: &str,
+) -> OpenFGAResult<Vec<T>> {
+    authorizer
+        .read_all(Some(ReadRequestTupleKey {
+            user: String::new(),
+            relation: relation.to_string(),
+            object: object.to_string(),
+        }))
+        .await?
+        .into_iter()
+        .filter_map(|t| t.key)
+        .map(|k| parse_uuid_suffix(&k.user).map(T::from))
+        .collect()
+}
+
+/// Children of a namespace, split into namespaces, tables and views.
+async fn namespace_children(
+    authorizer: &OpenFGAAuthorizer,
+    namespace_id: NamespaceId,
+) -> OpenFGAResult<(Vec<NamespaceId>, Vec<TableId>, Vec<ViewId>)> {
+    let mut namespaces = Vec::new();
+    let mut tables = Vec::new();
+    let mut views = Vec::new();
+    let children = authorizer
+        .read_all(Some(ReadRequestTupleKey {
+            user: String::new(),
+            relation: NamespaceRelation::Child.to_string(),
+            object: namespace_id.to_openfga(),
+        }))
+        .await?;
+    for key in children.into_iter().filter_map(|t| t.key) {
+        let id = parse_uuid_suffix(&key.user)?;
+        match key.user.split_once(':').map(|(t, _)| t) {
+            Some(t) if t == FgaType::Namespace.to_string() => namespaces.push(id.into()),
+            Some(t) if t == FgaType::Table.to_string() => tables.push(id.into()),
+            Some(t) if t == FgaType::View.to_string() => views.push(id.into()),
+            _ => return Err(OpenFGAError::InvalidEntity(key.user)),
+        }
+    }
+    Ok((namespaces, tables, views))
+}
+
+/// Parse the trailing UUID of an `OpenFGA` entity such as
+/// `warehouse:<id>` or `lakekeeper_table:<warehouse-id>/<id>`.
+fn parse_uuid_suffix(entity: &str) -> OpenFGAResult<uuid::Uuid> {
+    let id = entity
+        .rsplit(['/', ':'])
+        .next()
+        .ok_or_else(|| OpenFGAError::InvalidEntity(entity.to_string()))?;
+    uuid::Uuid::parse_str(id).map_err(|_| OpenFGAError::InvalidEntity(entity.to_string()))
+}
+
+/// Read all assignments of `object`, ordered by relation and principal.
+/// Expired assignments that have not been purged yet are only included if `include_expired` is set.
+async fn read_assignments<RA: Assignment>(
+    authorizer: &OpenFGAAuthorizer,
+    object: &str,
+    include_expired: bool,
+) -> OpenFGAResult<Vec<ExpiringAssignment<RA>>> {
+    let now = Utc::now();
+    let mut assignments = Vec::new();
+    for relation in RA::Relation::iter() {
+        let tuples = authorizer
+            .read_all(Some(ReadRequestTupleKey {
+                user: String::new(),
+                relation: relation.to_openfga().to_string(),
+                object: object.to_string(),
+            }))
+            .await?;
+        for tuple in tuples
+            .into_iter()
+            .filter(|t| include_expired || !is_expired(t, now))
+        {
+            let expires_at = grant_expires_at(&tuple);
+            let Some(key) = tuple.key else {
+                continue;
+            };
+            assignments.push(ExpiringAssignment {
+                assignment: RA::try_from_user(&key.user, &relation)?,
+                expires_at,
+            });
+        }
+    }
+    assignments.sort_by_key(|a| {
+        (
+            a.assignment.relation().to_openfga().to_string(),
+            a.assignment.openfga_user(),
+        )
+    });
+    Ok(assignments)
+}
+
+async fn export_warehouse_objects<C: CatalogStore>(
+    authorizer: &OpenFGAAuthorizer,
+    warehouse_id: WarehouseId,
+    catalog_state: C::State,
+) -> Result<Vec<PermissionObject>> {
+    // Tuples of deleted or inactive warehouses are not exported
+    let Some(warehouse) = C::get_warehouse_by_id(warehouse_id, catalog_state.clone()).await? else {
+        return Ok(vec![]);
+    };
+    let warehouse_object = warehouse_id.to_openfga();
+    let mut objects = vec![PermissionObject::Warehouse {
+        warehouse_id,
+        name: warehouse.name,
+        assignments: read_assignments(authorizer, &warehouse_object, false).await?,
+    }];
+
+    let mut pending = related_ids::<NamespaceId>(
+        authorizer,
+        &warehouse_object,
+        &WarehouseRelation::Namespace.to_string(),
+    )
+    .await?;
+    while let Some(namespace_id) = pending.pop() {
+        let Some(namespace) =
+            C::get_namespace(warehouse_id, namespace_id, catalog_state.clone()).await?
+        else {
+            continue;
+        };
+        objects.push(PermissionObject::Namespace {
+            namespace_id,
+            warehouse_id,
+            name: namespace.namespace_ident.clone().inner(),
+            assignments: read_assignments(authorizer, &namespace_id.to_openfga(), false).await?,
+        });
+
+        let (namespaces, tables, views) = namespace_children(authorizer, namespace_id).await?;
+        pending.extend(namespaces);
+        for table_id in tables {
+            let Some(table) = C::get_table_info(
+                warehouse_id,
+                table_id,
+                TabularListFlags::active(),
+                catalog_state.clone(),
+            )
+            .await?
+            else {
+                continue;
+            };
+            objects.push(PermissionObject::Table {
+                table_id,
+                warehouse_id,
+                namespace: table.tabular_ident.namespace.clone().inner(),
+                name: table.tabular_ident.name,
+                assignments: read_assignments(
+                    authorizer,
+                    &(warehouse_id, table_id).to_openfga(),
+                    false,
+                )
+                .await?,
+            });
+        }
+        for view_id in views {
+            let Some(view) = C::get_view_info(
+                warehouse_id,
+                view_id,
+                TabularListFlags::active(),
+                catalog_state.clone(),
+            )
+            .await?
+            else {
+                continue;
+            };
+            objects.push(PermissionObject::View {
+                view_id,
+                warehouse_id,
+                namespace: view.tabular_ident.namespace.clone().inner(),
+                name: view.tabular_ident.name,
+                assignments: read_assignments(
+                    authorizer,
+                    &(warehouse_id, view_id).to_openfga(),
+                    false,
+                )
+                .await?,
+            });
+        }
+    }
+
+    Ok(objects)
+}
+
+/// Maps ids of the source document to ids in the target.
+#[derive(Debug, Default)]
+struct Resolution {
+    roles: HashMap<RoleId, RoleId>,
+    warehouses: HashMap<WarehouseId, WarehouseId>,
+    unresolved: Vec<String>,
+}
+
+impl Resolution {
+    /// Map role principals of `assignments` to the roles of the target project.
+    /// Roles not listed in the document are kept as-is.
+    fn map_principals<RA: Assignment>(
+        &mut self,
+        document_roles: &[RoleReference],
+        assignments: Vec<ExpiringAssignment<RA>>,
+    ) -> OpenFGAResult<Vec<ExpiringAssignment<RA>>> {
+        let now = Utc::now();
+        let mut mapped = Vec::with_capacity(assignments.len());
+        for ExpiringAssignment {
+            assignment,
+            expires_at,
+        } in assignments
+        {
+            // Expired assignments have no effect
+            if expires_at.is_some_and(|e| e <= now) {
+                continue;
+            }
+            let user = match UserOrRole::parse_from_openfga(&assignment.openfga_user())? {
+                UserOrRole::Role(assignee) => {
+                    let source_role = assignee.role();
+                    match self.roles.get(&source_role) {
+                        Some(target_role) => target_role.into_assignees().to_openfga(),
+                        None if document_roles.iter().any(|r| r.role_id == source_role) => {
+                            // Listed in the document but not found in the target
+                            continue;
+                        }
+                        None => assignee.to_openfga(),
+                    }
+                }
+                UserOrRole::User(user_id) => user_id.to_openfga(),
+            };
+            mapped.push(ExpiringAssignment {
+                assignment: RA::try_from_user(&user, &assignment.relation())?,
+                expires_at,
+            });
+        }
+        Ok(mapped)
+    }
+}
+
+/// A resolved object of the document with the openfga object of the target.
+enum ResolvedObject {
+    Project(String, Vec<ExpiringAssignment<ProjectAssignment>>),
+    Role(String, Vec<ExpiringAssignment<RoleAssignment>>),
+    Warehouse(String, Vec<ExpiringAssignment<WarehouseAssignment>>),
+    Namespace(String, Vec<ExpiringAssignment<NamespaceAssignment>>),
+    Table(String, Vec<ExpiringAssignment<TableAssignment>>),
+    View(String, Vec<ExpiringAssignment<ViewAssignment>>),
+}
+
+#[allow(clippy::too_many_lines)]
+async fn import_permissions<C: CatalogStore, S: SecretStore>(
+    api_context: ApiContext<State<OpenFGAAuthorizer, C, S>>,
+    actor: &Actor,
+    project_id: &ProjectId,
+    target_warehouse: Option<WarehouseId>,
+    document: PermissionsDocument,
+    dry_run: bool,
+) -> Result<ImportPermissionsResponse> {
+    let authorizer = api_context.v1_state.authz;
+    let catalog_state = api_context.v1_state.catalog;
+
+    if document.version != PERMISSIONS_DOCUMENT_VERSION {
+        return Err(ErrorModel::bad_request(
+            format!(
+                "Unsupported permissions document version {}. Expected {PERMISSIONS_DOCUMENT_VERSION}.",
+                document.version
+            ),
+            "UnsupportedPermissionsDocumentVersion",
+            None,
+        )
+        .into());
+    }
+
+    let mut resolution = Resolution::default();
+
+    // Roles are matched by name
+    let target_roles = list_project_roles::<C>(project_id, catalog_state.clone()).await?;
+    for role in &document.roles {
+        match target_roles.iter().find(|r| r.name == role.name) {
+            Some(target) => {
+                resolution.roles.insert(role.role_id, target.role_id);
+            }
+            None => resolution
+                .unresolved
+                .push(format!("Role `{}` not found in project", role.name)),
+        }
+    }
+
+    // Warehouses are matched by name, or mapped to the target warehouse
+    for object in &document.objects {
+        match (object, target_warehouse) {
+            (PermissionObject::Project { .. } | PermissionObject::Role { .. }, Some(_)) => {
+                return Err(ErrorModel::bad_request(
+                    "Project and role permissions cannot be imported into a warehouse",
+                    "InvalidPermissionsDocument",
+                    None,
+                )
+                .into());
+            }
+            (PermissionObject::Warehouse { warehouse_id, .. }, Some(target)) => {
+                if resolution
+                    .warehouses
+                    .insert(*warehouse_id, target)
+                    .is_some()
+                    || resolution.warehouses.len() > 1
+                {
+                    return Err(ErrorModel::bad_request(
+                        "Documents imported into a warehouse must contain a single warehouse",
+                        "InvalidPermissionsDocument",
+                        None,
+                    )
+                    .into());
+                }
+            }
+            (
+                PermissionObject::Warehouse {
+                    warehouse_id, name, ..
+                },
+                None,
+            ) => match C::get_warehouse_by_name(name, project_id, catalog_state.clone()).await? {
+                Some(target) => {
+                    resolution.warehouses.insert(*warehouse_id, target.id);
+                }
+                None => resolution
+                    .unresolved
+                    .push(format!("Warehouse `{name}` not found in project")),
+            },
+            _ => {}
+        }
+    }
+
+    let mut resolved = Vec::with_capacity(document.objects.len());
+    for object in document.objects {
+        let target_warehouse_of = |resolution: &mut Resolution, warehouse_id: &WarehouseId| {
+            let target = resolution.warehouses.get(warehouse_id).copied();
+            if target.is_none() {
+                resolution.unresolved.push(format!(
+                    "Warehouse `{warehouse_id}` is not part of the import"
+                ));
+            }
+            target
+        };
+        match object {
+            PermissionObject::Project { assignments, .. } => {
+                resolved.push(ResolvedObject::Project(
+                    project_id.to_openfga(),
+                    resolution.map_principals(&document.roles, assignments)?,
+                ));
+            }
+            PermissionObject::Role {
+                role_id,
+                assignments,
+            } => {
+                if let Some(target_role) = resolution.roles.get(&role_id).copied() {
+                    resolved.push(ResolvedObject::Role(
+                        target_role.to_openfga(),
+                        resolution.map_principals(&document.roles, assignments)?,
+                    ));
+                }
+            }
+            PermissionObject::Warehouse {
+                warehouse_id,
+                assignments,
+                ..
+            } => {
+                if let Some(target) = resolution.warehouses.get(&warehouse_id).copied() {
+                    resolved.push(ResolvedObject::Warehouse(
+                        target.to_openfga(),
+                        resolution.map_principals(&document.roles, assignments)?,
+                    ));
+                }
+            }
+            PermissionObject::Namespace {
+                warehouse_id,
+                name,
+                assignments,
+                ..
+            } => {
+                let Some(target) = target_warehouse_of(&mut resolution, &warehouse_id) else {
+                    continue;
+                };
+                let ident = NamespaceIdent::from_vec(name.clone()).map_err(|e| {
+                    ErrorModel::bad_request(
+                        format!("Invalid namespace name in document: {e}"),
+                        "InvalidPermissionsDocument",
+                        None,
+                    )
+                })?;
+                match C::get_namespace(target, ident, catalog_state.clone()).await? {
+                    Some(namespace) => resolved.push(ResolvedObject::Namespace(
+                        namespace.namespace_id.to_openfga(),
+                        resolution.map_principals(&document.roles, assignments)?,
+                    )),
+                    None => resolution
+                        .unresolved
+                        .push(format!("Namespace `{}` not found", name.join("."))),
+                }
+            }
+            PermissionObject::Table {
+                warehouse_id,
+                namespace,
+                name,
+                assignments,
+                ..
+            } => {
+                let Some(target) = target_warehouse_of(&mut resolution, &warehouse_id) else {
+                    continue;
+                };
+                let ident = tabular_ident(namespace, name)?;
+                match C::get_table_info(
+                    target,
+                    ident.clone(),
+                    TabularListFlags::active(),
+                    catalog_state.clone(),
+                )
+                .await?
+                {
+                    Some(table) => resolved.push(ResolvedObject::Table(
+                        (target, table.tabular_id).to_openfga(),
+                        resolution.map_principals(&document.roles, assignments)?,
+                    )),
+                    None => resolution
+                        .unresolved
+                        .push(format!("Table `{ident}` not found")),
+                }
+            }
+            PermissionObject::View {
+                warehouse_id,
+                namespace,
+                name,
+                assignments,
+                ..
+            } => {
+                let Some(target) = target_warehouse_of(&mut resolution, &warehouse_id) else {
+                    continue;
+                };
+                let ident = tabular_ident(namespace, name)?;
+                match C::get_view_info(
+                    target,
+                    ident.clone(),
+                    TabularListFlags::active(),
+                    catalog_state.clone(),
+                )
+                .await?
+                {
+                    Some(view) => resolved.push(ResolvedObject::View(
+                        (target, view.tabular_id).to_openfga(),
+                        resolution.map_principals(&document.roles, assignments)?,
+                    )),
+                    None => resolution
+                        .unresolved
+                        .push(format!("View `{ident}` not found")),
+                }
+            }
+        }
+    }
+
+    if !dry_run && !resolution.unresolved.is_empty() {
+        return Err(ErrorModel::bad_request(
+            format!(
+                "Cannot import permissions, some references could not be resolved: {}",
+                resolution.unresolved.join(", ")
+            ),
+            "UnresolvedPermissionsReferences",
+            None,
+        )
+        .into());
+    }
+
+    let mut changes = Vec::new();
+    for object in resolved {
+        let object_changes = match object {
+            ResolvedObject::Project(o, a) => sync_object(&authorizer, actor, o, a, dry_run).await,
+            ResolvedObject::Role(o, a) => sync_object(&authorizer, actor, o, a, dry_run).await,
+            ResolvedObject::Warehouse(o, a) => sync_object(&authorizer, actor, o, a, dry_run).await,
+            ResolvedObject::Namespace(o, a) => sync_object(&authorizer, actor, o, a, dry_run).await,
+            ResolvedObject::Table(o, a) => sync_object(&authorizer, actor, o, a, dry_run).await,
+            ResolvedObject::View(o, a) => sync_object(&authorizer, actor, o, a, dry_run).await,
+        }?;
+        if !object_changes.writes.is_empty() || !object_changes.deletes.is_empty() {
+            changes.push(object_changes);
+        }
+    }
+
+    Ok(ImportPermissionsResponse {
+        dry_run,
+        changes,
+        unresolved: resolution.unresolved,
+    })
+}
+
+fn tabular_ident(namespace: Vec<String>, name: String) -> Result<TableIdent> {
+    let namespace = NamespaceIdent::from_vec(namespace).map_err(|e| {
+        ErrorModel::bad_request(
+            format!("Invalid namespace name in document: {e}"),
+            "InvalidPermissionsDocument",
+            None,
+        )
+    })?;
+    Ok(TableIdent::new(namespace, name))
+}
+
+/// Make the assignments of `object` match `desired`.
+/// Returns the required changes, which are only applied if `dry_run` is false.
+async fn sync_object<RA: Assignment + Clone + PartialEq>(
+    authorizer: &OpenFGAAuthorizer,
+    actor: &Actor,
+    object: String,
+    desired: Vec<ExpiringAssignment<RA>>,
+    dry_run: bool,
+) -> OpenFGAResult<ObjectChanges> {
+    // Expired tuples that were not purged yet still exist in OpenFGA and must be
+    // deleted before the same assignment can be written again.
+    let current = read_assignments::<RA>(authorizer, &object, true).await?;
+    // An assignment with a changed expiry is deleted and re-written
+    let deletes = current
+        .iter()
+        .filter(|a| !desired.contains(a))
+        .cloned()
+        .collect::<Vec<_>>();
+    let mut writes = Vec::new();
+    for assignment in desired {
+        if !current.contains(&assignment) && !writes.contains(&assignment) {
+            writes.push(assignment);
+        }
+    }
+
+    let changes = ObjectChanges {
+        object: object.clone(),
+        writes: writes.iter().map(tuple_change).collect(),
+        deletes: deletes.iter().map(tuple_change).collect(),
+    };
+    if dry_run {
+        return Ok(changes);
+    }
+
+    // Grants are written before revocations are applied, so that principals
+    // keeping their access never lose it in between. A tuple whose expiry changed
+    // or that has expired can only be re-written once its previous version is deleted.
+    let (rewrites, writes): (Vec<_>, Vec<_>) = writes
+        .into_iter()
+        .partition(|w| current.iter().any(|c| c.assignment == w.assignment));
+    write_grouped_by_expiry(authorizer, actor, writes, &object).await?;
+    if !deletes.is_empty() {
+        checked_write(
+            authorizer.clone(),
+            actor,
+            vec![],
+            deletes.into_iter().map(|a| a.assignment).collect(),
+            None,
+            &object,
+        )
+        .await?;
+    }
+    write_grouped_by_expiry(authorizer, actor, rewrites, &object).await?;
+
+    Ok(changes)
+}
+
+/// Write `assignments`, one request per distinct expiry.
+async fn write_grouped_by_expiry<RA: Assignment>(
+    authorizer: &OpenFGAAuthorizer,
+    actor: &Actor,
+    assignments: Vec<ExpiringAssignment<RA>>,
+    object: &str,
+) -> OpenFGAResult<()> {
+    let mut writes_by_expiry: HashMap<Option<DateTime<Utc>>, Vec<RA>> = HashMap::new();
+    for ExpiringAssignment {
+        assignment,
+        expires_at,
+    } in assignments
+    {
+        writes_by_expiry
+            .entry(expires_at)
+            .or_default()
+            .push(assignment);
+    }
+    for (expires_at, writes) in writes_by_expiry {
+        checked_write(
+            authorizer.clone(),
+            actor,
+            writes,
+            vec![],
+            expires_at,
+            object,
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+fn tuple_change<RA: Assignment>(assignment: &ExpiringAssignment<RA>) -> TupleChange {
+    TupleChange {
+        relation: assignment.assignment.relation().to_openfga().to_string(),
+        principal: assignment.assignment.openfga_user(),
+        expires_at: assignment.expires_at,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lakekeeper::service::UserId;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_uuid_suffix() {
+        let id = uuid::Uuid::now_v7();
+        assert_eq!(parse_uuid_suffix(&format!("warehouse:{id}")).unwrap(), id);
+        assert_eq!(
+            parse_uuid_suffix(&format!("lakekeeper_table:{}/{id}", uuid::Uuid::nil())).unwrap(),
+            id
+        );
+        parse_uuid_suffix("warehouse:foo").unwrap_err();
+    }
+
+    #[test]
+    fn test_document_roundtrip() {
+        let role_id = RoleId::new_random();
+        let document = new_document(
+            vec![RoleReference {
+                role_id,
+                name: "engineers".to_string(),
+            }],
+            vec![
+                PermissionObject::Warehouse {
+                    warehouse_id: WarehouseId::new_random(),
+                    name: "prod".to_string(),
+                    assignments: vec![ExpiringAssignment {
+                        assignment: WarehouseAssignment::Select(role_id.into()),
+                        expires_at: None,
+                    }],
+                },
+                PermissionObject::Project {
+                    project_id: ProjectId::new_random(),
+                    assignments: vec![ExpiringAssignment {
+                        assignment: ProjectAssignment::ProjectAdmin(
+                            UserId::new_unchecked("oidc", "admin").into(),
+                        ),
+                        expires_at: Some(Utc::now()),
+                    }],
+                },
+            ],
+        );
+        // Project is always sorted first
+        assert!(matches!(
+            document.objects[0],
+            PermissionObject::Project { .. }
+        ));
+
+        let serialized = serde_json::to_value(&document).unwrap();
+        assert_eq!(serialized["objects"][1]["type"], "warehouse");
+        assert_eq!(serialized["objects"][1]["assignments"][0]["type"], "select");
+        let deserialized: PermissionsDocument = serde_json::from_value(serialized).unwrap();
+        assert_eq!(deserialized, document);
+    }
+
+    #[test]
+    fn test_map_principals() {
+        let source_role = RoleId::new_random();
+        let target_role = RoleId::new_random();
+        let missing_role = RoleId::new_random();
+        let unknown_role = RoleId::new_random();
+        let mut resolution = Resolution::default();
+        resolution.roles.insert(source_role, target_role);
+        let document_roles = vec![
+            RoleReference {
+                role_id: source_role,
+                name: "a".to_string(),
+            },
+            RoleReference {
+                role_id: missing_role,
+                name: "b".to_string(),
+            },
+        ];
+
+        let mapped = resolution
+            .map_principals(
+                &document_roles,
+                vec![
+                    ExpiringAssignment {
+                        assignment: WarehouseAssignment::Select(source_role.into()),
+                        expires_at: None,
+                    },
+                    ExpiringAssignment {
+                        assignment: WarehouseAssignment::Select(missing_role.into()),
+                        expires_at: None,
+                    },
+                    ExpiringAssignment {
+                        assignment: WarehouseAssignment::Select(unknown_role.into()),
+                        expires_at: None,
+                    },
+                    ExpiringAssignment {
+                        assignment: WarehouseAssignment::Select(source_role.into()),
+                        expires_at: Some(Utc::now() - chrono::Duration::hours(1)),
+                    },
+                ],
+            )
+            .unwrap();
+
+        assert_eq!(
+            mapped,
+            vec![
+                ExpiringAssignment {
+                    assignment: WarehouseAssignment::Select(target_role.into()),
+                    expires_at: None,
+                },
+                ExpiringAssignment {
+                    assignment: WarehouseAssignment::Select(unknown_role.into()),
+                    expires_at: None,
+                },
+            ]
+        );
+    }
+
+    mod openfga_integration_tests {
+        use lakekeeper::{
+            implementations::postgres::{PostgresBackend, SecretsState},
+            sqlx,
+            tests::SetupTestCatalog,
+        };
+        use openfga_client::client::TupleKey;
+
+        use super::*;
+        use crate::{
+            conditions::not_expired_condition, migration::tests::authorizer_for_empty_store,
+        };
+
+        struct TestImport {
+            ctx: ApiContext<State<OpenFGAAuthorizer, PostgresBackend, SecretsState>>,
+            actor: Actor,
+            project_id: ProjectId,
+            warehouse_id: WarehouseId,
+            warehouse_name: String,
+        }
+
+        impl TestImport {
+            async fn setup(pool: sqlx::PgPool) -> Self {
+                let admin = UserId::new_unchecked("oidc", &uuid::Uuid::now_v7().to_string());
+                let authorizer = authorizer_for_empty_store().await.1;
+                let (ctx, warehouse) = SetupTestCatalog::builder()
+                    .pool(pool)
+                    .authorizer(authorizer)
+                    .user_id(Some(admin.clone()))
+                    .build()
+                    .setup()
+                    .await;
+                let project_id = PostgresBackend::require_warehouse_by_id(
+                    warehouse.warehouse_id,
+                    ctx.v1_state.catalog.clone(),
+                )
+                .await
+                .unwrap()
+                .project_id;
+                Self {
+                    ctx,
+                    actor: Actor::Principal(admin),
+                    project_id,
+                    warehouse_id: warehouse.warehouse_id,
+                    warehouse_name: warehouse.warehouse_name,
+                }
+            }
+
+            async fn assignments(&self) -> Vec<ExpiringAssignment<WarehouseAssignment>> {
+                read_assignments(
+                    &self.ctx.v1_state.authz,
+                    &self.warehouse_id.to_openfga(),
+                    true,
+                )
+                .await
+                .unwrap()
+            }
+
+            /// Document granting `user` select on the warehouse in addition to the current assignments
+            async fn document_granting(&self, user: &UserId) -> PermissionsDocument {
+                let mut assignments = self.assignments().await;
+                assignments.push(ExpiringAssignment {
+                    assignment: WarehouseAssignment::Select(user.clone().into()),
+                    expires_at: None,
+                });
+                new_document(
+                    vec![],
+                    vec![PermissionObject::Warehouse {
+                        warehouse_id: self.warehouse_id,
+                        name: self.warehouse_name.clone(),
+                        assignments,
+                    }],
+                )
+            }
+
+            async fn import(
+                &self,
+                document: PermissionsDocument,
+                dry_run: bool,
+            ) -> ImportPermissionsResponse {
+                import_permissions(
+                    self.ctx.clone(),
+                    &self.actor,
+                    &self.project_id,
+                    Some(self.warehouse_id),
+                    document,
+                    dry_run,
+                )
+                .await
+                .unwrap()
+            }
+        }
+
+        #[sqlx::test]
+        async fn test_import_is_idempotent(pool: sqlx::PgPool) {
+            let setup = TestImport::setup(pool).await;
+            let user = UserId::new_unchecked("oidc", &uuid::Uuid::now_v7().to_string());
+            let document = setup.document_granting(&user).await;
+
+            let response = setup.import(document.clone(), false).await;
+            assert_eq!(response.changes.len(), 1);
+            assert_eq!(response.changes[0].writes.len(), 1);
+            let assignments = setup.assignments().await;
+
+            let response = setup.import(document, false).await;
+            assert!(response.changes.is_empty());
+            assert_eq!(setup.assignments().await, assignments);
+        }
+
+        #[sqlx::test]
+        async fn test_import_over_expired_grant(pool: sqlx::PgPool) {
+            let setup = TestImport::setup(pool).await;
+            let user = UserId::new_unchecked("oidc", &uuid::Uuid::now_v7().to_string());
+            let document = setup.document_granting(&user).await;
+
+            // Expired, but not purged yet
+            setup
+                .ctx
+                .v1_state
+                .authz
+                .write(
+                    Some(vec![TupleKey {
+                        user: user.to_openfga(),
+                        relation: WarehouseRelation::Select.to_openfga().to_string(),
+                        object: setup.warehouse_id.to_openfga(),
+                        condition: Some(not_expired_condition(
+                            Utc::now() - chrono::Duration::hours(1),
+                        )),
+                    }]),
+                    None,
+                )
+                .await
+                .unwrap();
+
+            let response = setup.import(document.clone(), false).await;
+            assert_eq!(response.changes.len(), 1);
+            assert_eq!(response.changes[0].writes.len(), 1);
+            assert_eq!(response.changes[0].deletes.len(), 1);
+            assert!(setup.assignments().await.contains(&ExpiringAssignment {
+                assignment: WarehouseAssignment::Select(user.into()),
+                expires_at: None,
+            }));
+
+            assert!(setup.import(document, false).await.changes.is_empty());
+        }
+
+        #[sqlx::test]
+        async fn test_import_dry_run_changes_nothing(pool: sqlx::PgPool) {
+            let setup = TestImport::setup(pool).await;
+            let user = UserId::new_unchecked("oidc", &uuid::Uuid::now_v7().to_string());
+            let document = setup.document_granting(&user).await;
+            let assignments = setup.assignments().await;
+
+            let response = setup.import(document, true).await;
+            assert!(response.dry_run);
+            assert_eq!(response.changes.len(), 1);
+            assert_eq!(response.changes[0].writes.len(), 1);
+            assert_eq!(setup.assignments().await, assignments);
+        }
+    }
+}
@@ -17,18 +17,22 @@ pub use client::{
 pub(crate) use error::{OpenFGAError, OpenFGAResult};
 use openfga_client::migration::AuthorizationModelVersion;
 
+mod access_requests;
 mod api;
 mod authorizer;
 mod check;
 mod client;
+mod conditions;
 mod config;
 mod entities;
 pub mod error;
 mod health;
+mod import_export;
 mod migration;
 mod models;
 mod relations;
 
+pub use conditions::purge_expired_grants;
 pub use config::CONFIG;
 pub use migration::migrate;
 
@@ -24,18 +24,23 @@ pub use adls_location::{
 };
 pub use adls_storage::AdlsStorage;
 
-use crate::error::InitializeClientError;
+use crate::{error::InitializeClientError, trace_context::TraceContextPolicy};
 
 const DEFAULT_HOST: &str = "dfs.core.windows.net";
+const FEDERATED_TOKEN_FILE_ENV: &str = "AZURE_FEDERATED_TOKEN_FILE";
 static DEFAULT_AUTHORITY_HOST: LazyLock<Url> = LazyLock::new(|| {
     Url::parse("https://login.microsoftonline.com").expect("Default authority host is a valid URL")
 });
 static DEFAULT_CLIENT_OPTIONS: LazyLock<azure_core::ClientOptions> = LazyLock::new(|| {
-    azure_core::ClientOptions::default().retry(RetryOptions::fixed(
-        FixedRetryOptions::default()
-            .max_retries(3u32)
-            .max_total_elapsed(std::time::Duration::from_secs(5)),
-    ))
+    azure_core::ClientOptions::default()
+        .retry(RetryOptions::fixed(
+            FixedRetryOptions::default()
+                .max_retries(3u32)
+                .max_total_elapsed(std::time::Duration::from_secs(5)),
+        ))
+        .per_call_policies(vec![
+            Arc::new(TraceContextPolicy) as Arc<dyn azure_core::Policy>
+        ])
 });
 
 static HTTP_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(reqwest::Client::new);
@@ -58,6 +63,7 @@ pub enum AzureAuth {
     ClientCredentials(AzureClientCredentialsAuth),
     SharedAccessKey(AzureSharedAccessKeyAuth),
     AzureSystemIdentity,
+    WorkloadIdentity(AzureWorkloadIdentityAuth),
 }
 
 #[derive(Redact, Clone, PartialEq, typed_builder::TypedBuilder)]
@@ -74,6 +80,15 @@ pub struct AzureClientCredentialsAuth {
     pub client_secret: String,
 }
 
+/// Exchange the federated token of the host, i.e. a Kubernetes projected service account
+/// token, for an Entra ID token of the given app registration.
+/// The token is read from the file in `AZURE_FEDERATED_TOKEN_FILE`.
+#[derive(Debug, Clone, PartialEq, typed_builder::TypedBuilder)]
+pub struct AzureWorkloadIdentityAuth {
+    pub client_id: String,
+    pub tenant_id: String,
+}
+
 #[derive(Debug, Clone, typed_builder::TypedBuilder)]
 pub struct AzureSettings {
     // -------- Azure Settings for multiple services --------
@@ -132,6 +147,24 @@ impl AzureSettings {
                 let identity: Arc<DefaultAzureCredential> = self.get_system_identity().await?;
                 StorageCredentials::token_credential(identity)
             }
+            AzureAuth::WorkloadIdentity(AzureWorkloadIdentityAuth {
+                client_id,
+                tenant_id,
+            }) => {
+                // Projected tokens are rotated, so the file is read for every new client
+                let token = read_federated_token().await?;
+                let azure_auth = azure_identity::WorkloadIdentityCredential::new(
+                    HTTP_CLIENT_ARC.clone(),
+                    self.authority_host
+                        .clone()
+                        .unwrap_or(DEFAULT_AUTHORITY_HOST.clone()),
+                    tenant_id.clone(),
+                    client_id.clone(),
+                    token,
+                );
+
+                StorageCredentials::token_credential(Arc::new(azure_auth))
+            }
         })
     }
 
@@ -199,3 +232,17 @@ impl AzureSettings {
             })
     }
 }
+
+async fn read_federated_token() -> Result<String, InitializeClientError> {
+    let path = std::env::var(FEDERATED_TOKEN_FILE_ENV).map_err(|e| InitializeClientError {
+        reason: format!("Environment variable `{FEDERATED_TOKEN_FILE_ENV}` is not set."),
+        source: Some(Box::new(e)),
+    })?;
+    tokio::fs::read_to_string(&path)
+        .await
+        .map(|token| token.trim().to_string())
+        .map_err(|e| InitializeClientError {
+            reason: format!("Failed to read federated token from `{path}`: {e}"),
+            source: Some(Box::new(e)),
+        })
+}
@@ -1,4 +1,6 @@
 mod gcs_error;
+#[cfg(feature = "storage-s3")]
+mod gcs_interop;
 mod gcs_location;
 mod gcs_storage;
 
@@ -11,7 +13,7 @@ use google_cloud_storage::client::{Client, ClientConfig};
 use reqwest_middleware::ClientBuilder;
 use reqwest_retry::{policies::ExponentialBackoff, Jitter, RetryTransientMiddleware};
 
-use crate::InitializeClientError;
+use crate::{trace_context, InitializeClientError};
 
 static HTTP_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(reqwest::Client::new);
 
@@ -49,6 +51,15 @@ pub enum GcsAuth {
     /// Use the service account that the application is running as.
     /// This can be a Compute Engine default service account or a user-assigned service account.
     GcpSystemIdentity {},
+
+    /// HMAC Key
+    ///
+    /// Access GCS via its S3 compatible XML API.
+    #[cfg(feature = "storage-s3")]
+    HmacKey {
+        access_key_id: String,
+        secret: String,
+    },
 }
 
 impl GCSSettings {
@@ -60,6 +71,17 @@ impl GCSSettings {
         &self,
         auth: &GcsAuth,
     ) -> Result<GcsStorage, InitializeClientError> {
+        #[cfg(feature = "storage-s3")]
+        if let GcsAuth::HmacKey {
+            access_key_id,
+            secret,
+        } = auth
+        {
+            return Ok(GcsStorage::new_interop(
+                gcs_interop::storage_client(access_key_id, secret).await,
+            ));
+        }
+
         let client = self.get_gcs_storage_client(auth).await?;
         Ok(GcsStorage::new(client))
     }
@@ -73,6 +95,7 @@ impl GCSSettings {
             .jitter(Jitter::Full)
             .build_with_max_retries(3);
         let mid_client = ClientBuilder::new(HTTP_CLIENT.clone())
+            .with(trace_context::reqwest_middleware)
             .with(RetryTransientMiddleware::new_with_policy(retry_policy))
             .build();
 
@@ -100,6 +123,13 @@ impl GCSSettings {
                     reason: format!("Failed to initialize GCS client with credentials file: {e}"),
                     source: Some(e.into()),
                 })?,
+            #[cfg(feature = "storage-s3")]
+            GcsAuth::HmacKey { .. } => {
+                return Err(InitializeClientError {
+                    reason: "HMAC keys are only supported by the XML API.".to_string(),
+                    source: None,
+                })
+            }
         };
 
         Ok(Client::new(config))
@@ -162,6 +192,15 @@ impl std::fmt::Debug for GcsAuth {
             GcsAuth::GcpSystemIdentity {} => {
                 f.debug_struct("GcsCredential::GcpSystemIdentity").finish()
             }
+            #[cfg(feature = "storage-s3")]
+            GcsAuth::HmacKey {
+                access_key_id,
+                secret: _,
+            } => f
+                .debug_struct("GcsCredential::HmacKey")
+                .field("access_key_id", access_key_id)
+                .field("secret", &"<redacted>")
+                .finish(),
         }
     }
 }
@@ -0,0 +1,129 @@
+//! Access to GCS via its S3 compatible XML API, authenticated with HMAC keys.
+//!
+//! Requests are sent with the S3 client. `gs://` locations are translated to `s3://`
+//! locations and back.
+use std::sync::LazyLock;
+
+use bytes::Bytes;
+use futures::{stream::BoxStream, StreamExt as _};
+
+use crate::{
+    execute_with_parallelism,
+    gcs::GcsLocation,
+    s3::{S3AccessKeyAuth, S3Auth, S3Settings, S3Storage},
+    DeleteBatchError, DeleteError, ErrorKind, IOError, InvalidLocationError, LakekeeperStorage,
+    Location, ReadError, WriteError,
+};
+
+static GCS_XML_API_ENDPOINT: LazyLock<url::Url> = LazyLock::new(|| {
+    "https://storage.googleapis.com"
+        .parse()
+        .expect("failed to parse a constant to a url")
+});
+
+pub(super) async fn storage_client(access_key_id: &str, secret_access_key: &str) -> S3Storage {
+    let settings = S3Settings::builder()
+        .endpoint(Some(GCS_XML_API_ENDPOINT.clone()))
+        // Region is ignored by GCS, but required for signing
+        .region("auto".to_string())
+        .path_style_access(Some(true))
+        .build();
+    let auth = S3Auth::AccessKey(
+        S3AccessKeyAuth::builder()
+            .aws_access_key_id(access_key_id.to_string())
+            .aws_secret_access_key(secret_access_key.to_string())
+            .build(),
+    );
+    settings.get_storage_client(Some(&auth)).await
+}
+
+fn to_s3_path(path: &str) -> Result<String, InvalidLocationError> {
+    let mut location = GcsLocation::try_from_str(path)?.into_location();
+    location.set_scheme_unchecked_mut("s3");
+    Ok(location.to_string())
+}
+
+pub(super) async fn delete(s3: &S3Storage, path: impl AsRef<str>) -> Result<(), DeleteError> {
+    s3.delete(to_s3_path(path.as_ref())?).await
+}
+
+/// The XML API does not support deleting multiple objects in one request,
+/// so objects are deleted one by one.
+pub(super) async fn delete_batch(
+    s3: &S3Storage,
+    paths: impl IntoIterator<Item = impl AsRef<str>>,
+) -> Result<(), DeleteBatchError> {
+    let delete_futures = paths
+        .into_iter()
+        .map(|path| {
+            let path = to_s3_path(path.as_ref())?;
+            let s3 = s3.clone();
+            Ok::<_, DeleteBatchError>(async move { s3.delete(path).await })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let delete_stream = execute_with_parallelism(delete_futures, 100);
+    tokio::pin!(delete_stream);
+    while let Some(result) = delete_stream.next().await {
+        result.map_err(|join_err| {
+            DeleteBatchError::IOError(IOError::new(
+                ErrorKind::Unexpected,
+                format!("Task join error during batch delete: {join_err}"),
+                "batch_operation".to_string(),
+            ))
+        })??;
+    }
+
+    Ok(())
+}
+
+pub(super) async fn write(
+    s3: &S3Storage,
+    path: impl AsRef<str>,
+    bytes: Bytes,
+) -> Result<(), WriteError> {
+    s3.write(to_s3_path(path.as_ref())?, bytes).await
+}
+
+pub(super) async fn read(s3: &S3Storage, path: impl AsRef<str>) -> Result<Bytes, ReadError> {
+    s3.read(to_s3_path(path.as_ref())?).await
+}
+
+pub(super) async fn read_single(s3: &S3Storage, path: impl AsRef<str>) -> Result<Bytes, ReadError> {
+    s3.read_single(to_s3_path(path.as_ref())?).await
+}
+
+pub(super) async fn list<'a>(
+    s3: &'a S3Storage,
+    path: impl AsRef<str>,
+    page_size: Option<usize>,
+) -> Result<BoxStream<'a, Result<Vec<Location>, IOError>>, InvalidLocationError> {
+    let stream = s3.list(to_s3_path(path.as_ref())?, page_size).await?;
+    Ok(stream
+        .map(|page| {
+            page.map(|locations| {
+                locations
+                    .into_iter()
+                    .map(|mut location| {
+                        location.set_scheme_unchecked_mut("gs");
+                        location
+                    })
+                    .collect()
+            })
+        })
+        .boxed())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_s3_path() {
+        assert_eq!(
+            to_s3_path("gs://bucket/foo/bar.parquet").unwrap(),
+            "s3://bucket/foo/bar.parquet"
+        );
+        assert!(to_s3_path("s3://bucket/foo").is_err());
+    }
+}
@@ -26,13 +26,24 @@ use crate::{
     safe_usize_to_i32, safe_usize_to_i64, validate_file_size, DeleteBatchError, DeleteError,
     ErrorKind, IOError, InvalidLocationError, LakekeeperStorage, Location, ReadError, WriteError,
 };
+#[cfg(feature = "storage-s3")]
+use crate::{gcs::gcs_interop, s3::S3Storage};
 
 const MAX_BYTES_PER_REQUEST: usize = 25 * 1024 * 1024;
 const DEFAULT_BYTES_PER_REQUEST: usize = 16 * 1024 * 1024;
 
 #[derive(Clone)]
 pub struct GcsStorage {
-    client: Client,
+    client: GcsClient,
+}
+
+#[derive(Clone)]
+enum GcsClient {
+    /// JSON API
+    Json(Client),
+    /// S3 compatible XML API, authenticated with HMAC keys
+    #[cfg(feature = "storage-s3")]
+    Interop(S3Storage),
 }
 
 impl std::fmt::Debug for GcsStorage {
@@ -47,18 +58,39 @@ impl GcsStorage {
     /// Create a new `GCSStorage` instance with the provided client.
     #[must_use]
     pub fn new(client: Client) -> Self {
-        Self { client }
+        Self {
+            client: GcsClient::Json(client),
+        }
+    }
+
+    /// Create a new `GCSStorage` instance that accesses GCS via its S3 compatible XML API.
+    #[cfg(feature = "storage-s3")]
+    #[must_use]
+    pub(crate) fn new_interop(client: S3Storage) -> Self {
+        Self {
+            client: GcsClient::Interop(client),
+        }
     }
 
     /// Get the underlying GCS client.
+    /// Returns `None` if GCS is accessed via the XML API.
     #[must_use]
-    pub fn client(&self) -> &Client {
-        &self.client
+    pub fn client(&self) -> Option<&Client> {
+        match &self.client {
+            GcsClient::Json(client) => Some(client),
+            #[cfg(feature = "storage-s3")]
+            GcsClient::Interop(_) => None,
+        }
     }
 }
 
 impl LakekeeperStorage for GcsStorage {
     async fn delete(&self, path: impl AsRef<str>) -> Result<(), DeleteError> {
+        let client = match &self.client {
+            GcsClient::Json(client) => client,
+            #[cfg(feature = "storage-s3")]
+            GcsClient::Interop(s3) => return gcs_interop::delete(s3, path).await,
+        };
         let path = path.as_ref();
         let location = GcsLocation::try_from_str(path)?;
 
@@ -68,8 +100,7 @@ impl LakekeeperStorage for GcsStorage {
             ..Default::default()
         };
 
-        let result = self
-            .client
+        let result = client
             .delete_object(&delete_request)
             .await
             .map_err(|e| parse_error(e, location.as_str()));
@@ -84,13 +115,18 @@ impl LakekeeperStorage for GcsStorage {
         &self,
         paths: impl IntoIterator<Item = impl AsRef<str>>,
     ) -> Result<(), DeleteBatchError> {
+        let client = match &self.client {
+            GcsClient::Json(client) => client,
+            #[cfg(feature = "storage-s3")]
+            GcsClient::Interop(s3) => return gcs_interop::delete_batch(s3, paths).await,
+        };
         // Create futures for parallel deletion
         let delete_futures: Vec<_> = paths
             .into_iter()
             .map(|path| {
                 let path = path.as_ref();
                 let location = GcsLocation::try_from_str(path)?;
-                let client = self.client.clone();
+                let client = client.clone();
 
                 let future = async move {
                     let delete_request = DeleteObjectRequest {
@@ -149,6 +185,11 @@ impl LakekeeperStorage for GcsStorage {
 
     #[allow(clippy::too_many_lines)]
     async fn write(&self, path: impl AsRef<str>, bytes: Bytes) -> Result<(), WriteError> {
+        let client = match &self.client {
+            GcsClient::Json(client) => client,
+            #[cfg(feature = "storage-s3")]
+            GcsClient::Interop(s3) => return gcs_interop::write(s3, path, bytes).await,
+        };
         let path = path.as_ref();
         let location = GcsLocation::try_from_str(path)?;
 
@@ -162,7 +203,7 @@ impl LakekeeperStorage for GcsStorage {
             media.content_length = Some(bytes.len() as u64);
             let upload_type = UploadType::Simple(media);
 
-            self.client
+            client
                 .upload_object(&upload_request, bytes, &upload_type)
                 .await
                 .map(|_| ())
@@ -187,8 +228,7 @@ impl LakekeeperStorage for GcsStorage {
                 size: safe_usize_to_i64(bytes.len(), location.as_str())?,
                 ..Default::default()
             }));
-            let upload_client = self
-                .client
+            let upload_client = client
                 .prepare_resumable_upload(&upload_request, &upload_type)
                 .await
                 .map_err(|e| {
@@ -273,6 +313,11 @@ impl LakekeeperStorage for GcsStorage {
     }
 
     async fn read_single(&self, path: impl AsRef<str> + Send) -> Result<Bytes, ReadError> {
+        let client = match &self.client {
+            GcsClient::Json(client) => client,
+            #[cfg(feature = "storage-s3")]
+            GcsClient::Interop(s3) => return gcs_interop::read_single(s3, path).await,
+        };
         let path = path.as_ref();
         let location = GcsLocation::try_from_str(path)?;
 
@@ -283,8 +328,7 @@ impl LakekeeperStorage for GcsStorage {
         };
 
         let range = Range::default();
-        let data = self
-            .client
+        let data = client
             .download_object(&request, &range)
             .await
             .map_err(|e| {
@@ -298,6 +342,11 @@ impl LakekeeperStorage for GcsStorage {
     }
 
     async fn read(&self, path: impl AsRef<str>) -> Result<Bytes, ReadError> {
+        let client = match &self.client {
+            GcsClient::Json(client) => client,
+            #[cfg(feature = "storage-s3")]
+            GcsClient::Interop(s3) => return gcs_interop::read(s3, path).await,
+        };
         let path = path.as_ref();
         let location = GcsLocation::try_from_str(path)?;
 
@@ -307,7 +356,7 @@ impl LakekeeperStorage for GcsStorage {
             ..Default::default()
         };
 
-        let status = self.client.get_object(&request).await.map_err(|e| {
+        let status = client.get_object(&request).await.map_err(|e| {
             ReadError::IOError(
                 parse_error(e, location.as_str())
                     .with_context("Failed to get metadata about the object."),
@@ -319,8 +368,7 @@ impl LakekeeperStorage for GcsStorage {
         if file_size < MAX_BYTES_PER_REQUEST {
             let range = Range::default();
             // If the object is small enough, we can read it in one go
-            let data = self
-                .client
+            let data = client
                 .download_object(&request, &range)
                 .await
                 .map_err(|e| {
@@ -343,7 +391,7 @@ impl LakekeeperStorage for GcsStorage {
             .into_iter()
             .enumerate()
             .map(|(chunk_index, (start, end))| {
-                let client = self.client.clone();
+                let client = client.clone();
                 let request = request.clone();
                 let path = path.to_string();
 
@@ -391,6 +439,11 @@ impl LakekeeperStorage for GcsStorage {
         page_size: Option<usize>,
     ) -> Result<futures::stream::BoxStream<'_, Result<Vec<Location>, IOError>>, InvalidLocationError>
     {
+        let client = match &self.client {
+            GcsClient::Json(client) => client,
+            #[cfg(feature = "storage-s3")]
+            GcsClient::Interop(s3) => return gcs_interop::list(s3, path, page_size).await,
+        };
         let path = path.as_ref();
         let location = GcsLocation::try_from_str(path)?;
 
@@ -404,7 +457,7 @@ impl LakekeeperStorage for GcsStorage {
             ..Default::default()
         };
 
-        let client = self.client.clone();
+        let client = client.clone();
         let bucket_name = location.bucket_name().to_string();
 
         let stream = stream::try_unfold(
@@ -31,6 +31,7 @@ mod location;
 pub mod memory;
 #[cfg(feature = "storage-s3")]
 pub mod s3;
+pub mod trace_context;
 
 #[cfg(any(feature = "storage-s3", feature = "storage-gcs"))]
 /// Fallible usize→i32 conversion with additional context for diagnostics.
@@ -1,4 +1,7 @@
-use std::sync::{Arc, LazyLock};
+use std::{
+    collections::HashMap,
+    sync::{Arc, LazyLock},
+};
 
 use aws_config::{
     retry::RetryConfig, sts::AssumeRoleProvider, timeout::TimeoutConfig, AppName, BehaviorVersion,
@@ -104,6 +107,9 @@ pub struct S3Settings {
     pub path_style_access: Option<bool>,
     #[builder(default)]
     pub aws_kms_key_arn: Option<String>,
+    /// Access point ARNs to address requests to instead of the bucket, keyed by bucket name.
+    #[builder(default)]
+    pub access_points: HashMap<String, String>,
 }
 
 impl S3Settings {
@@ -116,8 +122,13 @@ impl S3Settings {
             s3_builder.set_force_path_style(Some(true));
         }
 
-        let client = aws_sdk_s3::Client::from_conf(s3_builder.build());
+        let client = aws_sdk_s3::Client::from_conf(
+            s3_builder
+                .interceptor(crate::trace_context::TraceContextInterceptor)
+                .build(),
+        );
         S3Storage::new(client, self.aws_kms_key_arn.clone())
+            .with_access_points(self.access_points.clone())
     }
 
     pub async fn get_sdk_config(&self, s3_credential: Option<&S3Auth>) -> SdkConfig {
@@ -128,6 +139,7 @@ impl S3Settings {
             // S3 specific settings
             path_style_access: _,
             aws_kms_key_arn: _,
+            access_points: _,
         } = self;
 
         let region = aws_config::Region::new(region.clone());
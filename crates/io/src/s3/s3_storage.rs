@@ -30,6 +30,7 @@ const MAX_DELETE_BATCH_SIZE: usize = 1000;
 pub struct S3Storage {
     client: aws_sdk_s3::Client,
     aws_kms_key_arn: Option<String>,
+    access_points: HashMap<String, String>,
 }
 
 impl S3Storage {
@@ -38,9 +39,18 @@ impl S3Storage {
         Self {
             client,
             aws_kms_key_arn,
+            access_points: HashMap::new(),
         }
     }
 
+    /// Send requests for the given buckets to access points instead.
+    /// Keys are bucket names, values are access point ARNs.
+    #[must_use]
+    pub fn with_access_points(mut self, access_points: HashMap<String, String>) -> Self {
+        self.access_points = access_points;
+        self
+    }
+
     #[must_use]
     pub fn client(&self) -> &aws_sdk_s3::Client {
         &self.client
@@ -50,6 +60,14 @@ impl S3Storage {
     pub fn aws_kms_key_arn(&self) -> Option<&String> {
         self.aws_kms_key_arn.as_ref()
     }
+
+    /// Bucket or access point ARN to address requests for `s3_location` to.
+    fn request_bucket<'a>(&'a self, s3_location: &'a S3Location) -> &'a str {
+        let bucket = s3_location.bucket_name();
+        self.access_points
+            .get(bucket)
+            .map_or(bucket, String::as_str)
+    }
 }
 
 impl LakekeeperStorage for S3Storage {
@@ -59,7 +77,7 @@ impl LakekeeperStorage for S3Storage {
 
         self.client
             .delete_object()
-            .bucket(s3_location.bucket_name())
+            .bucket(self.request_bucket(&s3_location))
             .key(s3_key_to_str(&s3_location.key()))
             .send()
             .await
@@ -74,7 +92,8 @@ impl LakekeeperStorage for S3Storage {
     ) -> Result<(), DeleteBatchError> {
         let s3_locations: HashMap<String, HashMap<String, String>> = group_paths_by_bucket(paths)?;
         let key_to_path_mapping = build_key_to_path_mapping(&s3_locations);
-        let delete_futures = create_delete_futures(&self.client, s3_locations)?;
+        let delete_futures =
+            create_delete_futures(&self.client, &self.access_points, s3_locations)?;
 
         process_delete_results(delete_futures, key_to_path_mapping)
             .await
@@ -91,7 +110,7 @@ impl LakekeeperStorage for S3Storage {
             let mut put_object = self
                 .client
                 .put_object()
-                .bucket(s3_location.bucket_name())
+                .bucket(self.request_bucket(&s3_location))
                 .key(s3_key_to_str(&s3_location.key()))
                 .body(bytes.into());
 
@@ -122,7 +141,7 @@ impl LakekeeperStorage for S3Storage {
             let mut create_multipart = self
                 .client
                 .create_multipart_upload()
-                .bucket(s3_location.bucket_name())
+                .bucket(self.request_bucket(&s3_location))
                 .key(s3_key_to_str(&s3_location.key()));
 
             if let Some(kms_key_arn) = &self.aws_kms_key_arn {
@@ -156,6 +175,7 @@ impl LakekeeperStorage for S3Storage {
             // Create upload futures
             let upload_futures = chunks.into_iter().map(|(part_number, chunk_data)| {
                 let client = self.client.clone();
+                let request_bucket = self.request_bucket(&s3_location).to_string();
                 let s3_location = s3_location.clone();
                 let upload_id = upload_id.to_string();
 
@@ -164,7 +184,7 @@ impl LakekeeperStorage for S3Storage {
                         .map_err(|e| e.with_context("Too many parts to write"))?;
                     let upload_part_response = client
                         .upload_part()
-                        .bucket(s3_location.bucket_name())
+                        .bucket(&request_bucket)
                         .key(s3_key_to_str(&s3_location.key()))
                         .upload_id(&upload_id)
                         .part_number(part_number_i32)
@@ -228,7 +248,7 @@ impl LakekeeperStorage for S3Storage {
 
             self.client
                 .complete_multipart_upload()
-                .bucket(s3_location.bucket_name())
+                .bucket(self.request_bucket(&s3_location))
                 .key(s3_key_to_str(&s3_location.key()))
                 .upload_id(upload_id)
                 .multipart_upload(completed_multipart_upload)
@@ -253,7 +273,7 @@ impl LakekeeperStorage for S3Storage {
         let head_response = self
             .client
             .head_object()
-            .bucket(s3_location.bucket_name())
+            .bucket(self.request_bucket(&s3_location))
             .key(s3_key_to_str(&s3_location.key()))
             .send()
             .await
@@ -271,6 +291,7 @@ impl LakekeeperStorage for S3Storage {
 
         let download_futures = chunks.into_iter().enumerate().map(|(chunk_index, (start, end))| {
             let client = self.client.clone();
+            let request_bucket = self.request_bucket(&s3_location).to_string();
             let s3_location = s3_location.clone();
             let path = path.to_string();
 
@@ -278,7 +299,7 @@ impl LakekeeperStorage for S3Storage {
                 let range_header = format!("bytes={start}-{end}");
                 let response = client
                     .get_object()
-                    .bucket(s3_location.bucket_name())
+                    .bucket(&request_bucket)
                     .key(s3_key_to_str(&s3_location.key()))
                     .range(range_header)
                     .send()
@@ -328,7 +349,7 @@ impl LakekeeperStorage for S3Storage {
         let response = self
             .client
             .get_object()
-            .bucket(s3_location.bucket_name())
+            .bucket(self.request_bucket(&s3_location))
             .key(s3_key_to_str(&s3_location.key()))
             .send()
             .await
@@ -360,7 +381,7 @@ impl LakekeeperStorage for S3Storage {
         let list_request_template = self
             .client
             .list_objects_v2()
-            .bucket(s3_bucket.clone())
+            .bucket(self.request_bucket(&s3_location))
             .prefix(s3_key_to_str(&s3_location.key()));
 
         let stream = stream::unfold(
@@ -495,6 +516,7 @@ enum AWSBatchDeleteError {
 /// Creates delete futures for batch operations, processing keys in batches of `MAX_DELETE_BATCH_SIZE`.
 fn create_delete_futures(
     client: &aws_sdk_s3::Client,
+    access_points: &HashMap<String, String>,
     s3_locations: HashMap<String, HashMap<String, String>>,
 ) -> Result<
     impl Iterator<
@@ -542,7 +564,7 @@ fn create_delete_futures(
                     )
                 })?;
 
-            let bucket_clone = bucket.clone();
+            let bucket_clone = access_points.get(&bucket).unwrap_or(&bucket).clone();
             let client_clone = client.clone();
             let delete_future = async move {
                 client_clone
@@ -0,0 +1,101 @@
+//! Propagation of the W3C trace context of the current span to outgoing requests.
+//!
+//! Uses the globally configured `OpenTelemetry` text map propagator. If no propagator is
+//! configured or the current span is not sampled, no headers are added.
+use std::collections::HashMap;
+
+use tracing_opentelemetry::OpenTelemetrySpanExt as _;
+
+/// Trace context headers (`traceparent`, `tracestate`) of the current span.
+#[must_use]
+pub fn current() -> HashMap<String, String> {
+    let context = tracing::Span::current().context();
+    let mut carrier = HashMap::new();
+    opentelemetry::global::get_text_map_propagator(|propagator| {
+        propagator.inject_context(&context, &mut carrier);
+    });
+    carrier
+}
+
+#[cfg(feature = "storage-s3")]
+pub use self::s3::TraceContextInterceptor;
+
+#[cfg(feature = "storage-s3")]
+mod s3 {
+    use aws_sdk_s3::{
+        config::{
+            interceptors::{BeforeTransmitInterceptorContextMut, Intercept},
+            ConfigBag, RuntimeComponents,
+        },
+        error::BoxError,
+    };
+
+    /// AWS SDK interceptor that adds the trace context of the current span to every request.
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct TraceContextInterceptor;
+
+    impl Intercept for TraceContextInterceptor {
+        fn name(&self) -> &'static str {
+            "TraceContextInterceptor"
+        }
+
+        fn modify_before_transmit(
+            &self,
+            context: &mut BeforeTransmitInterceptorContextMut<'_>,
+            _runtime_components: &RuntimeComponents,
+            _cfg: &mut ConfigBag,
+        ) -> Result<(), BoxError> {
+            let headers = context.request_mut().headers_mut();
+            for (name, value) in super::current() {
+                headers.try_insert(name, value)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+#[cfg(feature = "storage-adls")]
+pub(crate) use self::adls::TraceContextPolicy;
+
+#[cfg(feature = "storage-adls")]
+mod adls {
+    use std::sync::Arc;
+
+    use azure_core::{Context, Policy, PolicyResult, Request};
+
+    /// Azure SDK pipeline policy that adds the trace context of the current span to every request.
+    #[derive(Debug, Clone, Copy, Default)]
+    pub(crate) struct TraceContextPolicy;
+
+    #[async_trait::async_trait]
+    impl Policy for TraceContextPolicy {
+        async fn send(
+            &self,
+            ctx: &Context,
+            request: &mut Request,
+            next: &[Arc<dyn Policy>],
+        ) -> PolicyResult {
+            for (name, value) in super::current() {
+                request.insert_header(name, value);
+            }
+            next[0].send(ctx, request, &next[1..]).await
+        }
+    }
+}
+
+#[cfg(feature = "storage-gcs")]
+pub(crate) fn reqwest_middleware<'a>(
+    mut request: reqwest::Request,
+    extensions: &'a mut http::Extensions,
+    next: reqwest_middleware::Next<'a>,
+) -> futures::future::BoxFuture<'a, reqwest_middleware::Result<reqwest::Response>> {
+    for (name, value) in current() {
+        if let (Ok(name), Ok(value)) = (
+            reqwest::header::HeaderName::from_bytes(name.as_bytes()),
+            reqwest::header::HeaderValue::from_str(&value),
+        ) {
+            request.headers_mut().insert(name, value);
+        }
+    }
+    next.run(request, extensions)
+}
@@ -58,6 +58,7 @@ async fn create_s3_storage() -> anyhow::Result<(StorageBackend, TestConfig)> {
         region,
         path_style_access: Some(true),
         aws_kms_key_arn: None,
+        access_points: std::collections::HashMap::new(),
     };
     let s3_auth = lakekeeper_io::s3::S3Auth::AccessKey(lakekeeper_io::s3::S3AccessKeyAuth {
         aws_access_key_id: access_key,
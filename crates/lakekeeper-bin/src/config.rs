@@ -10,6 +10,15 @@ pub(crate) struct DynAppConfig {
     /// We do not recommend enabling this in production, especially if
     /// multiple instances of Lakekeeper are running.
     pub(crate) debug: DebugConfig,
+    #[serde(default)]
+    pub(crate) opentelemetry: OpenTelemetryConfig,
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug, Default)]
+pub(crate) struct OpenTelemetryConfig {
+    /// Export traces via OTLP over HTTP. The exporter is configured using the
+    /// standard `OTEL_EXPORTER_OTLP_*` environment variables.
+    pub(crate) enabled: bool,
 }
 
 #[derive(Clone, Deserialize, Serialize, Debug, Default)]
@@ -70,6 +79,22 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_opentelemetry_env_vars() {
+        figment::Jail::expect_with(|_jail| {
+            let config = get_config();
+            assert!(!config.opentelemetry.enabled);
+            Ok(())
+        });
+
+        figment::Jail::expect_with(|jail| {
+            jail.set_env("LAKEKEEPER_TEST__OPENTELEMETRY__ENABLED", "true");
+            let config = get_config();
+            assert!(config.opentelemetry.enabled);
+            Ok(())
+        });
+    }
+
     #[test]
     fn test_auto_serve_env_vars() {
         figment::Jail::expect_with(|_jail| {
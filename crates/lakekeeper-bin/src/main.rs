@@ -11,15 +11,15 @@ use clap::{Parser, Subcommand};
 use lakekeeper::{
     api::management::v1::api_doc as v1_api_doc,
     service::{authz::AllowAllAuthorizer, tasks::BUILT_IN_API_CONFIGS},
-    tokio, tracing, AuthZBackend, CONFIG,
+    tokio, tracing, AuthZBackend, SecretBackend, CONFIG,
 };
 use lakekeeper_authz_openfga::OpenFGAAuthorizer;
-use tracing_subscriber::{filter::LevelFilter, EnvFilter};
 
 mod authorizer;
 mod config;
 mod healthcheck;
 mod serve;
+mod telemetry;
 #[cfg(feature = "ui")]
 mod ui;
 mod wait_for_db;
@@ -100,6 +100,17 @@ enum Commands {
         )]
         check_server: bool,
     },
+    /// Re-encrypt all secrets stored in Postgres with the current `pg_encryption_key`.
+    /// Can run while the server is online and continues where it stopped if run again.
+    RotateSecretsKey {
+        #[clap(
+            default_value_t = 100,
+            long,
+            short,
+            help = "Number of secrets re-encrypted per transaction."
+        )]
+        batch_size: i64,
+    },
     /// Print the version of the server
     Version {},
     /// Get the `OpenAPI` specification of the Management API as yaml
@@ -110,18 +121,7 @@ enum Commands {
 async fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
 
-    tracing_subscriber::fmt()
-        .json()
-        .flatten_event(true)
-        .with_current_span(true)
-        .with_file(true)
-        .with_line_number(true)
-        .with_env_filter(
-            EnvFilter::builder()
-                .with_default_directive(LevelFilter::INFO.into())
-                .from_env_lossy(),
-        )
-        .init();
+    let _telemetry_guard = telemetry::init_tracing()?;
 
     match cli.command {
         Some(Commands::WaitForDB {
@@ -151,6 +151,10 @@ async fn main() -> anyhow::Result<()> {
             check_server |= check_all;
             healthcheck::health(check_db, check_server).await?;
         }
+        Some(Commands::RotateSecretsKey { batch_size }) => {
+            print_info();
+            rotate_secrets_key(batch_size).await?;
+        }
         Some(Commands::Version {}) => {
             println!("{}", env!("CARGO_PKG_VERSION"));
         }
@@ -210,6 +214,34 @@ async fn migrate() -> anyhow::Result<()> {
     Ok(())
 }
 
+async fn rotate_secrets_key(batch_size: i64) -> anyhow::Result<()> {
+    if CONFIG.secret_backend != SecretBackend::Postgres {
+        anyhow::bail!("Encryption keys can only be rotated for the `postgres` secret backend");
+    }
+    if batch_size < 1 {
+        anyhow::bail!("Batch size must be at least 1");
+    }
+
+    println!("Re-encrypting secrets...");
+    let write_pool = lakekeeper::implementations::postgres::get_writer_pool(
+        CONFIG
+            .to_pool_opts()
+            .acquire_timeout(std::time::Duration::from_secs(CONFIG.pg_acquire_timeout)),
+    )
+    .await?;
+
+    let encryption_keys = lakekeeper::implementations::postgres::EncryptionKeys::from_config();
+    let reencrypted = lakekeeper::implementations::postgres::reencrypt_secrets(
+        &write_pool,
+        &encryption_keys,
+        batch_size,
+    )
+    .await?;
+    println!("Re-encrypted {reencrypted} secrets.");
+
+    Ok(())
+}
+
 async fn serve(force_start: bool) -> anyhow::Result<()> {
     tracing::info!(
         "Starting server on {}:{}...",
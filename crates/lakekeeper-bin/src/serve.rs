@@ -1,18 +1,21 @@
-use std::{sync::Arc, vec};
+use std::{sync::Arc, time::Duration, vec};
 
 use lakekeeper::{
     implementations::{get_default_catalog_from_config, postgres::PostgresBackend},
     limes::{Authenticator, AuthenticatorEnum},
-    serve::{serve, ServeConfiguration},
+    serve::{serve, RegisterBackgroundServiceFn, ServeConfiguration},
     service::{
         authn::{get_default_authenticator_from_config, BuiltInAuthenticators},
         authz::Authorizer,
-        endpoint_statistics::EndpointStatisticsSink,
+        endpoint_statistics::{
+            get_additional_endpoint_statistics_sinks_from_config, EndpointStatisticsSink,
+        },
         event_publisher::get_default_cloud_event_backends_from_config,
         CatalogStore, SecretStore,
     },
     tracing,
 };
+use lakekeeper_authz_openfga::{purge_expired_grants, OpenFGAAuthorizer, CONFIG as OPENFGA_CONFIG};
 
 use crate::authorizer::AuthorizerEnum;
 #[cfg(feature = "ui")]
@@ -24,18 +27,33 @@ pub(crate) async fn serve_default(bind_addr: std::net::SocketAddr) -> anyhow::Re
         .await?
         .server_id();
     let authorizer = AuthorizerEnum::init_from_env(server_id).await?;
-    let stats = vec![stats];
+    let mut stats = vec![stats];
+    stats.extend(get_additional_endpoint_statistics_sinks_from_config());
 
     match authorizer {
         AuthorizerEnum::AllowAll(authz) => {
             tracing::info!("Using AllowAll authorizer");
-            serve_with_authn::<PostgresBackend, _, _>(bind_addr, secrets, catalog, authz, stats)
-                .await
+            serve_with_authn::<PostgresBackend, _, _>(
+                bind_addr,
+                secrets,
+                catalog,
+                authz,
+                stats,
+                vec![],
+            )
+            .await
         }
         AuthorizerEnum::OpenFGA(authz) => {
             tracing::info!("Using OpenFGA authorizer");
-            serve_with_authn::<PostgresBackend, _, _>(bind_addr, secrets, catalog, *authz, stats)
-                .await
+            serve_with_authn::<PostgresBackend, _, _>(
+                bind_addr,
+                secrets,
+                catalog,
+                *authz,
+                stats,
+                openfga_background_services(),
+            )
+            .await
         }
     }
 }
@@ -46,19 +64,46 @@ async fn serve_with_authn<C: CatalogStore, S: SecretStore, A: Authorizer>(
     catalog: C::State,
     authz: A,
     stats: Vec<Arc<dyn EndpointStatisticsSink + 'static>>,
+    background_services: Vec<RegisterBackgroundServiceFn<A, C, S>>,
 ) -> anyhow::Result<()> {
     let authentication = get_default_authenticator_from_config().await?;
 
     match authentication {
         None => {
-            serve_inner::<C, _, _, AuthenticatorEnum>(bind, secret, catalog, authz, None, stats)
-                .await
+            serve_inner::<C, _, _, AuthenticatorEnum>(
+                bind,
+                secret,
+                catalog,
+                authz,
+                None,
+                stats,
+                background_services,
+            )
+            .await
         }
         Some(BuiltInAuthenticators::Chain(authn)) => {
-            serve_inner::<C, _, _, _>(bind, secret, catalog, authz, Some(authn), stats).await
+            serve_inner::<C, _, _, _>(
+                bind,
+                secret,
+                catalog,
+                authz,
+                Some(authn),
+                stats,
+                background_services,
+            )
+            .await
         }
         Some(BuiltInAuthenticators::Single(authn)) => {
-            serve_inner::<C, _, _, _>(bind, secret, catalog, authz, Some(authn), stats).await
+            serve_inner::<C, _, _, _>(
+                bind,
+                secret,
+                catalog,
+                authz,
+                Some(authn),
+                stats,
+                background_services,
+            )
+            .await
         }
     }
 }
@@ -70,6 +115,7 @@ async fn serve_inner<C: CatalogStore, S: SecretStore, A: Authorizer, N: Authenti
     authorizer: A,
     authenticator: Option<N>,
     stats: Vec<Arc<dyn EndpointStatisticsSink + 'static>>,
+    background_services: Vec<RegisterBackgroundServiceFn<A, C, S>>,
 ) -> anyhow::Result<()> {
     let cloud_event_sinks = get_default_cloud_event_backends_from_config().await?;
 
@@ -82,11 +128,42 @@ async fn serve_inner<C: CatalogStore, S: SecretStore, A: Authorizer, N: Authenti
         .stats(stats)
         .modify_router_fn(Some(add_ui_routes))
         .cloud_event_sinks(cloud_event_sinks)
+        .register_additional_background_services_fn(background_services)
         .build();
 
     serve(config).await
 }
 
+/// Background services required by the `OpenFGA` authorizer.
+fn openfga_background_services<C: CatalogStore, S: SecretStore>(
+) -> Vec<RegisterBackgroundServiceFn<OpenFGAAuthorizer, C, S>> {
+    let interval_seconds = OPENFGA_CONFIG
+        .openfga
+        .as_ref()
+        .map_or(0, |c| c.purge_expired_grants_interval_seconds);
+    if interval_seconds == 0 {
+        tracing::info!("Purging of expired OpenFGA grants is disabled");
+        return vec![];
+    }
+    let interval = Duration::from_secs(interval_seconds);
+
+    vec![Box::new(
+        move |service_futures, cancellation_token, state| {
+            Box::pin(async move {
+                let authorizer = state.v1_state.authz;
+                let abort_handle = service_futures.spawn(async move {
+                    purge_expired_grants(authorizer, interval, cancellation_token).await;
+                    Ok(())
+                });
+                Ok(vec![(
+                    "openfga-purge-expired-grants".to_string(),
+                    abort_handle,
+                )])
+            })
+        },
+    )]
+}
+
 fn add_ui_routes(router: lakekeeper::axum::Router) -> lakekeeper::axum::Router {
     #[cfg(feature = "ui")]
     {
@@ -0,0 +1,111 @@
+//! Setup of the tracing subscriber, with optional `OpenTelemetry` trace and metric export.
+use lakekeeper::{tracing, CONFIG};
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_sdk::{
+    metrics::SdkMeterProvider, propagation::TraceContextPropagator, trace::SdkTracerProvider,
+    Resource,
+};
+use tracing_subscriber::{
+    filter::LevelFilter, layer::SubscriberExt as _, util::SubscriberInitExt as _, EnvFilter,
+};
+
+use crate::CONFIG_BIN;
+
+const SERVICE_NAME: &str = "lakekeeper";
+
+/// Flushes pending spans and metrics when dropped.
+#[derive(Debug)]
+pub(crate) struct TelemetryGuard {
+    tracer_provider: Option<SdkTracerProvider>,
+    meter_provider: Option<SdkMeterProvider>,
+}
+
+impl Drop for TelemetryGuard {
+    // Errors are reported through the subscriber, which outlives the guard. If the
+    // OTLP exporters are already shut down, only the local log layer emits them.
+    fn drop(&mut self) {
+        if let Some(tracer_provider) = self.tracer_provider.take() {
+            if let Err(e) = tracer_provider.shutdown() {
+                tracing::error!("Failed to shut down OpenTelemetry tracer provider: {e}");
+            }
+        }
+        if let Some(meter_provider) = self.meter_provider.take() {
+            if let Err(e) = meter_provider.shutdown() {
+                tracing::error!("Failed to shut down OpenTelemetry meter provider: {e}");
+            }
+        }
+    }
+}
+
+fn resource() -> Resource {
+    if std::env::var_os("OTEL_SERVICE_NAME").is_some() {
+        Resource::builder().build()
+    } else {
+        Resource::builder().with_service_name(SERVICE_NAME).build()
+    }
+}
+
+/// Initialize logging and, if enabled, export of spans and endpoint statistics via OTLP.
+///
+/// The W3C trace context propagator is always installed, so that incoming `traceparent`
+/// headers are extracted and forwarded to downstream services.
+///
+/// # Errors
+/// If the OTLP exporter cannot be built.
+pub(crate) fn init_tracing() -> anyhow::Result<TelemetryGuard> {
+    opentelemetry::global::set_text_map_propagator(TraceContextPropagator::new());
+
+    let tracer_provider = if CONFIG_BIN.opentelemetry.enabled {
+        let exporter = opentelemetry_otlp::SpanExporter::builder()
+            .with_http()
+            .build()?;
+        let tracer_provider = SdkTracerProvider::builder()
+            .with_batch_exporter(exporter)
+            .with_resource(resource())
+            .build();
+        opentelemetry::global::set_tracer_provider(tracer_provider.clone());
+        Some(tracer_provider)
+    } else {
+        None
+    };
+
+    let meter_provider = if CONFIG.endpoint_statistics.opentelemetry_enabled {
+        let exporter = opentelemetry_otlp::MetricExporter::builder()
+            .with_http()
+            .build()?;
+        let meter_provider = SdkMeterProvider::builder()
+            .with_periodic_exporter(exporter)
+            .with_resource(resource())
+            .build();
+        opentelemetry::global::set_meter_provider(meter_provider.clone());
+        Some(meter_provider)
+    } else {
+        None
+    };
+
+    let otel_layer = tracer_provider.as_ref().map(|tracer_provider| {
+        tracing_opentelemetry::layer().with_tracer(tracer_provider.tracer(SERVICE_NAME))
+    });
+
+    tracing_subscriber::registry()
+        .with(
+            EnvFilter::builder()
+                .with_default_directive(LevelFilter::INFO.into())
+                .from_env_lossy(),
+        )
+        .with(
+            tracing_subscriber::fmt::layer()
+                .json()
+                .flatten_event(true)
+                .with_current_span(true)
+                .with_file(true)
+                .with_line_number(true),
+        )
+        .with(otel_layer)
+        .init();
+
+    Ok(TelemetryGuard {
+        tracer_provider,
+        meter_provider,
+    })
+}
@@ -0,0 +1,21 @@
+create type access_request_status as enum ('pending', 'approved', 'rejected');
+
+create table access_request
+(
+    access_request_id uuid primary key,
+    warehouse_id      uuid                  not null references warehouse (warehouse_id) on delete cascade,
+    object            text                  not null,
+    relation          text                  not null,
+    requested_by      text                  not null,
+    justification     text,
+    duration_seconds  bigint                not null check (duration_seconds > 0),
+    status            access_request_status not null default 'pending',
+    decided_by        text,
+    decided_at        timestamptz,
+    expires_at        timestamptz
+);
+
+call add_time_columns('access_request');
+select trigger_updated_at('access_request');
+
+create index access_request_warehouse_id_status_idx on access_request (warehouse_id, status, created_at, access_request_id);
@@ -0,0 +1,45 @@
+-- Notify all catalog replicas about changed warehouses, namespaces and tabulars,
+-- so that in-memory caches can be invalidated.
+-- Notifications are only delivered once the transaction commits.
+CREATE OR REPLACE FUNCTION notify_cache_invalidation()
+    RETURNS TRIGGER AS
+$$
+DECLARE
+    payload jsonb;
+BEGIN
+    IF TG_TABLE_NAME = 'warehouse' THEN
+        payload := jsonb_build_object('type', 'warehouse', 'warehouse-id', OLD.warehouse_id);
+    ELSIF TG_TABLE_NAME = 'namespace' THEN
+        payload := jsonb_build_object('type', 'namespace', 'warehouse-id', OLD.warehouse_id,
+                                      'namespace-id', OLD.namespace_id);
+    ELSE
+        payload := jsonb_build_object('type', 'tabular', 'warehouse-id', OLD.warehouse_id,
+                                      'tabular-id', OLD.tabular_id);
+    END IF;
+
+    PERFORM pg_notify('lakekeeper_cache_invalidation', payload::text);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Inserts are not relevant, only existing entries are cached.
+DROP TRIGGER IF EXISTS notify_cache_invalidation ON warehouse;
+CREATE TRIGGER notify_cache_invalidation
+    AFTER UPDATE OR DELETE
+    ON warehouse
+    FOR EACH ROW
+EXECUTE PROCEDURE notify_cache_invalidation();
+
+DROP TRIGGER IF EXISTS notify_cache_invalidation ON namespace;
+CREATE TRIGGER notify_cache_invalidation
+    AFTER UPDATE OR DELETE
+    ON namespace
+    FOR EACH ROW
+EXECUTE PROCEDURE notify_cache_invalidation();
+
+DROP TRIGGER IF EXISTS notify_cache_invalidation ON tabular;
+CREATE TRIGGER notify_cache_invalidation
+    AFTER UPDATE OR DELETE
+    ON tabular
+    FOR EACH ROW
+EXECUTE PROCEDURE notify_cache_invalidation();
@@ -0,0 +1,8 @@
+-- Maximum number of tasks of a queue that may run concurrently for a warehouse.
+-- NULL means unlimited.
+alter table task_config
+    add column max_concurrent_tasks integer check (max_concurrent_tasks > 0);
+
+-- Used to count running tasks per warehouse when picking tasks
+create index if not exists task_queue_name_status_warehouse_id_idx
+    on task (queue_name, status, warehouse_id);
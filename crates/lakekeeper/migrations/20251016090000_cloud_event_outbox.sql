@@ -0,0 +1,31 @@
+-- Transactional outbox for CloudEvents.
+-- Events are inserted in the same transaction as the change they describe and relayed
+-- to every configured sink afterwards.
+create table cloud_event_outbox
+(
+    sequence_number bigint generated always as identity primary key,
+    -- Id of the inserting transaction. Sequence numbers are assigned on insert, not on commit,
+    -- so events are relayed in (transaction_id, sequence_number) order and only once
+    -- all older transactions finished.
+    transaction_id  bigint      not null default pg_current_xact_id()::text::bigint,
+    event           jsonb       not null,
+    created_at      timestamptz not null default now()
+);
+
+create index cloud_event_outbox_transaction_id_sequence_number_idx
+    on cloud_event_outbox (transaction_id, sequence_number);
+
+-- Position of the last event delivered to each sink.
+-- Relays claim a sink with a lease instead of holding a row lock while publishing,
+-- so that slow sinks do not keep transactions open.
+create table cloud_event_outbox_cursor
+(
+    sink_name            text primary key,
+    last_transaction_id  bigint not null default 0,
+    last_sequence_number bigint not null default 0,
+    lease_holder         uuid,
+    lease_expires_at     timestamptz
+);
+
+call add_time_columns('cloud_event_outbox_cursor');
+select trigger_updated_at('cloud_event_outbox_cursor');
@@ -0,0 +1,23 @@
+-- CloudEvents of warehouses, kept for the configured retention period.
+-- Events of deleted warehouses and tabulars are kept as well, so there are no foreign keys.
+create table event_log
+(
+    event_id     uuid primary key,
+    warehouse_id uuid        not null,
+    tabular_id   uuid,
+    namespace_id uuid,
+    event_type   text        not null,
+    -- Time the event occurred, i.e. the `time` attribute of the CloudEvent.
+    -- Events relayed from the outbox are logged later than they occurred.
+    event_time   timestamptz not null,
+    event        jsonb       not null,
+    created_at   timestamptz not null default now()
+);
+
+create index event_log_warehouse_id_created_at_idx on event_log (warehouse_id, created_at, event_id);
+create index event_log_tabular_id_created_at_idx on event_log (tabular_id, created_at, event_id)
+    where tabular_id is not null;
+create index event_log_warehouse_id_event_time_idx on event_log (warehouse_id, event_time);
+create index event_log_created_at_idx on event_log (created_at);
+
+ALTER TYPE api_endpoints ADD VALUE IF NOT EXISTS 'management-v1-list-warehouse-event-log';
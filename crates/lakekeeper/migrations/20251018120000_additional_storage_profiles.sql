@@ -0,0 +1,6 @@
+-- Named storage profiles of a warehouse in addition to its default storage profile.
+alter table warehouse
+    add column additional_storage_profiles jsonb not null default '[]';
+
+ALTER TYPE api_endpoints ADD VALUE IF NOT EXISTS 'management-v1-set-additional-storage-profile';
+ALTER TYPE api_endpoints ADD VALUE IF NOT EXISTS 'management-v1-delete-additional-storage-profile';
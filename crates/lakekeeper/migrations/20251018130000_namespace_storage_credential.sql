@@ -0,0 +1,9 @@
+-- Storage credential overrides of namespaces.
+-- If set, they are used instead of the credential of the warehouse's storage profile
+-- for tables and views of the namespace.
+alter table namespace
+    add column storage_secret_id uuid,
+    add column storage_sts_role_arn text;
+
+ALTER TYPE api_endpoints ADD VALUE IF NOT EXISTS 'management-v1-set-namespace-storage-credential';
+ALTER TYPE api_endpoints ADD VALUE IF NOT EXISTS 'management-v1-get-namespace-storage-credential';
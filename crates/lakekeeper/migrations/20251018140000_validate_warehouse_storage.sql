@@ -0,0 +1 @@
+ALTER TYPE api_endpoints ADD VALUE IF NOT EXISTS 'management-v1-validate-warehouse-storage';
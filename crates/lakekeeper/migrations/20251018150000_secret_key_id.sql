@@ -0,0 +1,7 @@
+-- Id of the key a secret is encrypted with, to support rotation of the encryption key.
+-- Secrets encrypted before key ids were introduced use the key with id `default`.
+alter table "secret"
+    add column key_id text not null default 'default';
+
+alter table "secret"
+    alter column key_id drop default;
@@ -0,0 +1,31 @@
+-- Notify all catalog replicas when the storage credential override of a namespace changes,
+-- so that credentials vended with the previous override are dropped from their caches.
+CREATE OR REPLACE FUNCTION notify_cache_invalidation()
+    RETURNS TRIGGER AS
+$$
+DECLARE
+    payload jsonb;
+BEGIN
+    IF TG_TABLE_NAME = 'warehouse' THEN
+        payload := jsonb_build_object('type', 'warehouse', 'warehouse-id', OLD.warehouse_id);
+    ELSIF TG_TABLE_NAME = 'namespace' THEN
+        payload := jsonb_build_object('type', 'namespace', 'warehouse-id', OLD.warehouse_id,
+                                      'namespace-id', OLD.namespace_id);
+        IF (TG_OP = 'DELETE'
+            AND (OLD.storage_secret_id IS NOT NULL OR OLD.storage_sts_role_arn IS NOT NULL))
+            OR (TG_OP = 'UPDATE'
+                AND (OLD.storage_secret_id, OLD.storage_sts_role_arn)
+                    IS DISTINCT FROM (NEW.storage_secret_id, NEW.storage_sts_role_arn)) THEN
+            PERFORM pg_notify('lakekeeper_cache_invalidation',
+                              jsonb_build_object('type', 'warehouse-credentials',
+                                                 'warehouse-id', OLD.warehouse_id)::text);
+        END IF;
+    ELSE
+        payload := jsonb_build_object('type', 'tabular', 'warehouse-id', OLD.warehouse_id,
+                                      'tabular-id', OLD.tabular_id);
+    END IF;
+
+    PERFORM pg_notify('lakekeeper_cache_invalidation', payload::text);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
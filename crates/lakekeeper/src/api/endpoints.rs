@@ -207,7 +207,13 @@ generate_endpoints! {
         ActivateWarehouse(POST, "/management/v1/warehouse/{warehouse_id}/activate"),
         UpdateStorageProfile(POST, "/management/v1/warehouse/{warehouse_id}/storage"),
         UpdateStorageCredential(POST, "/management/v1/warehouse/{warehouse_id}/storage-credential"),
+        ValidateWarehouseStorage(POST, "/management/v1/warehouse/{warehouse_id}/storage/validate"),
+        SetAdditionalStorageProfile(POST, "/management/v1/warehouse/{warehouse_id}/additional-storage/{storage_name}"),
+        DeleteAdditionalStorageProfile(DELETE, "/management/v1/warehouse/{warehouse_id}/additional-storage/{storage_name}"),
         GetWarehouseStatistics(GET, "/management/v1/warehouse/{warehouse_id}/statistics"),
+        GetWarehouseEvents(GET, "/management/v1/warehouse/{warehouse_id}/events"),
+        ListWarehouseEventLog(GET, "/management/v1/warehouse/{warehouse_id}/event-log"),
+        BackfillCloudEvents(POST, "/management/v1/warehouse/{warehouse_id}/cloud-events/backfill"),
         LoadEndpointStatistics(POST, "/management/v1/endpoint-statistics"),
         SearchTabular(POST, "/management/v1/warehouse/{warehouse_id}/search-tabular"),
         ListDeletedTabulars(GET, "/management/v1/warehouse/{warehouse_id}/deleted-tabulars"),
@@ -219,6 +225,8 @@ generate_endpoints! {
         SetViewProtection(POST, "/management/v1/warehouse/{warehouse_id}/view/{view_id}/protection"),
         SetNamespaceProtection(POST, "/management/v1/warehouse/{warehouse_id}/namespace/{namespace_id}/protection"),
         GetNamespaceProtection(GET, "/management/v1/warehouse/{warehouse_id}/namespace/{namespace_id}/protection"),
+        SetNamespaceStorageCredential(POST, "/management/v1/warehouse/{warehouse_id}/namespace/{namespace_id}/storage-credential"),
+        GetNamespaceStorageCredential(GET, "/management/v1/warehouse/{warehouse_id}/namespace/{namespace_id}/storage-credential"),
         SetWarehouseProtection(POST, "/management/v1/warehouse/{warehouse_id}/protection"),
         GetDefaultProjectDeprecated(GET, "/management/v1/default-project"),
         DeleteDefaultProjectDeprecated(DELETE, "/management/v1/default-project"),
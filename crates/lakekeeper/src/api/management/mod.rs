@@ -14,17 +14,25 @@ pub mod v1 {
     pub mod view;
     pub mod warehouse;
 
-    use std::marker::PhantomData;
+    use std::{convert::Infallible, marker::PhantomData};
 
     use axum::{
         extract::{Path, Query, State as AxumState},
-        response::{IntoResponse, Response},
+        response::{
+            sse::{Event as SseEvent, KeepAlive, Sse},
+            IntoResponse, Response,
+        },
         routing::{get, post},
         Extension, Json, Router,
     };
+    use cloudevents::AttributesReader;
+    use futures::{Stream, StreamExt};
     use http::StatusCode;
     use iceberg_ext::catalog::rest::ErrorModel;
-    use namespace::NamespaceManagementService as _;
+    use namespace::{
+        GetNamespaceStorageCredentialResponse, NamespaceManagementService as _,
+        SetNamespaceStorageCredentialRequest,
+    };
     use project::{
         CreateProjectRequest, CreateProjectResponse, GetProjectResponse, ListProjectsResponse,
         RenameProjectRequest, Service as _,
@@ -48,9 +56,11 @@ pub mod v1 {
     };
     use view::ViewManagementService as _;
     use warehouse::{
-        CreateWarehouseRequest, CreateWarehouseResponse, GetWarehouseResponse,
-        ListDeletedTabularsQuery, ListWarehousesRequest, ListWarehousesResponse,
-        RenameWarehouseRequest, Service as _, UpdateWarehouseCredentialRequest,
+        BackfillCloudEventsRequest, BackfillCloudEventsResponse, CreateWarehouseRequest,
+        CreateWarehouseResponse, GetWarehouseResponse, ListDeletedTabularsQuery,
+        ListWarehouseEventLogQuery, ListWarehouseEventLogResponse, ListWarehousesRequest,
+        ListWarehousesResponse, RenameWarehouseRequest, Service as _,
+        SetAdditionalStorageProfileRequest, UpdateWarehouseCredentialRequest,
         UpdateWarehouseDeleteProfileRequest, UpdateWarehouseStorageRequest,
         WarehouseStatisticsResponse,
     };
@@ -77,11 +87,12 @@ pub mod v1 {
         service::{
             authn::UserId,
             authz::Authorizer,
+            storage::health::StorageValidationReport,
             tasks::{QueueApiConfig, TaskId},
             Actor, CatalogStore, CreateOrUpdateUserResponse, NamespaceId, RoleId, SecretStore,
             State, TableId, TabularId, ViewId,
         },
-        ProjectId, WarehouseId,
+        ProjectId, WarehouseId, CONFIG,
     };
 
     #[derive(Debug, OpenApi)]
@@ -103,6 +114,7 @@ pub mod v1 {
         ),
         paths(
             activate_warehouse,
+            backfill_cloud_events,
             bootstrap,
             control_tasks,
             create_project,
@@ -110,6 +122,7 @@ pub mod v1 {
             create_user,
             create_warehouse,
             deactivate_warehouse,
+            delete_additional_storage_profile,
             delete_default_project,
             delete_default_project_deprecated,
             delete_project_by_id,
@@ -119,18 +132,21 @@ pub mod v1 {
             get_default_project,
             get_default_project_deprecated,
             get_endpoint_statistics,
+            get_namespace_storage_credential,
             get_project_by_id,
             get_role,
             get_server_info,
             get_task_details,
             get_user,
             get_warehouse,
+            get_warehouse_events,
             get_warehouse_statistics,
             list_deleted_tabulars,
             list_projects,
             list_roles,
             list_tasks,
             list_user,
+            list_warehouse_event_log,
             list_warehouses,
             rename_default_project,
             rename_default_project_deprecated,
@@ -139,7 +155,9 @@ pub mod v1 {
             search_role,
             search_user,
             search_tabular,
+            set_additional_storage_profile,
             set_namespace_protection,
+            set_namespace_storage_credential,
             set_table_protection,
             set_task_queue_config,
             get_task_queue_config,
@@ -155,6 +173,7 @@ pub mod v1 {
             update_storage_profile,
             update_user,
             update_warehouse_delete_profile,
+            validate_warehouse_storage,
             whoami,
         ),
         modifiers(&SecurityAddon)
@@ -844,7 +863,7 @@ pub mod v1 {
         ApiServer::<C, A, S>::get_warehouse(warehouse_id.into(), api_context, metadata).await
     }
 
-    #[derive(Debug, Deserialize, utoipa::IntoParams, TypedBuilder)]
+    #[derive(Debug, Clone, Copy, Deserialize, utoipa::IntoParams, TypedBuilder)]
     pub struct DeleteWarehouseQuery {
         #[serde(
             deserialize_with = "crate::api::iceberg::types::deserialize_bool",
@@ -1032,6 +1051,105 @@ pub mod v1 {
         .await
     }
 
+    /// Validate Warehouse Storage
+    ///
+    /// Validates access to the default and all additional storage profiles of a warehouse.
+    /// For each storage profile, a test file is written, read, listed and deleted in a random
+    /// table location using the storage credential of the warehouse. If the storage profile
+    /// vends credentials, the same is repeated with vended credentials.
+    /// Failing checks are reported in the response and do not result in an error status.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::ValidateWarehouseStorage.path(),
+        params(("warehouse_id" = Uuid,)),
+        responses(
+            (status = 200, description = "Storage validation report", body = StorageValidationReport),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn validate_warehouse_storage<C: CatalogStore, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<Json<StorageValidationReport>> {
+        ApiServer::<C, A, S>::validate_storage(warehouse_id.into(), api_context, metadata)
+            .await
+            .map(Json)
+    }
+
+    /// Set Additional Storage Profile
+    ///
+    /// Adds a named storage profile to the warehouse or replaces an existing one, i.e. to place
+    /// namespaces on an archive bucket or a bucket in another region.
+    /// New namespaces are placed on the storage profile if they are below any of its
+    /// `namespace-prefixes` or if the `storage_profile` namespace property names it.
+    /// The location of an existing storage profile cannot be changed.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::SetAdditionalStorageProfile.path(),
+        params(("warehouse_id" = Uuid,), ("storage_name" = String,)),
+        request_body = SetAdditionalStorageProfileRequest,
+        responses(
+            (status = 204, description = "Storage profile set successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn set_additional_storage_profile<
+        C: CatalogStore,
+        A: Authorizer + Clone,
+        S: SecretStore,
+    >(
+        Path((warehouse_id, storage_name)): Path<(uuid::Uuid, String)>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<SetAdditionalStorageProfileRequest>,
+    ) -> Result<StatusCode> {
+        ApiServer::<C, A, S>::set_additional_storage_profile(
+            warehouse_id.into(),
+            storage_name,
+            request,
+            api_context,
+            metadata,
+        )
+        .await?;
+        Ok(StatusCode::NO_CONTENT)
+    }
+
+    /// Delete Additional Storage Profile
+    ///
+    /// Removes a named storage profile from the warehouse.
+    /// Fails with `409 Conflict` while namespaces, tables or views are stored on it.
+    #[utoipa::path(
+        delete,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::DeleteAdditionalStorageProfile.path(),
+        params(("warehouse_id" = Uuid,), ("storage_name" = String,)),
+        responses(
+            (status = 204, description = "Storage profile deleted successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn delete_additional_storage_profile<
+        C: CatalogStore,
+        A: Authorizer + Clone,
+        S: SecretStore,
+    >(
+        Path((warehouse_id, storage_name)): Path<(uuid::Uuid, String)>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<StatusCode> {
+        ApiServer::<C, A, S>::delete_additional_storage_profile(
+            warehouse_id.into(),
+            storage_name,
+            api_context,
+            metadata,
+        )
+        .await?;
+        Ok(StatusCode::NO_CONTENT)
+    }
+
     #[derive(Deserialize, Debug, ToSchema)]
     pub struct SetProtectionRequest {
         /// Setting this to `true` will prevent the entity from being deleted unless `force` is used.
@@ -1104,6 +1222,143 @@ pub mod v1 {
         .map(Json)
     }
 
+    #[derive(Debug, Deserialize, utoipa::IntoParams)]
+    #[serde(rename_all = "kebab-case")]
+    pub struct GetWarehouseEventsQuery {
+        /// Resume the stream after the event with this id.
+        /// The `Last-Event-ID` header takes precedence if both are set.
+        #[serde(default)]
+        pub last_event_id: Option<String>,
+    }
+
+    /// Subscribe to Warehouse Events
+    ///
+    /// Streams the `CloudEvents` of a warehouse as Server-Sent Events.
+    /// Every SSE message carries the id of the `CloudEvent` as its `id`, the type of the
+    /// `CloudEvent` as its `event` and the `CloudEvent` in structured JSON format as `data`.
+    ///
+    /// Only events of tables, views and namespaces the caller has `get_metadata` permission on
+    /// are delivered. Permissions are checked once per object and stream. The stream is closed
+    /// when the caller's token expires.
+    /// The stream is fed from the event log, which must be enabled, and contains the events
+    /// of all server replicas. After a reconnect, the stream resumes after the event given in
+    /// the `Last-Event-ID` header as long as it is still buffered, also on a different replica.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::GetWarehouseEvents.path(),
+        params(("warehouse_id" = Uuid,), GetWarehouseEventsQuery),
+        responses(
+            (status = 200, description = "Stream of CloudEvents", content_type = "text/event-stream", body = String),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_warehouse_events<C: CatalogStore, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        Query(query): Query<GetWarehouseEventsQuery>,
+        headers: http::HeaderMap,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<Sse<impl Stream<Item = std::result::Result<SseEvent, Infallible>>>> {
+        let last_event_id = headers
+            .get("last-event-id")
+            .and_then(|v| v.to_str().ok())
+            .map(ToString::to_string)
+            .or(query.last_event_id);
+        let events = ApiServer::<C, A, S>::subscribe_warehouse_events(
+            warehouse_id.into(),
+            last_event_id,
+            api_context,
+            metadata,
+        )
+        .await?;
+
+        let events = events.filter_map(|event| async move {
+            SseEvent::default()
+                .id(event.id())
+                .event(event.ty())
+                .json_data(&*event)
+                .inspect_err(|e| {
+                    tracing::warn!("Failed to serialize event '{}': {e}", event.id());
+                })
+                .ok()
+                .map(Ok)
+        });
+        Ok(Sse::new(events)
+            .keep_alive(KeepAlive::new().interval(CONFIG.event_stream.keep_alive_interval)))
+    }
+
+    /// Backfill Warehouse CloudEvents
+    ///
+    /// Schedules one task per active table and view of the warehouse. Each task publishes a
+    /// `snapshotTable` or `snapshotView` `CloudEvent` with the current metadata location and
+    /// schema of the tabular to the given sink. This allows new consumers of an event topic
+    /// to bootstrap their state.
+    ///
+    /// Tasks are processed by the `cloud_events_backfill` queue. Tasks of tabulars that
+    /// were dropped before they ran succeed without publishing an event.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::BackfillCloudEvents.path(),
+        params(("warehouse_id" = Uuid,)),
+        request_body = BackfillCloudEventsRequest,
+        responses(
+            (status = 200, description = "Backfill tasks scheduled", body = BackfillCloudEventsResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn backfill_cloud_events<C: CatalogStore, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<BackfillCloudEventsRequest>,
+    ) -> Result<Json<BackfillCloudEventsResponse>> {
+        ApiServer::<C, A, S>::backfill_cloud_events(
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+        .map(Json)
+    }
+
+    /// List Warehouse Event Log
+    ///
+    /// Returns the `CloudEvents` of a warehouse stored in the event log, oldest first.
+    /// Events are only logged if `LAKEKEEPER__EVENT_LOG__ENABLED` is set and are deleted
+    /// after the configured retention period.
+    /// The log is served at `/event-log` rather than `/events`, which streams the
+    /// events of the warehouse as server-sent events.
+    ///
+    /// Requires `get_metadata` and `list_everything` permissions on the warehouse.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::ListWarehouseEventLog.path(),
+        params(("warehouse_id" = Uuid,), ListWarehouseEventLogQuery),
+        responses(
+            (status = 200, description = "Logged events", body = ListWarehouseEventLogResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn list_warehouse_event_log<C: CatalogStore, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        Query(query): Query<ListWarehouseEventLogQuery>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<Json<ListWarehouseEventLogResponse>> {
+        ApiServer::<C, A, S>::list_warehouse_event_log(
+            warehouse_id.into(),
+            query,
+            api_context,
+            metadata,
+        )
+        .await
+        .map(Json)
+    }
+
     /// Get API Statistics
     ///
     /// Retrieves detailed endpoint call statistics for your project, allowing you to monitor API usage patterns,
@@ -1464,6 +1719,78 @@ pub mod v1 {
         .await
     }
 
+    /// Get Namespace Storage Credential
+    ///
+    /// Retrieves the storage credential override of a namespace.
+    /// The credential itself is never returned.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::GetNamespaceStorageCredential.path(),
+        params(("warehouse_id" = Uuid,),("namespace_id" = Uuid,)),
+        responses(
+            (status = 200, body = GetNamespaceStorageCredentialResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_namespace_storage_credential<
+        C: CatalogStore,
+        A: Authorizer + Clone,
+        S: SecretStore,
+    >(
+        Path((warehouse_id, namespace_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+    ) -> Result<(StatusCode, Json<GetNamespaceStorageCredentialResponse>)> {
+        ApiServer::<C, A, S>::get_namespace_storage_credential(
+            NamespaceId::from(namespace_id),
+            warehouse_id.into(),
+            api_context,
+            metadata,
+        )
+        .await
+        .map(|r| (StatusCode::OK, Json(r)))
+    }
+
+    /// Set Namespace Storage Credential
+    ///
+    /// Sets the storage credential and / or the STS role used to access tables and views
+    /// of the namespace instead of the warehouse credential. Child namespaces without an override
+    /// inherit the override of their nearest ancestor.
+    /// The credential is validated like warehouse storage credentials.
+    /// If neither a credential nor a role is provided, the override is removed.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::SetNamespaceStorageCredential.path(),
+        params(("warehouse_id" = Uuid,),("namespace_id" = Uuid,)),
+        request_body = SetNamespaceStorageCredentialRequest,
+        responses(
+            (status = 204, description = "Namespace storage credential set successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn set_namespace_storage_credential<
+        C: CatalogStore,
+        A: Authorizer + Clone,
+        S: SecretStore,
+    >(
+        Path((warehouse_id, namespace_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Json(request): Json<SetNamespaceStorageCredentialRequest>,
+    ) -> Result<StatusCode> {
+        ApiServer::<C, A, S>::set_namespace_storage_credential(
+            NamespaceId::from(namespace_id),
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await?;
+        Ok(StatusCode::NO_CONTENT)
+    }
+
     /// Set Warehouse Protection
     ///
     /// Configures whether a warehouse should be protected from deletion.
@@ -1962,11 +2289,33 @@ pub mod v1 {
                     "/warehouse/{warehouse_id}/storage-credential",
                     post(update_storage_credential),
                 )
+                // Probe access to all storage profiles of the warehouse
+                .route(
+                    ManagementV1Endpoint::ValidateWarehouseStorage.path_in_management_v1(),
+                    post(validate_warehouse_storage),
+                )
+                // Named storage profiles in addition to the default storage profile
+                .route(
+                    ManagementV1Endpoint::SetAdditionalStorageProfile.path_in_management_v1(),
+                    post(set_additional_storage_profile).delete(delete_additional_storage_profile),
+                )
                 // Get warehouse statistics
                 .route(
                     "/warehouse/{warehouse_id}/statistics",
                     get(get_warehouse_statistics),
                 )
+                .route(
+                    ManagementV1Endpoint::GetWarehouseEvents.path_in_management_v1(),
+                    get(get_warehouse_events),
+                )
+                .route(
+                    ManagementV1Endpoint::ListWarehouseEventLog.path_in_management_v1(),
+                    get(list_warehouse_event_log),
+                )
+                .route(
+                    ManagementV1Endpoint::BackfillCloudEvents.path_in_management_v1(),
+                    post(backfill_cloud_events),
+                )
                 .route(
                     ManagementV1Endpoint::SearchTabular.path_in_management_v1(),
                     post(search_tabular),
@@ -2000,6 +2349,10 @@ pub mod v1 {
                     "/warehouse/{warehouse_id}/namespace/{namespace_id}/protection",
                     get(get_namespace_protection).post(set_namespace_protection),
                 )
+                .route(
+                    ManagementV1Endpoint::SetNamespaceStorageCredential.path_in_management_v1(),
+                    get(get_namespace_storage_credential).post(set_namespace_storage_credential),
+                )
                 .route(
                     "/warehouse/{warehouse_id}/protection",
                     post(set_warehouse_protection),
@@ -1,13 +1,47 @@
+use std::sync::Arc;
+
+use iceberg_ext::catalog::rest::ErrorModel;
+use utoipa::ToSchema;
+
 use super::{ApiServer, ProtectionResponse};
 use crate::{
     api::{ApiContext, RequestMetadata, Result},
+    server::{maybe_get_secret, tabular::namespace_location},
     service::{
-        authz::{Authorizer, AuthzNamespaceOps, CatalogNamespaceAction},
-        CatalogNamespaceOps, CatalogStore, NamespaceId, SecretStore, State, Transaction,
+        authz::{
+            Authorizer, AuthzNamespaceOps, AuthzWarehouseOps, CatalogNamespaceAction,
+            CatalogWarehouseAction,
+        },
+        secrets::{SecretReferenceError, StoredSecret},
+        storage::{StorageCredential, StorageLocations as _, StorageProfile},
+        CatalogNamespaceOps, CatalogStore, CatalogWarehouseOps, NamespaceId,
+        NamespaceStorageCredential, SecretStore, State, TableId, TabularId, Transaction,
     },
     WarehouseId,
 };
 
+#[derive(Debug, Clone, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct SetNamespaceStorageCredentialRequest {
+    /// Storage credential used for tables and views of the namespace instead of
+    /// the credential of the warehouse.
+    #[serde(default)]
+    pub storage_credential: Option<StorageCredential>,
+    /// Role to assume for tables and views of the namespace instead of the
+    /// `sts-role-arn` of the warehouse. Requires an S3 storage profile with STS enabled.
+    #[serde(default)]
+    pub sts_role_arn: Option<String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct GetNamespaceStorageCredentialResponse {
+    /// Whether the namespace has its own storage credential.
+    pub storage_credential_set: bool,
+    /// Role assumed for tables and views of the namespace, if overridden.
+    pub sts_role_arn: Option<String>,
+}
+
 impl<C: CatalogStore, A: Authorizer + Clone, S: SecretStore> NamespaceManagementService<C, A, S>
     for ApiServer<C, A, S>
 {
@@ -50,6 +84,18 @@ where
             C::set_namespace_protected(warehouse_id, namespace_id, protected, t.transaction())
                 .await?;
         t.commit().await?;
+
+        state
+            .v1_state
+            .hooks
+            .set_namespace_protection(
+                warehouse_id,
+                namespace_id,
+                status.protected,
+                Arc::new(request_metadata),
+            )
+            .await;
+
         let protection_response = ProtectionResponse {
             protected: status.protected,
             updated_at: status.updated_at,
@@ -84,4 +130,170 @@ where
             updated_at: namespace.updated_at,
         })
     }
+
+    async fn set_namespace_storage_credential(
+        namespace_id: NamespaceId,
+        warehouse_id: WarehouseId,
+        request: SetNamespaceStorageCredentialRequest,
+        state: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        //  ------------------- AUTHZ -------------------
+        let authorizer = state.v1_state.authz.clone();
+
+        let namespace =
+            C::get_namespace(warehouse_id, namespace_id, state.v1_state.catalog.clone()).await;
+
+        let namespace = authorizer
+            .require_namespace_action(
+                &request_metadata,
+                warehouse_id,
+                namespace_id,
+                namespace,
+                CatalogNamespaceAction::CanUpdateProperties,
+            )
+            .await?;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                CatalogWarehouseAction::CanUpdateStorageCredential,
+            )
+            .await?;
+
+        //  ------------------- BUSINESS LOGIC -------------------
+        let SetNamespaceStorageCredentialRequest {
+            storage_credential,
+            sts_role_arn,
+        } = request;
+        let sts_role_arn = sts_role_arn.map(|arn| arn.trim().to_string());
+        if sts_role_arn.as_deref().is_some_and(str::is_empty) {
+            return Err(ErrorModel::bad_request(
+                "`sts-role-arn` must not be empty. Omit it to use the role of the warehouse.",
+                "EmptyStsRoleArn",
+                None,
+            )
+            .into());
+        }
+        let warehouse =
+            C::require_warehouse_by_id(warehouse_id, state.v1_state.catalog.clone()).await?;
+
+        let location = namespace_location(&namespace, &warehouse)?;
+        let (storage_profile, storage_secret_id) = warehouse.storage_for_location(&location);
+        if sts_role_arn.is_some()
+            && !matches!(storage_profile, StorageProfile::S3(profile) if profile.sts_enabled)
+        {
+            return Err(ErrorModel::bad_request(
+                "`sts-role-arn` can only be set for namespaces on S3 storage profiles with STS enabled.",
+                "StsRoleArnNotSupported",
+                None,
+            )
+            .into());
+        }
+
+        let override_without_secret = NamespaceStorageCredential {
+            storage_secret_id: None,
+            sts_role_arn: sts_role_arn.clone(),
+        };
+        if !override_without_secret.is_empty() || storage_credential.is_some() {
+            let (storage_profile, _) = override_without_secret.apply(storage_profile, None);
+            let credential = match storage_credential.as_ref() {
+                Some(credential) => Some(
+                    credential
+                        .resolve_reference(&warehouse.project_id)
+                        .await
+                        .map_err(SecretReferenceError::into_bad_request)?
+                        .into_owned(),
+                ),
+                None => maybe_get_secret(storage_secret_id, &state.v1_state.secrets).await?,
+            };
+            // Validate with a fresh tabular location, validation removes the location afterwards
+            let test_location = storage_profile
+                .default_tabular_location(&location, TabularId::Table(TableId::new_random()));
+            Box::pin(storage_profile.validate_access(
+                credential.as_ref(),
+                Some(&test_location),
+                &request_metadata,
+            ))
+            .await?;
+        }
+
+        let secret_id = if let Some(storage_credential) = storage_credential {
+            Some(
+                state
+                    .v1_state
+                    .secrets
+                    .create_secret(StoredSecret::scoped(
+                        &storage_credential,
+                        &warehouse.project_id,
+                    )?)
+                    .await?,
+            )
+        } else {
+            None
+        };
+        let storage_credential = NamespaceStorageCredential {
+            storage_secret_id: secret_id,
+            sts_role_arn,
+        };
+
+        let mut t = C::Transaction::begin_write(state.v1_state.catalog).await?;
+        let old_secret_id = C::set_namespace_storage_credential(
+            warehouse_id,
+            namespace_id,
+            Some(storage_credential).filter(|c| !c.is_empty()),
+            t.transaction(),
+        )
+        .await?;
+        t.commit().await?;
+
+        // Delete the old secret if it exists - never fail the request if the deletion fails
+        if let Some(old_secret_id) = old_secret_id {
+            state
+                .v1_state
+                .secrets
+                .delete_secret(&old_secret_id)
+                .await
+                .map_err(|e| {
+                    tracing::warn!("Failed to delete old secret: {:?}", e.error);
+                })
+                .ok();
+        }
+
+        Ok(())
+    }
+
+    async fn get_namespace_storage_credential(
+        namespace_id: NamespaceId,
+        warehouse_id: WarehouseId,
+        state: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<GetNamespaceStorageCredentialResponse> {
+        //  ------------------- AUTHZ -------------------
+        let authorizer = state.v1_state.authz.clone();
+
+        let namespace =
+            C::get_namespace(warehouse_id, namespace_id, state.v1_state.catalog.clone()).await;
+
+        authorizer
+            .require_namespace_action(
+                &request_metadata,
+                warehouse_id,
+                namespace_id,
+                namespace,
+                CatalogNamespaceAction::CanGetMetadata,
+            )
+            .await?;
+
+        //  ------------------- BUSINESS LOGIC -------------------
+        let storage_credential =
+            C::get_namespace_storage_credential(warehouse_id, namespace_id, state.v1_state.catalog)
+                .await?
+                .unwrap_or_default();
+
+        Ok(GetNamespaceStorageCredentialResponse {
+            storage_credential_set: storage_credential.storage_secret_id.is_some(),
+            sts_role_arn: storage_credential.sts_role_arn,
+        })
+    }
 }
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use chrono::Utc;
 use iceberg_ext::catalog::rest::ErrorModel;
 use serde::{Deserialize, Serialize};
@@ -6,8 +8,8 @@ use uuid::Uuid;
 
 pub use crate::service::{
     storage::{
-        AdlsProfile, AzCredential, GcsCredential, GcsProfile, GcsServiceKey, S3Credential,
-        S3Profile, StorageCredential, StorageProfile,
+        AdlsProfile, AzCredential, GcsCredential, GcsExternalAccountConfig, GcsHmacKey, GcsProfile,
+        GcsServiceKey, S3Credential, S3Profile, StorageCredential, StorageProfile,
     },
     WarehouseStatus,
 };
@@ -103,12 +105,22 @@ pub trait Service<C: CatalogStore, A: Authorizer, S: SecretStore> {
         validate_project_name(&project_name)?;
         let mut t = C::Transaction::begin_write(context.v1_state.catalog).await?;
         let project_id = project_id.unwrap_or(ProjectId::from(uuid::Uuid::now_v7()));
-        C::create_project(&project_id, project_name, t.transaction()).await?;
+        C::create_project(&project_id, project_name.clone(), t.transaction()).await?;
         authorizer
             .create_project(&request_metadata, &project_id)
             .await?;
         t.commit().await?;
 
+        context
+            .v1_state
+            .hooks
+            .create_project(
+                project_id.clone(),
+                Arc::new(project_name),
+                Arc::new(request_metadata),
+            )
+            .await;
+
         Ok(CreateProjectResponse { project_id })
     }
 
@@ -135,6 +147,12 @@ pub trait Service<C: CatalogStore, A: Authorizer, S: SecretStore> {
         C::rename_project(&project_id, &request.new_name, transaction.transaction()).await?;
         transaction.commit().await?;
 
+        context
+            .v1_state
+            .hooks
+            .rename_project(project_id, Arc::new(request), Arc::new(request_metadata))
+            .await;
+
         Ok(())
     }
 
@@ -193,10 +211,16 @@ pub trait Service<C: CatalogStore, A: Authorizer, S: SecretStore> {
 
         C::delete_project(&project_id, transaction.transaction()).await?;
         authorizer
-            .delete_project(&request_metadata, project_id)
+            .delete_project(&request_metadata, project_id.clone())
             .await?;
         transaction.commit().await?;
 
+        context
+            .v1_state
+            .hooks
+            .delete_project(project_id, Arc::new(request_metadata))
+            .await;
+
         Ok(())
     }
 
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use super::{ApiServer, ProtectionResponse};
 use crate::{
     api::{ApiContext, RequestMetadata, Result},
@@ -55,6 +57,18 @@ where
         )
         .await?;
         t.commit().await?;
+
+        state
+            .v1_state
+            .hooks
+            .set_tabular_protection(
+                warehouse_id,
+                TabularId::View(view_id),
+                status.protected(),
+                Arc::new(request_metadata),
+            )
+            .await;
+
         Ok(ProtectionResponse {
             protected: status.protected(),
             updated_at: status.updated_at(),
@@ -0,0 +1,335 @@
+use std::{collections::HashMap, future::Future, sync::Arc};
+
+use cloudevents::{AttributesReader, Event};
+use futures::{stream::BoxStream, StreamExt};
+use tokio::sync::broadcast::{self, error::RecvError};
+use uuid::Uuid;
+
+use crate::{
+    request_metadata::RequestMetadata,
+    service::{
+        authz::{
+            AuthZTableOps, AuthZViewOps, Authorizer, AuthzNamespaceOps, AuthzWarehouseOps,
+            CatalogNamespaceAction, CatalogTableAction, CatalogViewAction, CatalogWarehouseAction,
+        },
+        CatalogNamespaceOps, CatalogStore, CatalogTabularOps, NamespaceId, TableId,
+        TabularListFlags, ViewId,
+    },
+    WarehouseId,
+};
+
+pub type WarehouseEventStream = BoxStream<'static, Arc<Event>>;
+
+/// Stream `history` followed by all events of `receiver` that belong to `warehouse_id`
+/// and are visible to the caller.
+///
+/// Permissions are checked once per table, view or namespace and cached for the
+/// lifetime of the stream. The stream ends when the caller's token expires, so that
+/// revoked permissions take effect once the client reconnects with a new token.
+///
+/// The stream also ends if the receiver falls behind. Clients are expected to reconnect and
+/// resume from the last event they received.
+pub(super) fn authorized_warehouse_events<C: CatalogStore, A: Authorizer>(
+    warehouse_id: WarehouseId,
+    history: Vec<Arc<Event>>,
+    mut receiver: broadcast::Receiver<Arc<Event>>,
+    authorizer: A,
+    catalog_state: C::State,
+    request_metadata: RequestMetadata,
+) -> WarehouseEventStream {
+    let token_expired = token_expiry(&request_metadata);
+    async_stream::stream! {
+        let mut visibility = HashMap::new();
+        for event in history {
+            if is_visible::<C, A>(&event, warehouse_id, &mut visibility, &authorizer, &catalog_state, &request_metadata).await {
+                yield event;
+            }
+        }
+
+        tokio::pin!(token_expired);
+        loop {
+            let received = tokio::select! {
+                () = &mut token_expired => {
+                    tracing::debug!("Closing event stream of warehouse {warehouse_id}, token expired");
+                    break;
+                }
+                received = receiver.recv() => received,
+            };
+            match received {
+                Ok(event) => {
+                    if is_visible::<C, A>(&event, warehouse_id, &mut visibility, &authorizer, &catalog_state, &request_metadata).await {
+                        yield event;
+                    }
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::debug!(
+                        "Closing event stream of warehouse {warehouse_id}, subscriber fell behind by {skipped} events"
+                    );
+                    break;
+                }
+                Err(RecvError::Closed) => break,
+            }
+        }
+    }
+    .boxed()
+}
+
+/// Completes when the token of the request expires, according to its `exp` claim.
+/// Never completes for requests without an expiring token.
+fn token_expiry(request_metadata: &RequestMetadata) -> impl Future<Output = ()> + Send + 'static {
+    let expires_in = request_metadata
+        .authentication()
+        .and_then(|authentication| authentication.claims().get("exp"))
+        .and_then(serde_json::Value::as_i64)
+        .and_then(|exp| chrono::DateTime::from_timestamp(exp, 0))
+        .map(|exp| (exp - chrono::Utc::now()).to_std().unwrap_or_default());
+    async move {
+        match expires_in {
+            Some(expires_in) => tokio::time::sleep(expires_in).await,
+            None => std::future::pending().await,
+        }
+    }
+}
+
+/// Object whose `CanGetMetadata` permission decides whether an event is visible.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum EventScope {
+    Table(TableId),
+    View(ViewId),
+    Namespace(NamespaceId),
+    Warehouse,
+}
+
+/// Events of tables and views require `CanGetMetadata` on the tabular, events of namespaces
+/// on the namespace and all other events of the warehouse on the warehouse.
+///
+/// Events of tabulars or namespaces that no longer exist are not visible, as permissions
+/// cannot be checked anymore.
+async fn is_visible<C: CatalogStore, A: Authorizer>(
+    event: &Event,
+    warehouse_id: WarehouseId,
+    visibility: &mut HashMap<EventScope, bool>,
+    authorizer: &A,
+    catalog_state: &C::State,
+    request_metadata: &RequestMetadata,
+) -> bool {
+    let Some(scope) = event_scope(event, warehouse_id) else {
+        return false;
+    };
+
+    let visible = if let Some(visible) = visibility.get(&scope) {
+        *visible
+    } else {
+        let visible = is_scope_visible::<C, A>(
+            scope,
+            warehouse_id,
+            authorizer,
+            catalog_state,
+            request_metadata,
+        )
+        .await;
+        visibility.insert(scope, visible);
+        visible
+    };
+
+    if !visible {
+        tracing::trace!(
+            "Event '{}' of warehouse {warehouse_id} is not visible to the subscriber",
+            event.id()
+        );
+    }
+    visible
+}
+
+/// Returns `None` for events of other warehouses and of unknown tabular types.
+fn event_scope(event: &Event, warehouse_id: WarehouseId) -> Option<EventScope> {
+    let extension_uuid = |name: &str| {
+        event
+            .extension(name)
+            .and_then(|value| Uuid::parse_str(&value.to_string()).ok())
+    };
+
+    if extension_uuid("warehouse-id") != Some(*warehouse_id) {
+        return None;
+    }
+
+    let tabular_type = event.extension("tabular-type").map(ToString::to_string);
+    match (tabular_type.as_deref(), extension_uuid("tabular-id")) {
+        (Some("Table"), Some(table_id)) => Some(EventScope::Table(table_id.into())),
+        (Some("View"), Some(view_id)) => Some(EventScope::View(view_id.into())),
+        (Some(_), _) => None,
+        (None, _) => Some(
+            extension_uuid("namespace-id")
+                .map_or(EventScope::Warehouse, |id| EventScope::Namespace(id.into())),
+        ),
+    }
+}
+
+async fn is_scope_visible<C: CatalogStore, A: Authorizer>(
+    scope: EventScope,
+    warehouse_id: WarehouseId,
+    authorizer: &A,
+    catalog_state: &C::State,
+    request_metadata: &RequestMetadata,
+) -> bool {
+    match scope {
+        EventScope::Table(table_id) => {
+            let table = C::get_table_info(
+                warehouse_id,
+                table_id,
+                TabularListFlags::all(),
+                catalog_state.clone(),
+            )
+            .await;
+            authorizer
+                .require_table_action(
+                    request_metadata,
+                    warehouse_id,
+                    table_id,
+                    table,
+                    CatalogTableAction::CanGetMetadata,
+                )
+                .await
+                .is_ok()
+        }
+        EventScope::View(view_id) => {
+            let view = C::get_view_info(
+                warehouse_id,
+                view_id,
+                TabularListFlags::all(),
+                catalog_state.clone(),
+            )
+            .await;
+            authorizer
+                .require_view_action(
+                    request_metadata,
+                    warehouse_id,
+                    view_id,
+                    view,
+                    CatalogViewAction::CanGetMetadata,
+                )
+                .await
+                .is_ok()
+        }
+        EventScope::Namespace(namespace_id) => {
+            let namespace =
+                C::get_namespace(warehouse_id, namespace_id, catalog_state.clone()).await;
+            authorizer
+                .require_namespace_action(
+                    request_metadata,
+                    warehouse_id,
+                    namespace_id,
+                    namespace,
+                    CatalogNamespaceAction::CanGetMetadata,
+                )
+                .await
+                .is_ok()
+        }
+        EventScope::Warehouse => authorizer
+            .require_warehouse_action(
+                request_metadata,
+                warehouse_id,
+                CatalogWarehouseAction::CanGetMetadata,
+            )
+            .await
+            .is_ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cloudevents::{EventBuilder, EventBuilderV10};
+
+    use super::*;
+    use crate::{
+        api::{
+            iceberg::{
+                types::Prefix,
+                v1::{views::ViewService, DataAccess, NamespaceParameters},
+            },
+            management::v1::warehouse::TabularDeleteProfile,
+        },
+        implementations::postgres::PostgresBackend,
+        server::CatalogServer,
+        service::authz::tests::HidingAuthorizer,
+        tests::create_view_request,
+    };
+
+    fn view_event(warehouse_id: WarehouseId, view_id: Uuid) -> Arc<Event> {
+        Arc::new(
+            EventBuilderV10::new()
+                .id(Uuid::now_v7().to_string())
+                .source("test")
+                .ty("updateView")
+                .extension("warehouse-id", warehouse_id.to_string())
+                .extension("tabular-type", "View")
+                .extension("tabular-id", view_id.to_string())
+                .build()
+                .unwrap(),
+        )
+    }
+
+    #[sqlx::test]
+    async fn test_unauthorized_events_are_filtered(pool: sqlx::PgPool) {
+        let authz = HidingAuthorizer::new();
+        let (ctx, warehouse) = crate::server::test::setup(
+            pool,
+            crate::server::test::memory_io_profile(),
+            None,
+            authz.clone(),
+            TabularDeleteProfile::Hard {},
+            None,
+        )
+        .await;
+        let warehouse_id = warehouse.warehouse_id;
+        let ns = crate::server::test::create_ns(
+            ctx.clone(),
+            warehouse_id.to_string(),
+            "ns1".to_string(),
+        )
+        .await;
+        let mut view_ids = vec![];
+        for name in ["visible", "hidden"] {
+            let view = CatalogServer::create_view(
+                NamespaceParameters {
+                    prefix: Some(Prefix(warehouse_id.to_string())),
+                    namespace: ns.namespace.clone(),
+                },
+                create_view_request(Some(name), None),
+                ctx.clone(),
+                DataAccess {
+                    vended_credentials: false,
+                    remote_signing: false,
+                },
+                RequestMetadata::new_unauthenticated(),
+            )
+            .await
+            .unwrap();
+            view_ids.push(view.metadata.uuid());
+        }
+        let (visible_id, hidden_id) = (view_ids[0], view_ids[1]);
+        authz.hide(&format!("view:{warehouse_id}/{hidden_id}"));
+
+        let history = vec![
+            view_event(warehouse_id, visible_id),
+            view_event(warehouse_id, hidden_id),
+            view_event(WarehouseId::new_random(), visible_id),
+            view_event(warehouse_id, visible_id),
+        ];
+        let expected = vec![history[0].id().to_string(), history[3].id().to_string()];
+        // The stream ends once the history is served, as the sender is dropped
+        let (_, receiver) = broadcast::channel(1);
+        let events = authorized_warehouse_events::<PostgresBackend, _>(
+            warehouse_id,
+            history,
+            receiver,
+            authz,
+            ctx.v1_state.catalog,
+            RequestMetadata::new_unauthenticated(),
+        )
+        .map(|event| event.id().to_string())
+        .collect::<Vec<_>>()
+        .await;
+        assert_eq!(events, expected);
+    }
+}
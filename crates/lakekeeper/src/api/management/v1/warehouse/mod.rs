@@ -1,3 +1,4 @@
+mod events;
 mod undrop;
 
 use std::sync::Arc;
@@ -9,11 +10,12 @@ use serde::{Deserialize, Serialize};
 use typed_builder::TypedBuilder;
 use utoipa::ToSchema;
 
+pub use self::events::WarehouseEventStream;
 use super::{DeleteWarehouseQuery, ProtectionResponse};
 pub use crate::service::{
     storage::{
-        AdlsProfile, AzCredential, GcsCredential, GcsProfile, GcsServiceKey, S3Credential,
-        S3Profile, StorageCredential, StorageProfile,
+        AdlsProfile, AzCredential, GcsCredential, GcsExternalAccountConfig, GcsHmacKey, GcsProfile,
+        GcsServiceKey, S3Credential, S3Profile, StorageCredential, StorageProfile,
     },
     WarehouseStatus,
 };
@@ -34,12 +36,22 @@ use crate::{
             AuthzWarehouseOps, CatalogProjectAction, CatalogTableAction, CatalogViewAction,
             CatalogWarehouseAction,
         },
-        secrets::SecretStore,
-        tasks::{tabular_expiration_queue::TabularExpirationTask, TaskFilter, TaskQueueName},
-        CatalogStore, CatalogTabularOps, CatalogTaskOps, CatalogWarehouseOps, NamespaceId, State,
-        TabularId, TabularListFlags, Transaction, ViewOrTableDeletionInfo,
+        secrets::{SecretStore, StoredSecret},
+        storage::{
+            credential_cache,
+            health::{validate_warehouse_storage, StorageValidationReport},
+            resolve_storage_credential, ValidationError,
+        },
+        tasks::{
+            cloud_events_backfill_queue::{CloudEventsBackfillPayload, CloudEventsBackfillTask},
+            tabular_expiration_queue::TabularExpirationTask,
+            TaskFilter, TaskMetadata, TaskQueueName,
+        },
+        AdditionalStorageProfile, CatalogStore, CatalogTabularOps, CatalogTaskOps,
+        CatalogWarehouseOps, EventLogEntry, EventLogFilter, ListEventLogResponse, NamedEntity,
+        NamespaceId, State, TabularId, TabularListFlags, Transaction, ViewOrTableDeletionInfo,
     },
-    ProjectId, WarehouseId,
+    ProjectId, WarehouseId, CONFIG,
 };
 
 #[derive(Debug, Deserialize, utoipa::IntoParams, Default)]
@@ -220,6 +232,18 @@ pub struct GetWarehouseResponse {
     pub status: WarehouseStatus,
     /// Whether the warehouse is protected from being deleted.
     pub protected: bool,
+    /// Named storage profiles in addition to the default storage profile.
+    pub additional_storage_profiles: Vec<AdditionalStorageProfileResponse>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct AdditionalStorageProfileResponse {
+    /// Name of the storage profile.
+    pub name: String,
+    pub storage_profile: StorageProfile,
+    /// New namespaces below any of these namespaces are placed on this storage profile.
+    pub namespace_prefixes: Vec<Vec<String>>,
 }
 
 #[derive(Debug, Clone, serde::Serialize, ToSchema)]
@@ -237,6 +261,25 @@ pub struct UpdateWarehouseCredentialRequest {
     pub new_storage_credential: Option<StorageCredential>,
 }
 
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct SetAdditionalStorageProfileRequest {
+    /// Storage profile to add to the warehouse.
+    /// It may not overlap with any other storage profile of the warehouse or project.
+    /// If a storage profile with this name already exists, the new profile must point
+    /// to the same location.
+    pub storage_profile: StorageProfile,
+    /// Optional storage credential for the storage profile.
+    /// If not set, an existing storage profile keeps its credential.
+    #[serde(default)]
+    pub storage_credential: Option<StorageCredential>,
+    /// New namespaces below any of these namespaces are placed on this storage profile,
+    /// unless another storage profile is selected via the `storage_profile` namespace property.
+    /// The most specific prefix of all storage profiles wins.
+    #[serde(default)]
+    pub namespace_prefixes: Vec<Vec<String>>,
+}
+
 impl axum::response::IntoResponse for CreateWarehouseResponse {
     fn into_response(self) -> axum::http::Response<axum::body::Body> {
         (http::StatusCode::CREATED, axum::Json(self)).into_response()
@@ -271,6 +314,96 @@ pub struct WarehouseStatisticsResponse {
     pub next_page_token: Option<String>,
 }
 
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct BackfillCloudEventsRequest {
+    /// Name of the `CloudEvent` backend the snapshot events are published to,
+    /// for example `nats-publisher` or `kafka-publisher`.
+    /// The event stream and the event log can not be backfilled.
+    pub sink: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct BackfillCloudEventsResponse {
+    /// Number of tables and views a snapshot event was scheduled for.
+    /// Tabulars with a pending backfill task are not scheduled again.
+    pub number_of_scheduled_tasks: usize,
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct ListWarehouseEventLogQuery {
+    /// Only return events of this table or view
+    #[serde(default)]
+    pub tabular_id: Option<uuid::Uuid>,
+    /// Only return events that occurred at or after this time (RFC 3339),
+    /// according to the `time` attribute of the `CloudEvent`
+    #[serde(default)]
+    #[param(value_type = Option<String>)]
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
+    /// Next page token
+    #[serde(default)]
+    pub page_token: Option<String>,
+    /// Signals an upper bound of the number of results that a client will receive.
+    /// Default: 100
+    #[serde(default)]
+    pub page_size: Option<i64>,
+}
+
+impl ListWarehouseEventLogQuery {
+    #[must_use]
+    pub fn pagination_query(&self) -> PaginationQuery {
+        PaginationQuery {
+            page_token: self
+                .page_token
+                .clone()
+                .map_or(PageToken::Empty, PageToken::Present),
+            page_size: self.page_size,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ListWarehouseEventLogResponse {
+    /// Logged events, oldest first.
+    pub events: Vec<EventLogEntryResponse>,
+    /// Token to fetch the next page
+    pub next_page_token: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct EventLogEntryResponse {
+    /// ID of the `CloudEvent`
+    pub id: uuid::Uuid,
+    /// Type of the `CloudEvent`, for example `updateTable`
+    pub event_type: String,
+    /// ID of the table or view the event belongs to
+    pub tabular_id: Option<uuid::Uuid>,
+    /// ID of the namespace the event belongs to
+    #[schema(value_type = Option<uuid::Uuid>)]
+    pub namespace_id: Option<NamespaceId>,
+    /// Time the event was logged
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// The `CloudEvent` in structured JSON format
+    pub event: serde_json::Value,
+}
+
+impl From<EventLogEntry> for EventLogEntryResponse {
+    fn from(entry: EventLogEntry) -> Self {
+        Self {
+            id: entry.event_id,
+            event_type: entry.event_type,
+            tabular_id: entry.tabular_id,
+            namespace_id: entry.namespace_id,
+            created_at: entry.created_at,
+            event: entry.event,
+        }
+    }
+}
+
 #[derive(Deserialize, Debug, ToSchema)]
 #[serde(rename_all = "kebab-case")]
 pub struct UndropTabularsRequest {
@@ -317,17 +450,24 @@ pub trait Service<C: CatalogStore, A: Authorizer, S: SecretStore> {
 
         // ------------------- Business Logic -------------------
         validate_warehouse_name(&warehouse_name)?;
-        storage_profile.normalize(storage_credential.as_ref())?;
+        let resolved_credential =
+            resolve_storage_credential(storage_credential.as_ref(), &project_id).await?;
+        storage_profile.normalize(resolved_credential.as_deref())?;
 
         // Run validation and overlap check in parallel
-        let validation_future =
-            storage_profile.validate_access(storage_credential.as_ref(), None, &request_metadata);
+        let validation_future = storage_profile.validate_access(
+            resolved_credential.as_deref(),
+            None,
+            &request_metadata,
+        );
         let overlap_check_future = async {
             let warehouses =
                 C::list_warehouses(&project_id, None, context.v1_state.catalog.clone()).await?;
 
             for w in &warehouses {
-                if storage_profile.is_overlapping_location(&w.storage_profile) {
+                if w.storage_profiles()
+                    .any(|p| storage_profile.is_overlapping_location(p))
+                {
                     return Err::<_, IcebergErrorResponse>(
                         ErrorModel::bad_request(
                             format!(
@@ -358,7 +498,7 @@ pub trait Service<C: CatalogStore, A: Authorizer, S: SecretStore> {
                 context
                     .v1_state
                     .secrets
-                    .create_secret(storage_credential)
+                    .create_secret(StoredSecret::scoped(&storage_credential, &project_id)?)
                     .await?,
             )
         } else {
@@ -366,9 +506,9 @@ pub trait Service<C: CatalogStore, A: Authorizer, S: SecretStore> {
         };
 
         let warehouse_id = C::create_warehouse(
-            warehouse_name,
+            warehouse_name.clone(),
             &project_id,
-            storage_profile,
+            storage_profile.clone(),
             delete_profile,
             secret_id,
             transaction.transaction(),
@@ -380,6 +520,18 @@ pub trait Service<C: CatalogStore, A: Authorizer, S: SecretStore> {
 
         transaction.commit().await?;
 
+        context
+            .v1_state
+            .hooks
+            .create_warehouse(
+                warehouse_id,
+                project_id,
+                Arc::new(warehouse_name),
+                Arc::new(storage_profile),
+                Arc::new(request_metadata),
+            )
+            .await;
+
         Ok(CreateWarehouseResponse { warehouse_id })
     }
 
@@ -477,6 +629,221 @@ pub trait Service<C: CatalogStore, A: Authorizer, S: SecretStore> {
         .await
     }
 
+    /// Subscribe to the `CloudEvents` of a warehouse.
+    ///
+    /// If `last_event_id` is given, events published after it are replayed first.
+    /// The stream is fed from the event log, which must be enabled.
+    async fn subscribe_warehouse_events(
+        warehouse_id: WarehouseId,
+        last_event_id: Option<String>,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<WarehouseEventStream> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                CatalogWarehouseAction::CanGetMetadata,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        if !CONFIG.event_log.enabled {
+            return Err(ErrorModel::bad_request(
+                "The event stream requires the event log to be enabled on this server",
+                "EventLogNotEnabled",
+                None,
+            )
+            .into());
+        }
+
+        let (history, receiver) = context
+            .v1_state
+            .event_stream
+            .subscribe(last_event_id.as_deref());
+
+        Ok(events::authorized_warehouse_events::<C, A>(
+            warehouse_id,
+            history,
+            receiver,
+            authorizer,
+            context.v1_state.catalog,
+            request_metadata,
+        ))
+    }
+
+    /// Schedule one `cloud_events_backfill` task per active table and view of the warehouse.
+    ///
+    /// Each task publishes a `snapshotTable` or `snapshotView` event to `request.sink`.
+    async fn backfill_cloud_events(
+        warehouse_id: WarehouseId,
+        request: BackfillCloudEventsRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<BackfillCloudEventsResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                CatalogWarehouseAction::CanControlAllTasks,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let backfill_sinks = &context.v1_state.cloud_event_backfill_sinks;
+        if !backfill_sinks.contains(&request.sink) {
+            return Err(ErrorModel::bad_request(
+                format!(
+                    "CloudEvent backend `{}` can not be backfilled. Available backends: [{}]",
+                    request.sink,
+                    backfill_sinks.iter().sorted().join(", ")
+                ),
+                "InvalidCloudEventBackend",
+                None,
+            )
+            .into());
+        }
+        let actor = serde_json::to_string(request_metadata.actor()).map_err(|e| {
+            ErrorModel::internal(
+                "Failed to serialize actor",
+                "SerializationError",
+                Some(Box::new(e)),
+            )
+        })?;
+        let payload = CloudEventsBackfillPayload {
+            sink: request.sink,
+            trace_id: request_metadata.request_id(),
+            actor,
+        };
+
+        // Each page is scheduled in its own transaction to keep transactions short
+        // for large warehouses. A retried request skips tabulars with a pending task.
+        let mut number_of_scheduled_tasks = 0;
+        let mut page_token = PageToken::Empty;
+        loop {
+            let mut transaction =
+                C::Transaction::begin_write(context.v1_state.catalog.clone()).await?;
+            let page = C::list_tabulars(
+                warehouse_id,
+                None,
+                TabularListFlags::active(),
+                transaction.transaction(),
+                None,
+                PaginationQuery {
+                    page_size: Some(CONFIG.pagination_size_max.into()),
+                    page_token,
+                },
+            )
+            .await?;
+            let mut next_page_token = None;
+            let tasks = page
+                .into_iter_with_page_tokens()
+                .map(|(tabular_id, info, token)| {
+                    next_page_token = Some(token);
+                    (
+                        TaskMetadata {
+                            warehouse_id,
+                            parent_task_id: None,
+                            entity_id: tabular_id.into(),
+                            entity_name: info.tabular_ident().clone().into_name_parts(),
+                            schedule_for: None,
+                        },
+                        payload.clone(),
+                    )
+                })
+                .collect_vec();
+            number_of_scheduled_tasks += CloudEventsBackfillTask::schedule_tasks::<C>(
+                tasks.into_iter(),
+                transaction.transaction(),
+            )
+            .await?
+            .len();
+            transaction.commit().await?;
+
+            let Some(token) = next_page_token else {
+                break;
+            };
+            page_token = PageToken::Present(token);
+        }
+
+        Ok(BackfillCloudEventsResponse {
+            number_of_scheduled_tasks,
+        })
+    }
+
+    /// List the `CloudEvents` of a warehouse stored in the event log.
+    async fn list_warehouse_event_log(
+        warehouse_id: WarehouseId,
+        query: ListWarehouseEventLogQuery,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<ListWarehouseEventLogResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        let [can_get_metadata, can_list_everything] = authorizer
+            .are_allowed_warehouse_actions_arr(
+                &request_metadata,
+                &[
+                    (warehouse_id, CatalogWarehouseAction::CanGetMetadata),
+                    (warehouse_id, CatalogWarehouseAction::CanListEverything),
+                ],
+            )
+            .await?
+            .into_inner();
+
+        if !can_get_metadata {
+            return Err(AuthZWarehouseActionForbidden::new(
+                warehouse_id,
+                CatalogWarehouseAction::CanGetMetadata,
+                request_metadata.actor().clone(),
+            )
+            .into());
+        }
+        // The log contains events of all tabulars and namespaces of the warehouse.
+        if !can_list_everything {
+            return Err(AuthZWarehouseActionForbidden::new(
+                warehouse_id,
+                CatalogWarehouseAction::CanListEverything,
+                request_metadata.actor().clone(),
+            )
+            .into());
+        }
+
+        // ------------------- Business Logic -------------------
+        if !CONFIG.event_log.enabled {
+            return Err(ErrorModel::bad_request(
+                "The event log is not enabled on this server",
+                "EventLogNotEnabled",
+                None,
+            )
+            .into());
+        }
+
+        let filter = EventLogFilter {
+            tabular_id: query.tabular_id,
+            since: query.since,
+        };
+        let ListEventLogResponse {
+            events,
+            next_page_token,
+        } = C::list_event_log(
+            warehouse_id,
+            filter,
+            query.pagination_query(),
+            context.v1_state.catalog,
+        )
+        .await?;
+
+        Ok(ListWarehouseEventLogResponse {
+            events: events.into_iter().map(Into::into).collect(),
+            next_page_token,
+        })
+    }
+
     async fn delete_warehouse(
         warehouse_id: WarehouseId,
         query: DeleteWarehouseQuery,
@@ -501,6 +868,12 @@ pub trait Service<C: CatalogStore, A: Authorizer, S: SecretStore> {
             .await?;
         transaction.commit().await?;
 
+        context
+            .v1_state
+            .hooks
+            .delete_warehouse(warehouse_id, query, Arc::new(request_metadata))
+            .await;
+
         Ok(())
     }
 
@@ -527,6 +900,12 @@ pub trait Service<C: CatalogStore, A: Authorizer, S: SecretStore> {
             C::set_warehouse_protected(warehouse_id, protection, transaction.transaction()).await?;
         transaction.commit().await?;
 
+        context
+            .v1_state
+            .hooks
+            .set_warehouse_protection(warehouse_id, status.protected, Arc::new(request_metadata))
+            .await;
+
         Ok(status)
     }
 
@@ -554,6 +933,12 @@ pub trait Service<C: CatalogStore, A: Authorizer, S: SecretStore> {
 
         transaction.commit().await?;
 
+        context
+            .v1_state
+            .hooks
+            .rename_warehouse(warehouse_id, Arc::new(request), Arc::new(request_metadata))
+            .await;
+
         Ok(())
     }
 
@@ -613,6 +998,16 @@ pub trait Service<C: CatalogStore, A: Authorizer, S: SecretStore> {
 
         transaction.commit().await?;
 
+        context
+            .v1_state
+            .hooks
+            .set_warehouse_status(
+                warehouse_id,
+                WarehouseStatus::Inactive,
+                Arc::new(request_metadata),
+            )
+            .await;
+
         Ok(())
     }
 
@@ -643,6 +1038,16 @@ pub trait Service<C: CatalogStore, A: Authorizer, S: SecretStore> {
 
         transaction.commit().await?;
 
+        context
+            .v1_state
+            .hooks
+            .set_warehouse_status(
+                warehouse_id,
+                WarehouseStatus::Active,
+                Arc::new(request_metadata),
+            )
+            .await;
+
         Ok(())
     }
 
@@ -668,16 +1073,18 @@ pub trait Service<C: CatalogStore, A: Authorizer, S: SecretStore> {
             storage_credential,
         } = request;
 
-        storage_profile.normalize(storage_credential.as_ref())?;
+        let warehouse =
+            C::require_warehouse_by_id(warehouse_id, context.v1_state.catalog.clone()).await?;
+        let resolved_credential =
+            resolve_storage_credential(storage_credential.as_ref(), &warehouse.project_id).await?;
+        storage_profile.normalize(resolved_credential.as_deref())?;
         Box::pin(storage_profile.validate_access(
-            storage_credential.as_ref(),
+            resolved_credential.as_deref(),
             None,
             &request_metadata,
         ))
         .await?;
 
-        let warehouse =
-            C::require_warehouse_by_id(warehouse_id, context.v1_state.catalog.clone()).await?;
         let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
         let storage_profile = warehouse.storage_profile.update_with(storage_profile)?;
         let old_secret_id = warehouse.storage_secret_id;
@@ -687,7 +1094,10 @@ pub trait Service<C: CatalogStore, A: Authorizer, S: SecretStore> {
                 context
                     .v1_state
                     .secrets
-                    .create_secret(storage_credential)
+                    .create_secret(StoredSecret::scoped(
+                        &storage_credential,
+                        &warehouse.project_id,
+                    )?)
                     .await?,
             )
         } else {
@@ -696,13 +1106,26 @@ pub trait Service<C: CatalogStore, A: Authorizer, S: SecretStore> {
 
         C::update_storage_profile(
             warehouse_id,
-            storage_profile,
+            storage_profile.clone(),
             secret_id,
             transaction.transaction(),
         )
         .await?;
 
         transaction.commit().await?;
+        // Credentials minted with the old configuration while the transaction was running
+        // must not be served anymore. Other replicas are notified by the catalog backend.
+        credential_cache::invalidate_warehouse(warehouse_id);
+
+        context
+            .v1_state
+            .hooks
+            .update_warehouse_storage(
+                warehouse_id,
+                Arc::new(storage_profile),
+                Arc::new(request_metadata),
+            )
+            .await;
 
         // Delete the old secret if it exists - never fail the request if the deletion fails
         if let Some(old_secret_id) = old_secret_id {
@@ -746,8 +1169,11 @@ pub trait Service<C: CatalogStore, A: Authorizer, S: SecretStore> {
         let old_secret_id = warehouse.storage_secret_id;
         let storage_profile = warehouse.storage_profile;
 
+        let resolved_credential =
+            resolve_storage_credential(new_storage_credential.as_ref(), &warehouse.project_id)
+                .await?;
         Box::pin(storage_profile.validate_access(
-            new_storage_credential.as_ref(),
+            resolved_credential.as_deref(),
             None,
             &request_metadata,
         ))
@@ -758,7 +1184,10 @@ pub trait Service<C: CatalogStore, A: Authorizer, S: SecretStore> {
                 context
                     .v1_state
                     .secrets
-                    .create_secret(new_storage_credential)
+                    .create_secret(StoredSecret::scoped(
+                        &new_storage_credential,
+                        &warehouse.project_id,
+                    )?)
                     .await?,
             )
         } else {
@@ -767,13 +1196,26 @@ pub trait Service<C: CatalogStore, A: Authorizer, S: SecretStore> {
 
         C::update_storage_profile(
             warehouse_id,
-            storage_profile,
+            storage_profile.clone(),
             secret_id,
             transaction.transaction(),
         )
         .await?;
 
         transaction.commit().await?;
+        // Credentials minted with the old configuration while the transaction was running
+        // must not be served anymore. Other replicas are notified by the catalog backend.
+        credential_cache::invalidate_warehouse(warehouse_id);
+
+        context
+            .v1_state
+            .hooks
+            .update_warehouse_storage(
+                warehouse_id,
+                Arc::new(storage_profile),
+                Arc::new(request_metadata),
+            )
+            .await;
 
         // Delete the old secret if it exists - never fail the request if the deletion fails
         if let Some(old_secret_id) = old_secret_id {
@@ -791,6 +1233,319 @@ pub trait Service<C: CatalogStore, A: Authorizer, S: SecretStore> {
         Ok(())
     }
 
+    async fn validate_storage(
+        warehouse_id: WarehouseId,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<StorageValidationReport> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                CatalogWarehouseAction::CanUpdateStorage,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let warehouse = C::require_warehouse_by_id(warehouse_id, context.v1_state.catalog).await?;
+
+        Ok(Box::pin(validate_warehouse_storage(
+            &warehouse,
+            &context.v1_state.secrets,
+            &request_metadata,
+        ))
+        .await)
+    }
+
+    async fn set_additional_storage_profile(
+        warehouse_id: WarehouseId,
+        name: String,
+        request: SetAdditionalStorageProfileRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                CatalogWarehouseAction::CanUpdateStorage,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        validate_storage_profile_name(&name)?;
+        let SetAdditionalStorageProfileRequest {
+            mut storage_profile,
+            storage_credential,
+            namespace_prefixes,
+        } = request;
+
+        let current_warehouse =
+            C::require_warehouse_by_id(warehouse_id, context.v1_state.catalog.clone()).await?;
+        // An existing profile keeps its credential if no new credential is provided
+        let kept_secret_id = if storage_credential.is_none() {
+            current_warehouse
+                .additional_storage_profiles
+                .iter()
+                .find(|p| p.name == name)
+                .and_then(|p| p.storage_secret_id)
+        } else {
+            None
+        };
+        let kept_credential = if let Some(secret_id) = kept_secret_id {
+            Some(
+                context
+                    .v1_state
+                    .secrets
+                    .get_secret_by_id::<StorageCredential>(secret_id)
+                    .await?
+                    .secret,
+            )
+        } else {
+            None
+        };
+
+        let resolved_credential = resolve_storage_credential(
+            storage_credential.as_ref().or(kept_credential.as_ref()),
+            &current_warehouse.project_id,
+        )
+        .await?;
+        storage_profile.normalize(resolved_credential.as_deref())?;
+        Box::pin(storage_profile.validate_access(
+            resolved_credential.as_deref(),
+            None,
+            &request_metadata,
+        ))
+        .await?;
+
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog.clone()).await?;
+        // Lock the warehouse, so that concurrent changes of additional storage profiles
+        // are not lost when the list of profiles is written back.
+        let warehouse =
+            C::require_warehouse_by_id_for_update(warehouse_id, transaction.transaction()).await?;
+        let existing = warehouse
+            .additional_storage_profiles
+            .iter()
+            .find(|p| p.name == name);
+        if storage_credential.is_none()
+            && existing.and_then(|p| p.storage_secret_id) != kept_secret_id
+        {
+            return Err(ErrorModel::conflict(
+                format!("Storage profile `{name}` was modified concurrently, please retry."),
+                "ConcurrentUpdateError",
+                None,
+            )
+            .into());
+        }
+        let storage_profile = match existing {
+            // The location of a storage profile in use may not change
+            Some(existing) => existing.storage_profile.update_with(storage_profile)?,
+            None => storage_profile,
+        };
+
+        // Storage profiles may not overlap, neither within the warehouse nor within the project
+        let other_profiles = std::iter::once(&warehouse.storage_profile).chain(
+            warehouse
+                .additional_storage_profiles
+                .iter()
+                .filter(|p| p.name != name)
+                .map(|p| &p.storage_profile),
+        );
+        for other in other_profiles {
+            if storage_profile.is_overlapping_location(other) {
+                return Err(ErrorModel::bad_request(
+                    "Storage profile overlaps with another storage profile of the warehouse",
+                    "StorageProfileOverlap",
+                    None,
+                )
+                .into());
+            }
+        }
+        let warehouses = C::list_warehouses(
+            &warehouse.project_id,
+            None,
+            context.v1_state.catalog.clone(),
+        )
+        .await?;
+        for w in warehouses.iter().filter(|w| w.id != warehouse_id) {
+            if w.storage_profiles()
+                .any(|p| storage_profile.is_overlapping_location(p))
+            {
+                return Err(ErrorModel::bad_request(
+                    format!(
+                        "Storage profile overlaps with existing warehouse {}",
+                        w.name
+                    ),
+                    "StorageProfileOverlap",
+                    None,
+                )
+                .into());
+            }
+        }
+
+        // The old secret is only replaced if a new credential is provided
+        let (storage_secret_id, old_secret_id) =
+            if let Some(storage_credential) = storage_credential {
+                let storage_secret_id = context
+                    .v1_state
+                    .secrets
+                    .create_secret(StoredSecret::scoped(
+                        &storage_credential,
+                        &warehouse.project_id,
+                    )?)
+                    .await?;
+                (
+                    Some(storage_secret_id),
+                    existing.and_then(|p| p.storage_secret_id),
+                )
+            } else {
+                (kept_secret_id, None)
+            };
+
+        let new_profile = AdditionalStorageProfile {
+            name: name.clone(),
+            storage_profile,
+            storage_secret_id,
+            namespace_prefixes,
+        };
+        let mut additional_storage_profiles = warehouse.additional_storage_profiles;
+        if let Some(p) = additional_storage_profiles
+            .iter_mut()
+            .find(|p| p.name == name)
+        {
+            *p = new_profile;
+        } else {
+            additional_storage_profiles.push(new_profile);
+        }
+
+        C::set_additional_storage_profiles(
+            warehouse_id,
+            additional_storage_profiles,
+            transaction.transaction(),
+        )
+        .await?;
+
+        transaction.commit().await?;
+        // Credentials minted with the old configuration while the transaction was running
+        // must not be served anymore. Other replicas are notified by the catalog backend.
+        credential_cache::invalidate_warehouse(warehouse_id);
+
+        context
+            .v1_state
+            .hooks
+            .update_warehouse_storage(
+                warehouse_id,
+                Arc::new(warehouse.storage_profile),
+                Arc::new(request_metadata),
+            )
+            .await;
+
+        // Delete the old secret if it exists - never fail the request if the deletion fails
+        if let Some(old_secret_id) = old_secret_id {
+            context
+                .v1_state
+                .secrets
+                .delete_secret(&old_secret_id)
+                .await
+                .map_err(|e| {
+                    tracing::warn!("Failed to delete old secret: {:?}", e.error);
+                })
+                .ok();
+        }
+
+        Ok(())
+    }
+
+    async fn delete_additional_storage_profile(
+        warehouse_id: WarehouseId,
+        name: String,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                CatalogWarehouseAction::CanUpdateStorage,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        // Lock the warehouse, so that concurrent changes of additional storage profiles
+        // are not lost when the list of profiles is written back.
+        let warehouse =
+            C::require_warehouse_by_id_for_update(warehouse_id, transaction.transaction()).await?;
+        let (removed, additional_storage_profiles): (Vec<_>, Vec<_>) = warehouse
+            .additional_storage_profiles
+            .into_iter()
+            .partition(|p| p.name == name);
+        let Some(removed) = removed.into_iter().next() else {
+            return Err(ErrorModel::not_found(
+                format!("Storage profile `{name}` does not exist in warehouse."),
+                "StorageProfileNotFound",
+                None,
+            )
+            .into());
+        };
+        let base_location = removed
+            .storage_profile
+            .base_location()
+            .map_err(ValidationError::from)?;
+
+        if C::is_location_in_use(warehouse_id, &base_location, transaction.transaction()).await? {
+            return Err(ErrorModel::conflict(
+                format!(
+                    "Storage profile `{name}` is still used by namespaces, tables or views of the warehouse."
+                ),
+                "StorageProfileInUse",
+                None,
+            )
+            .into());
+        }
+        C::set_additional_storage_profiles(
+            warehouse_id,
+            additional_storage_profiles,
+            transaction.transaction(),
+        )
+        .await?;
+        transaction.commit().await?;
+        // Credentials minted with the old configuration while the transaction was running
+        // must not be served anymore. Other replicas are notified by the catalog backend.
+        credential_cache::invalidate_warehouse(warehouse_id);
+
+        context
+            .v1_state
+            .hooks
+            .update_warehouse_storage(
+                warehouse_id,
+                Arc::new(warehouse.storage_profile),
+                Arc::new(request_metadata),
+            )
+            .await;
+
+        // Never fail the request if the deletion of the secret fails
+        if let Some(secret_id) = removed.storage_secret_id {
+            context
+                .v1_state
+                .secrets
+                .delete_secret(&secret_id)
+                .await
+                .map_err(|e| {
+                    tracing::warn!("Failed to delete secret of storage profile: {:?}", e.error);
+                })
+                .ok();
+        }
+
+        Ok(())
+    }
+
     async fn undrop_tabulars(
         warehouse_id: WarehouseId,
         request_metadata: RequestMetadata,
@@ -1029,6 +1784,19 @@ pub trait Service<C: CatalogStore, A: Authorizer, S: SecretStore> {
             .await?;
 
         // ------------------- Business Logic -------------------
+        if request
+            .max_concurrent_tasks
+            .flatten()
+            .is_some_and(|max| max < 1)
+        {
+            return Err(ErrorModel::bad_request(
+                "max-concurrent-tasks must be at least 1",
+                "InvalidQueueConfig",
+                None,
+            )
+            .into());
+        }
+
         let task_queues = context.v1_state.registered_task_queues;
 
         if let Some(validate_config_fn) = task_queues.validate_config_fn(queue_name).await {
@@ -1088,6 +1856,7 @@ pub trait Service<C: CatalogStore, A: Authorizer, S: SecretStore> {
                     queue_name: queue_name.clone(),
                 },
                 max_seconds_since_last_heartbeat: None,
+                max_concurrent_tasks: None,
             });
         Ok(config)
     }
@@ -1098,6 +1867,21 @@ pub trait Service<C: CatalogStore, A: Authorizer, S: SecretStore> {
 pub struct SetTaskQueueConfigRequest {
     pub queue_config: QueueConfig,
     pub max_seconds_since_last_heartbeat: Option<i64>,
+    /// Maximum number of tasks of this queue that run concurrently for the warehouse.
+    /// Other warehouses are not affected by this limit.
+    /// The current limit is kept if omitted, `null` removes the limit.
+    #[serde(default, deserialize_with = "deserialize_explicit_null")]
+    #[schema(value_type = Option<i32>)]
+    pub max_concurrent_tasks: Option<Option<i32>>,
+}
+
+/// Distinguish an explicit `null` (`Some(None)`) from an omitted field (`None`).
+fn deserialize_explicit_null<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -1109,6 +1893,7 @@ pub struct QueueConfig(pub(crate) serde_json::Value);
 pub struct GetTaskQueueConfigResponse {
     pub queue_config: QueueConfigResponse,
     pub max_seconds_since_last_heartbeat: Option<i64>,
+    pub max_concurrent_tasks: Option<i32>,
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -1148,6 +1933,15 @@ impl From<crate::service::GetWarehouseResponse> for GetWarehouseResponse {
             status: warehouse.status,
             delete_profile: warehouse.tabular_delete_profile,
             protected: warehouse.protected,
+            additional_storage_profiles: warehouse
+                .additional_storage_profiles
+                .into_iter()
+                .map(|p| AdditionalStorageProfileResponse {
+                    name: p.name,
+                    storage_profile: p.storage_profile,
+                    namespace_prefixes: p.namespace_prefixes,
+                })
+                .collect(),
         }
     }
 }
@@ -1173,8 +1967,46 @@ fn validate_warehouse_name(warehouse_name: &str) -> Result<()> {
     Ok(())
 }
 
+fn validate_storage_profile_name(name: &str) -> Result<()> {
+    if name.is_empty() {
+        return Err(ErrorModel::bad_request(
+            "Storage profile name cannot be empty",
+            "EmptyStorageProfileName",
+            None,
+        )
+        .into());
+    }
+
+    if name.len() > 128 {
+        return Err(ErrorModel::bad_request(
+            "Storage profile name must be shorter than 128 chars",
+            "StorageProfileNameTooLong",
+            None,
+        )
+        .into());
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod test {
+    #[test]
+    fn test_set_task_config_request_distinguishes_null_from_omitted() {
+        let omitted: super::SetTaskQueueConfigRequest =
+            serde_json::from_value(serde_json::json!({"queue-config": {}})).unwrap();
+        assert_eq!(omitted.max_concurrent_tasks, None);
+        let null: super::SetTaskQueueConfigRequest = serde_json::from_value(
+            serde_json::json!({"queue-config": {}, "max-concurrent-tasks": null}),
+        )
+        .unwrap();
+        assert_eq!(null.max_concurrent_tasks, Some(None));
+        let set: super::SetTaskQueueConfigRequest = serde_json::from_value(
+            serde_json::json!({"queue-config": {}, "max-concurrent-tasks": 3}),
+        )
+        .unwrap();
+        assert_eq!(set.max_concurrent_tasks, Some(Some(3)));
+    }
+
     #[test]
     fn test_de_create_warehouse_request() {
         let request = serde_json::json!({
@@ -1225,7 +2057,10 @@ mod test {
                 },
             },
             management::v1::{
-                warehouse::{ListDeletedTabularsQuery, Service as _, TabularDeleteProfile},
+                warehouse::{
+                    BackfillCloudEventsRequest, ListDeletedTabularsQuery, Service as _,
+                    TabularDeleteProfile,
+                },
                 ApiServer,
             },
             ApiContext,
@@ -1544,4 +2379,72 @@ mod test {
             assert_eq!(next_page_items[idx], format!("view-{i}"));
         }
     }
+
+    #[sqlx::test]
+    async fn test_backfill_cloud_events_validates_sink(pool: sqlx::PgPool) {
+        let (mut ctx, warehouse) = crate::server::test::setup(
+            pool.clone(),
+            crate::server::test::memory_io_profile(),
+            None,
+            crate::service::authz::AllowAllAuthorizer::default(),
+            TabularDeleteProfile::Hard {},
+            None,
+        )
+        .await;
+        ctx.v1_state.cloud_event_backfill_sinks =
+            std::sync::Arc::from(["kafka-publisher".to_string()]);
+        let ns = crate::server::test::create_ns(
+            ctx.clone(),
+            warehouse.warehouse_id.to_string(),
+            "ns1".to_string(),
+        )
+        .await;
+        let ns_params = NamespaceParameters {
+            prefix: Some(Prefix(warehouse.warehouse_id.to_string())),
+            namespace: ns.namespace.clone(),
+        };
+        for i in 0..3 {
+            CatalogServer::create_view(
+                ns_params.clone(),
+                create_view_request(Some(&format!("view-{i}")), None),
+                ctx.clone(),
+                DataAccess {
+                    vended_credentials: false,
+                    remote_signing: false,
+                },
+                RequestMetadata::new_unauthenticated(),
+            )
+            .await
+            .unwrap();
+        }
+
+        for sink in ["", "event-log", "event-stream", "nats-publisher"] {
+            let err = ApiServer::backfill_cloud_events(
+                warehouse.warehouse_id,
+                BackfillCloudEventsRequest {
+                    sink: sink.to_string(),
+                },
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+            .await
+            .unwrap_err();
+            assert_eq!(err.error.code, http::StatusCode::BAD_REQUEST, "{sink}");
+            assert_eq!(err.error.r#type, "InvalidCloudEventBackend");
+        }
+
+        let backfill = || {
+            ApiServer::backfill_cloud_events(
+                warehouse.warehouse_id,
+                BackfillCloudEventsRequest {
+                    sink: "kafka-publisher".to_string(),
+                },
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+        };
+        assert_eq!(backfill().await.unwrap().number_of_scheduled_tasks, 3);
+        // Tabulars with a pending task are not scheduled again
+        assert_eq!(backfill().await.unwrap().number_of_scheduled_tasks, 0);
+    }
 }
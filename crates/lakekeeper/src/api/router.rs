@@ -53,6 +53,8 @@ pub struct RouterArgs<C: CatalogStore, A: Authorizer + Clone, S: SecretStore, N:
     pub authenticator: Option<N>,
     pub state: ApiContext<State<A, C, S>>,
     pub service_health_provider: ServiceHealthProvider,
+    /// Served on `/health/storage`. Does not affect the state reported on `/health`.
+    pub storage_health_provider: Option<ServiceHealthProvider>,
     pub cors_origins: Option<&'static [HeaderValue]>,
     pub metrics_layer: Option<PrometheusMetricLayer<'static>>,
     pub endpoint_statistics_tracker_tx: EndpointStatisticsTrackerTx,
@@ -67,6 +69,7 @@ impl<C: CatalogStore, A: Authorizer + Clone, S: SecretStore, N: Authenticator +
             .field("state", &self.state)
             .field("authenticator", &self.authenticator)
             .field("service_health_provider", &self.service_health_provider)
+            .field("storage_health_provider", &self.storage_health_provider)
             .field("cors_origins", &self.cors_origins)
             .field(
                 "metrics_layer",
@@ -94,6 +97,7 @@ pub async fn new_full_router<
         authenticator,
         state,
         service_health_provider,
+        storage_health_provider,
         cors_origins,
         metrics_layer,
         endpoint_statistics_tracker_tx,
@@ -140,6 +144,17 @@ pub async fn new_full_router<
                 Json(health).into_response()
             }),
         );
+    let router = if let Some(storage_health_provider) = storage_health_provider {
+        router.route(
+            "/health/storage",
+            get(|| async move {
+                let health = storage_health_provider.collect_health().await;
+                Json(health).into_response()
+            }),
+        )
+    } else {
+        router
+    };
     let registered_api_config = state.v1_state.registered_task_queues.api_config().await;
     let router = maybe_merge_swagger_router(router, registered_api_config.iter().collect());
     let router = router
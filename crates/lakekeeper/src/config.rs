@@ -3,7 +3,7 @@
 
 use core::result::Result::Ok;
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     convert::Infallible,
     net::{IpAddr, Ipv4Addr},
     ops::{Deref, DerefMut},
@@ -24,6 +24,8 @@ use crate::{ProjectId, WarehouseId};
 
 const DEFAULT_RESERVED_NAMESPACES: [&str; 3] = ["system", "examples", "information_schema"];
 const DEFAULT_ENCRYPTION_KEY: &str = "<This is unsafe, please set a proper key>";
+/// Key id of secrets encrypted before key ids were introduced.
+const DEFAULT_ENCRYPTION_KEY_ID: &str = "default";
 
 pub static CONFIG: LazyLock<DynAppConfig> = LazyLock::new(get_config);
 pub static DEFAULT_PROJECT_ID: LazyLock<Option<ProjectId>> = LazyLock::new(|| {
@@ -76,6 +78,15 @@ fn get_config() -> DynAppConfig {
         tracing::warn!("THIS IS UNSAFE! Using default encryption key for secrets in postgres, please set a proper key using ICEBERG_REST__PG_ENCRYPTION_KEY environment variable.");
     }
 
+    // Fail early if the id of the current key is ambiguous
+    assert!(
+        !config
+            .pg_encryption_previous_keys
+            .contains_key(&config.pg_encryption_key_id),
+        "`pg_encryption_previous_keys` must not contain the id of `pg_encryption_key` (`{}`)",
+        config.pg_encryption_key_id
+    );
+
     config
 }
 
@@ -139,10 +150,19 @@ pub struct DynAppConfig {
 
     /// Enable GCP System Identities
     pub(crate) enable_gcp_system_credentials: bool,
+    /// Restrictions for workload identity federation credentials of GCS warehouses.
+    #[serde(default)]
+    pub(crate) gcp_workload_identity: GcpWorkloadIdentityConfig,
 
     // ------------- POSTGRES IMPLEMENTATION -------------
     #[redact]
     pub(crate) pg_encryption_key: String,
+    /// Id of `pg_encryption_key`. Stored alongside each secret to select the key for decryption.
+    pub(crate) pg_encryption_key_id: String,
+    /// Previous encryption keys by id. Used to decrypt secrets until they are re-encrypted
+    /// with `pg_encryption_key`.
+    #[redact]
+    pub(crate) pg_encryption_previous_keys: HashMap<String, String>,
     pub(crate) pg_database_url_read: Option<String>,
     pub(crate) pg_database_url_write: Option<String>,
     pub(crate) pg_host_r: Option<String>,
@@ -176,9 +196,34 @@ pub struct DynAppConfig {
     #[cfg(feature = "kafka")]
     pub kafka_config: Option<crate::service::event_publisher::kafka::KafkaConfig>,
 
+    // ------------- WEBHOOK CLOUDEVENTS -------------
+    /// Webhooks to send `CloudEvents` to, by name.
+    #[serde(default)]
+    pub webhooks: HashMap<String, crate::service::event_publisher::webhook::WebhookConfig>,
+
     // ------------- TRACING CLOUDEVENTS ----------
     pub log_cloudevents: Option<bool>,
 
+    // ------------- TABLE CHANGE CLOUDEVENTS ----------
+    /// Add computed schema, partition spec and snapshot changes to `updateTable` events.
+    pub cloud_events_table_changes: bool,
+
+    // ------------- CLOUDEVENTS OUTBOX ----------
+    #[serde(default)]
+    pub cloud_events_outbox: CloudEventsOutboxConfig,
+
+    // ------------- EVENT STREAM ----------
+    #[serde(default)]
+    pub event_stream: EventStreamConfig,
+
+    // ------------- EVENT LOG ----------
+    #[serde(default)]
+    pub event_log: EventLogConfig,
+
+    // ------------- STORAGE HEALTH CHECK ----------
+    #[serde(default)]
+    pub storage_health_check: StorageHealthCheckConfig,
+
     // ------------- AUTHENTICATION -------------
     pub openid_provider_uri: Option<Url>,
     /// Expected audience for the provided token.
@@ -219,6 +264,8 @@ pub struct DynAppConfig {
     pub kv2: Option<KV2Config>,
     // ------------- Secrets -------------
     pub secret_backend: SecretBackend,
+    #[serde(default)]
+    pub secret_references: SecretReferencesConfig,
     #[serde(
         deserialize_with = "crate::config::seconds_to_std_duration",
         serialize_with = "crate::config::serialize_std_duration_as_ms"
@@ -230,6 +277,8 @@ pub struct DynAppConfig {
     pub task_tabular_expiration_workers: usize,
     /// Number of workers to spawn for purging tabulars. (default: 2)
     pub task_tabular_purge_workers: usize,
+    /// Number of workers to spawn for publishing snapshot `CloudEvents` of tabulars. (default: 2)
+    pub task_cloud_events_backfill_workers: usize,
     // ------------- Tabular -------------
     /// Delay in seconds after which a tabular will be deleted
     #[serde(
@@ -252,6 +301,12 @@ pub struct DynAppConfig {
         serialize_with = "serialize_std_duration_as_ms"
     )]
     pub endpoint_stat_flush_interval: Duration,
+    #[serde(default)]
+    pub endpoint_statistics: EndpointStatisticsConfig,
+
+    // ------------- Caching -------------
+    #[serde(default)]
+    pub cache: CacheConfig,
 
     // ------------- Testing -------------
     pub skip_storage_validation: bool,
@@ -413,6 +468,210 @@ pub enum SecretBackend {
     Postgres,
 }
 
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+#[serde(default)]
+pub struct CacheConfig {
+    /// Cache warehouses, namespaces and tabulars resolved by id in memory. (default: true)
+    /// Entries are invalidated on change, also across replicas via Postgres `LISTEN/NOTIFY`.
+    pub enabled: bool,
+    /// Maximum number of entries per cache. (default: 10000)
+    pub max_capacity: u64,
+    /// Maximum time an entry is cached, bounding staleness if a notification is missed. (default: 300)
+    pub time_to_live_seconds: u64,
+    /// Cache vended storage credentials per storage secret, table location and permission level.
+    /// (default: true)
+    /// Entries are invalidated on all replicas if the storage profile or credential of the
+    /// warehouse changes.
+    pub vended_credentials_enabled: bool,
+    /// Cached credentials are evicted this many seconds before they expire, so that clients
+    /// always receive credentials with a remaining validity of at least this margin. (default: 300)
+    pub vended_credentials_expiry_margin_seconds: u64,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            // Caches are process-wide, tests run against many databases in parallel
+            enabled: !cfg!(test),
+            max_capacity: 10_000,
+            time_to_live_seconds: 300,
+            vended_credentials_enabled: !cfg!(test),
+            vended_credentials_expiry_margin_seconds: 300,
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+#[serde(default)]
+pub struct CloudEventsOutboxConfig {
+    /// Write `updateTable` events to an outbox table in the same transaction as the commit
+    /// and relay them to all `CloudEvent` sinks from there. (default: false)
+    /// Events are delivered at least once, also if a sink is unavailable or the server crashes.
+    /// All other events, e.g. of creating, dropping or renaming tabulars, are still
+    /// published from memory after the change is committed.
+    pub enabled: bool,
+    /// Interval to wait before polling the outbox again once all events are delivered.
+    ///
+    /// Accepts a string of format "{number}{ms|s}", e.g. "1s" or "500ms". (default: 1s)
+    #[serde(
+        deserialize_with = "seconds_to_std_duration",
+        serialize_with = "serialize_std_duration_as_ms"
+    )]
+    pub poll_interval: Duration,
+    /// Maximum number of events relayed to a sink per batch. (default: 100)
+    pub batch_size: i64,
+    /// Maximum interval between retries if a sink fails to publish an event. (default: 60s)
+    #[serde(
+        deserialize_with = "seconds_to_std_duration",
+        serialize_with = "serialize_std_duration_as_ms"
+    )]
+    pub max_retry_interval: Duration,
+    /// Duration for which a replica claims relaying to a sink. The claim is renewed with
+    /// every batch. If a batch takes longer, another replica may take over and events
+    /// can be delivered twice. (default: 60s)
+    #[serde(
+        deserialize_with = "seconds_to_std_duration",
+        serialize_with = "serialize_std_duration_as_ms"
+    )]
+    pub lease_duration: Duration,
+}
+
+impl Default for CloudEventsOutboxConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            poll_interval: Duration::from_secs(1),
+            batch_size: 100,
+            max_retry_interval: Duration::from_secs(60),
+            lease_duration: Duration::from_secs(60),
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+#[serde(default)]
+pub struct EventStreamConfig {
+    /// Number of recent events kept in memory to resume the event stream from a
+    /// `Last-Event-ID`. Set to 0 to disable resuming. (default: 1000)
+    pub history_size: usize,
+    /// Number of events buffered per subscriber. Subscribers that fall further behind are
+    /// disconnected and have to resume the stream. (default: 1024)
+    pub channel_capacity: usize,
+    /// Interval of keep-alive comments sent on idle streams. (default: 15s)
+    #[serde(
+        deserialize_with = "seconds_to_std_duration",
+        serialize_with = "serialize_std_duration_as_ms"
+    )]
+    pub keep_alive_interval: Duration,
+}
+
+impl Default for EventStreamConfig {
+    fn default() -> Self {
+        Self {
+            history_size: 1000,
+            channel_capacity: 1024,
+            keep_alive_interval: Duration::from_secs(15),
+        }
+    }
+}
+
+/// Sinks for endpoint statistics in addition to the catalog database.
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug, Default)]
+#[serde(default)]
+pub struct EndpointStatisticsConfig {
+    /// Expose endpoint statistics as Prometheus counters on the metrics port. (default: false)
+    pub prometheus_enabled: bool,
+    /// Record endpoint statistics with the global `OpenTelemetry` meter provider. (default: false)
+    pub opentelemetry_enabled: bool,
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+#[serde(default)]
+pub struct EventLogConfig {
+    /// Store all `CloudEvents` of warehouses in the catalog database, so that they can be
+    /// queried via the management API. (default: false)
+    pub enabled: bool,
+    /// Time in seconds after which logged events are deleted. (default: 30 days)
+    #[serde(
+        deserialize_with = "seconds_to_duration",
+        serialize_with = "duration_to_seconds"
+    )]
+    pub retention_seconds: chrono::Duration,
+}
+
+impl Default for EventLogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            retention_seconds: chrono::Duration::days(30),
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+#[serde(default)]
+pub struct StorageHealthCheckConfig {
+    /// Periodically validate access to the storage profiles of all active warehouses
+    /// and report the result per warehouse on `/health/storage`. Failing warehouses
+    /// do not affect the server health reported on `/health`. (default: false)
+    pub enabled: bool,
+    /// Interval between two validations of all warehouses. (default: 1h)
+    #[serde(
+        deserialize_with = "seconds_to_std_duration",
+        serialize_with = "serialize_std_duration_as_ms"
+    )]
+    pub interval: Duration,
+}
+
+impl Default for StorageHealthCheckConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval: Duration::from_secs(3600),
+        }
+    }
+}
+
+/// The credential configuration of an external account is provided by the creator of a
+/// warehouse, but the subject token is read and exchanged by Lakekeeper.
+/// Only token endpoints of GCP and subject token sources allowed here can be used.
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+#[serde(default)]
+pub struct GcpWorkloadIdentityConfig {
+    /// Universe domain of the token endpoints. `token_url` must point to `sts.<domain>`,
+    /// `service_account_impersonation_url` to `iamcredentials.<domain>`.
+    /// (default: googleapis.com)
+    pub universe_domain: String,
+    /// Comma separated list of files that can be used as `credential_source.file`.
+    /// (default: none)
+    #[serde(
+        deserialize_with = "deserialize_audience",
+        serialize_with = "serialize_audience"
+    )]
+    pub allowed_credential_files: Option<Vec<String>>,
+    /// Comma separated list of URLs that can be used as `credential_source.url`.
+    /// (default: none)
+    #[serde(
+        deserialize_with = "deserialize_audience",
+        serialize_with = "serialize_audience"
+    )]
+    pub allowed_credential_urls: Option<Vec<String>>,
+    /// Allow AWS credential sources (`credential_source.environment_id`), which use the
+    /// AWS credentials of the host running Lakekeeper. (default: false)
+    pub allow_aws_credential_source: bool,
+}
+
+impl Default for GcpWorkloadIdentityConfig {
+    fn default() -> Self {
+        Self {
+            universe_domain: "googleapis.com".to_string(),
+            allowed_credential_files: None,
+            allowed_credential_urls: None,
+            allow_aws_credential_source: false,
+        }
+    }
+}
+
 #[derive(Clone, Serialize, Deserialize, PartialEq, Debug, Default)]
 pub struct DebugConfig {
     /// If true, log all request bodies to the debug log for debugging purposes.
@@ -420,6 +679,20 @@ pub struct DebugConfig {
     pub log_request_bodies: bool,
 }
 
+/// Sources of secrets referenced by storage credentials of type `reference`.
+/// A source is disabled if it is not configured.
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug, Default)]
+#[serde(default)]
+pub struct SecretReferencesConfig {
+    /// Directory containing referenced secrets as files, i.e. a mounted Kubernetes secret.
+    /// Secrets of a project are read from the subdirectory named after the project id.
+    pub directory: Option<PathBuf>,
+    /// Prefix of environment variables containing referenced secrets.
+    /// Only environment variables with this prefix, followed by the project id and `__`,
+    /// can be referenced.
+    pub env_prefix: Option<String>,
+}
+
 #[derive(Clone, Serialize, Deserialize, PartialEq, Redact)]
 pub struct KV2Config {
     pub url: Url,
@@ -443,6 +716,8 @@ impl Default for DynAppConfig {
                 "examples".to_string(),
             ])),
             pg_encryption_key: DEFAULT_ENCRYPTION_KEY.to_string(),
+            pg_encryption_key_id: DEFAULT_ENCRYPTION_KEY_ID.to_string(),
+            pg_encryption_previous_keys: HashMap::new(),
             pg_database_url_read: None,
             pg_database_url_write: None,
             pg_host_r: None,
@@ -464,6 +739,7 @@ impl Default for DynAppConfig {
             s3_enable_direct_system_credentials: false,
             s3_require_external_id_for_system_credentials: true,
             enable_gcp_system_credentials: false,
+            gcp_workload_identity: GcpWorkloadIdentityConfig::default(),
             nats_address: None,
             nats_topic: None,
             nats_creds_file: None,
@@ -473,7 +749,9 @@ impl Default for DynAppConfig {
             #[cfg(feature = "kafka")]
             kafka_config: None,
             kafka_topic: None,
+            webhooks: HashMap::new(),
             log_cloudevents: None,
+            cloud_events_table_changes: false,
             authz_backend: AuthZBackend::default(),
             openid_provider_uri: None,
             openid_audience: None,
@@ -487,16 +765,24 @@ impl Default for DynAppConfig {
             bind_ip: IpAddr::V4(Ipv4Addr::UNSPECIFIED),
             health_check_frequency_seconds: 10,
             kv2: None,
+            secret_references: SecretReferencesConfig::default(),
             secret_backend: SecretBackend::Postgres,
             task_poll_interval: Duration::from_secs(10),
             task_tabular_expiration_workers: 2,
             task_tabular_purge_workers: 2,
+            task_cloud_events_backfill_workers: 2,
             default_tabular_expiration_delay_seconds: chrono::Duration::days(7),
             pagination_size_default: 100,
             pagination_size_max: 1000,
             endpoint_stat_flush_interval: Duration::from_secs(30),
+            endpoint_statistics: EndpointStatisticsConfig::default(),
             serve_swagger_ui: true,
             skip_storage_validation: false,
+            cache: CacheConfig::default(),
+            cloud_events_outbox: CloudEventsOutboxConfig::default(),
+            event_stream: EventStreamConfig::default(),
+            event_log: EventLogConfig::default(),
+            storage_health_check: StorageHealthCheckConfig::default(),
             debug: DebugConfig::default(),
         }
     }
@@ -919,6 +1205,239 @@ mod test {
         });
     }
 
+    #[test]
+    fn test_cache_config() {
+        figment::Jail::expect_with(|jail| {
+            jail.set_env("LAKEKEEPER_TEST__CACHE__ENABLED", "false");
+            jail.set_env("LAKEKEEPER_TEST__CACHE__TIME_TO_LIVE_SECONDS", "10");
+            jail.set_env(
+                "LAKEKEEPER_TEST__CACHE__VENDED_CREDENTIALS_EXPIRY_MARGIN_SECONDS",
+                "600",
+            );
+            let config = get_config();
+            assert!(!config.cache.enabled);
+            assert_eq!(config.cache.time_to_live_seconds, 10);
+            assert_eq!(config.cache.max_capacity, 10_000);
+            assert_eq!(config.cache.vended_credentials_expiry_margin_seconds, 600);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_cloud_events_outbox_config() {
+        figment::Jail::expect_with(|jail| {
+            jail.set_env("LAKEKEEPER_TEST__CLOUD_EVENTS_OUTBOX__ENABLED", "true");
+            jail.set_env(
+                "LAKEKEEPER_TEST__CLOUD_EVENTS_OUTBOX__POLL_INTERVAL",
+                "250ms",
+            );
+            let config = get_config();
+            assert!(config.cloud_events_outbox.enabled);
+            assert_eq!(
+                config.cloud_events_outbox.poll_interval,
+                Duration::from_millis(250)
+            );
+            assert_eq!(config.cloud_events_outbox.batch_size, 100);
+            assert_eq!(
+                config.cloud_events_outbox.max_retry_interval,
+                Duration::from_secs(60)
+            );
+            assert_eq!(
+                config.cloud_events_outbox.lease_duration,
+                Duration::from_secs(60)
+            );
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_endpoint_statistics_config() {
+        figment::Jail::expect_with(|_jail| {
+            let config = get_config();
+            assert!(!config.endpoint_statistics.prometheus_enabled);
+            assert!(!config.endpoint_statistics.opentelemetry_enabled);
+            Ok(())
+        });
+
+        figment::Jail::expect_with(|jail| {
+            jail.set_env(
+                "LAKEKEEPER_TEST__ENDPOINT_STATISTICS__PROMETHEUS_ENABLED",
+                "true",
+            );
+            jail.set_env(
+                "LAKEKEEPER_TEST__ENDPOINT_STATISTICS__OPENTELEMETRY_ENABLED",
+                "true",
+            );
+            let config = get_config();
+            assert!(config.endpoint_statistics.prometheus_enabled);
+            assert!(config.endpoint_statistics.opentelemetry_enabled);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_event_log_config() {
+        figment::Jail::expect_with(|jail| {
+            jail.set_env("LAKEKEEPER_TEST__EVENT_LOG__ENABLED", "true");
+            let config = get_config();
+            assert!(config.event_log.enabled);
+            assert_eq!(
+                config.event_log.retention_seconds,
+                chrono::Duration::days(30)
+            );
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_pg_encryption_keys() {
+        figment::Jail::expect_with(|jail| {
+            let config = get_config();
+            assert_eq!(config.pg_encryption_key_id, "default");
+            assert!(config.pg_encryption_previous_keys.is_empty());
+
+            jail.set_env("LAKEKEEPER_TEST__PG_ENCRYPTION_KEY", "new-key");
+            jail.set_env("LAKEKEEPER_TEST__PG_ENCRYPTION_KEY_ID", "2025-10");
+            jail.set_env(
+                "LAKEKEEPER_TEST__PG_ENCRYPTION_PREVIOUS_KEYS",
+                "{default=\"old-key\"}",
+            );
+            let config = get_config();
+            assert_eq!(config.pg_encryption_key, "new-key");
+            assert_eq!(config.pg_encryption_key_id, "2025-10");
+            assert_eq!(
+                config.pg_encryption_previous_keys,
+                HashMap::from([("default".to_string(), "old-key".to_string())])
+            );
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_secret_references_config() {
+        figment::Jail::expect_with(|jail| {
+            let config = get_config();
+            assert_eq!(config.secret_references, SecretReferencesConfig::default());
+
+            jail.set_env(
+                "LAKEKEEPER_TEST__SECRET_REFERENCES__DIRECTORY",
+                "/var/run/secrets/lakekeeper",
+            );
+            jail.set_env(
+                "LAKEKEEPER_TEST__SECRET_REFERENCES__ENV_PREFIX",
+                "LAKEKEEPER_SECRET_",
+            );
+            let config = get_config();
+            assert_eq!(
+                config.secret_references.directory,
+                Some(PathBuf::from("/var/run/secrets/lakekeeper"))
+            );
+            assert_eq!(
+                config.secret_references.env_prefix.as_deref(),
+                Some("LAKEKEEPER_SECRET_")
+            );
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_gcp_workload_identity_config() {
+        figment::Jail::expect_with(|jail| {
+            let config = get_config();
+            assert_eq!(
+                config.gcp_workload_identity,
+                GcpWorkloadIdentityConfig::default()
+            );
+
+            jail.set_env(
+                "LAKEKEEPER_TEST__GCP_WORKLOAD_IDENTITY__ALLOWED_CREDENTIAL_FILES",
+                "/var/run/secrets/tokens/gcp-token,/var/run/secrets/tokens/other",
+            );
+            jail.set_env(
+                "LAKEKEEPER_TEST__GCP_WORKLOAD_IDENTITY__ALLOW_AWS_CREDENTIAL_SOURCE",
+                "true",
+            );
+            let config = get_config();
+            assert_eq!(
+                config.gcp_workload_identity.allowed_credential_files,
+                Some(vec![
+                    "/var/run/secrets/tokens/gcp-token".to_string(),
+                    "/var/run/secrets/tokens/other".to_string()
+                ])
+            );
+            assert_eq!(config.gcp_workload_identity.allowed_credential_urls, None);
+            assert!(config.gcp_workload_identity.allow_aws_credential_source);
+            assert_eq!(
+                config.gcp_workload_identity.universe_domain,
+                "googleapis.com"
+            );
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_storage_health_check_config() {
+        figment::Jail::expect_with(|jail| {
+            let config = get_config();
+            assert!(!config.storage_health_check.enabled);
+            assert_eq!(
+                config.storage_health_check.interval,
+                Duration::from_secs(3600)
+            );
+
+            jail.set_env("LAKEKEEPER_TEST__STORAGE_HEALTH_CHECK__ENABLED", "true");
+            jail.set_env("LAKEKEEPER_TEST__STORAGE_HEALTH_CHECK__INTERVAL", "600");
+            let config = get_config();
+            assert!(config.storage_health_check.enabled);
+            assert_eq!(
+                config.storage_health_check.interval,
+                Duration::from_secs(600)
+            );
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_event_stream_config() {
+        figment::Jail::expect_with(|jail| {
+            jail.set_env("LAKEKEEPER_TEST__EVENT_STREAM__HISTORY_SIZE", "10");
+            jail.set_env("LAKEKEEPER_TEST__EVENT_STREAM__KEEP_ALIVE_INTERVAL", "5s");
+            let config = get_config();
+            assert_eq!(config.event_stream.history_size, 10);
+            assert_eq!(config.event_stream.channel_capacity, 1024);
+            assert_eq!(
+                config.event_stream.keep_alive_interval,
+                Duration::from_secs(5)
+            );
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_webhooks_config() {
+        figment::Jail::expect_with(|jail| {
+            jail.set_env(
+                "LAKEKEEPER_TEST__WEBHOOKS__ALERTS__URL",
+                "https://example.com/hook",
+            );
+            jail.set_env("LAKEKEEPER_TEST__WEBHOOKS__ALERTS__SECRET", "my-secret");
+            jail.set_env(
+                "LAKEKEEPER_TEST__WEBHOOKS__ALERTS__EVENT_TYPES",
+                "updateTable,dropTable",
+            );
+            let config = get_config();
+            let webhook = &config.webhooks["alerts"];
+            assert_eq!(webhook.url.as_str(), "https://example.com/hook");
+            assert_eq!(webhook.secret.as_deref(), Some("my-secret"));
+            assert_eq!(
+                webhook.event_types,
+                Some(vec!["updateTable".to_string(), "dropTable".to_string()])
+            );
+            assert!(webhook.warehouse_ids.is_none());
+            Ok(())
+        });
+    }
+
     #[test]
     fn test_use_x_forwarded_headers() {
         figment::Jail::expect_with(|jail| {
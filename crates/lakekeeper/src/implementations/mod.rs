@@ -7,7 +7,7 @@ use crate::implementations::postgres::{get_reader_pool, get_writer_pool};
 use crate::{
     service::{
         health::{Health, HealthExt},
-        secrets::{Secret, SecretInStorage},
+        secrets::{Secret, SecretInStorage, StoredSecret},
         SecretStore,
     },
     SecretIdent,
@@ -78,11 +78,25 @@ impl SecretStore for Secrets {
         &self,
         secret_id: SecretIdent,
     ) -> crate::api::Result<Secret<S>> {
-        match self {
+        // Secrets may be references to files or environment variables,
+        // which are resolved on every read.
+        let Secret {
+            secret_id,
+            secret,
+            created_at,
+            updated_at,
+        } = match self {
             #[cfg(feature = "sqlx-postgres")]
-            Self::Postgres(state) => state.get_secret_by_id(secret_id).await,
-            Self::KV2(state) => state.get_secret_by_id(secret_id).await,
-        }
+            Self::Postgres(state) => state.get_secret_by_id::<StoredSecret>(secret_id).await,
+            Self::KV2(state) => state.get_secret_by_id::<StoredSecret>(secret_id).await,
+        }?;
+
+        Ok(Secret {
+            secret_id,
+            secret: secret.resolve().await?,
+            created_at,
+            updated_at,
+        })
     }
 
     async fn create_secret<
@@ -0,0 +1,354 @@
+use iceberg_ext::catalog::rest::ErrorModel;
+use uuid::Uuid;
+
+use crate::{
+    api::iceberg::v1::PaginationQuery,
+    implementations::postgres::{
+        dbutils::DBErrorHandler,
+        pagination::{PaginateToken, V1PaginateToken},
+    },
+    service::{
+        AccessRequest, AccessRequestId, AccessRequestStatus, ListAccessRequestsResponse,
+        NewAccessRequest, Result, UserId,
+    },
+    WarehouseId, CONFIG,
+};
+
+#[derive(sqlx::FromRow, Debug)]
+struct AccessRequestRow {
+    access_request_id: Uuid,
+    warehouse_id: Uuid,
+    object: String,
+    relation: String,
+    requested_by: String,
+    justification: Option<String>,
+    duration_seconds: i64,
+    status: AccessRequestStatus,
+    decided_by: Option<String>,
+    decided_at: Option<chrono::DateTime<chrono::Utc>>,
+    expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl TryFrom<AccessRequestRow> for AccessRequest {
+    type Error = ErrorModel;
+
+    fn try_from(
+        AccessRequestRow {
+            access_request_id,
+            warehouse_id,
+            object,
+            relation,
+            requested_by,
+            justification,
+            duration_seconds,
+            status,
+            decided_by,
+            decided_at,
+            expires_at,
+            created_at,
+        }: AccessRequestRow,
+    ) -> std::result::Result<Self, Self::Error> {
+        let parse_user_id = |user_id: String| {
+            UserId::try_from(user_id.as_str()).map_err(|e| {
+                ErrorModel::internal(
+                    format!("Invalid user id stored for access request {access_request_id}"),
+                    "InvalidUserIdInDatabase",
+                    Some(Box::new(e)),
+                )
+            })
+        };
+
+        Ok(Self {
+            access_request_id: AccessRequestId::from(access_request_id),
+            warehouse_id: WarehouseId::from(warehouse_id),
+            object,
+            relation,
+            requested_by: parse_user_id(requested_by)?,
+            justification,
+            duration: chrono::Duration::seconds(duration_seconds),
+            status,
+            decided_by: decided_by.map(parse_user_id).transpose()?,
+            decided_at,
+            expires_at,
+            created_at,
+        })
+    }
+}
+
+pub(crate) async fn create_access_request<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    NewAccessRequest {
+        warehouse_id,
+        object,
+        relation,
+        requested_by,
+        justification,
+        duration,
+    }: NewAccessRequest<'_>,
+    connection: E,
+) -> Result<AccessRequest> {
+    let row = sqlx::query_as!(
+        AccessRequestRow,
+        r#"
+        INSERT INTO access_request (access_request_id, warehouse_id, object, relation, requested_by, justification, duration_seconds)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING access_request_id, warehouse_id, object, relation, requested_by, justification,
+            duration_seconds, status as "status: AccessRequestStatus", decided_by, decided_at, expires_at, created_at
+        "#,
+        Uuid::now_v7(),
+        *warehouse_id,
+        object,
+        relation,
+        requested_by.to_string(),
+        justification,
+        duration.num_seconds(),
+    )
+    .fetch_one(connection)
+    .await
+    .map_err(|e| match e {
+        sqlx::Error::Database(db_error) if db_error.is_foreign_key_violation() => {
+            ErrorModel::not_found(
+                format!("Warehouse {warehouse_id} not found"),
+                "WarehouseNotFound",
+                Some(Box::new(db_error)),
+            )
+        }
+        _ => e.into_error_model("Error creating access request"),
+    })?;
+
+    Ok(AccessRequest::try_from(row)?)
+}
+
+pub(crate) async fn get_access_request<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    access_request_id: AccessRequestId,
+    connection: E,
+) -> Result<Option<AccessRequest>> {
+    let row = sqlx::query_as!(
+        AccessRequestRow,
+        r#"
+        SELECT access_request_id, warehouse_id, object, relation, requested_by, justification,
+            duration_seconds, status as "status: AccessRequestStatus", decided_by, decided_at, expires_at, created_at
+        FROM access_request
+        WHERE access_request_id = $1
+        "#,
+        *access_request_id,
+    )
+    .fetch_optional(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching access request"))?;
+
+    Ok(row.map(AccessRequest::try_from).transpose()?)
+}
+
+pub(crate) async fn list_access_requests<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    warehouse_id: WarehouseId,
+    filter_status: Option<AccessRequestStatus>,
+    filter_requested_by: Option<&UserId>,
+    PaginationQuery {
+        page_size,
+        page_token,
+    }: PaginationQuery,
+    connection: E,
+) -> Result<ListAccessRequestsResponse> {
+    let page_size = CONFIG.page_size_or_pagination_default(page_size);
+
+    let token = page_token
+        .as_option()
+        .map(PaginateToken::try_from)
+        .transpose()?;
+
+    let (token_ts, token_id) = token
+        .as_ref()
+        .map(
+            |PaginateToken::V1(V1PaginateToken { created_at, id }): &PaginateToken<Uuid>| {
+                (created_at, id)
+            },
+        )
+        .unzip();
+
+    let access_requests = sqlx::query_as!(
+        AccessRequestRow,
+        r#"
+        SELECT access_request_id, warehouse_id, object, relation, requested_by, justification,
+            duration_seconds, status as "status: AccessRequestStatus", decided_by, decided_at, expires_at, created_at
+        FROM access_request ar
+        WHERE warehouse_id = $1
+            AND ($2::access_request_status IS NULL OR status = $2)
+            AND ($3::text IS NULL OR requested_by = $3)
+            --- PAGINATION
+            AND ((ar.created_at > $4 OR $4 IS NULL) OR (ar.created_at = $4 AND ar.access_request_id > $5))
+        ORDER BY ar.created_at, ar.access_request_id ASC
+        LIMIT $6
+        "#,
+        *warehouse_id,
+        filter_status as Option<AccessRequestStatus>,
+        filter_requested_by.map(ToString::to_string),
+        token_ts,
+        token_id,
+        page_size,
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching access requests"))?
+    .into_iter()
+    .map(AccessRequest::try_from)
+    .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    let next_page_token = access_requests.last().map(|r| {
+        PaginateToken::V1(V1PaginateToken::<Uuid> {
+            created_at: r.created_at,
+            id: r.access_request_id.into(),
+        })
+        .to_string()
+    });
+
+    Ok(ListAccessRequestsResponse {
+        access_requests,
+        next_page_token,
+    })
+}
+
+pub(crate) async fn decide_access_request<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    access_request_id: AccessRequestId,
+    status: AccessRequestStatus,
+    decided_by: &UserId,
+    expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    connection: E,
+) -> Result<Option<AccessRequest>> {
+    let row = sqlx::query_as!(
+        AccessRequestRow,
+        r#"
+        UPDATE access_request
+        SET status = $2, decided_by = $3, decided_at = now(), expires_at = $4
+        WHERE access_request_id = $1 AND status = 'pending'
+        RETURNING access_request_id, warehouse_id, object, relation, requested_by, justification,
+            duration_seconds, status as "status: AccessRequestStatus", decided_by, decided_at, expires_at, created_at
+        "#,
+        *access_request_id,
+        status as AccessRequestStatus,
+        decided_by.to_string(),
+        expires_at,
+    )
+    .fetch_optional(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error updating access request"))?;
+
+    Ok(row.map(AccessRequest::try_from).transpose()?)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        implementations::postgres::{
+            warehouse::test::initialize_warehouse, CatalogState, PostgresTransaction,
+        },
+        service::Transaction,
+    };
+
+    #[sqlx::test]
+    async fn test_access_request_lifecycle(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let requester = UserId::new_unchecked("oidc", "requester");
+        let approver = UserId::new_unchecked("oidc", "approver");
+
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        let created = create_access_request(
+            NewAccessRequest {
+                warehouse_id,
+                object: "warehouse:foo",
+                relation: "modify",
+                requested_by: &requester,
+                justification: Some("incident 42"),
+                duration: chrono::Duration::hours(4),
+            },
+            &mut **t.transaction(),
+        )
+        .await
+        .unwrap();
+        t.commit().await.unwrap();
+        assert_eq!(created.status, AccessRequestStatus::Pending);
+        assert_eq!(created.duration, chrono::Duration::hours(4));
+
+        let pending = list_access_requests(
+            warehouse_id,
+            Some(AccessRequestStatus::Pending),
+            None,
+            PaginationQuery::empty(),
+            &state.read_pool(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(pending.access_requests, vec![created.clone()]);
+
+        let expires_at = chrono::Utc::now() + created.duration;
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        let approved = decide_access_request(
+            created.access_request_id,
+            AccessRequestStatus::Approved,
+            &approver,
+            Some(expires_at),
+            &mut **t.transaction(),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        t.commit().await.unwrap();
+        assert_eq!(approved.status, AccessRequestStatus::Approved);
+        assert_eq!(approved.decided_by, Some(approver.clone()));
+
+        // Decided requests cannot be decided again
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        let again = decide_access_request(
+            created.access_request_id,
+            AccessRequestStatus::Rejected,
+            &approver,
+            None,
+            &mut **t.transaction(),
+        )
+        .await
+        .unwrap();
+        t.commit().await.unwrap();
+        assert!(again.is_none());
+
+        let pending = list_access_requests(
+            warehouse_id,
+            Some(AccessRequestStatus::Pending),
+            Some(&requester),
+            PaginationQuery::empty(),
+            &state.read_pool(),
+        )
+        .await
+        .unwrap();
+        assert!(pending.access_requests.is_empty());
+
+        let loaded = get_access_request(created.access_request_id, &state.read_pool())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(loaded, approved);
+    }
+}
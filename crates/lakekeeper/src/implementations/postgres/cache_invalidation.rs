@@ -0,0 +1,94 @@
+use super::{
+    listener::{listen, ListenerEvent},
+    CatalogState,
+};
+use crate::{
+    service::{cache, CacheInvalidation},
+    CancellationToken,
+};
+
+/// Channel used by the `notify_cache_invalidation` trigger.
+const CACHE_INVALIDATION_CHANNEL: &str = "lakekeeper_cache_invalidation";
+
+pub(super) async fn listen_for_cache_invalidations(
+    catalog_state: CatalogState,
+    cancellation_token: CancellationToken,
+) -> anyhow::Result<()> {
+    // Notifications are not forwarded to read replicas, listen on the primary
+    listen(
+        &catalog_state.write_pool(),
+        CACHE_INVALIDATION_CHANNEL,
+        "Cache invalidation listener",
+        &cancellation_token,
+        |event| async move {
+            match event {
+                // Entries cached while the listener was disconnected might be stale
+                ListenerEvent::Connected { reconnected: true } | ListenerEvent::ConnectionLost => {
+                    tracing::warn!("Invalidating all caches, invalidations might have been lost");
+                    cache::invalidate_all();
+                }
+                ListenerEvent::Connected { reconnected: false } => {}
+                ListenerEvent::Notification(payload) => {
+                    match serde_json::from_str::<CacheInvalidation>(&payload) {
+                        Ok(invalidation) => {
+                            tracing::trace!("Received cache invalidation: {invalidation:?}");
+                            cache::invalidate(invalidation).await;
+                        }
+                        Err(e) => {
+                            tracing::error!("Failed to parse cache invalidation `{payload}`: {e}");
+                        }
+                    }
+                }
+            }
+        },
+    )
+    .await;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        implementations::postgres::{
+            warehouse::test::initialize_warehouse, PostgresBackend, PostgresTransaction,
+        },
+        service::{CatalogWarehouseOps as _, Transaction as _},
+        ProjectId,
+    };
+
+    #[sqlx::test]
+    async fn test_cached_warehouse_is_invalidated_after_commit(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        // Pending invalidations are tracked per task
+        tokio::spawn(cache::with_caches_enabled(async move {
+            let project_id = ProjectId::from(uuid::Uuid::new_v4());
+            let warehouse_id =
+                initialize_warehouse(state.clone(), None, Some(&project_id), None, true).await;
+
+            let mut transaction = PostgresTransaction::begin_write(state.clone())
+                .await
+                .unwrap();
+            PostgresBackend::rename_warehouse(warehouse_id, "new_name", transaction.transaction())
+                .await
+                .unwrap();
+
+            // A concurrent reader caches the previous state before the rename is committed
+            let warehouse = PostgresBackend::get_warehouse_by_id(warehouse_id, state.clone())
+                .await
+                .unwrap()
+                .unwrap();
+            assert_ne!(warehouse.name, "new_name");
+
+            transaction.commit().await.unwrap();
+
+            let warehouse = PostgresBackend::get_warehouse_by_id(warehouse_id, state.clone())
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(warehouse.name, "new_name");
+        }))
+        .await
+        .unwrap();
+    }
+}
@@ -6,7 +6,16 @@ use iceberg_ext::catalog::rest::ErrorModel;
 use lakekeeper_io::Location;
 
 use super::{
+    access_request::{
+        create_access_request, decide_access_request, get_access_request, list_access_requests,
+    },
     bootstrap::{bootstrap, get_validation_data},
+    cache_invalidation::listen_for_cache_invalidations,
+    cloud_event_outbox::{
+        claim_cloud_event_outbox_batch, get_cloud_event_outbox_lag, insert_cloud_event_outbox,
+        prune_cloud_event_outbox, release_cloud_event_outbox_lease, set_cloud_event_outbox_cursor,
+    },
+    event_log::{insert_event_log, list_event_log, prune_event_log, stream_logged_events},
     namespace::{
         create_namespace, drop_namespace, get_namespace, list_namespaces,
         update_namespace_properties,
@@ -41,7 +50,10 @@ use crate::{
     },
     implementations::postgres::{
         endpoint_statistics::list::list_statistics,
-        namespace::set_namespace_protected,
+        namespace::{
+            get_namespace_storage_credential, resolve_namespace_storage_credential,
+            set_namespace_protected, set_namespace_storage_credential,
+        },
         role::search_role,
         tabular::{
             clear_tabular_deleted_at, drop_tabular, get_tabular_infos_by_idents,
@@ -52,38 +64,46 @@ use crate::{
         },
         tasks::{
             cancel_scheduled_tasks, check_and_heartbeat_task, get_task_details,
-            get_task_queue_config, list_tasks, pick_task, queue_task_batch, record_failure,
-            record_success, request_tasks_stop, reschedule_tasks_for, resolve_tasks,
-            set_task_queue_config,
+            get_task_queue_config, get_task_queue_stats, list_tasks, listen_for_task_wakeups,
+            pick_task, queue_task_batch, record_failure, record_success, request_tasks_stop,
+            reschedule_tasks_for, resolve_tasks, set_task_queue_config,
         },
         user::{create_or_update_user, delete_user, list_users, search_user},
-        warehouse::{get_warehouse_stats, set_warehouse_protection},
+        warehouse::{
+            get_warehouse_by_id_for_update, get_warehouse_stats, is_location_in_use,
+            set_additional_storage_profiles, set_warehouse_protection,
+        },
     },
     service::{
         authn::UserId,
+        event_publisher::stream::CloudEventStream,
         storage::StorageProfile,
         tasks::{
             Task, TaskAttemptId, TaskCheckState, TaskFilter, TaskId, TaskInput, TaskQueueName,
         },
-        CatalogCreateNamespaceError, CatalogCreateWarehouseError, CatalogDeleteWarehouseError,
-        CatalogGetNamespaceError, CatalogGetWarehouseByIdError, CatalogGetWarehouseByNameError,
-        CatalogListNamespaceError, CatalogListWarehousesError, CatalogNamespaceDropError,
-        CatalogRenameWarehouseError, CatalogSearchTabularResponse,
-        CatalogSetNamespaceProtectedError, CatalogStore, CatalogUpdateNamespacePropertiesError,
-        CatalogView, ClearTabularDeletedAtError, CommitTableTransactionError, CommitViewError,
-        CreateNamespaceRequest, CreateOrUpdateUserResponse, CreateTableError, CreateViewError,
-        DropTabularError, GetProjectResponse, GetTabularInfoByLocationError, GetTabularInfoError,
-        GetWarehouseResponse, ListNamespacesQuery, ListTabularsError, LoadTableError,
-        LoadTableResponse, LoadViewError, MarkTabularAsDeletedError, Namespace, NamespaceDropInfo,
-        NamespaceId, NamespaceIdentOrId, ProjectId, RenameTabularError, ResolvedTask, Result,
-        RoleId, SearchTabularError, ServerInfo, SetTabularProtectionError,
-        SetWarehouseDeletionProfileError, SetWarehouseProtectedError, SetWarehouseStatusError,
-        StagedTableId, TableCommit, TableCreation, TableId, TableIdent, TableInfo, TabularId,
-        TabularIdentBorrowed, TabularListFlags, Transaction, UpdateWarehouseStorageProfileError,
+        AccessRequest, AccessRequestId, AccessRequestStatus, AdditionalStorageProfile,
+        CatalogBackendError, CatalogCreateNamespaceError, CatalogCreateWarehouseError,
+        CatalogDeleteWarehouseError, CatalogGetNamespaceError, CatalogGetWarehouseByIdError,
+        CatalogGetWarehouseByNameError, CatalogListNamespaceError, CatalogListWarehousesError,
+        CatalogNamespaceDropError, CatalogRenameWarehouseError, CatalogSearchTabularResponse,
+        CatalogSetNamespaceProtectedError, CatalogSetNamespaceStorageCredentialError, CatalogStore,
+        CatalogUpdateNamespacePropertiesError, CatalogView, ClearTabularDeletedAtError,
+        CommitTableTransactionError, CommitViewError, CreateNamespaceRequest,
+        CreateOrUpdateUserResponse, CreateTableError, CreateViewError, DropTabularError,
+        EventLogFilter, GetProjectResponse, GetTabularInfoByLocationError, GetTabularInfoError,
+        GetWarehouseResponse, ListAccessRequestsResponse, ListEventLogResponse,
+        ListNamespacesQuery, ListTabularsError, LoadTableError, LoadTableResponse, LoadViewError,
+        MarkTabularAsDeletedError, Namespace, NamespaceDropInfo, NamespaceId, NamespaceIdentOrId,
+        NamespaceStorageCredential, NewAccessRequest, NewEventLogEntry, OutboxCursor, OutboxEntry,
+        OutboxLag, ProjectId, RenameTabularError, ResolvedTask, Result, RoleId, SearchTabularError,
+        ServerInfo, SetTabularProtectionError, SetWarehouseDeletionProfileError,
+        SetWarehouseProtectedError, SetWarehouseStatusError, StagedTableId, TableCommit,
+        TableCreation, TableId, TableIdent, TableInfo, TabularId, TabularIdentBorrowed,
+        TabularListFlags, TaskQueueStats, Transaction, UpdateWarehouseStorageProfileError,
         ViewCommit, ViewId, ViewInfo, ViewOrTableDeletionInfo, ViewOrTableInfo, WarehouseId,
         WarehouseStatus,
     },
-    SecretIdent,
+    CancellationToken, SecretIdent,
 };
 
 #[async_trait::async_trait]
@@ -97,6 +117,20 @@ impl CatalogStore for super::PostgresBackend {
         get_validation_data(&catalog_state.read_pool()).await
     }
 
+    async fn listen_for_cache_invalidations(
+        catalog_state: Self::State,
+        cancellation_token: CancellationToken,
+    ) -> anyhow::Result<()> {
+        listen_for_cache_invalidations(catalog_state, cancellation_token).await
+    }
+
+    async fn listen_for_task_wakeups(
+        catalog_state: Self::State,
+        cancellation_token: CancellationToken,
+    ) -> anyhow::Result<()> {
+        listen_for_task_wakeups(&catalog_state.write_pool(), cancellation_token).await
+    }
+
     // ---------------- Bootstrap ----------------
     async fn bootstrap<'a>(
         terms_accepted: bool,
@@ -417,6 +451,134 @@ impl CatalogStore for super::PostgresBackend {
         list_projects(project_ids, &mut **transaction).await
     }
 
+    async fn create_access_request<'a>(
+        request: NewAccessRequest<'_>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<AccessRequest> {
+        create_access_request(request, &mut **transaction).await
+    }
+
+    async fn get_access_request(
+        access_request_id: AccessRequestId,
+        catalog_state: Self::State,
+    ) -> Result<Option<AccessRequest>> {
+        get_access_request(access_request_id, &catalog_state.read_pool()).await
+    }
+
+    async fn list_access_requests(
+        warehouse_id: WarehouseId,
+        filter_status: Option<AccessRequestStatus>,
+        filter_requested_by: Option<&UserId>,
+        pagination: PaginationQuery,
+        catalog_state: Self::State,
+    ) -> Result<ListAccessRequestsResponse> {
+        list_access_requests(
+            warehouse_id,
+            filter_status,
+            filter_requested_by,
+            pagination,
+            &catalog_state.read_pool(),
+        )
+        .await
+    }
+
+    async fn decide_access_request<'a>(
+        access_request_id: AccessRequestId,
+        status: AccessRequestStatus,
+        decided_by: &UserId,
+        expires_at: Option<chrono::DateTime<chrono::Utc>>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<AccessRequest>> {
+        decide_access_request(
+            access_request_id,
+            status,
+            decided_by,
+            expires_at,
+            &mut **transaction,
+        )
+        .await
+    }
+
+    async fn insert_cloud_event_outbox<'a>(
+        events: Vec<serde_json::Value>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        insert_cloud_event_outbox(events, transaction).await
+    }
+
+    async fn claim_cloud_event_outbox_batch<'a>(
+        sink_name: &str,
+        lease_holder: uuid::Uuid,
+        lease_duration: std::time::Duration,
+        limit: i64,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<Vec<OutboxEntry>>> {
+        claim_cloud_event_outbox_batch(sink_name, lease_holder, lease_duration, limit, transaction)
+            .await
+    }
+
+    async fn set_cloud_event_outbox_cursor<'a>(
+        sink_name: &str,
+        lease_holder: uuid::Uuid,
+        cursor: OutboxCursor,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<bool> {
+        set_cloud_event_outbox_cursor(sink_name, lease_holder, cursor, transaction).await
+    }
+
+    async fn release_cloud_event_outbox_lease(
+        sink_name: &str,
+        lease_holder: uuid::Uuid,
+        catalog_state: Self::State,
+    ) -> Result<()> {
+        release_cloud_event_outbox_lease(sink_name, lease_holder, &catalog_state.write_pool()).await
+    }
+
+    async fn prune_cloud_event_outbox(
+        sink_names: &[String],
+        catalog_state: Self::State,
+    ) -> Result<u64> {
+        prune_cloud_event_outbox(sink_names, &catalog_state.write_pool()).await
+    }
+
+    async fn get_cloud_event_outbox_lag(
+        sink_names: &[String],
+        catalog_state: Self::State,
+    ) -> Result<Vec<OutboxLag>> {
+        get_cloud_event_outbox_lag(sink_names, &catalog_state.read_pool()).await
+    }
+
+    async fn insert_event_log(
+        entries: Vec<NewEventLogEntry>,
+        catalog_state: Self::State,
+    ) -> Result<()> {
+        insert_event_log(entries, &catalog_state.write_pool()).await
+    }
+
+    async fn list_event_log(
+        warehouse_id: WarehouseId,
+        filter: EventLogFilter,
+        pagination: PaginationQuery,
+        catalog_state: Self::State,
+    ) -> Result<ListEventLogResponse> {
+        list_event_log(warehouse_id, filter, pagination, &catalog_state.read_pool()).await
+    }
+
+    async fn stream_logged_events(
+        catalog_state: Self::State,
+        stream: CloudEventStream,
+        cancellation_token: CancellationToken,
+    ) -> anyhow::Result<()> {
+        stream_logged_events(&catalog_state.write_pool(), stream, cancellation_token).await
+    }
+
+    async fn prune_event_log(
+        older_than: chrono::DateTime<chrono::Utc>,
+        catalog_state: Self::State,
+    ) -> Result<u64> {
+        prune_event_log(older_than, &catalog_state.write_pool()).await
+    }
+
     async fn get_endpoint_statistics(
         project_id: ProjectId,
         warehouse_id: WarehouseFilter,
@@ -449,6 +611,13 @@ impl CatalogStore for super::PostgresBackend {
         get_warehouse_by_id(warehouse_id, &state.read_pool()).await
     }
 
+    async fn get_warehouse_by_id_for_update_impl<'a>(
+        warehouse_id: WarehouseId,
+        transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'a>,
+    ) -> std::result::Result<Option<GetWarehouseResponse>, CatalogGetWarehouseByIdError> {
+        get_warehouse_by_id_for_update(warehouse_id, transaction).await
+    }
+
     async fn get_warehouse_stats(
         warehouse_id: WarehouseId,
         pagination_query: PaginationQuery,
@@ -615,6 +784,37 @@ impl CatalogStore for super::PostgresBackend {
         set_namespace_protected(warehouse_id, namespace_id, protect, transaction).await
     }
 
+    async fn get_namespace_storage_credential_impl(
+        warehouse_id: WarehouseId,
+        namespace_id: NamespaceId,
+        state: Self::State,
+    ) -> std::result::Result<Option<NamespaceStorageCredential>, CatalogGetNamespaceError> {
+        get_namespace_storage_credential(warehouse_id, namespace_id, &state.read_pool()).await
+    }
+
+    async fn resolve_namespace_storage_credential_impl(
+        warehouse_id: WarehouseId,
+        namespace_id: NamespaceId,
+        state: Self::State,
+    ) -> std::result::Result<Option<NamespaceStorageCredential>, CatalogGetNamespaceError> {
+        resolve_namespace_storage_credential(warehouse_id, namespace_id, &state.read_pool()).await
+    }
+
+    async fn set_namespace_storage_credential_impl<'a>(
+        warehouse_id: WarehouseId,
+        namespace_id: NamespaceId,
+        storage_credential: Option<NamespaceStorageCredential>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> std::result::Result<Option<SecretIdent>, CatalogSetNamespaceStorageCredentialError> {
+        set_namespace_storage_credential(
+            warehouse_id,
+            namespace_id,
+            storage_credential,
+            transaction,
+        )
+        .await
+    }
+
     async fn set_warehouse_protected_impl(
         warehouse_id: WarehouseId,
         protect: bool,
@@ -623,6 +823,23 @@ impl CatalogStore for super::PostgresBackend {
         set_warehouse_protection(warehouse_id, protect, transaction).await
     }
 
+    async fn set_additional_storage_profiles_impl<'a>(
+        warehouse_id: WarehouseId,
+        additional_storage_profiles: Vec<AdditionalStorageProfile>,
+        transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'a>,
+    ) -> std::result::Result<(), UpdateWarehouseStorageProfileError> {
+        set_additional_storage_profiles(warehouse_id, additional_storage_profiles, transaction)
+            .await
+    }
+
+    async fn is_location_in_use_impl<'a>(
+        warehouse_id: WarehouseId,
+        location: &Location,
+        transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'a>,
+    ) -> std::result::Result<bool, CatalogBackendError> {
+        is_location_in_use(warehouse_id, location, transaction).await
+    }
+
     async fn pick_new_task_impl(
         queue_name: &TaskQueueName,
         default_max_time_since_last_heartbeat: Duration,
@@ -743,4 +960,8 @@ impl CatalogStore for super::PostgresBackend {
     ) -> Result<Option<GetTaskQueueConfigResponse>> {
         get_task_queue_config(&state.read_pool(), warehouse_id, queue_name).await
     }
+
+    async fn get_task_queue_stats_impl(state: Self::State) -> Result<Vec<TaskQueueStats>> {
+        get_task_queue_stats(&state.read_pool()).await
+    }
 }
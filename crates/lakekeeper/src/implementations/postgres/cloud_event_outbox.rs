@@ -0,0 +1,397 @@
+use sqlx::PgConnection;
+
+use crate::{
+    implementations::postgres::dbutils::DBErrorHandler,
+    service::{OutboxCursor, OutboxEntry, OutboxLag, Result},
+};
+
+pub(crate) async fn insert_cloud_event_outbox(
+    events: Vec<serde_json::Value>,
+    conn: &mut PgConnection,
+) -> Result<()> {
+    if events.is_empty() {
+        return Ok(());
+    }
+
+    sqlx::query!(
+        r#"
+        INSERT INTO cloud_event_outbox (event)
+        SELECT event FROM UNNEST($1::jsonb[]) WITH ORDINALITY AS e(event, idx)
+        ORDER BY idx
+        "#,
+        &events
+    )
+    .execute(conn)
+    .await
+    .map_err(|e| e.into_error_model("Error inserting events into cloud event outbox"))?;
+
+    Ok(())
+}
+
+pub(crate) async fn claim_cloud_event_outbox_batch(
+    sink_name: &str,
+    lease_holder: uuid::Uuid,
+    lease_duration: std::time::Duration,
+    limit: i64,
+    conn: &mut PgConnection,
+) -> Result<Option<Vec<OutboxEntry>>> {
+    let lease_duration_ms = i64::try_from(lease_duration.as_millis()).unwrap_or(i64::MAX);
+    sqlx::query!(
+        r#"
+        INSERT INTO cloud_event_outbox_cursor (sink_name)
+        VALUES ($1)
+        ON CONFLICT (sink_name) DO NOTHING
+        "#,
+        sink_name
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| e.into_error_model("Error creating cloud event outbox cursor"))?;
+
+    // Another replica holds an unexpired lease: it is relaying to this sink right now
+    let Some(cursor) = sqlx::query!(
+        r#"
+        UPDATE cloud_event_outbox_cursor
+        SET lease_holder = $2,
+            lease_expires_at = now() + $3::bigint * interval '1 millisecond'
+        WHERE sink_name = $1
+            AND (lease_holder IS NULL OR lease_holder = $2 OR lease_expires_at < now())
+        RETURNING last_transaction_id, last_sequence_number
+        "#,
+        sink_name,
+        lease_holder,
+        lease_duration_ms
+    )
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(|e| e.into_error_model("Error claiming cloud event outbox cursor"))?
+    else {
+        return Ok(None);
+    };
+
+    // Transactions below the snapshot's xmin are finished, no older transaction can still
+    // insert events before the returned ones.
+    let entries = sqlx::query!(
+        r#"
+        SELECT transaction_id, sequence_number, event, created_at
+        FROM cloud_event_outbox
+        WHERE (transaction_id, sequence_number) > ($1, $2)
+            AND transaction_id < pg_snapshot_xmin(pg_current_snapshot())::text::bigint
+        ORDER BY transaction_id, sequence_number
+        LIMIT $3
+        "#,
+        cursor.last_transaction_id,
+        cursor.last_sequence_number,
+        limit
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching events from cloud event outbox"))?
+    .into_iter()
+    .map(|row| OutboxEntry {
+        cursor: OutboxCursor {
+            transaction_id: row.transaction_id,
+            sequence_number: row.sequence_number,
+        },
+        event: row.event,
+        created_at: row.created_at,
+    })
+    .collect();
+
+    Ok(Some(entries))
+}
+
+pub(crate) async fn set_cloud_event_outbox_cursor(
+    sink_name: &str,
+    lease_holder: uuid::Uuid,
+    OutboxCursor {
+        transaction_id,
+        sequence_number,
+    }: OutboxCursor,
+    conn: &mut PgConnection,
+) -> Result<bool> {
+    // The cursor only moves forward, also if the lease was taken over in the meantime
+    // and the other replica already delivered further.
+    let result = sqlx::query!(
+        r#"
+        UPDATE cloud_event_outbox_cursor
+        SET last_transaction_id = $3, last_sequence_number = $4
+        WHERE sink_name = $1
+            AND lease_holder = $2
+            AND (last_transaction_id, last_sequence_number) < ($3, $4)
+        "#,
+        sink_name,
+        lease_holder,
+        transaction_id,
+        sequence_number
+    )
+    .execute(conn)
+    .await
+    .map_err(|e| e.into_error_model("Error updating cloud event outbox cursor"))?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+pub(crate) async fn release_cloud_event_outbox_lease<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    sink_name: &str,
+    lease_holder: uuid::Uuid,
+    connection: E,
+) -> Result<()> {
+    sqlx::query!(
+        r#"
+        UPDATE cloud_event_outbox_cursor
+        SET lease_holder = NULL, lease_expires_at = NULL
+        WHERE sink_name = $1 AND lease_holder = $2
+        "#,
+        sink_name,
+        lease_holder
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error releasing cloud event outbox lease"))?;
+
+    Ok(())
+}
+
+pub(crate) async fn prune_cloud_event_outbox<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    sink_names: &[String],
+    connection: E,
+) -> Result<u64> {
+    // Sinks without a cursor have not received any event yet, nothing is deleted
+    // until they caught up.
+    let result = sqlx::query!(
+        r#"
+        WITH oldest_cursor AS (
+            SELECT c.last_transaction_id, c.last_sequence_number
+            FROM UNNEST($1::text[]) AS s(sink_name)
+            LEFT JOIN cloud_event_outbox_cursor c ON c.sink_name = s.sink_name
+            ORDER BY c.last_transaction_id ASC NULLS FIRST, c.last_sequence_number ASC NULLS FIRST
+            LIMIT 1
+        )
+        DELETE FROM cloud_event_outbox o
+        USING oldest_cursor c
+        WHERE (o.transaction_id, o.sequence_number) <= (c.last_transaction_id, c.last_sequence_number)
+        "#,
+        sink_names
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error pruning cloud event outbox"))?;
+
+    Ok(result.rows_affected())
+}
+
+pub(crate) async fn get_cloud_event_outbox_lag<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    sink_names: &[String],
+    connection: E,
+) -> Result<Vec<OutboxLag>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT s.sink_name as "sink_name!",
+            count(o.sequence_number) as "pending_events!",
+            (EXTRACT(EPOCH FROM now() - min(o.created_at)) * 1000)::bigint as oldest_pending_event_age_ms
+        FROM UNNEST($1::text[]) AS s(sink_name)
+        LEFT JOIN cloud_event_outbox_cursor c ON c.sink_name = s.sink_name
+        LEFT JOIN cloud_event_outbox o
+            ON (o.transaction_id, o.sequence_number)
+                > (COALESCE(c.last_transaction_id, 0), COALESCE(c.last_sequence_number, 0))
+        GROUP BY s.sink_name
+        "#,
+        sink_names
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching cloud event outbox lag"))?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| OutboxLag {
+            sink_name: row.sink_name,
+            pending_events: row.pending_events,
+            oldest_pending_event_age: row
+                .oldest_pending_event_age_ms
+                .map(chrono::Duration::milliseconds),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        implementations::postgres::{CatalogState, PostgresTransaction},
+        service::Transaction,
+    };
+
+    async fn insert_events(state: &CatalogState, ids: &[&str]) {
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        insert_cloud_event_outbox(
+            ids.iter()
+                .map(|id| serde_json::json!({ "id": id }))
+                .collect(),
+            &mut **t.transaction(),
+        )
+        .await
+        .unwrap();
+        t.commit().await.unwrap();
+    }
+
+    async fn claim(
+        state: &CatalogState,
+        sink_name: &str,
+        holder: uuid::Uuid,
+        lease_duration: std::time::Duration,
+    ) -> Option<Vec<OutboxEntry>> {
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        let entries = claim_cloud_event_outbox_batch(
+            sink_name,
+            holder,
+            lease_duration,
+            100,
+            &mut **t.transaction(),
+        )
+        .await
+        .unwrap();
+        t.commit().await.unwrap();
+        entries
+    }
+
+    async fn set_cursor(
+        state: &CatalogState,
+        sink_name: &str,
+        holder: uuid::Uuid,
+        cursor: OutboxCursor,
+    ) -> bool {
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        let updated =
+            set_cloud_event_outbox_cursor(sink_name, holder, cursor, &mut **t.transaction())
+                .await
+                .unwrap();
+        t.commit().await.unwrap();
+        updated
+    }
+
+    async fn relay(state: &CatalogState, sink_name: &str) -> Vec<String> {
+        let holder = uuid::Uuid::now_v7();
+        let entries = claim(state, sink_name, holder, std::time::Duration::from_secs(60))
+            .await
+            .unwrap();
+        if let Some(last) = entries.last() {
+            assert!(set_cursor(state, sink_name, holder, last.cursor).await);
+        }
+        release_cloud_event_outbox_lease(sink_name, holder, &state.write_pool())
+            .await
+            .unwrap();
+        entries
+            .into_iter()
+            .map(|e| e.event["id"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[sqlx::test]
+    async fn test_outbox_is_relayed_per_sink(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let sinks = vec!["sink-a".to_string(), "sink-b".to_string()];
+
+        insert_events(&state, &["e1", "e2"]).await;
+        assert_eq!(relay(&state, "sink-a").await, vec!["e1", "e2"]);
+        insert_events(&state, &["e3"]).await;
+        assert_eq!(relay(&state, "sink-a").await, vec!["e3"]);
+        assert!(relay(&state, "sink-a").await.is_empty());
+
+        let lag = get_cloud_event_outbox_lag(&sinks, &pool).await.unwrap();
+        let lag_b = lag.iter().find(|l| l.sink_name == "sink-b").unwrap();
+        assert_eq!(lag_b.pending_events, 3);
+        assert!(lag_b.oldest_pending_event_age.is_some());
+
+        // sink-b has not received anything yet
+        assert_eq!(prune_cloud_event_outbox(&sinks, &pool).await.unwrap(), 0);
+        assert_eq!(relay(&state, "sink-b").await, vec!["e1", "e2", "e3"]);
+        assert_eq!(prune_cloud_event_outbox(&sinks, &pool).await.unwrap(), 3);
+
+        let lag = get_cloud_event_outbox_lag(&sinks, &pool).await.unwrap();
+        assert!(lag.iter().all(|l| l.pending_events == 0));
+    }
+
+    #[sqlx::test]
+    async fn test_outbox_waits_for_older_transactions(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+
+        // Open transaction that inserts first but commits last
+        let mut slow = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        insert_cloud_event_outbox(
+            vec![serde_json::json!({"id": "slow"})],
+            &mut **slow.transaction(),
+        )
+        .await
+        .unwrap();
+
+        insert_events(&state, &["fast"]).await;
+        assert!(
+            relay(&state, "sink").await.is_empty(),
+            "Events after a running transaction must not be relayed"
+        );
+
+        slow.commit().await.unwrap();
+        assert_eq!(relay(&state, "sink").await, vec!["slow", "fast"]);
+    }
+
+    #[sqlx::test]
+    async fn test_outbox_sink_is_leased_by_one_relay(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        insert_events(&state, &["e1", "e2"]).await;
+        let lease = std::time::Duration::from_secs(60);
+        let relay_a = uuid::Uuid::now_v7();
+        let relay_b = uuid::Uuid::now_v7();
+
+        // The claim is committed before publishing, no transaction stays open
+        let entries = claim(&state, "sink", relay_a, lease).await.unwrap();
+        assert_eq!(entries.len(), 2);
+        assert!(claim(&state, "sink", relay_b, lease).await.is_none());
+
+        // The holder renews its lease with the next batch
+        assert!(set_cursor(&state, "sink", relay_a, entries[0].cursor).await);
+        let entries = claim(&state, "sink", relay_a, lease).await.unwrap();
+        assert_eq!(entries.len(), 1);
+
+        // Expired leases are taken over, the previous holder can't move the cursor anymore
+        let entries = claim(&state, "sink", relay_a, std::time::Duration::ZERO)
+            .await
+            .unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        let taken_over = claim(&state, "sink", relay_b, lease).await.unwrap();
+        assert_eq!(taken_over, entries);
+        assert!(set_cursor(&state, "sink", relay_b, taken_over[0].cursor).await);
+        assert!(!set_cursor(&state, "sink", relay_a, entries[0].cursor).await);
+
+        // Cursors never move backwards
+        release_cloud_event_outbox_lease("sink", relay_b, &pool)
+            .await
+            .unwrap();
+        assert!(claim(&state, "sink", relay_a, lease)
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(!set_cursor(&state, "sink", relay_a, OutboxCursor::default()).await);
+    }
+}
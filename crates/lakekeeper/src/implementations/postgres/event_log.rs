@@ -0,0 +1,505 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use cloudevents::Event;
+use itertools::Itertools as _;
+use sqlx::PgPool;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::{
+    api::iceberg::v1::PaginationQuery,
+    implementations::postgres::{
+        dbutils::DBErrorHandler,
+        listener::{listen, ListenerEvent},
+        pagination::{PaginateToken, V1PaginateToken},
+    },
+    service::{
+        event_publisher::stream::CloudEventStream, EventLogEntry, EventLogFilter,
+        ListEventLogResponse, NewEventLogEntry, Result,
+    },
+    CancellationToken, WarehouseId, CONFIG,
+};
+
+/// Channel on which the ids of newly logged events are published, comma separated.
+const EVENT_LOG_CHANNEL: &str = "lakekeeper_event_log";
+/// Number of event ids per notification, to stay below the 8000 byte payload limit.
+const EVENT_IDS_PER_NOTIFICATION: usize = 100;
+/// Events logged up to this long before the newest streamed event are re-read after
+/// the listener reconnected, as `created_at` is the start of the inserting transaction.
+const CATCH_UP_MARGIN: chrono::Duration = chrono::Duration::seconds(60);
+
+/// Insert `entries` and notify the event log listeners of all replicas
+/// about the newly logged events once the insert is committed.
+pub(crate) async fn insert_event_log(entries: Vec<NewEventLogEntry>, pool: &PgPool) -> Result<()> {
+    if entries.is_empty() {
+        return Ok(());
+    }
+
+    let mut event_ids = Vec::with_capacity(entries.len());
+    let mut warehouse_ids = Vec::with_capacity(entries.len());
+    let mut tabular_ids = Vec::with_capacity(entries.len());
+    let mut namespace_ids = Vec::with_capacity(entries.len());
+    let mut event_types = Vec::with_capacity(entries.len());
+    let mut event_times = Vec::with_capacity(entries.len());
+    let mut events = Vec::with_capacity(entries.len());
+    for entry in entries {
+        event_ids.push(entry.event_id);
+        warehouse_ids.push(*entry.warehouse_id);
+        tabular_ids.push(entry.tabular_id);
+        namespace_ids.push(entry.namespace_id.map(|id| *id));
+        event_types.push(entry.event_type);
+        event_times.push(entry.event_time);
+        events.push(entry.event);
+    }
+
+    let mut transaction = pool
+        .begin()
+        .await
+        .map_err(|e| e.into_error_model("Error starting transaction to log events"))?;
+    // Events relayed from the outbox may be delivered more than once.
+    // Only newly inserted events are streamed.
+    let inserted = sqlx::query_scalar!(
+        r#"
+        INSERT INTO event_log (event_id, warehouse_id, tabular_id, namespace_id, event_type, event_time, event)
+        SELECT * FROM UNNEST($1::uuid[], $2::uuid[], $3::uuid[], $4::uuid[], $5::text[], $6::timestamptz[], $7::jsonb[])
+        ON CONFLICT (event_id) DO NOTHING
+        RETURNING event_id
+        "#,
+        &event_ids,
+        &warehouse_ids,
+        &tabular_ids as _,
+        &namespace_ids as _,
+        &event_types,
+        &event_times,
+        &events
+    )
+    .fetch_all(&mut *transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error inserting events into event log"))?;
+
+    for chunk in inserted.chunks(EVENT_IDS_PER_NOTIFICATION) {
+        sqlx::query("SELECT pg_notify($1, $2)")
+            .bind(EVENT_LOG_CHANNEL)
+            .bind(chunk.iter().join(","))
+            .execute(&mut *transaction)
+            .await
+            .map_err(|e| e.into_error_model("Error notifying about logged events"))?;
+    }
+    transaction
+        .commit()
+        .await
+        .map_err(|e| e.into_error_model("Error committing logged events"))?;
+
+    Ok(())
+}
+
+/// Publish the events logged by any replica to `stream` until `cancellation_token` is cancelled.
+pub(crate) async fn stream_logged_events(
+    pool: &PgPool,
+    stream: CloudEventStream,
+    cancellation_token: CancellationToken,
+) -> anyhow::Result<()> {
+    let stream = &stream;
+    // Creation time of the newest streamed event. Notifications are lost while the
+    // listener is disconnected, so the events logged since are read once it is connected again.
+    let newest_created_at = &Mutex::new(chrono::Utc::now());
+    let catch_up = &AtomicBool::new(false);
+
+    listen(
+        pool,
+        EVENT_LOG_CHANNEL,
+        "Event log listener",
+        &cancellation_token,
+        |event| async move {
+            let event_ids = match event {
+                ListenerEvent::Connected { reconnected } => {
+                    if reconnected {
+                        catch_up.store(true, Ordering::Relaxed);
+                    }
+                    vec![]
+                }
+                ListenerEvent::ConnectionLost => {
+                    catch_up.store(true, Ordering::Relaxed);
+                    return;
+                }
+                ListenerEvent::Notification(payload) => payload
+                    .split(',')
+                    .filter_map(|id| {
+                        Uuid::parse_str(id)
+                            .inspect_err(|e| {
+                                tracing::error!("Invalid event id `{id}` in notification: {e}");
+                            })
+                            .ok()
+                    })
+                    .collect(),
+            };
+
+            if catch_up.swap(false, Ordering::Relaxed) {
+                let since = *newest_created_at.lock().await - CATCH_UP_MARGIN;
+                if let Err(e) =
+                    stream_events(pool, stream, newest_created_at, LoggedEvents::Since(since)).await
+                {
+                    tracing::error!("Failed to stream events logged since {since}: {}", e.error);
+                }
+            }
+            if !event_ids.is_empty() {
+                if let Err(e) = stream_events(
+                    pool,
+                    stream,
+                    newest_created_at,
+                    LoggedEvents::Ids(&event_ids),
+                )
+                .await
+                {
+                    tracing::error!("Failed to stream logged events: {}", e.error);
+                }
+            }
+        },
+    )
+    .await;
+    Ok(())
+}
+
+enum LoggedEvents<'a> {
+    Ids(&'a [Uuid]),
+    Since(chrono::DateTime<chrono::Utc>),
+}
+
+async fn stream_events(
+    pool: &PgPool,
+    stream: &CloudEventStream,
+    newest_created_at: &Mutex<chrono::DateTime<chrono::Utc>>,
+    selection: LoggedEvents<'_>,
+) -> Result<()> {
+    let (event_ids, since) = match selection {
+        LoggedEvents::Ids(ids) => (Some(ids), None),
+        LoggedEvents::Since(since) => (None, Some(since)),
+    };
+    let rows = sqlx::query!(
+        r#"
+        SELECT event, created_at
+        FROM event_log
+        WHERE ($1::uuid[] IS NULL OR event_id = ANY($1))
+            AND ($2::timestamptz IS NULL OR created_at > $2)
+        ORDER BY created_at, event_id
+        "#,
+        event_ids as _,
+        since,
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.into_error_model("Error reading logged events"))?;
+
+    let mut newest_created_at = newest_created_at.lock().await;
+    for row in rows {
+        match serde_json::from_value::<Event>(row.event) {
+            Ok(event) => stream.publish(event),
+            Err(e) => tracing::error!("Failed to parse logged event: {e}"),
+        }
+        *newest_created_at = (*newest_created_at).max(row.created_at);
+    }
+    Ok(())
+}
+
+pub(crate) async fn list_event_log<'e, 'c: 'e, E: sqlx::Executor<'c, Database = sqlx::Postgres>>(
+    warehouse_id: WarehouseId,
+    EventLogFilter { tabular_id, since }: EventLogFilter,
+    PaginationQuery {
+        page_size,
+        page_token,
+    }: PaginationQuery,
+    connection: E,
+) -> Result<ListEventLogResponse> {
+    let page_size = CONFIG.page_size_or_pagination_default(page_size);
+
+    let token = page_token
+        .as_option()
+        .map(PaginateToken::try_from)
+        .transpose()?;
+
+    let (token_ts, token_id) = token
+        .as_ref()
+        .map(
+            |PaginateToken::V1(V1PaginateToken { created_at, id }): &PaginateToken<Uuid>| {
+                (created_at, id)
+            },
+        )
+        .unzip();
+
+    let events = sqlx::query!(
+        r#"
+        SELECT event_id, warehouse_id, tabular_id, namespace_id, event_type, event_time, event, created_at
+        FROM event_log el
+        WHERE warehouse_id = $1
+            AND ($2::uuid IS NULL OR tabular_id = $2)
+            AND ($3::timestamptz IS NULL OR event_time >= $3)
+            --- PAGINATION
+            AND ((el.created_at > $4 OR $4 IS NULL) OR (el.created_at = $4 AND el.event_id > $5))
+        ORDER BY el.created_at, el.event_id ASC
+        LIMIT $6
+        "#,
+        *warehouse_id,
+        tabular_id,
+        since,
+        token_ts,
+        token_id,
+        page_size,
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching events from event log"))?
+    .into_iter()
+    .map(|row| EventLogEntry {
+        event_id: row.event_id,
+        warehouse_id: row.warehouse_id.into(),
+        tabular_id: row.tabular_id,
+        namespace_id: row.namespace_id.map(Into::into),
+        event_type: row.event_type,
+        event_time: row.event_time,
+        event: row.event,
+        created_at: row.created_at,
+    })
+    .collect::<Vec<_>>();
+
+    // A shorter page is the last one
+    let next_page_token = (i64::try_from(events.len()).unwrap_or(i64::MAX) >= page_size)
+        .then(|| events.last())
+        .flatten()
+        .map(|e| {
+            PaginateToken::V1(V1PaginateToken::<Uuid> {
+                created_at: e.created_at,
+                id: e.event_id,
+            })
+            .to_string()
+        });
+
+    Ok(ListEventLogResponse {
+        events,
+        next_page_token,
+    })
+}
+
+pub(crate) async fn prune_event_log<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    older_than: chrono::DateTime<chrono::Utc>,
+    connection: E,
+) -> Result<u64> {
+    let result = sqlx::query!(
+        r#"
+        DELETE FROM event_log
+        WHERE created_at < $1
+        "#,
+        older_than
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error pruning event log"))?;
+
+    Ok(result.rows_affected())
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use cloudevents::{AttributesReader, EventBuilder, EventBuilderV10};
+
+    use super::*;
+    use crate::api::iceberg::types::PageToken;
+
+    fn entry(warehouse_id: WarehouseId, tabular_id: Option<Uuid>) -> NewEventLogEntry {
+        let event_id = Uuid::now_v7();
+        let event = EventBuilderV10::new()
+            .id(event_id.to_string())
+            .source("uri:test")
+            .ty("updateTable")
+            .build()
+            .unwrap();
+        NewEventLogEntry {
+            event_id,
+            warehouse_id,
+            tabular_id,
+            namespace_id: None,
+            event_type: "updateTable".to_string(),
+            event_time: chrono::Utc::now(),
+            event: serde_json::to_value(event).unwrap(),
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_event_log_filter_and_pagination(pool: sqlx::PgPool) {
+        let warehouse_id = WarehouseId::new_random();
+        let table_id = Uuid::now_v7();
+        let entries = vec![
+            entry(warehouse_id, Some(table_id)),
+            entry(warehouse_id, None),
+            entry(warehouse_id, Some(table_id)),
+            entry(WarehouseId::new_random(), Some(table_id)),
+        ];
+        insert_event_log(entries.clone(), &pool).await.unwrap();
+        // Duplicates are ignored
+        insert_event_log(entries[..1].to_vec(), &pool)
+            .await
+            .unwrap();
+
+        let all = list_event_log(
+            warehouse_id,
+            EventLogFilter::default(),
+            PaginationQuery::new(PageToken::NotSpecified, None),
+            &pool,
+        )
+        .await
+        .unwrap();
+        assert_eq!(all.events.len(), 3);
+
+        let filter = EventLogFilter {
+            tabular_id: Some(table_id),
+            since: None,
+        };
+        let first_page = list_event_log(
+            warehouse_id,
+            filter.clone(),
+            PaginationQuery {
+                page_size: Some(1),
+                page_token: PageToken::NotSpecified,
+            },
+            &pool,
+        )
+        .await
+        .unwrap();
+        assert_eq!(first_page.events.len(), 1);
+        let second_page = list_event_log(
+            warehouse_id,
+            filter.clone(),
+            PaginationQuery {
+                page_size: Some(5),
+                page_token: first_page.next_page_token.clone().into(),
+            },
+            &pool,
+        )
+        .await
+        .unwrap();
+        assert_eq!(second_page.events.len(), 1);
+        assert!(first_page.next_page_token.is_some());
+        assert!(second_page.next_page_token.is_none());
+        assert_ne!(
+            first_page.events[0].event_id,
+            second_page.events[0].event_id
+        );
+        assert!(second_page
+            .events
+            .iter()
+            .chain(&first_page.events)
+            .all(|e| e.tabular_id == Some(table_id)));
+
+        let future = list_event_log(
+            warehouse_id,
+            EventLogFilter {
+                tabular_id: None,
+                since: Some(chrono::Utc::now() + chrono::Duration::hours(1)),
+            },
+            PaginationQuery::new(PageToken::NotSpecified, None),
+            &pool,
+        )
+        .await
+        .unwrap();
+        assert!(future.events.is_empty());
+    }
+
+    #[sqlx::test]
+    async fn test_event_log_since_filters_on_event_time(pool: sqlx::PgPool) {
+        let warehouse_id = WarehouseId::new_random();
+        let mut old_event = entry(warehouse_id, None);
+        // Logged now, but occurred before `since`
+        old_event.event_time = chrono::Utc::now() - chrono::Duration::hours(2);
+        let new_event = entry(warehouse_id, None);
+        insert_event_log(vec![old_event, new_event.clone()], &pool)
+            .await
+            .unwrap();
+
+        let events = list_event_log(
+            warehouse_id,
+            EventLogFilter {
+                tabular_id: None,
+                since: Some(chrono::Utc::now() - chrono::Duration::hours(1)),
+            },
+            PaginationQuery::new(PageToken::NotSpecified, None),
+            &pool,
+        )
+        .await
+        .unwrap()
+        .events;
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_id, new_event.event_id);
+    }
+
+    #[sqlx::test]
+    async fn test_prune_event_log(pool: sqlx::PgPool) {
+        let warehouse_id = WarehouseId::new_random();
+        insert_event_log(vec![entry(warehouse_id, None)], &pool)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            prune_event_log(chrono::Utc::now() - chrono::Duration::hours(1), &pool)
+                .await
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            prune_event_log(chrono::Utc::now() + chrono::Duration::hours(1), &pool)
+                .await
+                .unwrap(),
+            1
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_logged_events_are_streamed_by_all_replicas(pool: sqlx::PgPool) {
+        let cancellation_token = CancellationToken::new();
+        let streams = [CloudEventStream::new(16, 16), CloudEventStream::new(16, 16)];
+        let mut receivers = Vec::new();
+        for stream in &streams {
+            receivers.push(stream.subscribe(None).1);
+            let (pool, stream) = (pool.clone(), stream.clone());
+            let cancellation_token = cancellation_token.clone();
+            tokio::spawn(
+                async move { stream_logged_events(&pool, stream, cancellation_token).await },
+            );
+        }
+
+        // Wait until both replicas are listening
+        tokio::time::timeout(Duration::from_secs(10), async {
+            loop {
+                let listeners = sqlx::query_scalar!(
+                    r#"SELECT count(*) as "count!" FROM pg_stat_activity
+                    WHERE datname = current_database() AND query LIKE 'LISTEN%'"#
+                )
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+                if listeners >= 2 {
+                    break;
+                }
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+        })
+        .await
+        .unwrap();
+
+        let entry = entry(WarehouseId::new_random(), None);
+        insert_event_log(vec![entry.clone()], &pool).await.unwrap();
+
+        for receiver in &mut receivers {
+            let event = tokio::time::timeout(Duration::from_secs(10), receiver.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(event.id(), entry.event_id.to_string());
+        }
+        cancellation_token.cancel();
+    }
+}
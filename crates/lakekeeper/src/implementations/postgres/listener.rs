@@ -0,0 +1,108 @@
+//! `LISTEN` on a Postgres notification channel that reconnects with an exponential backoff.
+use std::{future::Future, time::Duration};
+
+use sqlx::{postgres::PgListener, PgPool};
+
+use crate::CancellationToken;
+
+/// Backoff before the first reconnect attempt after the listener failed.
+const INITIAL_RECONNECT_BACKOFF: Duration = Duration::from_secs(1);
+/// Upper bound of the exponentially increasing reconnect backoff.
+const MAX_RECONNECT_BACKOFF: Duration = Duration::from_secs(60);
+
+#[derive(Debug)]
+pub(super) enum ListenerEvent {
+    /// The listener subscribed to the channel.
+    /// `reconnected` is true if the listener had failed before.
+    Connected { reconnected: bool },
+    /// The connection was lost. Notifications are lost until it is re-established.
+    ConnectionLost,
+    /// Payload of a received notification.
+    Notification(String),
+}
+
+/// Listen on `channel` until `cancellation_token` is cancelled and pass all
+/// [`ListenerEvent`]s to `on_event`.
+///
+/// `name` identifies the listener in logs.
+pub(super) async fn listen<F, Fut>(
+    pool: &PgPool,
+    channel: &str,
+    name: &str,
+    cancellation_token: &CancellationToken,
+    mut on_event: F,
+) where
+    F: FnMut(ListenerEvent) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    let mut backoff = INITIAL_RECONNECT_BACKOFF;
+    let mut reconnected = false;
+    loop {
+        let Err(e) = receive(
+            pool,
+            channel,
+            name,
+            cancellation_token,
+            reconnected,
+            &mut backoff,
+            &mut on_event,
+        )
+        .await
+        else {
+            return;
+        };
+        tracing::error!(?e, "{name} failed, reconnecting in {backoff:?}");
+        on_event(ListenerEvent::ConnectionLost).await;
+
+        tokio::select! {
+            () = cancellation_token.cancelled() => {
+                tracing::info!("Stopping {name}");
+                return;
+            }
+            () = tokio::time::sleep(backoff) => {}
+        }
+        backoff = (backoff * 2).min(MAX_RECONNECT_BACKOFF);
+        reconnected = true;
+    }
+}
+
+/// Receive notifications until `cancellation_token` is cancelled or the listener fails.
+async fn receive<F, Fut>(
+    pool: &PgPool,
+    channel: &str,
+    name: &str,
+    cancellation_token: &CancellationToken,
+    reconnected: bool,
+    backoff: &mut Duration,
+    on_event: &mut F,
+) -> anyhow::Result<()>
+where
+    F: FnMut(ListenerEvent) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    let mut listener = PgListener::connect_with(pool).await?;
+    listener.listen(channel).await?;
+    tracing::info!("{name} listening on channel `{channel}`");
+    *backoff = INITIAL_RECONNECT_BACKOFF;
+    on_event(ListenerEvent::Connected { reconnected }).await;
+
+    loop {
+        let notification = tokio::select! {
+            () = cancellation_token.cancelled() => {
+                tracing::info!("Stopping {name}");
+                return Ok(());
+            }
+            notification = listener.try_recv() => notification?,
+        };
+
+        // `None` if the connection was lost, it is re-established on the next receive.
+        let event = match notification {
+            Some(notification) => ListenerEvent::Notification(notification.payload().to_string()),
+            None => {
+                tracing::warn!("{name} lost its connection to channel `{channel}`");
+                ListenerEvent::ConnectionLost
+            }
+        };
+        on_event(event).await;
+    }
+}
@@ -1,7 +1,12 @@
+pub(crate) mod access_request;
 mod bootstrap;
+mod cache_invalidation;
 mod catalog;
+pub(crate) mod cloud_event_outbox;
 pub(crate) mod dbutils;
 pub mod endpoint_statistics;
+pub(crate) mod event_log;
+mod listener;
 pub mod migrations;
 pub(crate) mod namespace;
 mod pagination;
@@ -16,8 +21,9 @@ use std::{str::FromStr, sync::Arc};
 
 use anyhow::anyhow;
 use async_trait::async_trait;
+use axum_prometheus::metrics;
 pub use endpoint_statistics::sink::PostgresStatisticsSink;
-pub use secrets::SecretsState;
+pub use secrets::{reencrypt_secrets, EncryptionKeys, SecretsState};
 use sqlx::{
     postgres::{PgConnectOptions, PgPoolOptions},
     ConnectOptions, Executor, PgPool,
@@ -33,6 +39,9 @@ use crate::{
     CONFIG,
 };
 
+const METRIC_POOL_CONNECTIONS: &str = "lakekeeper_pg_pool_connections";
+const METRIC_POOL_MAX_CONNECTIONS: &str = "lakekeeper_pg_pool_max_connections";
+
 /// # Errors
 /// Returns an error if the pool creation fails.
 pub async fn get_reader_pool(pool_opts: PgPoolOptions) -> anyhow::Result<sqlx::PgPool> {
@@ -97,6 +106,7 @@ impl crate::service::Transaction<CatalogState> for PostgresTransaction {
             .commit()
             .await
             .map_err(|e| e.into_error_model("Error committing transaction".to_string()))?;
+        crate::service::cache::apply_pending_invalidations().await;
         Ok(())
     }
 
@@ -127,6 +137,8 @@ impl HealthExt for ReadWrite {
     }
 
     async fn update_health(&self) {
+        export_pool_metrics("read", &self.read_pool);
+        export_pool_metrics("write", &self.write_pool);
         let read = self.read_health().await;
         let write = self.write_health().await;
         let mut lock = self.health.write().await;
@@ -173,6 +185,20 @@ impl ReadWrite {
     }
 }
 
+/// Export the saturation of `pool` as Prometheus gauges.
+/// Updated with every health check.
+fn export_pool_metrics(pool_name: &'static str, pool: &PgPool) {
+    let size = pool.size();
+    #[allow(clippy::cast_possible_truncation)]
+    let idle = pool.num_idle() as u32;
+    metrics::gauge!(METRIC_POOL_CONNECTIONS, "pool" => pool_name, "state" => "active")
+        .set(f64::from(size.saturating_sub(idle)));
+    metrics::gauge!(METRIC_POOL_CONNECTIONS, "pool" => pool_name, "state" => "idle")
+        .set(f64::from(idle));
+    metrics::gauge!(METRIC_POOL_MAX_CONNECTIONS, "pool" => pool_name)
+        .set(f64::from(pool.options().get_max_connections()));
+}
+
 #[derive(Clone, Debug)]
 
 pub struct CatalogState {
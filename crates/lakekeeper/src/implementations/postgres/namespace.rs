@@ -16,15 +16,15 @@ use crate::{
     service::{
         storage::join_location, tasks::TaskId, CatalogCreateNamespaceError,
         CatalogGetNamespaceError, CatalogListNamespaceError, CatalogNamespaceDropError,
-        CatalogSetNamespaceProtectedError, CatalogUpdateNamespacePropertiesError,
-        ChildNamespaceProtected, ChildTabularProtected, CreateNamespaceRequest,
-        InternalParseLocationError, InvalidNamespaceIdentifier, ListNamespacesQuery, Namespace,
-        NamespaceAlreadyExists, NamespaceDropInfo, NamespaceHasRunningTabularExpirations,
-        NamespaceId, NamespaceIdent, NamespaceIdentOrId, NamespaceNotEmpty, NamespaceNotFound,
-        NamespacePropertiesSerializationError, NamespaceProtected, Result, TabularId,
-        WarehouseIdNotFound,
+        CatalogSetNamespaceProtectedError, CatalogSetNamespaceStorageCredentialError,
+        CatalogUpdateNamespacePropertiesError, ChildNamespaceProtected, ChildTabularProtected,
+        CreateNamespaceRequest, InternalParseLocationError, InvalidNamespaceIdentifier,
+        ListNamespacesQuery, Namespace, NamespaceAlreadyExists, NamespaceDropInfo,
+        NamespaceHasRunningTabularExpirations, NamespaceId, NamespaceIdent, NamespaceIdentOrId,
+        NamespaceNotEmpty, NamespaceNotFound, NamespacePropertiesSerializationError,
+        NamespaceProtected, NamespaceStorageCredential, Result, TabularId, WarehouseIdNotFound,
     },
-    WarehouseId, CONFIG,
+    SecretIdent, WarehouseId, CONFIG,
 };
 
 pub(crate) async fn get_namespace<'c, 'e: 'c, E: sqlx::Executor<'c, Database = sqlx::Postgres>>(
@@ -466,7 +466,7 @@ pub(crate) async fn drop_namespace(
         .into());
     }
 
-    let record = sqlx::query!(
+    let deleted = sqlx::query!(
         r#"
         DELETE FROM namespace
             WHERE warehouse_id = $1
@@ -476,12 +476,13 @@ pub(crate) async fn drop_namespace(
                 SELECT warehouse_id FROM warehouse WHERE status = 'active'
                 AND warehouse_id = $1
             )
+        RETURNING storage_secret_id
         "#,
         *warehouse_id,
         &info.child_namespaces,
         *namespace_id,
     )
-    .execute(&mut **transaction)
+    .fetch_all(&mut **transaction)
     .await
     .map_err(|e| match &e {
         sqlx::Error::Database(db_error) if db_error.is_foreign_key_violation() => {
@@ -495,10 +496,10 @@ pub(crate) async fn drop_namespace(
 
     tracing::debug!(
         "Deleted {deleted_count} namespaces while dropping namespace {namespace_ident} with id {namespace_id} in warehouse {warehouse_id}",
-        deleted_count = record.rows_affected()
+        deleted_count = deleted.len()
     );
 
-    if record.rows_affected() == 0 {
+    if deleted.is_empty() {
         return Err(NamespaceNotFound::new(warehouse_id, namespace_ident.clone()).into());
     }
 
@@ -533,6 +534,10 @@ pub(crate) async fn drop_namespace(
             .into_iter()
             .map(TaskId::from)
             .collect(),
+        storage_secret_ids: deleted
+            .into_iter()
+            .filter_map(|row| row.storage_secret_id.map(SecretIdent::from))
+            .collect(),
     })
 }
 
@@ -626,6 +631,114 @@ pub(crate) async fn set_namespace_protected(
     })
 }
 
+pub(crate) async fn get_namespace_storage_credential<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    warehouse_id: WarehouseId,
+    namespace_id: NamespaceId,
+    connection: E,
+) -> std::result::Result<Option<NamespaceStorageCredential>, CatalogGetNamespaceError> {
+    let row = sqlx::query!(
+        r#"
+        SELECT storage_secret_id, storage_sts_role_arn
+        FROM namespace
+        WHERE warehouse_id = $1 AND namespace_id = $2
+        "#,
+        *warehouse_id,
+        *namespace_id
+    )
+    .fetch_optional(connection)
+    .await
+    .map_err(|e| e.into_catalog_backend_error())?;
+
+    Ok(row
+        .map(|row| NamespaceStorageCredential {
+            storage_secret_id: row.storage_secret_id.map(SecretIdent::from),
+            sts_role_arn: row.storage_sts_role_arn,
+        })
+        .filter(|credential| !credential.is_empty()))
+}
+
+/// Storage credential override of the namespace or, if it has none, of its nearest ancestor.
+pub(crate) async fn resolve_namespace_storage_credential<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    warehouse_id: WarehouseId,
+    namespace_id: NamespaceId,
+    connection: E,
+) -> std::result::Result<Option<NamespaceStorageCredential>, CatalogGetNamespaceError> {
+    let row = sqlx::query!(
+        r#"
+        SELECT a.storage_secret_id, a.storage_sts_role_arn
+        FROM namespace n
+        INNER JOIN namespace a
+            ON a.warehouse_id = n.warehouse_id
+            AND a.namespace_name = n.namespace_name[1:cardinality(a.namespace_name)]
+        WHERE n.warehouse_id = $1 AND n.namespace_id = $2
+            AND (a.storage_secret_id IS NOT NULL OR a.storage_sts_role_arn IS NOT NULL)
+        ORDER BY cardinality(a.namespace_name) DESC
+        LIMIT 1
+        "#,
+        *warehouse_id,
+        *namespace_id
+    )
+    .fetch_optional(connection)
+    .await
+    .map_err(|e| e.into_catalog_backend_error())?;
+
+    Ok(row.map(|row| NamespaceStorageCredential {
+        storage_secret_id: row.storage_secret_id.map(SecretIdent::from),
+        sts_role_arn: row.storage_sts_role_arn,
+    }))
+}
+
+pub(crate) async fn set_namespace_storage_credential(
+    warehouse_id: WarehouseId,
+    namespace_id: NamespaceId,
+    storage_credential: Option<NamespaceStorageCredential>,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> std::result::Result<Option<SecretIdent>, CatalogSetNamespaceStorageCredentialError> {
+    let storage_credential = storage_credential.unwrap_or_default();
+    let row = sqlx::query!(
+        r#"
+        WITH previous AS (
+            SELECT namespace_id, storage_secret_id
+            FROM namespace
+            WHERE warehouse_id = $1 AND namespace_id = $2
+            FOR UPDATE
+        )
+        UPDATE namespace n
+        SET storage_secret_id = $3, storage_sts_role_arn = $4
+        FROM previous p
+        WHERE n.namespace_id = p.namespace_id
+        AND n.warehouse_id IN (
+            SELECT warehouse_id FROM warehouse WHERE status = 'active'
+        )
+        RETURNING p.storage_secret_id as previous_secret_id
+        "#,
+        *warehouse_id,
+        *namespace_id,
+        storage_credential
+            .storage_secret_id
+            .map(|id| id.into_uuid()),
+        storage_credential.sts_role_arn,
+    )
+    .fetch_one(&mut **transaction)
+    .await
+    .map_err(|e| match e {
+        sqlx::Error::RowNotFound => CatalogSetNamespaceStorageCredentialError::from(
+            NamespaceNotFound::new(warehouse_id, namespace_id),
+        ),
+        _ => e.into_catalog_backend_error().into(),
+    })?;
+
+    Ok(row.previous_secret_id.map(SecretIdent::from))
+}
+
 pub(crate) async fn update_namespace_properties(
     warehouse_id: WarehouseId,
     namespace_id: NamespaceId,
@@ -1430,4 +1543,210 @@ pub(crate) mod tests {
 
         transaction.commit().await.unwrap();
     }
+
+    #[sqlx::test]
+    async fn test_namespace_storage_credential(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let namespace = NamespaceIdent::from_vec(vec!["test".to_string()]).unwrap();
+        let namespace_id = initialize_namespace(state.clone(), warehouse_id, &namespace, None)
+            .await
+            .namespace_id;
+
+        let credential = PostgresBackend::get_namespace_storage_credential(
+            warehouse_id,
+            namespace_id,
+            state.clone(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(credential, None);
+
+        let first_secret = SecretIdent::from(Uuid::now_v7());
+        let second_secret = SecretIdent::from(Uuid::now_v7());
+        for (secret, expected_previous) in
+            [(first_secret, None), (second_secret, Some(first_secret))]
+        {
+            let mut transaction = PostgresTransaction::begin_write(state.clone())
+                .await
+                .unwrap();
+            let previous = set_namespace_storage_credential(
+                warehouse_id,
+                namespace_id,
+                Some(NamespaceStorageCredential {
+                    storage_secret_id: Some(secret),
+                    sts_role_arn: Some("arn:aws:iam::123456789012:role/team".to_string()),
+                }),
+                transaction.transaction(),
+            )
+            .await
+            .unwrap();
+            transaction.commit().await.unwrap();
+            assert_eq!(previous, expected_previous);
+        }
+
+        let credential = PostgresBackend::get_namespace_storage_credential(
+            warehouse_id,
+            namespace_id,
+            state.clone(),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(credential.storage_secret_id, Some(second_secret));
+        assert_eq!(
+            credential.sts_role_arn.as_deref(),
+            Some("arn:aws:iam::123456789012:role/team")
+        );
+
+        // Dropping the namespace returns its secret for cleanup
+        let mut transaction = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        let drop_info = drop_namespace(
+            warehouse_id,
+            namespace_id,
+            NamespaceDropFlags::default(),
+            transaction.transaction(),
+        )
+        .await
+        .unwrap();
+        transaction.commit().await.unwrap();
+        assert_eq!(drop_info.storage_secret_ids, vec![second_secret]);
+
+        let mut transaction = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        let result = set_namespace_storage_credential(
+            warehouse_id,
+            namespace_id,
+            None,
+            transaction.transaction(),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(
+            result,
+            CatalogSetNamespaceStorageCredentialError::NamespaceNotFound(_)
+        ));
+    }
+
+    #[sqlx::test]
+    async fn test_namespace_storage_credential_is_inherited(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        // Pending invalidations are tracked per task
+        tokio::spawn(crate::service::cache::with_caches_enabled(async move {
+            let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+            let mut namespace_ids = HashMap::new();
+            for name in [vec!["a"], vec!["a", "b"], vec!["a", "b", "c"], vec!["ab"]] {
+                let ident = NamespaceIdent::from_strs(&name).unwrap();
+                let namespace =
+                    initialize_namespace(state.clone(), warehouse_id, &ident, None).await;
+                namespace_ids.insert(name.join("."), namespace.namespace_id);
+            }
+            let set_override = |name: &'static str, secret: SecretIdent| {
+                let state = state.clone();
+                let namespace_id = namespace_ids[name];
+                async move {
+                    let mut transaction = PostgresTransaction::begin_write(state).await.unwrap();
+                    PostgresBackend::set_namespace_storage_credential(
+                        warehouse_id,
+                        namespace_id,
+                        Some(NamespaceStorageCredential {
+                            storage_secret_id: Some(secret),
+                            sts_role_arn: None,
+                        }),
+                        transaction.transaction(),
+                    )
+                    .await
+                    .unwrap();
+                    transaction.commit().await.unwrap();
+                }
+            };
+            let resolve = |name: &'static str| {
+                let state = state.clone();
+                let namespace_id = namespace_ids[name];
+                async move {
+                    PostgresBackend::resolve_namespace_storage_credential(
+                        warehouse_id,
+                        namespace_id,
+                        state,
+                    )
+                    .await
+                    .unwrap()
+                    .and_then(|credential| credential.storage_secret_id)
+                }
+            };
+
+            let parent_secret = SecretIdent::from(Uuid::now_v7());
+            set_override("a", parent_secret).await;
+            assert_eq!(resolve("a.b").await, Some(parent_secret));
+            assert_eq!(resolve("a.b.c").await, Some(parent_secret));
+            assert_eq!(resolve("ab").await, None);
+
+            // The nearest override wins, cached lookups of children are invalidated
+            let child_secret = SecretIdent::from(Uuid::now_v7());
+            set_override("a.b", child_secret).await;
+            assert_eq!(resolve("a").await, Some(parent_secret));
+            assert_eq!(resolve("a.b").await, Some(child_secret));
+            assert_eq!(resolve("a.b.c").await, Some(child_secret));
+        }))
+        .await
+        .unwrap();
+    }
+
+    #[sqlx::test]
+    async fn test_namespace_storage_credential_notifies_credential_invalidation(
+        pool: sqlx::PgPool,
+    ) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let namespace = NamespaceIdent::from_vec(vec!["test".to_string()]).unwrap();
+        let namespace_id = initialize_namespace(state.clone(), warehouse_id, &namespace, None)
+            .await
+            .namespace_id;
+
+        let mut listener = sqlx::postgres::PgListener::connect_with(&pool)
+            .await
+            .unwrap();
+        listener
+            .listen("lakekeeper_cache_invalidation")
+            .await
+            .unwrap();
+
+        let mut transaction = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        set_namespace_storage_credential(
+            warehouse_id,
+            namespace_id,
+            Some(NamespaceStorageCredential {
+                storage_secret_id: Some(SecretIdent::from(Uuid::now_v7())),
+                sts_role_arn: None,
+            }),
+            transaction.transaction(),
+        )
+        .await
+        .unwrap();
+        transaction.commit().await.unwrap();
+
+        let mut invalidations = Vec::new();
+        while let Ok(Ok(notification)) =
+            tokio::time::timeout(std::time::Duration::from_secs(1), listener.recv()).await
+        {
+            invalidations.push(
+                serde_json::from_str::<crate::service::CacheInvalidation>(notification.payload())
+                    .unwrap(),
+            );
+        }
+        assert!(invalidations
+            .contains(&crate::service::CacheInvalidation::WarehouseCredentials { warehouse_id }));
+        assert!(
+            invalidations.contains(&crate::service::CacheInvalidation::Namespace {
+                warehouse_id,
+                namespace_id
+            })
+        );
+    }
 }
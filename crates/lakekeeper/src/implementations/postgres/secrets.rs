@@ -1,3 +1,5 @@
+use std::{collections::HashMap, sync::Arc};
+
 use async_trait::async_trait;
 use http::StatusCode;
 use serde::{Deserialize, Serialize};
@@ -16,6 +18,52 @@ use crate::{
 #[derive(Debug, Clone)]
 pub struct SecretsState {
     read_write: ReadWrite,
+    encryption_keys: Arc<EncryptionKeys>,
+}
+
+/// Keys used to encrypt secrets, identified by their id.
+/// New secrets are encrypted with the primary key. Previous keys are only used for decryption.
+#[derive(Clone, veil::Redact)]
+pub struct EncryptionKeys {
+    primary_key_id: String,
+    #[redact]
+    primary_key: String,
+    #[redact]
+    previous_keys: HashMap<String, String>,
+}
+
+impl EncryptionKeys {
+    #[must_use]
+    pub fn new(
+        primary_key_id: String,
+        primary_key: String,
+        previous_keys: HashMap<String, String>,
+    ) -> Self {
+        Self {
+            primary_key_id,
+            primary_key,
+            previous_keys,
+        }
+    }
+
+    /// Keys configured via `pg_encryption_key`, `pg_encryption_key_id`
+    /// and `pg_encryption_previous_keys`.
+    #[must_use]
+    pub fn from_config() -> Self {
+        Self::new(
+            CONFIG.pg_encryption_key_id.clone(),
+            CONFIG.pg_encryption_key.clone(),
+            CONFIG.pg_encryption_previous_keys.clone(),
+        )
+    }
+
+    /// Ids and keys of all keys as separate arrays, to be passed to `unnest`.
+    fn all_keys(&self) -> (Vec<String>, Vec<String>) {
+        std::iter::once((&self.primary_key_id, &self.primary_key))
+            .chain(&self.previous_keys)
+            .map(|(id, key)| (id.clone(), key.clone()))
+            .unzip()
+    }
 }
 
 #[async_trait]
@@ -34,9 +82,16 @@ impl SecretsState {
     pub fn from_pools(read_pool: PgPool, write_pool: PgPool) -> Self {
         Self {
             read_write: ReadWrite::from_pools(read_pool, write_pool),
+            encryption_keys: Arc::new(EncryptionKeys::from_config()),
         }
     }
 
+    #[must_use]
+    pub fn with_encryption_keys(mut self, encryption_keys: EncryptionKeys) -> Self {
+        self.encryption_keys = Arc::new(encryption_keys);
+        self
+    }
+
     #[must_use]
     pub fn read_pool(&self) -> PgPool {
         self.read_write.read_pool.clone()
@@ -56,23 +111,28 @@ impl SecretStore for SecretsState {
         secret_id: SecretIdent,
     ) -> Result<Secret<S>> {
         struct SecretRow {
+            key_id: String,
             secret: Option<String>,
             created_at: chrono::DateTime<chrono::Utc>,
             updated_at: Option<chrono::DateTime<chrono::Utc>>,
         }
 
+        let (key_ids, keys) = self.encryption_keys.all_keys();
         let secret: SecretRow = sqlx::query_as!(
             SecretRow,
             r#"
             SELECT 
-                pgp_sym_decrypt(secret, $2, 'cipher-algo=aes256') as secret,
-                created_at,
-                updated_at
-            FROM secret
-            WHERE secret_id = $1
+                s.key_id,
+                pgp_sym_decrypt(s.secret, k.key, 'cipher-algo=aes256') as secret,
+                s.created_at,
+                s.updated_at
+            FROM secret s
+            LEFT JOIN unnest($2::text[], $3::text[]) AS k(key_id, key) ON k.key_id = s.key_id
+            WHERE s.secret_id = $1
             "#,
             secret_id.as_uuid(),
-            CONFIG.pg_encryption_key
+            &key_ids,
+            &keys,
         )
         .fetch_one(&self.read_write.read_pool)
         .await
@@ -91,16 +151,28 @@ impl SecretStore for SecretsState {
                 .build(),
         })?;
 
-        let inner =
-            serde_json::from_str(&secret.secret.unwrap_or("{}".to_string())).map_err(|_e| {
-                ErrorModel::builder()
-                    .code(StatusCode::INTERNAL_SERVER_ERROR.into())
-                    .message("Error parsing secret".to_string())
-                    .r#type("SecretParseError".to_string())
-                    // We do not add the error here as it might contain sensitive information
-                    .stack(vec![format!("Secret ID: {}", secret_id)])
-                    .build()
-            })?;
+        // Decryption only returns NULL if no key with the id of the secret is configured
+        let secret_str = secret.secret.ok_or_else(|| {
+            ErrorModel::builder()
+                .code(StatusCode::INTERNAL_SERVER_ERROR.into())
+                .message(format!(
+                    "No encryption key with id `{}` is configured",
+                    secret.key_id
+                ))
+                .r#type("SecretEncryptionKeyMissing".to_string())
+                .stack(vec![format!("secret_id: {}", secret_id)])
+                .build()
+        })?;
+
+        let inner = serde_json::from_str(&secret_str).map_err(|_e| {
+            ErrorModel::builder()
+                .code(StatusCode::INTERNAL_SERVER_ERROR.into())
+                .message("Error parsing secret".to_string())
+                .r#type("SecretParseError".to_string())
+                // We do not add the error here as it might contain sensitive information
+                .stack(vec![format!("Secret ID: {}", secret_id)])
+                .build()
+        })?;
 
         Ok(Secret {
             secret_id,
@@ -127,12 +199,13 @@ impl SecretStore for SecretsState {
 
         let secret_id = sqlx::query_scalar!(
             r#"
-            INSERT INTO secret (secret)
-            VALUES (pgp_sym_encrypt($1, $2, 'cipher-algo=aes256'))
+            INSERT INTO secret (secret, key_id)
+            VALUES (pgp_sym_encrypt($1, $2, 'cipher-algo=aes256'), $3)
             RETURNING secret_id
             "#,
             secret_str,
-            CONFIG.pg_encryption_key,
+            self.encryption_keys.primary_key,
+            self.encryption_keys.primary_key_id,
         )
         .fetch_one(&self.write_pool())
         .await
@@ -173,6 +246,88 @@ impl SecretStore for SecretsState {
     }
 }
 
+/// Re-encrypt all secrets that are not encrypted with the primary key.
+///
+/// Secrets are re-encrypted in batches of `batch_size`, each in its own transaction.
+/// Secrets locked by concurrent transactions are skipped, so the server can stay online
+/// as long as it is configured with both the primary and the previous keys.
+/// If interrupted, a new run continues with the remaining secrets.
+/// Returns the number of re-encrypted secrets.
+///
+/// # Errors
+/// - If a query fails, i.e. because a previous key is wrong.
+/// - If secrets remain that are encrypted with a key that is not configured,
+///   or that were locked until the end of the run.
+pub async fn reencrypt_secrets(
+    write_pool: &PgPool,
+    encryption_keys: &EncryptionKeys,
+    batch_size: i64,
+) -> anyhow::Result<u64> {
+    let (key_ids, keys) = encryption_keys.all_keys();
+    let mut reencrypted = 0;
+
+    loop {
+        let result = sqlx::query!(
+            r#"
+            WITH batch AS (
+                SELECT s.secret_id, pgp_sym_decrypt(s.secret, k.key, 'cipher-algo=aes256') AS plaintext
+                FROM secret s
+                JOIN unnest($1::text[], $2::text[]) AS k(key_id, key) ON k.key_id = s.key_id
+                WHERE s.key_id <> $3
+                ORDER BY s.secret_id
+                LIMIT $5
+                FOR UPDATE OF s SKIP LOCKED
+            )
+            UPDATE secret s
+            SET secret = pgp_sym_encrypt(b.plaintext, $4, 'cipher-algo=aes256'),
+                key_id = $3
+            FROM batch b
+            WHERE s.secret_id = b.secret_id
+            "#,
+            &key_ids,
+            &keys,
+            encryption_keys.primary_key_id,
+            encryption_keys.primary_key,
+            batch_size,
+        )
+        .execute(write_pool)
+        .await
+        .map_err(|e| anyhow::anyhow!(e).context("Error re-encrypting secrets"))?;
+
+        if result.rows_affected() == 0 {
+            break;
+        }
+        reencrypted += result.rows_affected();
+        tracing::info!("Re-encrypted {reencrypted} secrets");
+    }
+
+    let remaining = sqlx::query!(
+        r#"
+        SELECT key_id, count(*) AS "count!"
+        FROM secret
+        WHERE key_id <> $1
+        GROUP BY key_id
+        "#,
+        encryption_keys.primary_key_id,
+    )
+    .fetch_all(write_pool)
+    .await
+    .map_err(|e| anyhow::anyhow!(e).context("Error counting remaining secrets"))?;
+
+    if !remaining.is_empty() {
+        let remaining = remaining
+            .into_iter()
+            .map(|r| format!("{} with key id `{}`", r.count, r.key_id))
+            .collect::<Vec<_>>()
+            .join(", ");
+        anyhow::bail!(
+            "Re-encrypted {reencrypted} secrets, but secrets remain: {remaining}. Make sure all previous keys are configured and run the rotation again."
+        );
+    }
+
+    Ok(reencrypted)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -218,4 +373,67 @@ mod tests {
 
         assert!(read_secret.is_err());
     }
+
+    #[sqlx::test]
+    async fn test_reencrypt_secrets(pool: sqlx::PgPool) {
+        let old_keys =
+            EncryptionKeys::new("old".to_string(), "old-key".to_string(), HashMap::new());
+        let old_state =
+            SecretsState::from_pools(pool.clone(), pool.clone()).with_encryption_keys(old_keys);
+
+        let secret: StorageCredential = S3Credential::AccessKey(S3AccessKeyCredential {
+            aws_access_key_id: "my access key".to_string(),
+            aws_secret_access_key: "my secret key".to_string(),
+            external_id: None,
+        })
+        .into();
+        let secret_id = old_state.create_secret(secret.clone()).await.unwrap();
+
+        // Only the new key is known - the secret cannot be decrypted
+        let new_key_only =
+            EncryptionKeys::new("new".to_string(), "new-key".to_string(), HashMap::new());
+        let new_key_only_state = SecretsState::from_pools(pool.clone(), pool.clone())
+            .with_encryption_keys(new_key_only.clone());
+        assert!(new_key_only_state
+            .get_secret_by_id::<StorageCredential>(secret_id)
+            .await
+            .is_err());
+        assert!(reencrypt_secrets(&pool, &new_key_only, 10).await.is_err());
+
+        // Both keys are known during the rotation
+        let rotation_keys = EncryptionKeys::new(
+            "new".to_string(),
+            "new-key".to_string(),
+            HashMap::from([("old".to_string(), "old-key".to_string())]),
+        );
+        let rotation_state = SecretsState::from_pools(pool.clone(), pool.clone())
+            .with_encryption_keys(rotation_keys.clone());
+        assert_eq!(
+            rotation_state
+                .get_secret_by_id::<StorageCredential>(secret_id)
+                .await
+                .unwrap()
+                .secret,
+            secret
+        );
+        assert_eq!(
+            reencrypt_secrets(&pool, &rotation_keys, 1).await.unwrap(),
+            1
+        );
+        // Resuming a finished rotation is a no-op
+        assert_eq!(
+            reencrypt_secrets(&pool, &rotation_keys, 1).await.unwrap(),
+            0
+        );
+
+        // The old key is no longer required
+        assert_eq!(
+            new_key_only_state
+                .get_secret_by_id::<StorageCredential>(secret_id)
+                .await
+                .unwrap()
+                .secret,
+            secret
+        );
+    }
 }
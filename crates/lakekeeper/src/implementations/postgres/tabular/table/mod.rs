@@ -19,10 +19,15 @@ use uuid::Uuid;
 
 use crate::{
     api::iceberg::v1::tables::{LoadTableFilters, SnapshotsQuery},
+    implementations::postgres::warehouse::{
+        into_additional_storage_profiles, DbAdditionalStorageProfile,
+    },
     service::{
+        resolve_storage_for_location,
         storage::{join_location, StorageProfile},
         ConversionError, InternalParseLocationError, InternalTableMetadataBuildFailed,
-        LoadTableError, LoadTableResponse, RequiredTableComponentMissing, TableId,
+        LoadTableError, LoadTableResponse, NamespaceStorageCredential,
+        RequiredTableComponentMissing, TableId,
     },
     SecretIdent, WarehouseId,
 };
@@ -148,6 +153,9 @@ struct TableQueryStruct {
     table_fs_protocol: String,
     storage_profile: Json<StorageProfile>,
     storage_secret_id: Option<Uuid>,
+    additional_storage_profiles: Json<Vec<DbAdditionalStorageProfile>>,
+    namespace_storage_secret_id: Option<Uuid>,
+    namespace_sts_role_arn: Option<String>,
     table_properties_keys: Option<Vec<String>>,
     table_properties_values: Option<Vec<String>>,
     default_partition_spec_id: Option<i32>,
@@ -519,6 +527,9 @@ pub(crate) async fn load_tables(
             ti."metadata_location",
             w.storage_profile as "storage_profile: Json<StorageProfile>",
             w."storage_secret_id",
+            w.additional_storage_profiles as "additional_storage_profiles: Json<Vec<DbAdditionalStorageProfile>>",
+            n.storage_secret_id as "namespace_storage_secret_id",
+            n.storage_sts_role_arn as "namespace_sts_role_arn",
             ts.schema_ids,
             tcs.schema_id as "current_schema",
             tdps.partition_spec_id as "default_partition_spec_id",
@@ -563,6 +574,7 @@ pub(crate) async fn load_tables(
         FROM "table" t
         INNER JOIN tabular ti ON ti.warehouse_id = $1 AND t.table_id = ti.tabular_id
         INNER JOIN warehouse w ON w.warehouse_id = $1
+        INNER JOIN namespace n ON n.warehouse_id = $1 AND n.namespace_id = ti.namespace_id
         INNER JOIN table_current_schema tcs
             ON tcs.warehouse_id = $1 AND tcs.table_id = t.table_id
         LEFT JOIN table_default_partition_spec tdps
@@ -678,8 +690,23 @@ pub(crate) async fn load_tables(
                 .transpose()
                 .map_err(InternalParseLocationError::from)?;
             let namespace_id = table.namespace_id.into();
-            let storage_secret_ident = table.storage_secret_id.map(SecretIdent::from);
-            let storage_profile = table.storage_profile.deref().clone();
+            let table_location = join_location(&table.table_fs_protocol, &table.table_fs_location)
+                .map_err(InternalParseLocationError::from)?;
+            let additional_storage_profiles =
+                into_additional_storage_profiles(table.additional_storage_profiles.clone());
+            let (storage_profile, storage_secret_ident) = resolve_storage_for_location(
+                table.storage_profile.deref(),
+                table.storage_secret_id.map(SecretIdent::from),
+                &additional_storage_profiles,
+                &table_location,
+            );
+            let namespace_storage_credential = NamespaceStorageCredential {
+                storage_secret_id: table.namespace_storage_secret_id.map(SecretIdent::from),
+                sts_role_arn: table.namespace_sts_role_arn.clone(),
+            };
+            let (storage_profile, storage_secret_ident) =
+                namespace_storage_credential.apply(storage_profile, storage_secret_ident);
+            let storage_profile = storage_profile.into_owned();
 
             let table_metadata = table.into_table_metadata()?;
 
@@ -7,8 +7,14 @@ use crate::{
     api::management::v1::warehouse::{
         GetTaskQueueConfigResponse, QueueConfigResponse, SetTaskQueueConfigRequest,
     },
-    implementations::postgres::dbutils::DBErrorHandler,
-    service::tasks::{Task, TaskAttemptId, TaskFilter, TaskQueueName, TaskStatus},
+    implementations::postgres::{
+        dbutils::DBErrorHandler,
+        listener::{listen, ListenerEvent},
+    },
+    service::{
+        tasks::{wake_task_queue, Task, TaskAttemptId, TaskFilter, TaskQueueName, TaskStatus},
+        TaskQueueStats,
+    },
     WarehouseId,
 };
 
@@ -85,7 +91,7 @@ pub(crate) async fn queue_task_batch(
         })?);
     }
 
-    Ok(sqlx::query!(
+    let inserted = sqlx::query!(
         r#"WITH input_rows AS (
             SELECT
                 task_id,
@@ -156,7 +162,7 @@ pub(crate) async fn queue_task_batch(
         TaskStatus::Scheduled as _,
         &entity_names,
     )
-    .fetch_all(conn)
+    .fetch_all(&mut *conn)
     .await
     .map(|records| {
         records
@@ -172,12 +178,66 @@ pub(crate) async fn queue_task_batch(
             })
             .collect_vec()
     })
-    .map_err(|e| e.into_error_model("failed queueing tasks"))?)
+    .map_err(|e| e.into_error_model("failed queueing tasks"))?;
+
+    let now = chrono::Utc::now();
+    let any_due = scheduled_fors
+        .iter()
+        .any(|scheduled_for| !matches!(scheduled_for, Some(t) if *t > now));
+    if !inserted.is_empty() && any_due {
+        notify_task_queued(conn, queue_name).await?;
+    }
+
+    Ok(inserted)
+}
+
+/// Channel on which the names of queues with new runnable tasks are published.
+const TASK_QUEUED_CHANNEL: &str = "lakekeeper_task_queued";
+
+/// Notify idle workers of all replicas that `queue_name` has runnable tasks.
+/// The notification is delivered when the transaction commits.
+async fn notify_task_queued(
+    conn: &mut PgConnection,
+    queue_name: &str,
+) -> Result<(), IcebergErrorResponse> {
+    sqlx::query("SELECT pg_notify($1, $2)")
+        .bind(TASK_QUEUED_CHANNEL)
+        .bind(queue_name)
+        .execute(conn)
+        .await
+        .map_err(|e| e.into_error_model(format!("Failed to notify task queue `{queue_name}`")))?;
+    Ok(())
+}
+
+/// Listen for newly runnable tasks and wake up idle workers of the corresponding queue.
+/// If the listener fails, it reconnects with an exponential backoff.
+/// Workers fall back to polling in the meantime, which also covers missed wakeups.
+pub(crate) async fn listen_for_task_wakeups(
+    pool: &PgPool,
+    cancellation_token: crate::CancellationToken,
+) -> anyhow::Result<()> {
+    listen(
+        pool,
+        TASK_QUEUED_CHANNEL,
+        "Task wakeup listener",
+        &cancellation_token,
+        |event| async move {
+            if let ListenerEvent::Notification(queue_name) = event {
+                wake_task_queue(&queue_name);
+            }
+        },
+    )
+    .await;
+    Ok(())
 }
 
 /// `default_max_time_since_last_heartbeat` is only used if no task configuration is found
 /// in the DB for the given `queue_name`, typically before a user has configured the value explicitly.
-#[allow(clippy::too_many_lines)]
+///
+/// Picks for warehouses with `max_concurrent_tasks` are serialized per queue and warehouse
+/// with a transaction scoped advisory lock. The number of active tasks is re-checked
+/// after the lock is acquired, so that concurrent workers cannot exceed the limit.
+/// Warehouses found at their limit are excluded from the following candidate selections.
 pub(crate) async fn pick_task(
     pool: &PgPool,
     queue_name: &TaskQueueName,
@@ -195,6 +255,170 @@ pub(crate) async fn pick_task(
                 None,
             ))?,
     };
+
+    let mut saturated_warehouses = Vec::new();
+    loop {
+        let mut transaction = pool.begin().await.map_err(|e| {
+            tracing::error!(
+                ?e,
+                "Failed to start transaction to pick a task for '{queue_name}'"
+            );
+            e.into_error_model(format!("Failed to pick a '{queue_name}' task"))
+        })?;
+
+        let Some(candidate) = select_task_candidate(
+            &mut transaction,
+            queue_name,
+            max_time_since_last_heartbeat,
+            &saturated_warehouses,
+        )
+        .await?
+        else {
+            return Ok(None);
+        };
+
+        if let Some(max_concurrent_tasks) = candidate
+            .max_concurrent_tasks
+            .filter(|_| candidate.is_scheduled)
+        {
+            let active_tasks =
+                lock_and_count_active_tasks(&mut transaction, queue_name, candidate.warehouse_id)
+                    .await?;
+            if active_tasks >= i64::from(max_concurrent_tasks) {
+                // A concurrent pick for the same warehouse committed in the meantime.
+                // Retry with a fresh snapshot which skips this warehouse.
+                tracing::debug!(
+                    "Warehouse '{}' reached its limit of {max_concurrent_tasks} concurrent '{queue_name}' tasks, retrying pick.",
+                    candidate.warehouse_id
+                );
+                saturated_warehouses.push(candidate.warehouse_id);
+                continue;
+            }
+        }
+
+        let task = start_task_attempt(&mut transaction, candidate.task_id).await?;
+        transaction.commit().await.map_err(|e| {
+            tracing::error!(?e, "Failed to commit pick of a task for '{queue_name}'");
+            e.into_error_model(format!("Failed to pick a '{queue_name}' task"))
+        })?;
+        return Ok(task);
+    }
+}
+
+#[derive(Debug)]
+struct TaskCandidate {
+    task_id: Uuid,
+    warehouse_id: Uuid,
+    is_scheduled: bool,
+    max_concurrent_tasks: Option<i32>,
+}
+
+/// Select and lock the next task to run. The number of active tasks per warehouse is only
+/// a snapshot, it must be re-checked with [`lock_and_count_active_tasks`].
+/// Scheduled tasks of `excluded_warehouses` are skipped.
+async fn select_task_candidate(
+    transaction: &mut PgConnection,
+    queue_name: &str,
+    max_time_since_last_heartbeat: PgInterval,
+    excluded_warehouses: &[Uuid],
+) -> Result<Option<TaskCandidate>, IcebergErrorResponse> {
+    let candidate = sqlx::query_as!(
+        TaskCandidate,
+        r#"
+        WITH active_per_warehouse AS (
+            SELECT warehouse_id, count(*) AS active_tasks
+            FROM task
+            WHERE queue_name = $1 AND status != 'scheduled'
+            GROUP BY warehouse_id
+        )
+        SELECT
+            t.task_id,
+            t.warehouse_id,
+            t.status = 'scheduled' AS "is_scheduled!",
+            tc.max_concurrent_tasks AS "max_concurrent_tasks?"
+        FROM task t
+        LEFT JOIN task_config tc
+            ON tc.queue_name = t.queue_name
+                AND tc.warehouse_id = t.warehouse_id
+        LEFT JOIN active_per_warehouse a
+            ON a.warehouse_id = t.warehouse_id
+        WHERE (t.queue_name = $1 AND scheduled_for <= now()) 
+            AND (
+                (status = 'scheduled' AND t.warehouse_id != ALL($3) AND (
+                    tc.max_concurrent_tasks IS NULL
+                    OR COALESCE(a.active_tasks, 0) < tc.max_concurrent_tasks
+                )) OR 
+                (status != 'scheduled' AND (now() - last_heartbeat_at) > COALESCE(tc.max_time_since_last_heartbeat, $2))
+            )
+        -- Fair share: prefer warehouses with the fewest active tasks of this queue
+        ORDER BY COALESCE(a.active_tasks, 0) ASC, t.scheduled_for ASC
+        -- FOR UPDATE locks the row we select here, SKIP LOCKED makes us not wait for rows other
+        -- transactions locked
+        FOR UPDATE OF t SKIP LOCKED
+        LIMIT 1
+        "#,
+        queue_name,
+        max_time_since_last_heartbeat,
+        excluded_warehouses
+    )
+    .fetch_optional(transaction)
+    .await
+    .map_err(|e| {
+        tracing::error!(?e, "Failed to select a task for '{queue_name}'");
+        e.into_error_model(format!("Failed to pick a '{queue_name}' task"))
+    })?;
+
+    Ok(candidate)
+}
+
+/// Serialize picks for the same queue and warehouse until the end of the transaction
+/// and count the active tasks with a snapshot taken after the lock was acquired.
+async fn lock_and_count_active_tasks(
+    transaction: &mut PgConnection,
+    queue_name: &str,
+    warehouse_id: Uuid,
+) -> Result<i64, IcebergErrorResponse> {
+    sqlx::query!(
+        r#"SELECT pg_advisory_xact_lock(hashtext('task-pick:' || $1 || ':' || $2::text))"#,
+        queue_name,
+        warehouse_id
+    )
+    .execute(&mut *transaction)
+    .await
+    .map_err(|e| {
+        tracing::error!(
+            ?e,
+            "Failed to lock warehouse '{warehouse_id}' for '{queue_name}'"
+        );
+        e.into_error_model(format!("Failed to pick a '{queue_name}' task"))
+    })?;
+
+    let active_tasks = sqlx::query_scalar!(
+        r#"
+        SELECT count(*) AS "active_tasks!"
+        FROM task
+        WHERE queue_name = $1 AND warehouse_id = $2 AND status != 'scheduled'
+        "#,
+        queue_name,
+        warehouse_id
+    )
+    .fetch_one(&mut *transaction)
+    .await
+    .map_err(|e| {
+        tracing::error!(?e, "Failed to count active tasks for '{queue_name}'");
+        e.into_error_model(format!("Failed to pick a '{queue_name}' task"))
+    })?;
+
+    Ok(active_tasks)
+}
+
+/// Start a new attempt of a task locked by [`select_task_candidate`].
+/// Timed out attempts are recorded as failed in the task log.
+#[allow(clippy::too_many_lines)]
+async fn start_task_attempt(
+    transaction: &mut PgConnection,
+    task_id: Uuid,
+) -> Result<Option<Task>, IcebergErrorResponse> {
     let x = sqlx::query!(
         r#"
         WITH picked_task AS (
@@ -203,15 +427,7 @@ pub(crate) async fn pick_task(
             LEFT JOIN task_config tc
                 ON tc.queue_name = t.queue_name
                     AND tc.warehouse_id = t.warehouse_id
-            WHERE (t.queue_name = $1 AND scheduled_for <= now()) 
-                AND (
-                    (status = 'scheduled') OR 
-                    (status != 'scheduled' AND (now() - last_heartbeat_at) > COALESCE(tc.max_time_since_last_heartbeat, $2))
-                )
-            -- FOR UPDATE locks the row we select here, SKIP LOCKED makes us not wait for rows other
-            -- transactions locked
-            FOR UPDATE OF t SKIP LOCKED
-            LIMIT 1
+            WHERE t.task_id = $1
         ),
         inserted AS (
             INSERT INTO task_log(
@@ -280,14 +496,13 @@ pub(crate) async fn pick_task(
             task.queue_name,
             (select config from picked_task)
             "#,
-        queue_name,
-        max_time_since_last_heartbeat
+        task_id
     )
-    .fetch_optional(pool)
+    .fetch_optional(transaction)
     .await
     .map_err(|e| {
-        tracing::error!(?e, "Failed to pick a task for '{queue_name}'");
-        e.into_error_model(format!("Failed to pick a '{queue_name}' task"))
+        tracing::error!(?e, "Failed to start attempt of task '{task_id}'");
+        e.into_error_model(format!("Failed to start attempt of task '{task_id}'"))
     })?;
 
     if let Some(task) = x {
@@ -627,7 +842,7 @@ pub(crate) async fn get_task_queue_config<
 ) -> crate::api::Result<Option<GetTaskQueueConfigResponse>> {
     let result = sqlx::query!(
         r#"
-        SELECT config, max_time_since_last_heartbeat
+        SELECT config, max_time_since_last_heartbeat, max_concurrent_tasks
         FROM task_config
         WHERE warehouse_id = $1 AND queue_name = $2
         "#,
@@ -651,9 +866,46 @@ pub(crate) async fn get_task_queue_config<
         max_seconds_since_last_heartbeat: result
             .max_time_since_last_heartbeat
             .map(|x| x.microseconds / 1_000_000),
+        max_concurrent_tasks: result.max_concurrent_tasks,
     }))
 }
 
+pub(crate) async fn get_task_queue_stats<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    connection: E,
+) -> crate::api::Result<Vec<TaskQueueStats>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT queue_name,
+            count(*) FILTER (WHERE status = 'scheduled') as "scheduled_tasks!",
+            count(*) FILTER (WHERE status != 'scheduled') as "running_tasks!",
+            (EXTRACT(EPOCH FROM now() - min(scheduled_for) FILTER (
+                WHERE status = 'scheduled' AND scheduled_for <= now()
+            )) * 1000)::bigint as oldest_due_task_age_ms
+        FROM task
+        GROUP BY queue_name
+        "#
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Failed to get task queue stats"))?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| TaskQueueStats {
+            queue_name: TaskQueueName::from(row.queue_name),
+            scheduled_tasks: row.scheduled_tasks,
+            running_tasks: row.running_tasks,
+            oldest_due_task_age: row
+                .oldest_due_task_age_ms
+                .map(chrono::Duration::milliseconds),
+        })
+        .collect())
+}
+
 pub(crate) async fn set_task_queue_config(
     transaction: &mut PgConnection,
     queue_name: &TaskQueueName,
@@ -679,15 +931,19 @@ pub(crate) async fn set_task_queue_config(
         };
     sqlx::query!(
         r#"
-        INSERT INTO task_config (queue_name, warehouse_id, config, max_time_since_last_heartbeat)
-        VALUES ($1, $2, $3, $4)
+        INSERT INTO task_config (queue_name, warehouse_id, config, max_time_since_last_heartbeat, max_concurrent_tasks)
+        VALUES ($1, $2, $3, $4, $5)
         ON CONFLICT (queue_name, warehouse_id) DO UPDATE
-        SET config = $3, max_time_since_last_heartbeat = COALESCE($4, task_config.max_time_since_last_heartbeat )
+        SET config = $3,
+            max_time_since_last_heartbeat = COALESCE($4, task_config.max_time_since_last_heartbeat ),
+            max_concurrent_tasks = CASE WHEN $6 THEN $5 ELSE task_config.max_concurrent_tasks END
         "#,
         queue_name.as_str(),
         *warehouse_id,
         serialized,
-        max_time_since_last_heartbeat
+        max_time_since_last_heartbeat,
+        config.max_concurrent_tasks.flatten(),
+        config.max_concurrent_tasks.is_some()
     )
     .execute(transaction)
     .await
@@ -797,7 +1053,7 @@ pub(crate) async fn reschedule_tasks_for(
         scheduled_not_null,
         run_now
     )
-    .execute(transaction)
+    .execute(&mut *transaction)
     .await
     .map_err(|e| {
         tracing::error!(?e, "Failed to reschedule tasks");
@@ -809,6 +1065,20 @@ pub(crate) async fn reschedule_tasks_for(
         e.into_error_model(format!("Failed to reschedule tasks {time_str}."))
     })?;
 
+    if run_now {
+        sqlx::query(
+            r#"
+            SELECT pg_notify($1, queue_name)
+            FROM (SELECT DISTINCT queue_name FROM task WHERE task_id = ANY($2)) q
+            "#,
+        )
+        .bind(TASK_QUEUED_CHANNEL)
+        .bind(task_ids.iter().map(|s| **s).collect_vec())
+        .execute(transaction)
+        .await
+        .map_err(|e| e.into_error_model("Failed to notify task queues of rescheduled tasks"))?;
+    }
+
     Ok(())
 }
 
@@ -1101,6 +1371,45 @@ mod test {
         assert_ne!(id, id3);
     }
 
+    #[sqlx::test]
+    async fn test_queue_task_notifies_listeners(pool: PgPool) {
+        let warehouse_id = setup_warehouse(pool.clone()).await;
+        let mut listener = sqlx::postgres::PgListener::connect_with(&pool)
+            .await
+            .unwrap();
+        listener.listen(TASK_QUEUED_CHANNEL).await.unwrap();
+
+        let scheduled_tq_name = generate_tq_name();
+        let tq_name = generate_tq_name();
+        let mut conn = pool.acquire().await.unwrap();
+        // Tasks scheduled in the future do not wake up workers
+        queue_task(
+            &mut conn,
+            &scheduled_tq_name,
+            None,
+            EntityId::Table(Uuid::now_v7().into()),
+            warehouse_id,
+            Some(Utc::now() + chrono::Duration::hours(1)),
+            None,
+        )
+        .await
+        .unwrap();
+        queue_task(
+            &mut conn,
+            &tq_name,
+            None,
+            EntityId::Table(Uuid::now_v7().into()),
+            warehouse_id,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let notification = listener.recv().await.unwrap();
+        assert_eq!(notification.payload(), tq_name.as_str());
+    }
+
     pub(crate) async fn setup_warehouse(pool: PgPool) -> WarehouseId {
         let prof = crate::tests::memory_io_profile();
         let (_, wh) = crate::tests::setup(
@@ -1188,6 +1497,38 @@ mod test {
         );
     }
 
+    #[sqlx::test]
+    async fn test_get_task_queue_stats(pool: PgPool) {
+        let mut conn = pool.acquire().await.unwrap();
+        let tq_name = generate_tq_name();
+        let warehouse_id = setup_warehouse(pool.clone()).await;
+
+        for _ in 0..2 {
+            queue_task(
+                &mut conn,
+                &tq_name,
+                None,
+                EntityId::Table(Uuid::now_v7().into()),
+                warehouse_id,
+                None,
+                None,
+            )
+            .await
+            .unwrap()
+            .unwrap();
+        }
+        pick_task(&pool, &tq_name, DEFAULT_MAX_TIME_SINCE_LAST_HEARTBEAT)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let stats = get_task_queue_stats(&pool).await.unwrap();
+        let stats = stats.into_iter().find(|s| s.queue_name == tq_name).unwrap();
+        assert_eq!(stats.scheduled_tasks, 1);
+        assert_eq!(stats.running_tasks, 1);
+        assert!(stats.oldest_due_task_age.is_some());
+    }
+
     #[sqlx::test]
     async fn test_success_tasks_are_not_polled(pool: PgPool) {
         let mut conn = pool.acquire().await.unwrap();
@@ -1574,6 +1915,237 @@ mod test {
             .unwrap();
     }
 
+    #[sqlx::test]
+    async fn test_max_concurrent_tasks_per_warehouse(pool: PgPool) {
+        let mut conn = pool.acquire().await.unwrap();
+        let (warehouse_id, other_warehouse_id) = setup_two_warehouses(pool.clone()).await;
+        let tq_name = generate_tq_name();
+
+        set_task_queue_config(
+            &mut conn,
+            &tq_name,
+            warehouse_id,
+            SetTaskQueueConfigRequest {
+                queue_config: QueueConfig(serde_json::json!({})),
+                max_seconds_since_last_heartbeat: None,
+                max_concurrent_tasks: Some(Some(1)),
+            },
+        )
+        .await
+        .unwrap();
+
+        let mut capped_ids = Vec::new();
+        for _ in 0..2 {
+            let id = queue_task(
+                &mut conn,
+                &tq_name,
+                None,
+                EntityId::Table(Uuid::now_v7().into()),
+                warehouse_id,
+                None,
+                None,
+            )
+            .await
+            .unwrap()
+            .unwrap();
+            capped_ids.push(id);
+        }
+        let other_id = queue_task(
+            &mut conn,
+            &tq_name,
+            None,
+            EntityId::Table(Uuid::now_v7().into()),
+            other_warehouse_id,
+            None,
+            None,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        let task = pick_task(&pool, &tq_name, DEFAULT_MAX_TIME_SINCE_LAST_HEARTBEAT)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(task.task_id(), capped_ids[0]);
+
+        // The second task of the capped warehouse must wait until the first one is done
+        let task2 = pick_task(&pool, &tq_name, DEFAULT_MAX_TIME_SINCE_LAST_HEARTBEAT)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(task2.task_id(), other_id);
+        assert!(
+            pick_task(&pool, &tq_name, DEFAULT_MAX_TIME_SINCE_LAST_HEARTBEAT)
+                .await
+                .unwrap()
+                .is_none(),
+            "Warehouse is at its concurrency limit, no task should be picked."
+        );
+
+        record_success(&task, &mut pool.acquire().await.unwrap(), Some(""))
+            .await
+            .unwrap();
+        let task3 = pick_task(&pool, &tq_name, DEFAULT_MAX_TIME_SINCE_LAST_HEARTBEAT)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(task3.task_id(), capped_ids[1]);
+    }
+
+    #[sqlx::test]
+    async fn test_max_concurrent_tasks_with_concurrent_picks(pool: PgPool) {
+        let mut conn = pool.acquire().await.unwrap();
+        let warehouse_id = setup_warehouse(pool.clone()).await;
+        let tq_name = generate_tq_name();
+
+        set_task_queue_config(
+            &mut conn,
+            &tq_name,
+            warehouse_id,
+            SetTaskQueueConfigRequest {
+                queue_config: QueueConfig(serde_json::json!({})),
+                max_seconds_since_last_heartbeat: None,
+                max_concurrent_tasks: Some(Some(2)),
+            },
+        )
+        .await
+        .unwrap();
+
+        for _ in 0..10 {
+            queue_task(
+                &mut conn,
+                &tq_name,
+                None,
+                EntityId::Table(Uuid::now_v7().into()),
+                warehouse_id,
+                None,
+                None,
+            )
+            .await
+            .unwrap()
+            .unwrap();
+        }
+
+        let picks = futures::future::join_all((0..10).map(|_| {
+            let pool = pool.clone();
+            let tq_name = tq_name.clone();
+            async move {
+                pick_task(&pool, &tq_name, DEFAULT_MAX_TIME_SINCE_LAST_HEARTBEAT)
+                    .await
+                    .unwrap()
+            }
+        }))
+        .await;
+
+        assert_eq!(picks.iter().flatten().count(), 2);
+    }
+
+    #[sqlx::test]
+    async fn test_saturated_warehouse_does_not_block_picks(pool: PgPool) {
+        let mut conn = pool.acquire().await.unwrap();
+        let (capped_warehouse_id, other_warehouse_id) = setup_two_warehouses(pool.clone()).await;
+        let tq_name = generate_tq_name();
+
+        set_task_queue_config(
+            &mut conn,
+            &tq_name,
+            capped_warehouse_id,
+            SetTaskQueueConfigRequest {
+                queue_config: QueueConfig(serde_json::json!({})),
+                max_seconds_since_last_heartbeat: None,
+                max_concurrent_tasks: Some(Some(1)),
+            },
+        )
+        .await
+        .unwrap();
+
+        for warehouse_id in [capped_warehouse_id, other_warehouse_id] {
+            for _ in 0..5 {
+                queue_task(
+                    &mut conn,
+                    &tq_name,
+                    None,
+                    EntityId::Table(Uuid::now_v7().into()),
+                    warehouse_id,
+                    None,
+                    None,
+                )
+                .await
+                .unwrap()
+                .unwrap();
+            }
+        }
+
+        let picks = futures::future::join_all((0..6).map(|_| {
+            let pool = pool.clone();
+            let tq_name = tq_name.clone();
+            async move {
+                pick_task(&pool, &tq_name, DEFAULT_MAX_TIME_SINCE_LAST_HEARTBEAT)
+                    .await
+                    .unwrap()
+            }
+        }))
+        .await;
+
+        let picked = picks.iter().flatten().collect_vec();
+        assert_eq!(picked.len(), 6);
+        assert_eq!(
+            picked
+                .iter()
+                .filter(|t| t.task_metadata.warehouse_id == capped_warehouse_id)
+                .count(),
+            1
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_tasks_are_picked_fairly_across_warehouses(pool: PgPool) {
+        let mut conn = pool.acquire().await.unwrap();
+        let (warehouse_id, other_warehouse_id) = setup_two_warehouses(pool.clone()).await;
+        let tq_name = generate_tq_name();
+
+        for _ in 0..3 {
+            queue_task(
+                &mut conn,
+                &tq_name,
+                None,
+                EntityId::Table(Uuid::now_v7().into()),
+                warehouse_id,
+                None,
+                None,
+            )
+            .await
+            .unwrap()
+            .unwrap();
+        }
+        let other_id = queue_task(
+            &mut conn,
+            &tq_name,
+            None,
+            EntityId::Table(Uuid::now_v7().into()),
+            other_warehouse_id,
+            None,
+            None,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        let task = pick_task(&pool, &tq_name, DEFAULT_MAX_TIME_SINCE_LAST_HEARTBEAT)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(task.task_metadata.warehouse_id, warehouse_id);
+        // The other warehouse has no running tasks and is preferred, even though its task
+        // was queued last.
+        let task2 = pick_task(&pool, &tq_name, DEFAULT_MAX_TIME_SINCE_LAST_HEARTBEAT)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(task2.task_id(), other_id);
+    }
+
     #[sqlx::test]
     async fn test_queue_batch(pool: PgPool) {
         let mut conn = pool.acquire().await.unwrap();
@@ -1789,6 +2361,7 @@ mod test {
         let config = SetTaskQueueConfigRequest {
             queue_config: QueueConfig(serde_json::json!({"max_attempts": 5})),
             max_seconds_since_last_heartbeat: Some(3600),
+            max_concurrent_tasks: Some(Some(2)),
         };
 
         set_task_queue_config(&mut conn, &tq_name, warehouse_id, config)
@@ -1806,6 +2379,41 @@ mod test {
             serde_json::json!({"max_attempts": 5})
         );
         assert_eq!(response.max_seconds_since_last_heartbeat, Some(3600));
+        assert_eq!(response.max_concurrent_tasks, Some(2));
+    }
+
+    #[sqlx::test]
+    async fn test_set_task_config_keeps_max_concurrent_tasks_if_omitted(pool: PgPool) {
+        let mut conn = pool.acquire().await.unwrap();
+        let warehouse_id = setup_warehouse(pool.clone()).await;
+        let tq_name = generate_tq_name();
+
+        let set_config = |max_concurrent_tasks| SetTaskQueueConfigRequest {
+            queue_config: QueueConfig(serde_json::json!({})),
+            max_seconds_since_last_heartbeat: None,
+            max_concurrent_tasks,
+        };
+
+        set_task_queue_config(&mut conn, &tq_name, warehouse_id, set_config(Some(Some(2))))
+            .await
+            .unwrap();
+        set_task_queue_config(&mut conn, &tq_name, warehouse_id, set_config(None))
+            .await
+            .unwrap();
+        let response = get_task_queue_config(&mut *conn, warehouse_id, &tq_name)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(response.max_concurrent_tasks, Some(2));
+
+        set_task_queue_config(&mut conn, &tq_name, warehouse_id, set_config(Some(None)))
+            .await
+            .unwrap();
+        let response = get_task_queue_config(&mut *conn, warehouse_id, &tq_name)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(response.max_concurrent_tasks, None);
     }
 
     #[sqlx::test]
@@ -1817,6 +2425,7 @@ mod test {
         let config = SetTaskQueueConfigRequest {
             queue_config: QueueConfig(serde_json::json!({"max_attempts": 5})),
             max_seconds_since_last_heartbeat: Some(3600),
+            max_concurrent_tasks: None,
         };
 
         set_task_queue_config(&mut conn, &tq_name, warehouse_id, config)
@@ -1,5 +1,6 @@
 use std::{collections::HashSet, ops::Deref};
 
+use lakekeeper_io::Location;
 use sqlx::{types::Json, PgPool};
 
 use super::CatalogState;
@@ -17,13 +18,14 @@ use crate::{
         pagination::{PaginateToken, V1PaginateToken},
     },
     service::{
-        storage::StorageProfile, CatalogCreateWarehouseError, CatalogDeleteWarehouseError,
-        CatalogGetWarehouseByIdError, CatalogGetWarehouseByNameError, CatalogListWarehousesError,
-        CatalogRenameWarehouseError, DatabaseIntegrityError, GetProjectResponse,
-        GetWarehouseResponse, ProjectIdNotFoundError, SetWarehouseDeletionProfileError,
-        SetWarehouseProtectedError, SetWarehouseStatusError, StorageProfileSerializationError,
-        UpdateWarehouseStorageProfileError, WarehouseAlreadyExists, WarehouseHasUnfinishedTasks,
-        WarehouseIdNotFound, WarehouseNotEmpty, WarehouseProtected, WarehouseStatus,
+        storage::StorageProfile, AdditionalStorageProfile, CatalogBackendError,
+        CatalogCreateWarehouseError, CatalogDeleteWarehouseError, CatalogGetWarehouseByIdError,
+        CatalogGetWarehouseByNameError, CatalogListWarehousesError, CatalogRenameWarehouseError,
+        DatabaseIntegrityError, GetProjectResponse, GetWarehouseResponse, ProjectIdNotFoundError,
+        SetWarehouseDeletionProfileError, SetWarehouseProtectedError, SetWarehouseStatusError,
+        StorageProfileSerializationError, UpdateWarehouseStorageProfileError,
+        WarehouseAlreadyExists, WarehouseHasUnfinishedTasks, WarehouseIdNotFound,
+        WarehouseNotEmpty, WarehouseProtected, WarehouseStatus,
     },
     ProjectId, SecretIdent, WarehouseId, CONFIG,
 };
@@ -261,6 +263,7 @@ pub(crate) async fn list_warehouses<
         tabular_delete_mode: DbTabularDeleteProfile,
         tabular_expiration_seconds: Option<i64>,
         protected: bool,
+        additional_storage_profiles: Json<Vec<DbAdditionalStorageProfile>>,
     }
 
     let include_status = include_status.unwrap_or_else(|| vec![WarehouseStatus::Active]);
@@ -275,7 +278,8 @@ pub(crate) async fn list_warehouses<
                 status AS "status: WarehouseStatus",
                 tabular_delete_mode as "tabular_delete_mode: DbTabularDeleteProfile",
                 tabular_expiration_seconds,
-                protected
+                protected,
+                additional_storage_profiles as "additional_storage_profiles: Json<Vec<DbAdditionalStorageProfile>>"
             FROM warehouse
             WHERE project_id = $1
             AND status = ANY($2)
@@ -304,6 +308,9 @@ pub(crate) async fn list_warehouses<
                 status: warehouse.status,
                 tabular_delete_profile,
                 protected: warehouse.protected,
+                additional_storage_profiles: into_additional_storage_profiles(
+                    warehouse.additional_storage_profiles,
+                ),
             })
         })
         .collect()
@@ -325,7 +332,8 @@ pub(super) async fn get_warehouse_by_name(
             status AS "status: WarehouseStatus",
             tabular_delete_mode as "tabular_delete_mode: DbTabularDeleteProfile",
             tabular_expiration_seconds,
-            protected
+            protected,
+            additional_storage_profiles as "additional_storage_profiles: Json<Vec<DbAdditionalStorageProfile>>"
         FROM warehouse
         WHERE warehouse_name = $1 AND project_id = $2
         AND status = 'active'
@@ -352,6 +360,9 @@ pub(super) async fn get_warehouse_by_name(
             status: warehouse.status,
             tabular_delete_profile,
             protected: warehouse.protected,
+            additional_storage_profiles: into_additional_storage_profiles(
+                warehouse.additional_storage_profiles,
+            ),
         }))
     } else {
         Ok(None)
@@ -376,7 +387,8 @@ pub(crate) async fn get_warehouse_by_id<
             status AS "status: WarehouseStatus",
             tabular_delete_mode as "tabular_delete_mode: DbTabularDeleteProfile",
             tabular_expiration_seconds,
-            protected
+            protected,
+            additional_storage_profiles as "additional_storage_profiles: Json<Vec<DbAdditionalStorageProfile>>"
         FROM warehouse
         WHERE warehouse_id = $1
         "#,
@@ -401,12 +413,35 @@ pub(crate) async fn get_warehouse_by_id<
             status: warehouse.status,
             tabular_delete_profile,
             protected: warehouse.protected,
+            additional_storage_profiles: into_additional_storage_profiles(
+                warehouse.additional_storage_profiles,
+            ),
         }))
     } else {
         Ok(None)
     }
 }
 
+/// Like [`get_warehouse_by_id`], but locks the warehouse until the end of the transaction,
+/// so that concurrent read-modify-write cycles of the warehouse are serialized.
+pub(crate) async fn get_warehouse_by_id_for_update(
+    warehouse_id: WarehouseId,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<Option<GetWarehouseResponse>, CatalogGetWarehouseByIdError> {
+    let locked = sqlx::query_scalar!(
+        r#"SELECT warehouse_id FROM warehouse WHERE warehouse_id = $1 FOR UPDATE"#,
+        *warehouse_id
+    )
+    .fetch_optional(&mut **transaction)
+    .await
+    .map_err(DBErrorHandler::into_catalog_backend_error)?;
+    if locked.is_none() {
+        return Ok(None);
+    }
+
+    get_warehouse_by_id(warehouse_id, &mut **transaction).await
+}
+
 pub(crate) async fn list_projects<'e, 'c: 'e, E: sqlx::Executor<'c, Database = sqlx::Postgres>>(
     project_ids: Option<HashSet<ProjectId>>,
     connection: E,
@@ -598,6 +633,110 @@ pub(crate) async fn update_storage_profile(
     Ok(())
 }
 
+/// Representation of an [`AdditionalStorageProfile`] in the `additional_storage_profiles` column.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct DbAdditionalStorageProfile {
+    name: String,
+    storage_profile: StorageProfile,
+    storage_secret_id: Option<uuid::Uuid>,
+    #[serde(default)]
+    namespace_prefixes: Vec<Vec<String>>,
+}
+
+impl From<DbAdditionalStorageProfile> for AdditionalStorageProfile {
+    fn from(profile: DbAdditionalStorageProfile) -> Self {
+        Self {
+            name: profile.name,
+            storage_profile: profile.storage_profile,
+            storage_secret_id: profile.storage_secret_id.map(SecretIdent::from),
+            namespace_prefixes: profile.namespace_prefixes,
+        }
+    }
+}
+
+impl From<AdditionalStorageProfile> for DbAdditionalStorageProfile {
+    fn from(profile: AdditionalStorageProfile) -> Self {
+        Self {
+            name: profile.name,
+            storage_profile: profile.storage_profile,
+            storage_secret_id: profile.storage_secret_id.map(|id| id.into_uuid()),
+            namespace_prefixes: profile.namespace_prefixes,
+        }
+    }
+}
+
+pub(crate) fn into_additional_storage_profiles(
+    profiles: Json<Vec<DbAdditionalStorageProfile>>,
+) -> Vec<AdditionalStorageProfile> {
+    profiles.0.into_iter().map(Into::into).collect()
+}
+
+pub(crate) async fn set_additional_storage_profiles(
+    warehouse_id: WarehouseId,
+    additional_storage_profiles: Vec<AdditionalStorageProfile>,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<(), UpdateWarehouseStorageProfileError> {
+    let profiles_ser = serde_json::to_value(
+        additional_storage_profiles
+            .into_iter()
+            .map(DbAdditionalStorageProfile::from)
+            .collect::<Vec<_>>(),
+    )
+    .map_err(StorageProfileSerializationError::from)?;
+
+    let row_count = sqlx::query!(
+        r#"
+            UPDATE warehouse
+            SET additional_storage_profiles = $1
+            WHERE warehouse_id = $2
+            AND status = 'active'
+        "#,
+        profiles_ser,
+        *warehouse_id
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(DBErrorHandler::into_catalog_backend_error)?
+    .rows_affected();
+
+    if row_count == 0 {
+        return Err(WarehouseIdNotFound::new(warehouse_id).into());
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn is_location_in_use(
+    warehouse_id: WarehouseId,
+    location: &Location,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<bool, CatalogBackendError> {
+    // Tabulars store their location without scheme, s3 profiles accept multiple schemes.
+    // Compared with `starts_with`, as `_` and `%` in locations are wildcards for `LIKE`.
+    let fs_location = location.authority_and_path().trim_end_matches('/');
+
+    sqlx::query_scalar!(
+        r#"SELECT (
+            EXISTS (
+                SELECT 1 FROM tabular
+                WHERE warehouse_id = $1
+                AND starts_with(TRIM(TRAILING '/' FROM fs_location) || '/', $2 || '/')
+            ) OR EXISTS (
+                SELECT 1 FROM namespace
+                WHERE warehouse_id = $1
+                AND starts_with(TRIM(TRAILING '/' FROM split_part(namespace_properties->>'location', '://', 2)) || '/', $2 || '/')
+            )
+        ) as "in_use!"
+        "#,
+        *warehouse_id,
+        fs_location
+    )
+    .fetch_one(&mut **transaction)
+    .await
+    .map_err(DBErrorHandler::into_catalog_backend_error)
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]
 #[sqlx(type_name = "tabular_delete_mode", rename_all = "kebab-case")]
 enum DbTabularDeleteProfile {
@@ -908,6 +1047,42 @@ pub(crate) mod test {
         assert!(warehouses.iter().any(|w| w.id == warehouse_id_2));
     }
 
+    #[sqlx::test]
+    async fn test_is_location_in_use_does_not_match_wildcards(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        crate::implementations::postgres::namespace::tests::initialize_namespace(
+            state.clone(),
+            warehouse_id,
+            &iceberg::NamespaceIdent::new("ns".to_string()),
+            Some(std::collections::HashMap::from([(
+                "location".to_string(),
+                "s3://test-bucket/a1b/ns".to_string(),
+            )])),
+        )
+        .await;
+
+        let mut transaction = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        for (location, expected) in [
+            ("s3://test-bucket/a1b", true),
+            ("s3://test-bucket/a_b", false),
+            ("s3://test-bucket/a%", false),
+            ("s3://test-bucket/a1", false),
+        ] {
+            let in_use = is_location_in_use(
+                warehouse_id,
+                &location.parse().unwrap(),
+                transaction.transaction(),
+            )
+            .await
+            .unwrap();
+            assert_eq!(in_use, expected, "{location}");
+        }
+        transaction.rollback().await.unwrap();
+    }
+
     #[sqlx::test]
     async fn test_rename_warehouse(pool: sqlx::PgPool) {
         let state = CatalogState::from_pools(pool.clone(), pool.clone());
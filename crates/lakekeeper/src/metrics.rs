@@ -31,6 +31,10 @@ pub fn get_axum_layer_and_install_recorder(
             ),
             utils::SECONDS_DURATION_BUCKETS,
         )?
+        .set_buckets_for_metric(
+            Matcher::Suffix("_duration_seconds".to_string()),
+            utils::SECONDS_DURATION_BUCKETS,
+        )?
         .with_http_listener((CONFIG.bind_ip, metrics_port))
         .build()?;
     let handle = recorder.handle();
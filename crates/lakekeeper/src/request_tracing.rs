@@ -1,9 +1,11 @@
 use http::Request;
+use opentelemetry_http::HeaderExtractor;
 use tower_http::{
     request_id::{MakeRequestId, RequestId},
     trace::MakeSpan,
 };
 use tracing::{Level, Span};
+use tracing_opentelemetry::OpenTelemetrySpanExt as _;
 use uuid::Uuid;
 
 use crate::{
@@ -29,6 +31,8 @@ impl RestMakeSpan {
 
 /// tower-http's `MakeSpan` implementation does not attach a `request_id` to the span. The impl below
 /// does.
+///
+/// If the request carries a W3C `traceparent` header, the span continues the caller's trace.
 impl<B> MakeSpan<B> for RestMakeSpan {
     fn make_span(&mut self, request: &Request<B>) -> Span {
         // This ugly macro is needed, unfortunately, because `tracing::span!`
@@ -55,13 +59,19 @@ impl<B> MakeSpan<B> for RestMakeSpan {
                     )
             }
         }
-        match self.level {
+        let span = match self.level {
             Level::TRACE => make_span!(tracing::Level::TRACE),
             Level::DEBUG => make_span!(tracing::Level::DEBUG),
             Level::INFO => make_span!(tracing::Level::INFO),
             Level::WARN => make_span!(tracing::Level::WARN),
             Level::ERROR => make_span!(tracing::Level::ERROR),
-        }
+        };
+
+        let parent_context = opentelemetry::global::get_text_map_propagator(|propagator| {
+            propagator.extract(&HeaderExtractor(request.headers()))
+        });
+        span.set_parent(parent_context);
+        span
     }
 }
 
@@ -19,11 +19,15 @@ use crate::{
             EndpointStatisticsMessage, EndpointStatisticsSink, EndpointStatisticsTracker, FlushMode,
         },
         event_publisher::{
+            event_log::{prune_event_log_periodically, EventLogSink},
+            outbox::CloudEventsOutboxRelay,
+            stream::CloudEventStream,
             CloudEventBackend, CloudEventsMessage, CloudEventsPublisher,
             CloudEventsPublisherBackgroundTask,
         },
-        health::ServiceHealthProvider,
-        tasks::TaskQueueRegistry,
+        health::{HealthExt, ServiceHealthProvider},
+        storage::health::StorageHealthProbe,
+        tasks::{export_task_queue_metrics_periodically, TaskQueueRegistry},
         CatalogStore, EndpointStatisticsTrackerTx, SecretStore, ServerInfo, State,
     },
     CancellationToken, CONFIG,
@@ -305,7 +309,7 @@ async fn serve_inner<
         stats,
         contract_verification,
         modify_router_fn,
-        cloud_event_sinks,
+        mut cloud_event_sinks,
         enable_built_in_task_queues: enable_built_in_queues,
         register_additional_task_queues_fn,
         additional_endpoint_hooks,
@@ -324,19 +328,46 @@ async fn serve_inner<
     validate_server_info(&server_info)?;
 
     // Health checks
-    let health_provider = ServiceHealthProvider::new(
-        vec![
-            ("catalog", Arc::new(catalog_state.clone())),
-            ("secrets", Arc::new(secrets_state.clone())),
-            ("auth", Arc::new(authorizer.clone())),
-        ],
-        CONFIG.health_check_frequency_seconds,
-    );
+    let health_checks: Vec<(&'static str, Arc<dyn HealthExt>)> = vec![
+        ("catalog", Arc::new(catalog_state.clone())),
+        ("secrets", Arc::new(secrets_state.clone())),
+        ("auth", Arc::new(authorizer.clone())),
+    ];
+    let health_provider =
+        ServiceHealthProvider::new(health_checks, CONFIG.health_check_frequency_seconds);
+    // Storage of individual warehouses is reported separately, so that a misconfigured
+    // warehouse does not mark the whole server as unhealthy.
+    let storage_health_provider = CONFIG.storage_health_check.enabled.then(|| {
+        let storage_health_checks: Vec<(&'static str, Arc<dyn HealthExt>)> = vec![(
+            "storage",
+            Arc::new(StorageHealthProbe::<C, S>::new(
+                catalog_state.clone(),
+                secrets_state.clone(),
+            )),
+        )];
+        ServiceHealthProvider::new(storage_health_checks, CONFIG.health_check_frequency_seconds)
+    });
 
     // Cloud events publisher setup
+    // Backfills re-publish snapshots to external sinks only. The event log is fed
+    // by the outbox and must not receive duplicates. The event stream of every
+    // replica is fed from the event log, see `C::stream_logged_events`.
+    let cloud_event_backfill_sinks = cloud_event_sinks.clone();
+    let event_stream = CloudEventStream::default();
+    if CONFIG.event_log.enabled {
+        cloud_event_sinks.push(Arc::new(EventLogSink::<C>::new(catalog_state.clone())));
+    }
+    let cloud_events_outbox_relay =
+        CONFIG
+            .cloud_events_outbox
+            .enabled
+            .then(|| CloudEventsOutboxRelay::<C> {
+                catalog_state: catalog_state.clone(),
+                sinks: cloud_event_sinks.clone(),
+            });
     let cloud_events_background_task = CloudEventsPublisherBackgroundTask {
         source: cloud_events_rx,
-        sinks: cloud_event_sinks,
+        sinks: cloud_event_sinks.clone(),
     };
 
     // Metrics server
@@ -371,6 +402,7 @@ async fn serve_inner<
                 catalog_state.clone(),
                 secrets_state.clone(),
                 authorizer.clone(),
+                cloud_event_backfill_sinks.clone(),
                 CONFIG.task_poll_interval,
             )
             .await;
@@ -388,6 +420,11 @@ async fn serve_inner<
             contract_verifiers: contract_verification,
             registered_task_queues,
             hooks,
+            event_stream,
+            cloud_event_backfill_sinks: cloud_event_backfill_sinks
+                .iter()
+                .map(|sink| sink.name().to_string())
+                .collect(),
             license_status,
         },
     };
@@ -401,6 +438,7 @@ async fn serve_inner<
         authenticator: authenticator.clone(),
         state: state.clone(),
         service_health_provider: health_provider.clone(),
+        storage_health_provider: storage_health_provider.clone(),
         cors_origins: CONFIG.allow_origin.as_deref(),
         metrics_layer: Some(layer),
         endpoint_statistics_tracker_tx: endpoint_statistics_tracker_tx.clone(),
@@ -421,8 +459,14 @@ async fn serve_inner<
     service_ids.insert(metrics_handle.id(), "Metrics Server".to_string());
 
     // Periodic health checks:
-    let health_abort_handles =
+    let mut health_abort_handles =
         health_provider.spawn_update_health_checks(service_futures, &cancellation_token);
+    if let Some(storage_health_provider) = &storage_health_provider {
+        health_abort_handles.extend(
+            storage_health_provider
+                .spawn_update_health_checks(service_futures, &cancellation_token),
+        );
+    }
     for (service_name, abort_handle) in health_abort_handles {
         service_ids.insert(abort_handle.id(), service_name);
     }
@@ -436,6 +480,46 @@ async fn serve_inner<
     });
     service_ids.insert(ce_abort_handle.id(), "Event Publisher".to_string());
 
+    // Cloud events outbox relay:
+    if let Some(relay) = cloud_events_outbox_relay {
+        let cancellation_token = cancellation_token.clone();
+        let relay_abort_handle = service_futures.spawn(async move {
+            relay
+                .run(cancellation_token)
+                .await
+                .map_err(|e| e.context("CloudEvents outbox relay exited with error"))
+        });
+        service_ids.insert(
+            relay_abort_handle.id(),
+            "CloudEvents Outbox Relay".to_string(),
+        );
+    }
+
+    // Event log retention:
+    if CONFIG.event_log.enabled {
+        let catalog_state = state.v1_state.catalog.clone();
+        let cancellation_token = cancellation_token.clone();
+        let prune_abort_handle = service_futures.spawn(async move {
+            prune_event_log_periodically::<C>(catalog_state, cancellation_token).await;
+            Ok(())
+        });
+        service_ids.insert(prune_abort_handle.id(), "Event Log Retention".to_string());
+
+        // Events logged by any replica, including this one:
+        let catalog_state = state.v1_state.catalog.clone();
+        let event_stream = state.v1_state.event_stream.clone();
+        let cancellation_token = cancellation_token.clone();
+        let stream_abort_handle = service_futures.spawn(async move {
+            C::stream_logged_events(catalog_state, event_stream, cancellation_token)
+                .await
+                .map_err(|e| e.context("Event stream listener exited with error"))
+        });
+        service_ids.insert(
+            stream_abort_handle.id(),
+            "Event Stream Listener".to_string(),
+        );
+    }
+
     // Endpoint statistics tracker:
     let tracker_abort_handle = service_futures.spawn(async move {
         tracker.run().await;
@@ -446,6 +530,21 @@ async fn serve_inner<
         "Endpoint Statistics Tracker".to_string(),
     );
 
+    // Cache invalidations of changes committed by any replica, including this one:
+    if CONFIG.cache.enabled || CONFIG.cache.vended_credentials_enabled {
+        let catalog_state = state.v1_state.catalog.clone();
+        let cancellation_token = cancellation_token.clone();
+        let cache_abort_handle = service_futures.spawn(async move {
+            C::listen_for_cache_invalidations(catalog_state, cancellation_token)
+                .await
+                .map_err(|e| e.context("Cache invalidation listener exited with error"))
+        });
+        service_ids.insert(
+            cache_abort_handle.id(),
+            "Cache Invalidation Listener".to_string(),
+        );
+    }
+
     // Execute additional background services:
     for additional_service_register_fn in additional_background_services {
         let abort_handles = additional_service_register_fn(
@@ -472,6 +571,32 @@ async fn serve_inner<
             Ok(())
         });
         service_ids.insert(task_abort_handle.id(), "Task Worker Monitor".to_string());
+
+        let catalog_state = state.v1_state.catalog.clone();
+        let cancellation_token = cancellation_token.clone();
+        let wakeup_abort_handle = service_futures.spawn(async move {
+            C::listen_for_task_wakeups(catalog_state, cancellation_token)
+                .await
+                .map_err(|e| e.context("Task wakeup listener exited with error"))
+        });
+        service_ids.insert(wakeup_abort_handle.id(), "Task Wakeup Listener".to_string());
+
+        let catalog_state = state.v1_state.catalog.clone();
+        let registered_task_queues = state.v1_state.registered_task_queues.clone();
+        let cancellation_token = cancellation_token.clone();
+        let task_metrics_abort_handle = service_futures.spawn(async move {
+            export_task_queue_metrics_periodically::<C>(
+                catalog_state,
+                registered_task_queues,
+                cancellation_token,
+            )
+            .await;
+            Ok(())
+        });
+        service_ids.insert(
+            task_metrics_abort_handle.id(),
+            "Task Queue Metrics".to_string(),
+        );
     }
 
     // HTTP Server / Axum:
@@ -0,0 +1,61 @@
+//! Prometheus metrics of table and view commits.
+use std::time::Instant;
+
+use axum_prometheus::metrics;
+
+use crate::WarehouseId;
+
+const METRIC_COMMIT_DURATION: &str = "lakekeeper_commit_duration_seconds";
+const METRIC_COMMIT_RETRIES: &str = "lakekeeper_commit_retries_total";
+const METRIC_COMMIT_CONFLICTS: &str = "lakekeeper_commit_conflicts_total";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CommitKind {
+    Table,
+    View,
+}
+
+impl CommitKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            CommitKind::Table => "table",
+            CommitKind::View => "view",
+        }
+    }
+}
+
+/// Record a `ConcurrentUpdateError` of a commit, whether or not it is retried.
+pub(crate) fn record_commit_conflict(warehouse_id: WarehouseId, kind: CommitKind) {
+    metrics::counter!(
+        METRIC_COMMIT_CONFLICTS,
+        "warehouse_id" => warehouse_id.to_string(),
+        "tabular_type" => kind.as_str(),
+    )
+    .increment(1);
+}
+
+/// Record a retry of a commit after a `ConcurrentUpdateError`.
+pub(crate) fn record_commit_retry(warehouse_id: WarehouseId, kind: CommitKind) {
+    metrics::counter!(
+        METRIC_COMMIT_RETRIES,
+        "warehouse_id" => warehouse_id.to_string(),
+        "tabular_type" => kind.as_str(),
+    )
+    .increment(1);
+}
+
+/// Record the duration of a commit including all retries.
+pub(crate) fn record_commit_duration(
+    warehouse_id: WarehouseId,
+    kind: CommitKind,
+    started_at: Instant,
+    success: bool,
+) {
+    metrics::histogram!(
+        METRIC_COMMIT_DURATION,
+        "warehouse_id" => warehouse_id.to_string(),
+        "tabular_type" => kind.as_str(),
+        "status" => if success { "success" } else { "failure" },
+    )
+    .record(started_at.elapsed());
+}
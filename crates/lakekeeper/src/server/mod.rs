@@ -1,3 +1,4 @@
+mod commit_metrics;
 pub(crate) mod commit_tables;
 pub(crate) mod compression_codec;
 mod config;
@@ -10,12 +11,13 @@ pub mod tables;
 pub(crate) mod tabular;
 pub mod views;
 
-use std::{collections::HashMap, fmt::Debug, marker::PhantomData, sync::Arc};
+use std::{borrow::Cow, collections::HashMap, fmt::Debug, marker::PhantomData, sync::Arc};
 
 use futures::future::BoxFuture;
 use iceberg::spec::{TableMetadata, ViewMetadata};
 use iceberg_ext::catalog::rest::IcebergErrorResponse;
 use itertools::{FoldWhile, Itertools};
+use lakekeeper_io::Location;
 pub use namespace::{MAX_NAMESPACE_DEPTH, NAMESPACE_ID_PROPERTY, UNSUPPORTED_NAMESPACE_PROPERTIES};
 
 use crate::{
@@ -23,8 +25,13 @@ use crate::{
         iceberg::v1::{PageToken, Prefix},
         ErrorModel, Result,
     },
-    service::{authz::Authorizer, secrets::SecretStore, storage::StorageCredential, CatalogStore},
-    WarehouseId, CONFIG,
+    service::{
+        authz::Authorizer,
+        secrets::SecretStore,
+        storage::{StorageCredential, StorageProfile},
+        CatalogNamespaceOps, CatalogStore, GetWarehouseResponse, NamespaceId,
+    },
+    SecretIdent, WarehouseId, CONFIG,
 };
 
 pub trait MetadataProperties {
@@ -116,6 +123,26 @@ pub(crate) async fn maybe_get_secret<S: SecretStore>(
     }
 }
 
+/// Storage profile and secret used to access a table or view at `location`.
+///
+/// The storage credential override of the tabular's namespace takes precedence over the
+/// credential of the warehouse's storage profile responsible for `location`. A namespace
+/// without an override inherits the override of its nearest ancestor.
+pub(crate) async fn tabular_storage<'a, C: CatalogStore>(
+    warehouse: &'a GetWarehouseResponse,
+    namespace_id: NamespaceId,
+    location: &Location,
+    catalog_state: C::State,
+) -> Result<(Cow<'a, StorageProfile>, Option<SecretIdent>), IcebergErrorResponse> {
+    let (storage_profile, storage_secret_id) = warehouse.storage_for_location(location);
+    let namespace_storage_credential =
+        C::resolve_namespace_storage_credential(warehouse.id, namespace_id, catalog_state).await?;
+    Ok(match namespace_storage_credential {
+        Some(credential) => credential.apply(storage_profile, storage_secret_id),
+        None => (Cow::Borrowed(storage_profile), storage_secret_id),
+    })
+}
+
 pub struct UnfilteredPage<Entity, EntityId> {
     pub entities: Vec<Entity>,
     pub entity_ids: Vec<EntityId>,
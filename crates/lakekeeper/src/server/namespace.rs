@@ -33,7 +33,7 @@ use crate::{
         CatalogNamespaceOps, CatalogStore, CatalogTaskOps, CatalogWarehouseOps,
         GetWarehouseResponse, NamedEntity, NamespaceId, State, TabularId, Transaction,
     },
-    CONFIG,
+    SecretIdent, CONFIG,
 };
 
 pub const UNSUPPORTED_NAMESPACE_PROPERTIES: &[&str] = &[];
@@ -41,6 +41,8 @@ pub const UNSUPPORTED_NAMESPACE_PROPERTIES: &[&str] = &[];
 // to take care of the hierarchical structure.
 pub const MAX_NAMESPACE_DEPTH: i32 = 5;
 pub const NAMESPACE_ID_PROPERTY: &str = "namespace_id";
+/// Name of the additional storage profile of the warehouse a new namespace is placed on.
+pub const STORAGE_PROFILE_PROPERTY: &str = "storage_profile";
 pub(crate) const MANAGED_ACCESS_PROPERTY: &str = "managed_access";
 
 #[async_trait::async_trait]
@@ -274,7 +276,7 @@ impl<C: CatalogStore, A: Authorizer + Clone, S: SecretStore>
         let mut namespace_props = NamespaceProperties::try_from_maybe_props(properties.clone())
             .map_err(|e| ErrorModel::bad_request(e.to_string(), e.err_type(), None))?;
         // Set location if not specified - validate location if specified
-        set_namespace_location_property(&mut namespace_props, &warehouse, namespace_id)?;
+        set_namespace_location_property(&mut namespace_props, &warehouse, namespace, namespace_id)?;
         remove_managed_namespace_properties(&mut namespace_props);
 
         let mut request = request;
@@ -293,10 +295,23 @@ impl<C: CatalogStore, A: Authorizer + Clone, S: SecretStore>
         t.commit().await?;
         let mut properties = r.properties.map(Arc::unwrap_or_clone).unwrap_or_default();
         properties.insert(NAMESPACE_ID_PROPERTY.to_string(), namespace_id.to_string());
-        Ok(CreateNamespaceResponse {
+        let response = CreateNamespaceResponse {
             namespace: r.namespace_ident,
             properties: Some(properties),
-        })
+        };
+
+        state
+            .v1_state
+            .hooks
+            .create_namespace(
+                warehouse_id,
+                namespace_id,
+                Arc::new(response.clone()),
+                Arc::new(request_metadata),
+            )
+            .await;
+
+        Ok(response)
     }
 
     /// Return all stored metadata properties for a given namespace
@@ -403,7 +418,7 @@ impl<C: CatalogStore, A: Authorizer + Clone, S: SecretStore>
             .require_namespace_action(
                 &request_metadata,
                 warehouse_id,
-                parameters.namespace,
+                parameters.namespace.clone(),
                 namespace,
                 CatalogNamespaceAction::CanDelete,
             )
@@ -414,7 +429,7 @@ impl<C: CatalogStore, A: Authorizer + Clone, S: SecretStore>
         //  ------------------- BUSINESS LOGIC -------------------
         let namespace_id = namespace.namespace_id;
         let mut t = C::Transaction::begin_write(state.v1_state.catalog).await?;
-        if flags.recursive {
+        let storage_secret_ids = if flags.recursive {
             try_recursive_drop::<_, C>(
                 flags,
                 authorizer,
@@ -423,15 +438,46 @@ impl<C: CatalogStore, A: Authorizer + Clone, S: SecretStore>
                 namespace_id,
                 &request_metadata,
             )
-            .await
+            .await?
         } else {
-            C::drop_namespace(warehouse_id, namespace_id, flags, t.transaction()).await?;
+            let drop_info =
+                C::drop_namespace(warehouse_id, namespace_id, flags, t.transaction()).await?;
             authorizer
                 .delete_namespace(&request_metadata, namespace_id)
                 .await?;
             t.commit().await?;
-            Ok(())
+            drop_info.storage_secret_ids
+        };
+
+        // Delete storage credentials of the dropped namespaces - never fail the request
+        for secret_id in storage_secret_ids {
+            state
+                .v1_state
+                .secrets
+                .delete_secret(&secret_id)
+                .await
+                .map_err(|e| {
+                    tracing::warn!(
+                        "Failed to delete storage secret of dropped namespace: {:?}",
+                        e.error
+                    );
+                })
+                .ok();
         }
+
+        state
+            .v1_state
+            .hooks
+            .drop_namespace(
+                warehouse_id,
+                parameters,
+                flags,
+                namespace_id,
+                Arc::new(request_metadata),
+            )
+            .await;
+
+        Ok(())
     }
 
     /// Set or remove properties on a namespace
@@ -471,7 +517,7 @@ impl<C: CatalogStore, A: Authorizer + Clone, S: SecretStore>
             .require_namespace_action(
                 &request_metadata,
                 warehouse_id,
-                parameters.namespace,
+                parameters.namespace.clone(),
                 namespace,
                 CatalogNamespaceAction::CanUpdateProperties,
             )
@@ -489,7 +535,21 @@ impl<C: CatalogStore, A: Authorizer + Clone, S: SecretStore>
         C::update_namespace_properties(warehouse_id, namespace_id, new_properties, t.transaction())
             .await?;
         t.commit().await?;
-        Ok(r)
+
+        let r = Arc::new(r);
+        state
+            .v1_state
+            .hooks
+            .update_namespace_properties(
+                warehouse_id,
+                parameters,
+                namespace_id,
+                r.clone(),
+                Arc::new(request_metadata),
+            )
+            .await;
+
+        Ok(Arc::unwrap_or_clone(r))
     }
 }
 
@@ -501,7 +561,7 @@ async fn try_recursive_drop<A: Authorizer, C: CatalogStore>(
     mut t: <C as CatalogStore>::Transaction,
     namespace_id: NamespaceId,
     request_metadata: &RequestMetadata,
-) -> Result<()> {
+) -> Result<Vec<SecretIdent>> {
     if matches!(
         warehouse.tabular_delete_profile,
         TabularDeleteProfile::Hard {}
@@ -532,9 +592,9 @@ async fn try_recursive_drop<A: Authorizer, C: CatalogStore>(
                         schedule_for: None,
                         entity_name: tabular_ident.clone().into_name_parts(),
                     },
-                    TabularPurgePayload {
-                        tabular_location: tabular_location.to_string(),
-                    },
+                    // The storage credential of the dropped namespace is gone by the
+                    // time the purge runs.
+                    TabularPurgePayload::new(tabular_location.to_string()),
                     t.transaction(),
                 )
                 .await?;
@@ -602,7 +662,7 @@ async fn try_recursive_drop<A: Authorizer, C: CatalogStore>(
                 .ok();
         }
 
-        Ok(())
+        Ok(drop_info.storage_secret_ids)
     } else {
         Err(ErrorModel::bad_request(
             "Cannot recursively delete namespace with soft-deletion without force flag",
@@ -688,6 +748,7 @@ fn remove_managed_namespace_properties(namespace_props: &mut NamespaceProperties
 fn set_namespace_location_property(
     namespace_props: &mut NamespaceProperties,
     warehouse: &GetWarehouseResponse,
+    namespace: &NamespaceIdent,
     namespace_id: NamespaceId,
 ) -> Result<()> {
     let mut location = namespace_props.get_location();
@@ -696,16 +757,16 @@ fn set_namespace_location_property(
     location.as_mut().map(Location::with_trailing_slash);
 
     // For customer specified location, we need to check if we can write to the location.
-    // If no location is specified, we use our default location.
+    // If no location is specified, we use the default location of the storage profile
+    // the namespace is placed on.
     let location = if let Some(location) = location {
-        warehouse
-            .storage_profile
-            .require_allowed_location(&location)?;
+        warehouse.require_allowed_location(&location)?;
         location
     } else {
-        warehouse
-            .storage_profile
-            .default_namespace_location(namespace_id)?
+        let requested_storage_profile = namespace_props.get_custom_prop(STORAGE_PROFILE_PROPERTY);
+        let (storage_profile, _) =
+            warehouse.storage_for_namespace(namespace, requested_storage_profile.as_deref())?;
+        storage_profile.default_namespace_location(namespace_id)?
     };
 
     namespace_props.insert(&location);
@@ -3,10 +3,11 @@ use std::{
     time::{Duration, Instant},
 };
 
-use crate::{service::storage::s3::S3UrlStyleDetectionMode, WarehouseId};
+use super::sign::WarehouseS3UrlSettings;
+use crate::WarehouseId;
 
-pub(super) static WAREHOUSE_S3_URL_STYLE_CACHE: LazyLock<
-    moka::future::Cache<WarehouseId, S3UrlStyleDetectionMode>,
+pub(super) static WAREHOUSE_S3_URL_SETTINGS_CACHE: LazyLock<
+    moka::future::Cache<WarehouseId, WarehouseS3UrlSettings>,
 > = LazyLock::new(|| {
     moka::future::Cache::builder()
         .max_capacity(10000)
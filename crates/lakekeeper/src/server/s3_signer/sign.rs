@@ -15,7 +15,7 @@ use crate::{
         S3SignRequest, S3SignResponse,
     },
     request_metadata::RequestMetadata,
-    server::require_warehouse_id,
+    server::{require_warehouse_id, tabular_storage},
     service::{
         authz::{
             AuthZTableOps, Authorizer, AuthzWarehouseOps, CatalogTableAction,
@@ -24,7 +24,7 @@ use crate::{
         secrets::SecretStore,
         storage::{
             s3::S3UrlStyleDetectionMode, S3Credential, S3Profile, StorageCredential,
-            ValidationError,
+            StorageProfile, ValidationError,
         },
         AuthZTableInfo, CatalogStore, CatalogTabularOps, CatalogWarehouseOps,
         GetTabularInfoByLocationError, State, TableId, TableInfo, TabularListFlags,
@@ -76,12 +76,15 @@ impl<C: CatalogStore, A: Authorizer + Clone, S: SecretStore>
             body: request_body,
         } = request.clone();
 
-        let (parsed_url, operation) = s3_utils::parse_s3_url(
+        let url_settings =
+            warehouse_s3_url_settings::<C>(state.v1_state.catalog.clone(), warehouse_id).await?;
+        let (mut parsed_url, operation) = s3_utils::parse_s3_url(
             &request_url,
-            s3_url_style_detection::<C>(state.v1_state.catalog.clone(), warehouse_id).await?,
+            url_settings.url_style,
             &request_method,
             request_body.as_deref(),
         )?;
+        s3_utils::resolve_access_points(&mut parsed_url, &url_settings.access_point_buckets)?;
 
         let first_location = parsed_url.locations.first().ok_or_else(|| {
             ErrorModel::internal(
@@ -165,6 +168,7 @@ impl<C: CatalogStore, A: Authorizer + Clone, S: SecretStore>
         let table_info = table_info?;
         let warehouse = warehouse?;
         let table_id = table_info.table_id();
+        let namespace_id = table_info.namespace_id;
         let location = table_info.location;
 
         let extend_err = |mut e: IcebergErrorResponse| {
@@ -177,16 +181,38 @@ impl<C: CatalogStore, A: Authorizer + Clone, S: SecretStore>
             e
         };
 
-        let storage_profile = warehouse
-            .storage_profile
+        // Sign with the storage profile the table is stored on
+        let (storage_profile, storage_secret_id) = tabular_storage::<C>(
+            &warehouse,
+            namespace_id,
+            &location,
+            state.v1_state.catalog.clone(),
+        )
+        .await
+        .map_err(extend_err)?;
+        let storage_profile = storage_profile
+            .into_owned()
             .try_into_s3()
             .map_err(|e| extend_err(IcebergErrorResponse::from(e)))?;
 
         validate_region(&request_region, &storage_profile).map_err(extend_err)?;
         validate_uri(&parsed_url, &location).map_err(extend_err)?;
 
+        // Requests to directory buckets are authorized with session credentials obtained
+        // by the client via `CreateSession`, which a remote signer cannot provide.
+        if storage_profile.is_directory_bucket() {
+            return Err(extend_err(
+                ErrorModel::bad_request(
+                    "Remote signing is not supported for S3 directory buckets. Use vended-credentials instead.",
+                    "RemoteSigningNotSupported",
+                    None,
+                )
+                .into(),
+            ));
+        }
+
         // If all is good, we need the storage secret
-        let storage_secret = if let Some(storage_secret_id) = warehouse.storage_secret_id {
+        let storage_secret = if let Some(storage_secret_id) = storage_secret_id {
             Some(
                 state
                     .v1_state
@@ -220,32 +246,53 @@ impl<C: CatalogStore, A: Authorizer + Clone, S: SecretStore>
     }
 }
 
-async fn s3_url_style_detection<C: CatalogStore>(
+/// Settings of a warehouse required to parse the URL of a sign request.
+#[derive(Debug, Clone)]
+pub(super) struct WarehouseS3UrlSettings {
+    url_style: S3UrlStyleDetectionMode,
+    /// Buckets of access points, keyed by the access point host label `<name>-<account-id>`.
+    access_point_buckets: HashMap<String, String>,
+}
+
+async fn warehouse_s3_url_settings<C: CatalogStore>(
     state: C::State,
     warehouse_id: WarehouseId,
-) -> Result<S3UrlStyleDetectionMode, IcebergErrorResponse> {
-    let t = super::cache::WAREHOUSE_S3_URL_STYLE_CACHE
+) -> Result<WarehouseS3UrlSettings, IcebergErrorResponse> {
+    let t = super::cache::WAREHOUSE_S3_URL_SETTINGS_CACHE
         .try_get_with(warehouse_id, async {
             tracing::trace!("No cache hit for {warehouse_id}");
-            let result = C::require_warehouse_by_id(warehouse_id, state)
-                .await
-                .map(|w| {
-                    w.storage_profile
-                        .try_into_s3()
-                        .map(|s| s.remote_signing_url_style)
-                        .map_err(|e| {
-                            IcebergErrorResponse::from(ErrorModel::bad_request(
-                                "Warehouse storage profile is not an S3 profile",
-                                "InvalidWarehouse",
-                                Some(Box::new(e)),
-                            ))
-                        })
+            let warehouse = C::require_warehouse_by_id(warehouse_id, state).await?;
+            let url_style = warehouse
+                .storage_profile
+                .clone()
+                .try_into_s3()
+                .map(|s| s.remote_signing_url_style)
+                .map_err(|e| {
+                    IcebergErrorResponse::from(ErrorModel::bad_request(
+                        "Warehouse storage profile is not an S3 profile",
+                        "InvalidWarehouse",
+                        Some(Box::new(e)),
+                    ))
                 })?;
-            result
+            let access_point_buckets = warehouse
+                .storage_profiles()
+                .filter_map(|profile| match profile {
+                    StorageProfile::S3(profile) => profile
+                        .access_point_host_label()
+                        .map(|host_label| (host_label, profile.bucket.clone())),
+                    _ => None,
+                })
+                .collect();
+            Ok::<_, IcebergErrorResponse>(WarehouseS3UrlSettings {
+                url_style,
+                access_point_buckets,
+            })
         })
         .await
         .map_err(|e: Arc<IcebergErrorResponse>| {
-            tracing::debug!("Failed to get warehouse S3 URL style detection mode from cache due to error: '{e:?}'");
+            tracing::debug!(
+                "Failed to get warehouse S3 URL settings from cache due to error: '{e:?}'"
+            );
             IcebergErrorResponse::from(ErrorModel::new(
                 e.error.message.as_str(),
                 e.error.r#type.as_str(),
@@ -475,6 +522,8 @@ fn validate_uri(
 }
 
 pub(super) mod s3_utils {
+    use std::collections::HashMap;
+
     use lakekeeper_io::s3::S3Location;
     use lazy_regex::regex;
     use serde::{Deserialize, Serialize};
@@ -487,7 +536,6 @@ pub(super) mod s3_utils {
         pub(super) url: url::Url,
         pub(super) locations: Vec<S3Location>,
         // Used endpoint without the bucket
-        #[allow(dead_code)]
         pub(super) endpoint: String,
         #[allow(dead_code)]
         pub(super) port: u16,
@@ -551,6 +599,28 @@ pub(super) mod s3_utils {
         Ok(keys)
     }
 
+    /// Access points are addressed via `<name>-<account-id>.s3-accesspoint.<region>.amazonaws.com`.
+    /// Replace the host label in the parsed locations with the bucket of the access point,
+    /// so that locations can be matched against table locations.
+    pub(super) fn resolve_access_points(
+        parsed_request: &mut ParsedSignRequest,
+        access_point_buckets: &HashMap<String, String>,
+    ) -> Result<()> {
+        if !parsed_request.endpoint.starts_with("s3-accesspoint") {
+            return Ok(());
+        }
+
+        for location in &mut parsed_request.locations {
+            if let Some(bucket) = access_point_buckets.get(location.bucket_name()) {
+                let resolved = S3Location::new(bucket, &location.key(), None)
+                    .map_err(ValidationError::from)?;
+                *location = resolved;
+            }
+        }
+
+        Ok(())
+    }
+
     pub(super) fn parse_s3_url(
         uri: &url::Url,
         s3_url_style_detection: S3UrlStyleDetectionMode,
@@ -662,7 +732,8 @@ pub(super) mod s3_utils {
 
         let host_str = host.to_string();
 
-        let re_host_pattern = regex!(r"^((.+)\.)?(s3[.-]([a-z0-9-]+)(\..*)?)");
+        // Directory buckets (S3 Express One Zone) use zonal `s3express-<zone-id>` endpoints
+        let re_host_pattern = regex!(r"^((.+)\.)?(s3(?:express)?[.-]([a-z0-9-]+)(\..*)?)");
         let (bucket, used_endpoint) = if is_known || host_str.ends_with(".r2.cloudflarestorage.com")
         {
             known_host_style(&host_str)?
@@ -1005,6 +1076,11 @@ mod test {
                 "https://bucket.s3-accesspoint.dualstack.us-gov-west-1.amazonaws.com/file",
                 "s3://bucket/file",
             ),
+            // S3 Express One Zone
+            (
+                "https://bucket--usw2-az1--x-s3.s3express-usw2-az1.us-west-2.amazonaws.com/file",
+                "s3://bucket--usw2-az1--x-s3/file",
+            ),
             // Cloudflare R2
             (
                 "https://bucket.accountid123.r2.cloudflarestorage.com/file",
@@ -1082,6 +1158,45 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_resolve_access_points() {
+        let access_point_buckets =
+            HashMap::from([("my-ap-123456789012".to_string(), "bucket".to_string())]);
+        let cases = vec![
+            (
+                "https://my-ap-123456789012.s3-accesspoint.us-east-1.amazonaws.com/ns/table/file",
+                "s3://bucket/ns/table/file",
+            ),
+            (
+                "https://my-ap-123456789012.s3-accesspoint.dualstack.us-east-1.amazonaws.com/file",
+                "s3://bucket/file",
+            ),
+            // Unknown access point
+            (
+                "https://other-ap-123456789012.s3-accesspoint.us-east-1.amazonaws.com/file",
+                "s3://other-ap-123456789012/file",
+            ),
+            // Not an access point endpoint
+            (
+                "https://my-ap-123456789012.s3.us-east-1.amazonaws.com/file",
+                "s3://my-ap-123456789012/file",
+            ),
+        ];
+
+        for (uri, expected) in cases {
+            let uri = url::Url::parse(uri).unwrap();
+            let (mut parsed, _operation) = parse_s3_url(
+                &uri,
+                S3UrlStyleDetectionMode::Auto,
+                &http::Method::GET,
+                None,
+            )
+            .unwrap();
+            s3_utils::resolve_access_points(&mut parsed, &access_point_buckets).unwrap();
+            assert_eq!(parsed.locations[0].to_string(), expected, "{uri}");
+        }
+    }
+
     #[test]
     fn test_uri_virtual_host() {
         let cases = vec![
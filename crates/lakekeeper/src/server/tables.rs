@@ -19,17 +19,20 @@ use iceberg_ext::{
     configs::ParseFromStr,
 };
 use itertools::Itertools;
-use lakekeeper_io::Location;
+use lakekeeper_io::{Location, StorageBackend};
 use serde::Serialize;
 use uuid::Uuid;
 pub(crate) mod create_table;
 mod load_table;
 use super::{
+    commit_metrics::{
+        record_commit_conflict, record_commit_duration, record_commit_retry, CommitKind,
+    },
     commit_tables::apply_commit,
     io::{delete_file, read_metadata_file, write_file},
     maybe_get_secret,
     namespace::validate_namespace_ident,
-    require_warehouse_id, CatalogServer,
+    require_warehouse_id, tabular_storage, CatalogServer,
 };
 use crate::{
     api::{
@@ -57,19 +60,20 @@ use crate::{
             CatalogNamespaceAction, CatalogTableAction, RequireTableActionError,
         },
         contract_verification::{ContractVerification, ContractVerificationOutcome},
+        event_publisher::update_table_payloads,
         secrets::SecretStore,
-        storage::{StorageLocations as _, StoragePermissions},
+        storage::{StorageLocations as _, StoragePermissions, StorageProfile},
         tasks::{
             tabular_expiration_queue::{TabularExpirationPayload, TabularExpirationTask},
             tabular_purge_queue::{TabularPurgePayload, TabularPurgeTask},
             EntityId, TaskMetadata,
         },
         AuthZTableInfo as _, CatalogNamespaceOps, CatalogStore, CatalogTableOps, CatalogTabularOps,
-        CatalogWarehouseOps, NamedEntity, State, TableCommit, TableCreation, TableId,
-        TableIdentOrId, TableInfo, TabularId, TabularListFlags, TabularNotFound, Transaction,
-        WarehouseStatus, CONCURRENT_UPDATE_ERROR_TYPE,
+        CatalogWarehouseOps, GetWarehouseResponse, NamedEntity, NamespaceId, State, TableCommit,
+        TableCreation, TableId, TableIdentOrId, TableInfo, TabularId, TabularListFlags,
+        TabularNotFound, Transaction, WarehouseStatus, CONCURRENT_UPDATE_ERROR_TYPE,
     },
-    WarehouseId,
+    WarehouseId, CONFIG,
 };
 
 const PROPERTY_METADATA_DELETE_AFTER_COMMIT_ENABLED: &str =
@@ -189,13 +193,18 @@ impl<C: CatalogStore, A: Authorizer + Clone, S: SecretStore>
         let namespace_id = namespace.namespace_id;
         let warehouse =
             C::require_warehouse_by_id(warehouse_id, state.v1_state.catalog.clone()).await?;
-        let storage_profile = &warehouse.storage_profile;
 
         require_active_warehouse(warehouse.status)?;
-        storage_profile.require_allowed_location(&metadata_location)?;
+        warehouse.require_allowed_location(&metadata_location)?;
 
-        let storage_secret =
-            maybe_get_secret(warehouse.storage_secret_id, &state.v1_state.secrets).await?;
+        let (storage_profile, storage_secret_id) = tabular_storage::<C>(
+            &warehouse,
+            namespace_id,
+            &metadata_location,
+            state.v1_state.catalog.clone(),
+        )
+        .await?;
+        let storage_secret = maybe_get_secret(storage_secret_id, &state.v1_state.secrets).await?;
         let file_io = storage_profile.file_io(storage_secret.as_ref()).await?;
         let table_metadata = read_metadata_file(&file_io, &metadata_location).await?;
         let table_location = parse_location(table_metadata.location(), StatusCode::BAD_REQUEST)?;
@@ -266,6 +275,7 @@ impl<C: CatalogStore, A: Authorizer + Clone, S: SecretStore>
             .generate_table_config(
                 DataAccess::not_specified().into(),
                 storage_secret.as_ref(),
+                storage_secret_id,
                 &table_location,
                 StoragePermissions::ReadWriteDelete,
                 &request_metadata,
@@ -378,17 +388,24 @@ impl<C: CatalogStore, A: Authorizer + Clone, S: SecretStore>
         let warehouse =
             C::require_warehouse_by_id(warehouse_id, state.v1_state.catalog.clone()).await?;
 
-        let storage_secret =
-            maybe_get_secret(warehouse.storage_secret_id, &state.v1_state.secrets).await?;
-        let storage_config = warehouse
-            .storage_profile
+        let table_location = parse_location(
+            tabular_details.location.as_str(),
+            StatusCode::INTERNAL_SERVER_ERROR,
+        )?;
+        let (storage_profile, storage_secret_id) = tabular_storage::<C>(
+            &warehouse,
+            tabular_details.namespace_id,
+            &table_location,
+            state.v1_state.catalog,
+        )
+        .await?;
+        let storage_secret = maybe_get_secret(storage_secret_id, &state.v1_state.secrets).await?;
+        let storage_config = storage_profile
             .generate_table_config(
                 data_access.into(),
                 storage_secret.as_ref(),
-                &parse_location(
-                    tabular_details.location.as_str(),
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                )?,
+                storage_secret_id,
+                &table_location,
                 storage_permission,
                 &request_metadata,
                 warehouse_id,
@@ -528,6 +545,7 @@ impl<C: CatalogStore, A: Authorizer + Clone, S: SecretStore>
                         },
                         TabularPurgePayload {
                             tabular_location: location.to_string(),
+                            namespace_id: Some(table_info.namespace_id),
                         },
                         t.transaction(),
                     )
@@ -861,6 +879,7 @@ async fn commit_tables_inner<
     let include_deleted = false;
 
     // Start the retry loop
+    let started_at = std::time::Instant::now();
     let mut attempt = 0;
     loop {
         let result = try_commit_tables::<C, A, S, _>(
@@ -869,11 +888,19 @@ async fn commit_tables_inner<
             table_ident_map.clone(),
             &state,
             include_deleted,
+            &request_metadata,
         )
         .await;
 
+        if let Err(e) = &result {
+            if e.error.r#type == CONCURRENT_UPDATE_ERROR_TYPE {
+                record_commit_conflict(warehouse_id, CommitKind::Table);
+            }
+        }
+
         match result {
             Ok(commits) => {
+                record_commit_duration(warehouse_id, CommitKind::Table, started_at, true);
                 // Fire hooks
                 state
                     .v1_state
@@ -893,6 +920,7 @@ async fn commit_tables_inner<
                     && attempt < MAX_RETRIES_ON_CONCURRENT_UPDATE =>
             {
                 attempt += 1;
+                record_commit_retry(warehouse_id, CommitKind::Table);
                 tracing::info!(
                     warehouse_id = %warehouse_id,
                     n_tables = %table_ident_map.len(),
@@ -909,6 +937,7 @@ async fn commit_tables_inner<
                 tokio::time::sleep(std::time::Duration::from_millis(base + jitter)).await;
             }
             Err(e) => {
+                record_commit_duration(warehouse_id, CommitKind::Table, started_at, false);
                 if attempt > 0 {
                     tracing::warn!(
                         warehouse_id = %warehouse_id,
@@ -998,13 +1027,14 @@ async fn try_commit_tables<
     C: CatalogStore,
     A: Authorizer + Clone,
     S: SecretStore,
-    H: ::std::hash::BuildHasher,
+    H: ::std::hash::BuildHasher + Sync,
 >(
     request: &CommitTransactionRequest,
     warehouse_id: WarehouseId,
     table_ident_map: Arc<HashMap<TableIdent, TableInfo, H>>,
     state: &ApiContext<State<A, C, S>>,
     include_deleted: bool,
+    request_metadata: &RequestMetadata,
 ) -> Result<Vec<CommitContext>> {
     let warehouse =
         C::require_warehouse_by_id(warehouse_id, state.v1_state.catalog.clone()).await?;
@@ -1027,7 +1057,7 @@ async fn try_commit_tables<
 
     transaction.commit().await?;
 
-    let mut expired_metadata_logs: Vec<MetadataLog> = vec![];
+    let mut expired_metadata_logs: Vec<(NamespaceId, MetadataLog)> = vec![];
 
     // Apply changes
     let commits = request
@@ -1064,7 +1094,8 @@ async fn try_commit_tables<
             let number_expired_metadata_log_entries = this_expired.len();
 
             if delete_after_commit_enabled(new_metadata.properties()) {
-                expired_metadata_logs.extend(this_expired);
+                let namespace_id = previous_table_metadata.namespace_id;
+                expired_metadata_logs.extend(this_expired.drain(..).map(|l| (namespace_id, l)));
             } else {
                 this_expired.clear();
             }
@@ -1120,28 +1151,55 @@ async fn try_commit_tables<
         .collect::<Result<Vec<()>, ErrorModel>>()?;
 
     // We don't commit the transaction yet, first we need to write the metadata file.
-    let storage_secret =
-        maybe_get_secret(warehouse.storage_secret_id, &state.v1_state.secrets).await?;
+    // Tables of one transaction may be stored on different storage profiles of the warehouse
+    // and in namespaces with their own storage credential.
+    let commit_namespace = |commit: &CommitContext| {
+        table_ident_map
+            .get(&commit.table_ident)
+            .map(|ti| ti.namespace_id)
+            .expect("table_ident_map contains every committed table")
+    };
+    let file_ios = file_ios_for_locations::<C, S>(
+        &warehouse,
+        commits
+            .iter()
+            .map(|c| (commit_namespace(c), &c.new_metadata_location)),
+        state.v1_state.catalog.clone(),
+        &state.v1_state.secrets,
+    )
+    .await?;
 
     // Write metadata files
-    let file_io = warehouse
-        .storage_profile
-        .file_io(storage_secret.as_ref())
-        .await?;
-
-    let write_futures: Vec<_> = commits
+    let write_futures = commits
         .iter()
         .map(|commit| {
-            write_file(
-                &file_io,
+            Ok(write_file(
+                file_io_for_location(
+                    &warehouse,
+                    &file_ios,
+                    commit_namespace(commit),
+                    &commit.new_metadata_location,
+                )?,
                 &commit.new_metadata_location,
                 &commit.new_metadata,
                 commit.new_compression_codec,
-            )
+            ))
         })
-        .collect();
+        .collect::<Result<Vec<_>>>()?;
     futures::future::try_join_all(write_futures).await?;
 
+    let outbox_events = if CONFIG.cloud_events_outbox.enabled {
+        cloud_event_outbox_entries(
+            warehouse_id,
+            request,
+            &commits,
+            &table_ident_map,
+            request_metadata,
+        )?
+    } else {
+        vec![]
+    };
+
     // Make changes in DB
     let transaction_result = async {
         let mut transaction = C::Transaction::begin_write(state.v1_state.catalog.clone()).await?;
@@ -1151,6 +1209,7 @@ async fn try_commit_tables<
             transaction.transaction(),
         )
         .await?;
+        C::insert_cloud_event_outbox(outbox_events, transaction.transaction()).await?;
 
         transaction.commit().await?;
         Result::<_, IcebergErrorResponse>::Ok(())
@@ -1160,10 +1219,20 @@ async fn try_commit_tables<
     // If transaction fails, delete the metadata files we just wrote (best-effort), then
     // return the original error.
     if let Err(e) = transaction_result {
+        // Every new metadata file was written with its file IO, so none is missing here
         let delete_result = futures::future::join_all(
             commits
                 .iter()
-                .map(|commit| delete_file(&file_io, &commit.new_metadata_location))
+                .filter_map(|commit| {
+                    file_io_for_location(
+                        &warehouse,
+                        &file_ios,
+                        commit_namespace(commit),
+                        &commit.new_metadata_location,
+                    )
+                    .ok()
+                    .map(|file_io| delete_file(file_io, &commit.new_metadata_location))
+                })
                 .collect::<Vec<_>>(),
         )
         .await;
@@ -1179,8 +1248,9 @@ async fn try_commit_tables<
     // Delete files in parallel - if one delete fails, we still want to delete the rest
     let expired_locations = expired_metadata_logs
         .into_iter()
-        .filter_map(|expired_metadata_log| {
+        .filter_map(|(namespace_id, expired_metadata_log)| {
             Location::parse_value(&expired_metadata_log.metadata_file)
+                .map(|location| (namespace_id, location))
                 .map_err(|e| {
                     tracing::warn!(
                         "Failed to parse expired metadata file location {}: {:?}",
@@ -1191,10 +1261,27 @@ async fn try_commit_tables<
                 .ok()
         })
         .collect::<Vec<_>>();
+    // Expired metadata files might be stored on another storage profile than the new ones
+    let expired_file_ios = file_ios_for_locations::<C, S>(
+        &warehouse,
+        expired_locations
+            .iter()
+            .map(|(namespace_id, location)| (*namespace_id, location)),
+        state.v1_state.catalog.clone(),
+        &state.v1_state.secrets,
+    )
+    .await
+    .map_err(|e| tracing::warn!("Failed to create file IO for expired metadata files: {e:?}"))
+    .unwrap_or_default();
     let _ = futures::future::join_all(
         expired_locations
             .iter()
-            .map(|location| delete_file(&file_io, location))
+            .filter_map(|(namespace_id, location)| {
+                file_io_for_location(&warehouse, &expired_file_ios, *namespace_id, location)
+                    .map_err(|e| tracing::warn!("Failed to delete expired metadata file: {e:?}"))
+                    .ok()
+                    .map(|file_io| delete_file(file_io, location))
+            })
             .collect::<Vec<_>>(),
     )
     .await
@@ -1207,6 +1294,58 @@ async fn try_commit_tables<
     Ok(commits)
 }
 
+/// File IO for each combination of namespace and storage profile of the warehouse
+/// that contains any of `locations`.
+async fn file_ios_for_locations<'a, C: CatalogStore, S: SecretStore>(
+    warehouse: &'a GetWarehouseResponse,
+    locations: impl IntoIterator<Item = (NamespaceId, &Location)>,
+    catalog_state: C::State,
+    secrets: &S,
+) -> Result<Vec<(NamespaceId, &'a StorageProfile, StorageBackend)>> {
+    let mut file_ios: Vec<(NamespaceId, &StorageProfile, StorageBackend)> = Vec::new();
+    for (namespace_id, location) in locations {
+        let (base_profile, _) = warehouse.storage_for_location(location);
+        if file_ios
+            .iter()
+            .any(|(n, p, _)| *n == namespace_id && std::ptr::eq(*p, base_profile))
+        {
+            continue;
+        }
+        let (storage_profile, storage_secret_id) =
+            tabular_storage::<C>(warehouse, namespace_id, location, catalog_state.clone()).await?;
+        let storage_secret = maybe_get_secret(storage_secret_id, secrets).await?;
+        let file_io = storage_profile.file_io(storage_secret.as_ref()).await?;
+        file_ios.push((namespace_id, base_profile, file_io));
+    }
+    Ok(file_ios)
+}
+
+/// Pick the file IO for `location` of a table in `namespace_id` from [`file_ios_for_locations`].
+///
+/// # Errors
+/// If `location` was not passed to [`file_ios_for_locations`]. Using the file IO of another
+/// storage profile would access the location with the wrong credential.
+fn file_io_for_location<'a>(
+    warehouse: &GetWarehouseResponse,
+    file_ios: &'a [(NamespaceId, &StorageProfile, StorageBackend)],
+    namespace_id: NamespaceId,
+    location: &Location,
+) -> Result<&'a StorageBackend> {
+    let (base_profile, _) = warehouse.storage_for_location(location);
+    file_ios
+        .iter()
+        .find(|(n, p, _)| *n == namespace_id && std::ptr::eq(*p, base_profile))
+        .map(|(_, _, file_io)| file_io)
+        .ok_or_else(|| {
+            ErrorModel::internal(
+                format!("No file IO for location `{location}` of namespace `{namespace_id}`"),
+                "FileIONotFound",
+                None,
+            )
+            .into()
+        })
+}
+
 pub(crate) fn extract_count_from_metadata_location(location: &Location) -> Option<usize> {
     let last_segment = location
         .as_str()
@@ -1225,6 +1364,39 @@ pub(crate) fn extract_count_from_metadata_location(location: &Location) -> Optio
     }
 }
 
+/// Serialized `updateTable` `CloudEvents` to store in the outbox together with the commit.
+fn cloud_event_outbox_entries<H: ::std::hash::BuildHasher + Sync>(
+    warehouse_id: WarehouseId,
+    request: &CommitTransactionRequest,
+    commits: &[CommitContext],
+    table_ident_map: &HashMap<TableIdent, TableInfo, H>,
+    request_metadata: &RequestMetadata,
+) -> Result<Vec<serde_json::Value>> {
+    let table_ident_to_id_fn =
+        |ident: &TableIdent| table_ident_map.get(ident).map(|t| t.tabular_id);
+    update_table_payloads(
+        warehouse_id,
+        request,
+        commits,
+        &table_ident_to_id_fn,
+        request_metadata,
+    )
+    .and_then(|payloads| {
+        payloads
+            .into_iter()
+            .map(|payload| Ok(serde_json::to_value(payload.into_event()?)?))
+            .collect::<anyhow::Result<Vec<_>>>()
+    })
+    .map_err(|e| {
+        ErrorModel::internal(
+            "Failed to build CloudEvents for commit",
+            "CloudEventBuildError",
+            Some(e.into()),
+        )
+        .into()
+    })
+}
+
 #[derive(Clone, Debug)]
 pub struct CommitContext {
     pub new_metadata: TableMetadataRef,
@@ -18,7 +18,9 @@ use crate::{
         NamespaceParameters, Result, TableIdent,
     },
     request_metadata::RequestMetadata,
-    server::{compression_codec::CompressionCodec, tabular::determine_tabular_location},
+    server::{
+        compression_codec::CompressionCodec, tabular::determine_tabular_location, tabular_storage,
+    },
     service::{
         authz::{Authorizer, AuthzNamespaceOps, CatalogNamespaceAction},
         secrets::SecretStore,
@@ -171,15 +173,17 @@ async fn create_table_inner<C: CatalogStore, A: Authorizer + Clone, S: SecretSto
 
     let warehouse =
         C::require_warehouse_by_id(warehouse_id, state.v1_state.catalog.clone()).await?;
-    let storage_profile = &warehouse.storage_profile;
     require_active_warehouse(warehouse.status)?;
 
-    let table_location = determine_tabular_location(
-        &namespace,
-        request.location.clone(),
-        tabular_id,
-        storage_profile,
-    )?;
+    let table_location =
+        determine_tabular_location(&namespace, request.location.clone(), tabular_id, &warehouse)?;
+    let (storage_profile, storage_secret_id) = tabular_storage::<C>(
+        &warehouse,
+        namespace.namespace_id,
+        &table_location,
+        state.v1_state.catalog.clone(),
+    )
+    .await?;
 
     // Update the request for event
     request.location = Some(table_location.to_string());
@@ -215,7 +219,7 @@ async fn create_table_inner<C: CatalogStore, A: Authorizer + Clone, S: SecretSto
     let table_metadata = Arc::new(table_metadata);
 
     // We don't commit the transaction yet, first we need to write the metadata file.
-    let storage_secret = if let Some(secret_id) = warehouse.storage_secret_id {
+    let storage_secret = if let Some(secret_id) = storage_secret_id {
         let secret_state = state.v1_state.secrets;
         Some(secret_state.get_secret_by_id(secret_id).await?.secret)
     } else {
@@ -251,6 +255,7 @@ async fn create_table_inner<C: CatalogStore, A: Authorizer + Clone, S: SecretSto
         .generate_table_config(
             data_access,
             storage_secret.as_ref(),
+            storage_secret_id,
             &table_location,
             StoragePermissions::ReadWriteDelete,
             &request_metadata,
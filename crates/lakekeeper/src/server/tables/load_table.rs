@@ -90,6 +90,7 @@ pub(super) async fn load_table<C: CatalogStore, A: Authorizer + Clone, S: Secret
                 .generate_table_config(
                     data_access.into(),
                     storage_secret.as_ref(),
+                    storage_secret_ident,
                     &table_location,
                     storage_permissions,
                     &request_metadata,
@@ -1,46 +1,26 @@
 use crate::{
     server::tables::parse_location,
-    service::{
-        storage::{StorageLocations as _, StorageProfile},
-        Namespace, TabularId,
-    },
+    service::{storage::StorageLocations as _, GetWarehouseResponse, Namespace, TabularId},
 };
 
 pub(super) fn determine_tabular_location(
     namespace: &Namespace,
     request_table_location: Option<String>,
     table_id: TabularId,
-    storage_profile: &StorageProfile,
+    warehouse: &GetWarehouseResponse,
 ) -> Result<Location, ErrorModel> {
     let request_table_location = request_table_location
         .map(|l| parse_location(&l, StatusCode::BAD_REQUEST))
         .transpose()?;
 
     let mut location = if let Some(location) = request_table_location {
-        storage_profile.require_allowed_location(&location)?;
+        warehouse.require_allowed_location(&location)?;
         location
     } else {
-        let namespace_props = NamespaceProperties::from_props_unchecked(
-            namespace
-                .properties
-                .as_ref()
-                .map(|arc| (**arc).clone())
-                .unwrap_or_default(),
-        );
-
-        let namespace_location = match namespace_props.get_location() {
-            Some(location) => location,
-            None => storage_profile
-                .default_namespace_location(namespace.namespace_id)
-                .map_err(|e| {
-                    ErrorModel::internal(
-                        "Failed to generate default namespace location",
-                        "InvalidDefaultNamespaceLocation",
-                        Some(Box::new(e)),
-                    )
-                })?,
-        };
+        let namespace_location = namespace_location(namespace, warehouse)?;
 
+        // Tabulars are stored on the storage profile of their namespace
+        let (storage_profile, _) = warehouse.storage_for_location(&namespace_location);
         storage_profile.default_tabular_location(&namespace_location, table_id)
     };
     // all locations are without a trailing slash
@@ -48,6 +28,35 @@ pub(super) fn determine_tabular_location(
     Ok(location)
 }
 
+/// Location of a namespace: the `location` property if set, the default location
+/// on the warehouse's storage profile otherwise.
+pub(crate) fn namespace_location(
+    namespace: &Namespace,
+    warehouse: &GetWarehouseResponse,
+) -> Result<Location, ErrorModel> {
+    let namespace_props = NamespaceProperties::from_props_unchecked(
+        namespace
+            .properties
+            .as_ref()
+            .map(|arc| (**arc).clone())
+            .unwrap_or_default(),
+    );
+
+    match namespace_props.get_location() {
+        Some(location) => Ok(location),
+        None => warehouse
+            .storage_profile
+            .default_namespace_location(namespace.namespace_id)
+            .map_err(|e| {
+                ErrorModel::internal(
+                    "Failed to generate default namespace location",
+                    "InvalidDefaultNamespaceLocation",
+                    Some(Box::new(e)),
+                )
+            }),
+    }
+}
+
 macro_rules! list_entities {
     ($entity:ident, $list_fn:ident, $namespace_response:ident, $authorizer:ident, $request_metadata:ident) => {
         |ps, page_token, trx| {
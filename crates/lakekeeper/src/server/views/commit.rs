@@ -2,7 +2,7 @@ use std::{str::FromStr as _, sync::Arc};
 
 use iceberg::spec::{ViewFormatVersion, ViewMetadata, ViewMetadataBuilder};
 use iceberg_ext::catalog::{rest::ViewUpdate, ViewRequirement};
-use lakekeeper_io::Location;
+use lakekeeper_io::{Location, StorageBackend};
 use uuid::Uuid;
 
 use crate::{
@@ -12,25 +12,28 @@ use crate::{
     },
     request_metadata::RequestMetadata,
     server::{
+        commit_metrics::{
+            record_commit_conflict, record_commit_duration, record_commit_retry, CommitKind,
+        },
         compression_codec::CompressionCodec,
         io::{remove_all, write_file},
-        require_warehouse_id,
+        maybe_get_secret, require_warehouse_id,
         tables::{
             determine_table_ident, extract_count_from_metadata_location, require_active_warehouse,
             validate_table_or_view_ident, MAX_RETRIES_ON_CONCURRENT_UPDATE,
         },
+        tabular_storage,
         views::validate_view_updates,
     },
     service::{
         authz::{AuthZViewOps, Authorizer, AuthzWarehouseOps, CatalogViewAction},
         contract_verification::ContractVerification,
         secrets::SecretStore,
-        storage::{StorageLocations as _, StoragePermissions, StorageProfile},
+        storage::{StorageLocations as _, StoragePermissions},
         AuthZViewInfo, CatalogStore, CatalogTabularOps, CatalogView, CatalogViewOps,
-        CatalogWarehouseOps, InternalParseLocationError, State, TabularListFlags, Transaction,
-        ViewCommit, ViewId, ViewInfo, CONCURRENT_UPDATE_ERROR_TYPE,
+        CatalogWarehouseOps, GetWarehouseResponse, InternalParseLocationError, NamespaceId, State,
+        TabularListFlags, Transaction, ViewCommit, ViewId, ViewInfo, CONCURRENT_UPDATE_ERROR_TYPE,
     },
-    SecretIdent,
 };
 
 /// Commit updates to a view
@@ -88,19 +91,17 @@ pub(crate) async fn commit_view<C: CatalogStore, A: Authorizer + Clone, S: Secre
 
     let warehouse =
         C::require_warehouse_by_id(warehouse_id, state.v1_state.catalog.clone()).await?;
-    let storage_profile = &warehouse.storage_profile;
-    let storage_secret_id = warehouse.storage_secret_id;
     require_active_warehouse(warehouse.status)?;
 
     // Start the retry loop
     let request = Arc::new(request);
+    let started_at = std::time::Instant::now();
     let mut attempt = 0;
     loop {
         let result = try_commit_view::<C, A, S>(
             CommitViewContext {
                 view_info: &view_info,
-                storage_profile,
-                storage_secret_id,
+                warehouse: &warehouse,
                 request: request.as_ref(),
                 data_access,
             },
@@ -109,8 +110,15 @@ pub(crate) async fn commit_view<C: CatalogStore, A: Authorizer + Clone, S: Secre
         )
         .await;
 
+        if let Err(e) = &result {
+            if e.error.r#type == CONCURRENT_UPDATE_ERROR_TYPE {
+                record_commit_conflict(warehouse_id, CommitKind::View);
+            }
+        }
+
         match result {
             Ok((result, commit)) => {
+                record_commit_duration(warehouse_id, CommitKind::View, started_at, true);
                 state
                     .v1_state
                     .hooks
@@ -131,6 +139,7 @@ pub(crate) async fn commit_view<C: CatalogStore, A: Authorizer + Clone, S: Secre
                     && attempt < MAX_RETRIES_ON_CONCURRENT_UPDATE =>
             {
                 attempt += 1;
+                record_commit_retry(warehouse_id, CommitKind::View);
                 tracing::info!(
                     "Concurrent update detected (attempt {attempt}/{MAX_RETRIES_ON_CONCURRENT_UPDATE}), retrying view commit operation",
                 );
@@ -142,7 +151,10 @@ pub(crate) async fn commit_view<C: CatalogStore, A: Authorizer + Clone, S: Secre
                 tracing::debug!(attempt, base, jitter, "Concurrent update backoff");
                 tokio::time::sleep(std::time::Duration::from_millis(base + jitter)).await;
             }
-            Err(e) => return Err(e),
+            Err(e) => {
+                record_commit_duration(warehouse_id, CommitKind::View, started_at, false);
+                return Err(e);
+            }
         }
     }
 }
@@ -150,8 +162,7 @@ pub(crate) async fn commit_view<C: CatalogStore, A: Authorizer + Clone, S: Secre
 // Context structure to hold static parameters for retry function
 struct CommitViewContext<'a> {
     view_info: &'a ViewInfo,
-    storage_profile: &'a StorageProfile,
-    storage_secret_id: Option<SecretIdent>,
+    warehouse: &'a GetWarehouseResponse,
     request: &'a CommitViewRequest,
     data_access: DataAccessMode,
 }
@@ -194,7 +205,15 @@ async fn try_commit_view<C: CatalogStore, A: Authorizer + Clone, S: SecretStore>
 
     let new_location =
         Location::from_str(new_metadata.location()).map_err(InternalParseLocationError::from)?;
-    let metadata_location = ctx.storage_profile.default_metadata_location(
+    // The view is written to the storage profile of its (new) location
+    let (storage_profile, storage_secret_id) = tabular_storage::<C>(
+        ctx.warehouse,
+        ctx.view_info.namespace_id,
+        &new_location,
+        state.v1_state.catalog.clone(),
+    )
+    .await?;
+    let metadata_location = storage_profile.default_metadata_location(
         &new_location,
         &CompressionCodec::try_from_properties(new_metadata.properties())?,
         Uuid::now_v7(),
@@ -202,8 +221,7 @@ async fn try_commit_view<C: CatalogStore, A: Authorizer + Clone, S: SecretStore>
     );
 
     if delete_old_location.is_some() {
-        ctx.storage_profile
-            .require_allowed_location(&new_location)?;
+        ctx.warehouse.require_allowed_location(&new_location)?;
     }
 
     let new_view = CatalogView {
@@ -225,21 +243,10 @@ async fn try_commit_view<C: CatalogStore, A: Authorizer + Clone, S: SecretStore>
     .await?;
 
     // Get storage secret
-    let storage_secret = if let Some(secret_id) = ctx.storage_secret_id {
-        Some(
-            state
-                .v1_state
-                .secrets
-                .get_secret_by_id(secret_id)
-                .await?
-                .secret,
-        )
-    } else {
-        None
-    };
+    let storage_secret = maybe_get_secret(storage_secret_id, &state.v1_state.secrets).await?;
 
     // Write metadata file
-    let file_io = ctx.storage_profile.file_io(storage_secret.as_ref()).await?;
+    let file_io = storage_profile.file_io(storage_secret.as_ref()).await?;
     write_file(
         &file_io,
         &new_view.metadata_location,
@@ -254,11 +261,11 @@ async fn try_commit_view<C: CatalogStore, A: Authorizer + Clone, S: SecretStore>
     );
 
     // Generate config for client
-    let config = ctx
-        .storage_profile
+    let config = storage_profile
         .generate_table_config(
             ctx.data_access,
             storage_secret.as_ref(),
+            storage_secret_id,
             &new_view.metadata_location,
             StoragePermissions::ReadWriteDelete,
             request_metadata,
@@ -273,16 +280,23 @@ async fn try_commit_view<C: CatalogStore, A: Authorizer + Clone, S: SecretStore>
     // Handle file cleanup after transaction is committed
     if let Some(DeleteLocation(before_update_view_location)) = delete_old_location {
         tracing::debug!("Deleting old view location at: '{before_update_view_location}'");
-        let _ = remove_all(&file_io, before_update_view_location)
-            .await
-            .inspect(|()| {
-                tracing::debug!("Deleted old view location {before_update_view_location}");
-            })
-            .inspect_err(|e| {
-                tracing::error!(
-                    "Failed to delete old view location '{before_update_view_location}': {e:?}"
-                );
-            });
+        let _ = remove_old_location(
+            ctx.warehouse,
+            ctx.view_info.namespace_id,
+            before_update_view_location,
+            &new_view.location,
+            &file_io,
+            state,
+        )
+        .await
+        .inspect(|()| {
+            tracing::debug!("Deleted old view location {before_update_view_location}");
+        })
+        .inspect_err(|e| {
+            tracing::error!(
+                "Failed to delete old view location '{before_update_view_location}': {e:?}"
+            );
+        });
     }
 
     Ok((
@@ -300,6 +314,34 @@ async fn try_commit_view<C: CatalogStore, A: Authorizer + Clone, S: SecretStore>
     ))
 }
 
+/// Remove the location of a view before it was moved, possibly on another storage profile.
+async fn remove_old_location<A: Authorizer + Clone, C: CatalogStore, S: SecretStore>(
+    warehouse: &GetWarehouseResponse,
+    namespace_id: NamespaceId,
+    old_location: &Location,
+    new_location: &Location,
+    new_file_io: &StorageBackend,
+    state: &ApiContext<State<A, C, S>>,
+) -> Result<()> {
+    let (old_base_profile, _) = warehouse.storage_for_location(old_location);
+    let (new_base_profile, _) = warehouse.storage_for_location(new_location);
+    if std::ptr::eq(old_base_profile, new_base_profile) {
+        remove_all(new_file_io, old_location).await?;
+    } else {
+        let (old_storage_profile, old_secret_id) = tabular_storage::<C>(
+            warehouse,
+            namespace_id,
+            old_location,
+            state.v1_state.catalog.clone(),
+        )
+        .await?;
+        let old_secret = maybe_get_secret(old_secret_id, &state.v1_state.secrets).await?;
+        let file_io = old_storage_profile.file_io(old_secret.as_ref()).await?;
+        remove_all(&file_io, old_location).await?;
+    }
+    Ok(())
+}
+
 fn check_requirements(requirements: Option<&Vec<ViewRequirement>>, view_id: ViewId) -> Result<()> {
     if let Some(requirements) = requirements {
         for assertion in requirements {
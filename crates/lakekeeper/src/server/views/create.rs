@@ -15,6 +15,7 @@ use crate::{
         maybe_get_secret, require_warehouse_id,
         tables::{require_active_warehouse, validate_table_or_view_ident},
         tabular::determine_tabular_location,
+        tabular_storage,
         views::validate_view_properties,
     },
     service::{
@@ -76,18 +77,20 @@ pub(crate) async fn create_view<C: CatalogStore, A: Authorizer + Clone, S: Secre
     let warehouse =
         C::require_warehouse_by_id(warehouse_id, state.v1_state.catalog.clone()).await?;
 
-    let mut t = C::Transaction::begin_write(state.v1_state.catalog).await?;
-    let storage_profile = warehouse.storage_profile;
+    let mut t = C::Transaction::begin_write(state.v1_state.catalog.clone()).await?;
     require_active_warehouse(warehouse.status)?;
 
     let view_id: TabularId = TabularId::View(uuid::Uuid::now_v7().into());
 
-    let view_location = determine_tabular_location(
-        &namespace,
-        request.location.clone(),
-        view_id,
-        &storage_profile,
-    )?;
+    let view_location =
+        determine_tabular_location(&namespace, request.location.clone(), view_id, &warehouse)?;
+    let (storage_profile, storage_secret_id) = tabular_storage::<C>(
+        &warehouse,
+        namespace.namespace_id,
+        &view_location,
+        state.v1_state.catalog.clone(),
+    )
+    .await?;
 
     // Update the request for event
     let mut request = request;
@@ -133,8 +136,7 @@ pub(crate) async fn create_view<C: CatalogStore, A: Authorizer + Clone, S: Secre
     .await?;
 
     // We don't commit the transaction yet, first we need to write the metadata file.
-    let storage_secret =
-        maybe_get_secret(warehouse.storage_secret_id, &state.v1_state.secrets).await?;
+    let storage_secret = maybe_get_secret(storage_secret_id, &state.v1_state.secrets).await?;
 
     let file_io = storage_profile.file_io(storage_secret.as_ref()).await?;
     let compression_codec = CompressionCodec::try_from_metadata(&metadata_build_result.metadata)?;
@@ -157,6 +159,7 @@ pub(crate) async fn create_view<C: CatalogStore, A: Authorizer + Clone, S: Secre
         .generate_table_config(
             data_access,
             storage_secret.as_ref(),
+            storage_secret_id,
             &view_location,
             StoragePermissions::Read,
             &request_metadata,
@@ -85,6 +85,7 @@ pub(crate) async fn drop_view<C: CatalogStore, A: Authorizer + Clone, S: SecretS
                     },
                     TabularPurgePayload {
                         tabular_location: location.to_string(),
+                        namespace_id: Some(view_info.namespace_id),
                     },
                     t.transaction(),
                 )
@@ -12,6 +12,7 @@ use crate::{
     server::{
         require_warehouse_id,
         tables::{require_active_warehouse, validate_table_or_view_ident},
+        tabular_storage,
     },
     service::{
         authz::{
@@ -19,7 +20,7 @@ use crate::{
         },
         storage::{StorageCredential, StoragePermissions},
         AuthZViewInfo as _, CatalogStore, CatalogTabularOps, CatalogViewOps, CatalogWarehouseOps,
-        GetWarehouseResponse, InternalParseLocationError, Result, SecretStore, State, Transaction,
+        InternalParseLocationError, Result, SecretStore, State, Transaction,
     },
 };
 
@@ -73,24 +74,23 @@ pub(crate) async fn load_view<C: CatalogStore, A: Authorizer + Clone, S: SecretS
         return Err(AuthZCannotSeeView::new(warehouse_id, view.clone()).into());
     }
     // ------------------- BUSINESS LOGIC -------------------
-    let GetWarehouseResponse {
-        id: _,
-        name: _,
-        project_id: _,
-        storage_profile,
-        storage_secret_id,
-        status,
-        tabular_delete_profile: _,
-        protected: _,
-    } = C::require_warehouse_by_id(warehouse_id, state.v1_state.catalog.clone()).await?;
-    require_active_warehouse(status)?;
-
-    let mut t = C::Transaction::begin_read(state.v1_state.catalog).await?;
+    let warehouse =
+        C::require_warehouse_by_id(warehouse_id, state.v1_state.catalog.clone()).await?;
+    require_active_warehouse(warehouse.status)?;
+
+    let mut t = C::Transaction::begin_read(state.v1_state.catalog.clone()).await?;
     let view = C::load_view(warehouse_id, view_id, false, t.transaction()).await?;
     t.commit().await?;
 
     let view_location =
         Location::from_str(view.metadata.location()).map_err(InternalParseLocationError::from)?;
+    let (storage_profile, storage_secret_id) = tabular_storage::<C>(
+        &warehouse,
+        view_info.namespace_id,
+        &view_location,
+        state.v1_state.catalog,
+    )
+    .await?;
 
     let storage_secret: Option<StorageCredential> = if let Some(secret_id) = storage_secret_id {
         Some(
@@ -115,6 +115,7 @@ pub(crate) async fn load_view<C: CatalogStore, A: Authorizer + Clone, S: SecretS
         .generate_table_config(
             data_access,
             storage_secret.as_ref(),
+            storage_secret_id,
             &view_location,
             storage_permissions,
             &request_metadata,
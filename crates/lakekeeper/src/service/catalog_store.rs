@@ -32,14 +32,23 @@ use crate::{
     },
     service::{
         authn::UserId,
+        event_publisher::stream::CloudEventStream,
         health::HealthExt,
         tasks::{
             Task, TaskAttemptId, TaskCheckState, TaskFilter, TaskId, TaskInput, TaskQueueName,
         },
-        TabularId, TabularIdentBorrowed,
+        AccessRequestId, TabularId, TabularIdentBorrowed,
     },
-    SecretIdent,
+    CancellationToken, SecretIdent,
 };
+mod access_request;
+pub use access_request::*;
+pub mod cache;
+pub use cache::CacheInvalidation;
+mod cloud_event_outbox;
+pub use cloud_event_outbox::*;
+mod event_log;
+pub use event_log::*;
 mod namespace;
 pub use namespace::*;
 mod tabular;
@@ -93,6 +102,22 @@ where
     /// Get data required for startup validations and server info endpoint
     async fn get_server_info(catalog_state: Self::State) -> Result<ServerInfo, ErrorModel>;
 
+    /// Listen for changes of warehouses, namespaces and tabulars committed by any
+    /// server replica and apply them to the local caches using [`cache::invalidate`].
+    /// Runs until `cancellation_token` is cancelled.
+    async fn listen_for_cache_invalidations(
+        catalog_state: Self::State,
+        cancellation_token: CancellationToken,
+    ) -> anyhow::Result<()>;
+
+    /// Listen for tasks that became runnable on any server replica and wake up
+    /// idle workers of the corresponding queue using [`crate::service::tasks::wake_task_queue`].
+    /// Runs until `cancellation_token` is cancelled.
+    async fn listen_for_task_wakeups(
+        catalog_state: Self::State,
+        cancellation_token: CancellationToken,
+    ) -> anyhow::Result<()>;
+
     /// Bootstrap the catalog.
     /// Must return Ok(false) if the catalog is not open for bootstrap.
     /// If bootstrapping succeeds, return Ok(true).
@@ -178,6 +203,15 @@ where
         state: Self::State,
     ) -> std::result::Result<Option<GetWarehouseResponse>, CatalogGetWarehouseByIdError>;
 
+    /// Get the warehouse metadata and lock the warehouse until the end of the transaction.
+    /// Used for read-modify-write cycles that must not run concurrently.
+    ///
+    /// Return Ok(None) if the warehouse does not exist.
+    async fn get_warehouse_by_id_for_update_impl<'a>(
+        warehouse_id: WarehouseId,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> std::result::Result<Option<GetWarehouseResponse>, CatalogGetWarehouseByIdError>;
+
     /// Get the warehouse metadata. Return only active warehouses.
     ///
     /// Return Ok(None) if the warehouse does not exist or is not active.
@@ -220,6 +254,21 @@ where
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
     ) -> std::result::Result<ProtectionResponse, SetWarehouseProtectedError>;
 
+    /// Replace the additional storage profiles of a warehouse.
+    async fn set_additional_storage_profiles_impl<'a>(
+        warehouse_id: WarehouseId,
+        additional_storage_profiles: Vec<AdditionalStorageProfile>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> std::result::Result<(), UpdateWarehouseStorageProfileError>;
+
+    /// Whether any namespace or tabular of the warehouse, including soft-deleted tabulars,
+    /// is stored below `location`.
+    async fn is_location_in_use_impl<'a>(
+        warehouse_id: WarehouseId,
+        location: &Location,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> std::result::Result<bool, CatalogBackendError>;
+
     // ---------------- Namespace Management ----------------
     // Should only return namespaces if the warehouse is active.
     async fn list_namespaces_impl<'a>(
@@ -267,6 +316,31 @@ where
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
     ) -> std::result::Result<Namespace, CatalogSetNamespaceProtectedError>;
 
+    /// Storage credential override of a namespace.
+    /// Returns `None` if the namespace does not exist or has no override.
+    async fn get_namespace_storage_credential_impl(
+        warehouse_id: WarehouseId,
+        namespace_id: NamespaceId,
+        state: Self::State,
+    ) -> std::result::Result<Option<NamespaceStorageCredential>, CatalogGetNamespaceError>;
+
+    /// Storage credential override of the namespace or, if it has none,
+    /// of its nearest ancestor that has one.
+    async fn resolve_namespace_storage_credential_impl(
+        warehouse_id: WarehouseId,
+        namespace_id: NamespaceId,
+        state: Self::State,
+    ) -> std::result::Result<Option<NamespaceStorageCredential>, CatalogGetNamespaceError>;
+
+    /// Set or remove (`None`) the storage credential override of a namespace.
+    /// Returns the secret of the previous override.
+    async fn set_namespace_storage_credential_impl<'a>(
+        warehouse_id: WarehouseId,
+        namespace_id: NamespaceId,
+        storage_credential: Option<NamespaceStorageCredential>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> std::result::Result<Option<SecretIdent>, CatalogSetNamespaceStorageCredentialError>;
+
     // ---------------- Tabular Management ----------------
     async fn list_tabulars_impl(
         warehouse_id: WarehouseId,
@@ -458,6 +532,124 @@ where
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
     ) -> Result<Option<()>>;
 
+    // ---------------- Access Requests ----------------
+    async fn create_access_request<'a>(
+        request: NewAccessRequest<'_>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<AccessRequest>;
+
+    /// Return Ok(None) if the access request does not exist.
+    async fn get_access_request(
+        access_request_id: AccessRequestId,
+        catalog_state: Self::State,
+    ) -> Result<Option<AccessRequest>>;
+
+    /// List access requests of a warehouse, oldest first.
+    async fn list_access_requests(
+        warehouse_id: WarehouseId,
+        filter_status: Option<AccessRequestStatus>,
+        filter_requested_by: Option<&UserId>,
+        pagination: PaginationQuery,
+        catalog_state: Self::State,
+    ) -> Result<ListAccessRequestsResponse>;
+
+    /// Approve or reject a pending access request.
+    ///
+    /// Return Ok(None) if the access request does not exist or is not pending anymore.
+    async fn decide_access_request<'a>(
+        access_request_id: AccessRequestId,
+        status: AccessRequestStatus,
+        decided_by: &UserId,
+        expires_at: Option<chrono::DateTime<chrono::Utc>>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<AccessRequest>>;
+
+    // ---------------- Cloud Event Outbox ----------------
+    /// Store serialized `CloudEvents` in the outbox as part of `transaction`.
+    /// Events are only relayed to sinks if the transaction commits.
+    async fn insert_cloud_event_outbox<'a>(
+        events: Vec<serde_json::Value>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Claim relaying to `sink_name` for `lease_holder` until `lease_duration` elapsed and
+    /// return up to `limit` events after the cursor of the sink, oldest first.
+    /// Claiming again renews the lease.
+    /// Only events of transactions that finished, and after which no older transaction
+    /// can still insert events, are returned.
+    ///
+    /// Return Ok(None) if another relay holds an unexpired lease.
+    async fn claim_cloud_event_outbox_batch<'a>(
+        sink_name: &str,
+        lease_holder: uuid::Uuid,
+        lease_duration: std::time::Duration,
+        limit: i64,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<Vec<OutboxEntry>>>;
+
+    /// Move the cursor of `sink_name` forward to `cursor`.
+    ///
+    /// Return Ok(false) if `lease_holder` lost its lease or the cursor is already
+    /// at or past `cursor`.
+    async fn set_cloud_event_outbox_cursor<'a>(
+        sink_name: &str,
+        lease_holder: uuid::Uuid,
+        cursor: OutboxCursor,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<bool>;
+
+    /// Release the lease of `lease_holder` on `sink_name`, if it still holds it.
+    async fn release_cloud_event_outbox_lease(
+        sink_name: &str,
+        lease_holder: uuid::Uuid,
+        catalog_state: Self::State,
+    ) -> Result<()>;
+
+    /// Delete all events that have been delivered to every sink in `sink_names`.
+    /// Returns the number of deleted events.
+    async fn prune_cloud_event_outbox(
+        sink_names: &[String],
+        catalog_state: Self::State,
+    ) -> Result<u64>;
+
+    /// Get the number and age of events not yet delivered to each sink in `sink_names`.
+    async fn get_cloud_event_outbox_lag(
+        sink_names: &[String],
+        catalog_state: Self::State,
+    ) -> Result<Vec<OutboxLag>>;
+
+    // ---------------- Event Log ----------------
+    /// Store serialized `CloudEvents` in the event log.
+    /// Events that are already logged are ignored.
+    async fn insert_event_log(
+        entries: Vec<NewEventLogEntry>,
+        catalog_state: Self::State,
+    ) -> Result<()>;
+
+    /// List logged events of a warehouse, oldest first.
+    async fn list_event_log(
+        warehouse_id: WarehouseId,
+        filter: EventLogFilter,
+        pagination: PaginationQuery,
+        catalog_state: Self::State,
+    ) -> Result<ListEventLogResponse>;
+
+    /// Publish all events logged by any server replica to `stream`, including this one.
+    /// Events logged while the listener was disconnected are published once it reconnects.
+    /// Runs until `cancellation_token` is cancelled.
+    async fn stream_logged_events(
+        catalog_state: Self::State,
+        stream: CloudEventStream,
+        cancellation_token: CancellationToken,
+    ) -> anyhow::Result<()>;
+
+    /// Delete all events logged before `older_than`.
+    /// Returns the number of deleted events.
+    async fn prune_event_log(
+        older_than: chrono::DateTime<chrono::Utc>,
+        catalog_state: Self::State,
+    ) -> Result<u64>;
+
     // ---------------- Endpoint Statistics ----------------
     /// Get endpoint statistics for the project
     ///
@@ -574,4 +766,7 @@ where
         queue_name: &TaskQueueName,
         state: Self::State,
     ) -> Result<Option<GetTaskQueueConfigResponse>>;
+
+    /// Get the number and age of tasks of every queue that has tasks.
+    async fn get_task_queue_stats_impl(state: Self::State) -> Result<Vec<TaskQueueStats>>;
 }
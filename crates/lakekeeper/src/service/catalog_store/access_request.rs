@@ -0,0 +1,74 @@
+use crate::{
+    service::{AccessRequestId, UserId},
+    WarehouseId,
+};
+
+/// Status of an access request
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    strum_macros::Display,
+    strum_macros::EnumIter,
+    serde::Serialize,
+    serde::Deserialize,
+    utoipa::ToSchema,
+)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
+#[cfg_attr(
+    feature = "sqlx",
+    sqlx(type_name = "access_request_status", rename_all = "kebab-case")
+)]
+pub enum AccessRequestStatus {
+    /// The request waits for a decision
+    Pending,
+    /// The request was approved and the grant was written
+    Approved,
+    /// The request was rejected
+    Rejected,
+}
+
+/// A request of a user for temporary access to an object.
+///
+/// `object` and `relation` are opaque to the catalog and interpreted
+/// by the authorizer that created the request.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccessRequest {
+    pub access_request_id: AccessRequestId,
+    pub warehouse_id: WarehouseId,
+    /// Authorizer specific identifier of the object access is requested for
+    pub object: String,
+    /// Authorizer specific relation that is requested
+    pub relation: String,
+    pub requested_by: UserId,
+    pub justification: Option<String>,
+    /// Duration of the grant once approved
+    pub duration: chrono::Duration,
+    pub status: AccessRequestStatus,
+    pub decided_by: Option<UserId>,
+    pub decided_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Expiry of the grant. Only set for approved requests.
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewAccessRequest<'a> {
+    pub warehouse_id: WarehouseId,
+    pub object: &'a str,
+    pub relation: &'a str,
+    pub requested_by: &'a UserId,
+    pub justification: Option<&'a str>,
+    pub duration: chrono::Duration,
+}
+
+#[derive(Debug, Clone)]
+pub struct ListAccessRequestsResponse {
+    pub access_requests: Vec<AccessRequest>,
+    pub next_page_token: Option<String>,
+}
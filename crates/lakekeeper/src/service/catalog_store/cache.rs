@@ -0,0 +1,361 @@
+//! In-memory caches for warehouses, namespaces, storage credential overrides of
+//! namespaces and tabulars resolved by id.
+//!
+//! Entries are invalidated locally by the `Catalog*Ops` traits whenever an entity is
+//! modified, and once more by [`apply_pending_invalidations`] after the transaction is
+//! committed, as concurrent readers might have cached the previous state in the meantime.
+//! Catalog backends additionally publish a [`CacheInvalidation`] for every change,
+//! which is applied on all replicas via [`invalidate`] once the change is committed.
+use std::{
+    sync::LazyLock,
+    time::{Duration, Instant},
+};
+
+use super::{GetWarehouseResponse, Namespace, NamespaceStorageCredential, ViewOrTableInfo};
+use crate::{
+    service::{storage::credential_cache, NamespaceId, TabularId},
+    ProjectId, WarehouseId, CONFIG,
+};
+
+/// Change of a catalog entity that requires cached entries to be invalidated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum CacheInvalidation {
+    #[serde(rename_all = "kebab-case")]
+    Warehouse { warehouse_id: WarehouseId },
+    #[serde(rename_all = "kebab-case")]
+    Namespace {
+        warehouse_id: WarehouseId,
+        namespace_id: NamespaceId,
+    },
+    /// Tabulars are identified by their uuid only, both table and view entries are invalidated.
+    #[serde(rename_all = "kebab-case")]
+    Tabular {
+        warehouse_id: WarehouseId,
+        tabular_id: uuid::Uuid,
+    },
+    /// Storage credentials of a warehouse changed without a change of the warehouse itself,
+    /// i.e. the credential override of a namespace. Only vended credentials are invalidated.
+    #[serde(rename_all = "kebab-case")]
+    WarehouseCredentials { warehouse_id: WarehouseId },
+}
+
+struct CacheExpiry;
+impl<K, V> moka::Expiry<K, V> for CacheExpiry {
+    fn expire_after_create(&self, _key: &K, _value: &V, _created_at: Instant) -> Option<Duration> {
+        Some(Duration::from_secs(CONFIG.cache.time_to_live_seconds))
+    }
+}
+
+fn build_cache<K, V>() -> moka::future::Cache<K, V>
+where
+    K: std::hash::Hash + Eq + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    moka::future::Cache::builder()
+        .max_capacity(CONFIG.cache.max_capacity)
+        .expire_after(CacheExpiry)
+        .support_invalidation_closures()
+        .build()
+}
+
+static WAREHOUSE_CACHE: LazyLock<moka::future::Cache<WarehouseId, GetWarehouseResponse>> =
+    LazyLock::new(build_cache);
+static WAREHOUSE_NAME_CACHE: LazyLock<moka::future::Cache<(ProjectId, String), WarehouseId>> =
+    LazyLock::new(build_cache);
+static NAMESPACE_CACHE: LazyLock<moka::future::Cache<(WarehouseId, NamespaceId), Namespace>> =
+    LazyLock::new(build_cache);
+/// Storage credential override that applies to a namespace, which may be inherited.
+/// A change of an override invalidates the entries of the whole warehouse.
+static NAMESPACE_STORAGE_CREDENTIAL_CACHE: LazyLock<
+    moka::future::Cache<(WarehouseId, NamespaceId), Option<NamespaceStorageCredential>>,
+> = LazyLock::new(build_cache);
+static TABULAR_CACHE: LazyLock<moka::future::Cache<(WarehouseId, TabularId), ViewOrTableInfo>> =
+    LazyLock::new(build_cache);
+
+/// Invalidations of uncommitted changes, by the task running the write transaction.
+/// Entries of transactions that are never committed expire.
+static PENDING_INVALIDATIONS: LazyLock<
+    moka::future::Cache<tokio::task::Id, Vec<PendingInvalidation>>,
+> = LazyLock::new(|| {
+    moka::future::Cache::builder()
+        .time_to_live(Duration::from_secs(3600))
+        .build()
+});
+
+#[derive(Debug, Clone, Copy)]
+enum PendingInvalidation {
+    Entity(CacheInvalidation),
+    WarehouseChildren(WarehouseId),
+}
+
+#[cfg(test)]
+tokio::task_local! {
+    /// Caches are disabled by default in tests, as they are shared by all tests.
+    static ENABLED_IN_TEST: bool;
+}
+
+/// Run `f` with caches enabled, independent of the configuration.
+#[cfg(test)]
+pub(crate) async fn with_caches_enabled<F: std::future::Future>(f: F) -> F::Output {
+    ENABLED_IN_TEST.scope(true, f).await
+}
+
+fn enabled() -> bool {
+    #[cfg(test)]
+    if ENABLED_IN_TEST
+        .try_with(|enabled| *enabled)
+        .unwrap_or(false)
+    {
+        return true;
+    }
+    CONFIG.cache.enabled
+}
+
+pub(super) async fn get_warehouse(warehouse_id: WarehouseId) -> Option<GetWarehouseResponse> {
+    if !enabled() {
+        return None;
+    }
+    WAREHOUSE_CACHE.get(&warehouse_id).await
+}
+
+pub(super) async fn get_warehouse_id_by_name(
+    project_id: &ProjectId,
+    warehouse_name: &str,
+) -> Option<WarehouseId> {
+    if !enabled() {
+        return None;
+    }
+    WAREHOUSE_NAME_CACHE
+        .get(&(project_id.clone(), warehouse_name.to_string()))
+        .await
+}
+
+pub(super) async fn insert_warehouse(warehouse: &GetWarehouseResponse) {
+    if !enabled() {
+        return;
+    }
+    WAREHOUSE_NAME_CACHE
+        .insert(
+            (warehouse.project_id.clone(), warehouse.name.clone()),
+            warehouse.id,
+        )
+        .await;
+    WAREHOUSE_CACHE
+        .insert(warehouse.id, warehouse.clone())
+        .await;
+}
+
+pub(super) async fn get_namespace(
+    warehouse_id: WarehouseId,
+    namespace_id: NamespaceId,
+) -> Option<Namespace> {
+    if !enabled() {
+        return None;
+    }
+    NAMESPACE_CACHE.get(&(warehouse_id, namespace_id)).await
+}
+
+pub(super) async fn insert_namespace(namespace: &Namespace) {
+    if !enabled() {
+        return;
+    }
+    NAMESPACE_CACHE
+        .insert(
+            (namespace.warehouse_id, namespace.namespace_id),
+            namespace.clone(),
+        )
+        .await;
+}
+
+pub(super) async fn get_namespace_storage_credential(
+    warehouse_id: WarehouseId,
+    namespace_id: NamespaceId,
+) -> Option<Option<NamespaceStorageCredential>> {
+    if !enabled() {
+        return None;
+    }
+    NAMESPACE_STORAGE_CREDENTIAL_CACHE
+        .get(&(warehouse_id, namespace_id))
+        .await
+}
+
+pub(super) async fn insert_namespace_storage_credential(
+    warehouse_id: WarehouseId,
+    namespace_id: NamespaceId,
+    storage_credential: Option<NamespaceStorageCredential>,
+) {
+    if !enabled() {
+        return;
+    }
+    NAMESPACE_STORAGE_CREDENTIAL_CACHE
+        .insert((warehouse_id, namespace_id), storage_credential)
+        .await;
+}
+
+pub(super) async fn get_tabular(
+    warehouse_id: WarehouseId,
+    tabular_id: TabularId,
+) -> Option<ViewOrTableInfo> {
+    if !enabled() {
+        return None;
+    }
+    TABULAR_CACHE.get(&(warehouse_id, tabular_id)).await
+}
+
+pub(super) async fn insert_tabular(warehouse_id: WarehouseId, info: &ViewOrTableInfo) {
+    if !enabled() {
+        return;
+    }
+    TABULAR_CACHE
+        .insert((warehouse_id, info.tabular_id()), info.clone())
+        .await;
+}
+
+/// Invalidate all cached entries affected by `invalidation`.
+pub async fn invalidate(invalidation: CacheInvalidation) {
+    match invalidation {
+        CacheInvalidation::Warehouse { warehouse_id } => {
+            // Deleting a warehouse deletes everything in it
+            WAREHOUSE_CACHE.invalidate(&warehouse_id).await;
+            invalidate_warehouse_children(warehouse_id);
+            // The storage profile or credential might have changed
+            credential_cache::invalidate_warehouse(warehouse_id);
+        }
+        CacheInvalidation::Namespace {
+            warehouse_id,
+            namespace_id,
+        } => {
+            NAMESPACE_CACHE
+                .invalidate(&(warehouse_id, namespace_id))
+                .await;
+        }
+        CacheInvalidation::Tabular {
+            warehouse_id,
+            tabular_id,
+        } => {
+            TABULAR_CACHE
+                .invalidate(&(warehouse_id, TabularId::Table(tabular_id.into())))
+                .await;
+            TABULAR_CACHE
+                .invalidate(&(warehouse_id, TabularId::View(tabular_id.into())))
+                .await;
+        }
+        CacheInvalidation::WarehouseCredentials { warehouse_id } => {
+            // Overrides are inherited by child namespaces
+            invalidate_namespace_storage_credentials(warehouse_id);
+            credential_cache::invalidate_warehouse(warehouse_id);
+        }
+    }
+}
+
+/// Invalidate all cached entries affected by an uncommitted change now,
+/// and again once [`apply_pending_invalidations`] is called after the commit.
+pub(super) async fn invalidate_on_commit(invalidation: CacheInvalidation) {
+    invalidate(invalidation).await;
+    defer(PendingInvalidation::Entity(invalidation)).await;
+}
+
+/// Like [`invalidate_warehouse_children`], but applied again after the commit.
+pub(super) async fn invalidate_warehouse_children_on_commit(warehouse_id: WarehouseId) {
+    invalidate_warehouse_children(warehouse_id);
+    defer(PendingInvalidation::WarehouseChildren(warehouse_id)).await;
+}
+
+async fn defer(invalidation: PendingInvalidation) {
+    if !enabled() && !CONFIG.cache.vended_credentials_enabled {
+        return;
+    }
+    // Outside of a task the change is only invalidated by the backend notifications
+    let Some(task_id) = tokio::task::try_id() else {
+        return;
+    };
+    let mut pending = PENDING_INVALIDATIONS
+        .get(&task_id)
+        .await
+        .unwrap_or_default();
+    pending.push(invalidation);
+    PENDING_INVALIDATIONS.insert(task_id, pending).await;
+}
+
+/// Apply the invalidations of all changes made by the current task a second time.
+/// Must be called by catalog backends after a write transaction is committed.
+pub async fn apply_pending_invalidations() {
+    let Some(task_id) = tokio::task::try_id() else {
+        return;
+    };
+    let Some(pending) = PENDING_INVALIDATIONS.remove(&task_id).await else {
+        return;
+    };
+    for invalidation in pending {
+        match invalidation {
+            PendingInvalidation::Entity(invalidation) => invalidate(invalidation).await,
+            PendingInvalidation::WarehouseChildren(warehouse_id) => {
+                invalidate_warehouse_children(warehouse_id);
+            }
+        }
+    }
+}
+
+/// Invalidate all namespaces and tabulars of a warehouse.
+/// Used if the affected children of a change are not known, i.e. for recursive drops.
+pub(super) fn invalidate_warehouse_children(warehouse_id: WarehouseId) {
+    if let Err(e) = NAMESPACE_CACHE.invalidate_entries_if(move |(w, _), _| *w == warehouse_id) {
+        tracing::error!("Failed to invalidate cached namespaces of warehouse {warehouse_id}: {e}");
+    }
+    if let Err(e) = TABULAR_CACHE.invalidate_entries_if(move |(w, _), _| *w == warehouse_id) {
+        tracing::error!("Failed to invalidate cached tabulars of warehouse {warehouse_id}: {e}");
+    }
+    invalidate_namespace_storage_credentials(warehouse_id);
+}
+
+fn invalidate_namespace_storage_credentials(warehouse_id: WarehouseId) {
+    if let Err(e) = NAMESPACE_STORAGE_CREDENTIAL_CACHE
+        .invalidate_entries_if(move |(w, _), _| *w == warehouse_id)
+    {
+        tracing::error!(
+            "Failed to invalidate cached storage credential overrides of warehouse {warehouse_id}: {e}"
+        );
+    }
+}
+
+/// Invalidate all cached entries.
+/// Used if invalidations might have been missed, i.e. after losing the connection to the backend.
+pub fn invalidate_all() {
+    WAREHOUSE_CACHE.invalidate_all();
+    WAREHOUSE_NAME_CACHE.invalidate_all();
+    NAMESPACE_CACHE.invalidate_all();
+    NAMESPACE_STORAGE_CREDENTIAL_CACHE.invalidate_all();
+    TABULAR_CACHE.invalidate_all();
+    credential_cache::invalidate_all();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_invalidation_payload() {
+        let warehouse_id = WarehouseId::new_random();
+        let tabular_id = uuid::Uuid::now_v7();
+        // Format produced by the `notify_cache_invalidation` postgres trigger
+        let payload = format!(
+            r#"{{"type": "tabular", "warehouse-id": "{warehouse_id}", "tabular-id": "{tabular_id}"}}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<CacheInvalidation>(&payload).unwrap(),
+            CacheInvalidation::Tabular {
+                warehouse_id,
+                tabular_id
+            }
+        );
+
+        // Emitted if the storage credential override of a namespace changes
+        let payload =
+            format!(r#"{{"type": "warehouse-credentials", "warehouse-id": "{warehouse_id}"}}"#);
+        assert_eq!(
+            serde_json::from_str::<CacheInvalidation>(&payload).unwrap(),
+            CacheInvalidation::WarehouseCredentials { warehouse_id }
+        );
+    }
+}
@@ -0,0 +1,26 @@
+/// Position of an event in the cloud event outbox.
+///
+/// Events are ordered by the id of the transaction that inserted them first and by their
+/// sequence number second. A cursor points to the last event delivered to a sink.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct OutboxCursor {
+    pub transaction_id: i64,
+    pub sequence_number: i64,
+}
+
+/// A serialized `CloudEvent` stored in the outbox.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutboxEntry {
+    pub cursor: OutboxCursor,
+    pub event: serde_json::Value,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Events in the outbox that have not been delivered to a sink yet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutboxLag {
+    pub sink_name: String,
+    pub pending_events: i64,
+    /// Age of the oldest pending event. `None` if no events are pending.
+    pub oldest_pending_event_age: Option<chrono::Duration>,
+}
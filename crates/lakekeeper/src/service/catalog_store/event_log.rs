@@ -0,0 +1,43 @@
+use uuid::Uuid;
+
+use crate::{service::NamespaceId, WarehouseId};
+
+/// A serialized `CloudEvent` to store in the event log.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewEventLogEntry {
+    pub event_id: Uuid,
+    pub warehouse_id: WarehouseId,
+    pub tabular_id: Option<Uuid>,
+    pub namespace_id: Option<NamespaceId>,
+    pub event_type: String,
+    /// `time` attribute of the event
+    pub event_time: chrono::DateTime<chrono::Utc>,
+    pub event: serde_json::Value,
+}
+
+/// A serialized `CloudEvent` stored in the event log.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventLogEntry {
+    pub event_id: Uuid,
+    pub warehouse_id: WarehouseId,
+    pub tabular_id: Option<Uuid>,
+    pub namespace_id: Option<NamespaceId>,
+    pub event_type: String,
+    pub event_time: chrono::DateTime<chrono::Utc>,
+    pub event: serde_json::Value,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EventLogFilter {
+    /// Only events of this table or view
+    pub tabular_id: Option<Uuid>,
+    /// Only events that occurred at or after this time
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ListEventLogResponse {
+    pub events: Vec<EventLogEntry>,
+    pub next_page_token: Option<String>,
+}
@@ -1,18 +1,20 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{borrow::Cow, collections::HashMap, sync::Arc};
 
 use http::StatusCode;
 use iceberg::NamespaceIdent;
 use iceberg_ext::catalog::rest::{CreateNamespaceRequest, ErrorModel, IcebergErrorResponse};
 use lakekeeper_io::Location;
 
+use super::{cache, CacheInvalidation};
 use crate::{
     api::iceberg::v1::{namespace::NamespaceDropFlags, PaginatedMapping},
     service::{
-        define_transparent_error, impl_error_stack_methods, impl_from_with_detail, tasks::TaskId,
-        CatalogBackendError, CatalogStore, InternalParseLocationError, InvalidPaginationToken,
-        ListNamespacesQuery, NamespaceId, TableIdent, TabularId, Transaction, WarehouseIdNotFound,
+        define_transparent_error, impl_error_stack_methods, impl_from_with_detail,
+        storage::StorageProfile, tasks::TaskId, CatalogBackendError, CatalogStore,
+        InternalParseLocationError, InvalidPaginationToken, ListNamespacesQuery, NamespaceId,
+        TableIdent, TabularId, Transaction, WarehouseIdNotFound,
     },
-    WarehouseId,
+    SecretIdent, WarehouseId,
 };
 
 #[derive(Debug, PartialEq, Clone)]
@@ -38,6 +40,51 @@ pub struct NamespaceDropInfo {
     // table-id, location, table-ident
     pub child_tables: Vec<(TabularId, Location, TableIdent)>,
     pub open_tasks: Vec<TaskId>,
+    /// Secrets of storage credential overrides of the dropped namespaces.
+    pub storage_secret_ids: Vec<SecretIdent>,
+}
+
+/// Storage credential override of a namespace.
+///
+/// Tables and views of the namespace are accessed with this credential instead of the
+/// credential of the warehouse's storage profile. Child namespaces without an override
+/// of their own inherit the override of their nearest ancestor.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct NamespaceStorageCredential {
+    pub storage_secret_id: Option<SecretIdent>,
+    /// Role to assume instead of the `sts-role-arn` of the storage profile. S3 only.
+    pub sts_role_arn: Option<String>,
+}
+
+impl NamespaceStorageCredential {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.storage_secret_id.is_none() && self.sts_role_arn.is_none()
+    }
+
+    /// Apply the override to the storage profile and secret responsible for a location
+    /// of the namespace. An overridden STS role replaces the principal role mappings
+    /// of the warehouse.
+    #[must_use]
+    pub fn apply<'a>(
+        &self,
+        storage_profile: &'a StorageProfile,
+        storage_secret_id: Option<SecretIdent>,
+    ) -> (Cow<'a, StorageProfile>, Option<SecretIdent>) {
+        let storage_profile = match (&self.sts_role_arn, storage_profile) {
+            (Some(sts_role_arn), StorageProfile::S3(profile)) => {
+                let mut profile = profile.clone();
+                profile.sts_role_arn = Some(sts_role_arn.clone());
+                profile.sts_role_mappings.clear();
+                Cow::Owned(StorageProfile::S3(profile))
+            }
+            _ => Cow::Borrowed(storage_profile),
+        };
+        (
+            storage_profile,
+            self.storage_secret_id.or(storage_secret_id),
+        )
+    }
 }
 
 macro_rules! define_simple_namespace_err {
@@ -363,6 +410,16 @@ define_transparent_error! {
     ]
 }
 
+// --------------------------- Set Namespace Storage Credential Error ---------------------------
+define_transparent_error! {
+    pub enum CatalogSetNamespaceStorageCredentialError,
+    stack_message: "Error setting Namespace storage credential in catalog",
+    variants: [
+        CatalogBackendError,
+        NamespaceNotFound,
+    ]
+}
+
 // --------------------------- Set Namespace Protected Error ---------------------------
 define_transparent_error! {
     pub enum CatalogSetNamespaceProtectedError,
@@ -385,7 +442,17 @@ where
         namespace: impl Into<NamespaceIdentOrId> + Send,
         catalog_state: Self::State,
     ) -> Result<Option<Namespace>, CatalogGetNamespaceError> {
-        Self::get_namespace_impl(warehouse_id, namespace.into(), catalog_state).await
+        let namespace = namespace.into();
+        if let NamespaceIdentOrId::Id(namespace_id) = &namespace {
+            if let Some(cached) = cache::get_namespace(warehouse_id, *namespace_id).await {
+                return Ok(Some(cached));
+            }
+        }
+        let result = Self::get_namespace_impl(warehouse_id, namespace, catalog_state).await?;
+        if let Some(result) = &result {
+            cache::insert_namespace(result).await;
+        }
+        Ok(result)
     }
 
     async fn list_namespaces<'a>(
@@ -412,7 +479,11 @@ where
         flags: NamespaceDropFlags,
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
     ) -> std::result::Result<NamespaceDropInfo, CatalogNamespaceDropError> {
-        Self::drop_namespace_impl(warehouse_id, namespace_id, flags, transaction).await
+        let drop_info =
+            Self::drop_namespace_impl(warehouse_id, namespace_id, flags, transaction).await?;
+        // Recursive drops remove child namespaces and tabulars
+        cache::invalidate_warehouse_children_on_commit(warehouse_id).await;
+        Ok(drop_info)
     }
 
     async fn update_namespace_properties<'a>(
@@ -421,8 +492,19 @@ where
         properties: HashMap<String, String>,
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
     ) -> std::result::Result<Namespace, CatalogUpdateNamespacePropertiesError> {
-        Self::update_namespace_properties_impl(warehouse_id, namespace_id, properties, transaction)
-            .await
+        let namespace = Self::update_namespace_properties_impl(
+            warehouse_id,
+            namespace_id,
+            properties,
+            transaction,
+        )
+        .await?;
+        cache::invalidate_on_commit(CacheInvalidation::Namespace {
+            warehouse_id,
+            namespace_id,
+        })
+        .await;
+        Ok(namespace)
     }
 
     async fn set_namespace_protected(
@@ -431,7 +513,67 @@ where
         protect: bool,
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
     ) -> std::result::Result<Namespace, CatalogSetNamespaceProtectedError> {
-        Self::set_namespace_protected_impl(warehouse_id, namespace_id, protect, transaction).await
+        let namespace =
+            Self::set_namespace_protected_impl(warehouse_id, namespace_id, protect, transaction)
+                .await?;
+        cache::invalidate_on_commit(CacheInvalidation::Namespace {
+            warehouse_id,
+            namespace_id,
+        })
+        .await;
+        Ok(namespace)
+    }
+
+    /// Storage credential override of a namespace, `None` if the namespace has none.
+    async fn get_namespace_storage_credential(
+        warehouse_id: WarehouseId,
+        namespace_id: NamespaceId,
+        catalog_state: Self::State,
+    ) -> std::result::Result<Option<NamespaceStorageCredential>, CatalogGetNamespaceError> {
+        Self::get_namespace_storage_credential_impl(warehouse_id, namespace_id, catalog_state).await
+    }
+
+    /// Storage credential override that applies to tables and views of a namespace:
+    /// the override of the namespace itself or, if it has none, of its nearest ancestor.
+    async fn resolve_namespace_storage_credential(
+        warehouse_id: WarehouseId,
+        namespace_id: NamespaceId,
+        catalog_state: Self::State,
+    ) -> std::result::Result<Option<NamespaceStorageCredential>, CatalogGetNamespaceError> {
+        if let Some(cached) =
+            cache::get_namespace_storage_credential(warehouse_id, namespace_id).await
+        {
+            return Ok(cached);
+        }
+        let result = Self::resolve_namespace_storage_credential_impl(
+            warehouse_id,
+            namespace_id,
+            catalog_state,
+        )
+        .await?;
+        cache::insert_namespace_storage_credential(warehouse_id, namespace_id, result.clone())
+            .await;
+        Ok(result)
+    }
+
+    /// Set the storage credential override of a namespace. `None` removes the override.
+    /// Returns the secret of the previous override.
+    async fn set_namespace_storage_credential<'a>(
+        warehouse_id: WarehouseId,
+        namespace_id: NamespaceId,
+        storage_credential: Option<NamespaceStorageCredential>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> std::result::Result<Option<SecretIdent>, CatalogSetNamespaceStorageCredentialError> {
+        let previous_secret_id = Self::set_namespace_storage_credential_impl(
+            warehouse_id,
+            namespace_id,
+            storage_credential,
+            transaction,
+        )
+        .await?;
+        // Child namespaces inheriting the override and vended credentials are affected
+        cache::invalidate_on_commit(CacheInvalidation::WarehouseCredentials { warehouse_id }).await;
+        Ok(previous_secret_id)
     }
 }
 
@@ -8,6 +8,7 @@ use iceberg::{
 use iceberg_ext::catalog::rest::{ErrorModel, IcebergErrorResponse};
 use lakekeeper_io::Location;
 
+use super::tabular::invalidate_tabular;
 use crate::{
     api::iceberg::v1::tables::LoadTableFilters,
     server::tables::TableMetadataDiffs,
@@ -228,7 +229,11 @@ where
         commits: impl IntoIterator<Item = TableCommit> + Send,
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
     ) -> Result<Vec<TableInfo>, CommitTableTransactionError> {
-        Self::commit_table_transaction_impl(warehouse_id, commits, transaction).await
+        let infos = Self::commit_table_transaction_impl(warehouse_id, commits, transaction).await?;
+        for info in &infos {
+            invalidate_tabular(warehouse_id, info.tabular_id.into()).await;
+        }
+        Ok(infos)
     }
 }
 
@@ -3,6 +3,7 @@ use iceberg::TableIdent;
 use iceberg_ext::catalog::rest::{ErrorModel, IcebergErrorResponse};
 use lakekeeper_io::{Location, LocationParseError};
 
+use super::{cache, CacheInvalidation};
 use crate::{
     api::{
         iceberg::v1::{PaginatedMapping, PaginationQuery},
@@ -1032,7 +1033,11 @@ where
         force: bool,
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
     ) -> std::result::Result<Location, DropTabularError> {
-        Self::drop_tabular_impl(warehouse_id, tabular_id.into(), force, transaction).await
+        let tabular_id = tabular_id.into();
+        let location =
+            Self::drop_tabular_impl(warehouse_id, tabular_id, force, transaction).await?;
+        invalidate_tabular(warehouse_id, tabular_id).await;
+        Ok(location)
     }
 
     async fn clear_tabular_deleted_at(
@@ -1049,8 +1054,11 @@ where
         force: bool,
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
     ) -> Result<ViewOrTableInfo, MarkTabularAsDeletedError> {
-        Self::mark_tabular_as_deleted_impl(warehouse_id, tabular_id.into(), force, transaction)
-            .await
+        let tabular_id = tabular_id.into();
+        let info = Self::mark_tabular_as_deleted_impl(warehouse_id, tabular_id, force, transaction)
+            .await?;
+        invalidate_tabular(warehouse_id, tabular_id).await;
+        Ok(info)
     }
 
     async fn search_tabular(
@@ -1068,14 +1076,17 @@ where
         destination_ident: &TableIdent,
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
     ) -> std::result::Result<ViewOrTableInfo, RenameTabularError> {
-        Self::rename_tabular_impl(
+        let source_id = source_id.into();
+        let info = Self::rename_tabular_impl(
             warehouse_id,
-            source_id.into(),
+            source_id,
             source_ident,
             destination_ident,
             transaction,
         )
-        .await
+        .await?;
+        invalidate_tabular(warehouse_id, source_id).await;
+        Ok(info)
     }
 
     async fn get_tabular_infos_by_ident(
@@ -1121,7 +1132,41 @@ where
         list_flags: TabularListFlags,
         catalog_state: Self::State,
     ) -> Result<Vec<ViewOrTableInfo>, GetTabularInfoError> {
-        Self::get_tabular_infos_by_id_impl(warehouse_id, tabulars, list_flags, catalog_state).await
+        // Only active tabulars are cached, which are by far the most common lookup
+        if list_flags != TabularListFlags::active() {
+            return Self::get_tabular_infos_by_id_impl(
+                warehouse_id,
+                tabulars,
+                list_flags,
+                catalog_state,
+            )
+            .await;
+        }
+
+        let mut results = Vec::with_capacity(tabulars.len());
+        let mut not_cached = Vec::new();
+        for tabular_id in tabulars {
+            match cache::get_tabular(warehouse_id, *tabular_id).await {
+                Some(info) => results.push(info),
+                None => not_cached.push(*tabular_id),
+            }
+        }
+        if not_cached.is_empty() {
+            return Ok(results);
+        }
+
+        let fetched = Self::get_tabular_infos_by_id_impl(
+            warehouse_id,
+            &not_cached,
+            list_flags,
+            catalog_state,
+        )
+        .await?;
+        for info in fetched {
+            cache::insert_tabular(warehouse_id, &info).await;
+            results.push(info);
+        }
+        Ok(results)
     }
 
     async fn get_tabular_infos_by_s3_location(
@@ -1223,7 +1268,10 @@ where
         protect: bool,
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
     ) -> Result<ViewOrTableInfo, SetTabularProtectionError> {
-        Self::set_tabular_protected_impl(warehouse_id, tabular_id, protect, transaction).await
+        let info = Self::set_tabular_protected_impl(warehouse_id, tabular_id, protect, transaction)
+            .await?;
+        invalidate_tabular(warehouse_id, tabular_id).await;
+        Ok(info)
     }
 
     async fn list_tabulars(
@@ -1311,3 +1359,11 @@ where
 }
 
 impl<T> CatalogTabularOps for T where T: CatalogStore {}
+
+pub(super) async fn invalidate_tabular(warehouse_id: WarehouseId, tabular_id: TabularId) {
+    cache::invalidate_on_commit(CacheInvalidation::Tabular {
+        warehouse_id,
+        tabular_id: *tabular_id,
+    })
+    .await;
+}
@@ -51,6 +51,19 @@ impl ResolvedTask {
     }
 }
 
+/// Number and age of the tasks of a queue across all warehouses.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskQueueStats {
+    pub queue_name: TaskQueueName,
+    /// Tasks waiting to be picked up, including tasks scheduled for the future.
+    pub scheduled_tasks: i64,
+    /// Tasks currently running or requested to stop.
+    pub running_tasks: i64,
+    /// Time since the oldest task that is due was scheduled for.
+    /// `None` if no task is due.
+    pub oldest_due_task_age: Option<chrono::Duration>,
+}
+
 #[async_trait::async_trait]
 pub trait CatalogTaskOps
 where
@@ -261,6 +274,10 @@ where
     ) -> Result<Option<GetTaskQueueConfigResponse>> {
         Self::get_task_queue_config_impl(warehouse_id, queue_name, state).await
     }
+
+    async fn get_task_queue_stats(state: Self::State) -> Result<Vec<TaskQueueStats>> {
+        Self::get_task_queue_stats_impl(state).await
+    }
 }
 
 impl<T> CatalogTaskOps for T where T: CatalogStore {}
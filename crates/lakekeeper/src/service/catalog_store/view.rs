@@ -6,6 +6,7 @@ use iceberg::{
 use iceberg_ext::catalog::rest::{ErrorModel, IcebergErrorResponse};
 use lakekeeper_io::Location;
 
+use super::tabular::invalidate_tabular;
 use crate::{
     service::{
         define_simple_tabular_err, define_transparent_error, impl_error_stack_methods,
@@ -248,7 +249,9 @@ where
         commit: ViewCommit<'_>,
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
     ) -> Result<ViewInfo, CommitViewError> {
-        Self::commit_view_impl(commit, transaction).await
+        let info = Self::commit_view_impl(commit, transaction).await?;
+        invalidate_tabular(info.warehouse_id, info.tabular_id.into()).await;
+        Ok(info)
     }
 }
 
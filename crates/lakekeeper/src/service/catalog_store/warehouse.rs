@@ -1,7 +1,9 @@
 use http::StatusCode;
+use iceberg::NamespaceIdent;
 use iceberg_ext::catalog::rest::{ErrorModel, IcebergErrorResponse};
+use lakekeeper_io::Location;
 
-use super::{CatalogStore, Transaction};
+use super::{cache, CacheInvalidation, CatalogStore, Transaction};
 use crate::{
     api::management::v1::{
         warehouse::TabularDeleteProfile, DeleteWarehouseQuery, ProtectionResponse,
@@ -72,6 +74,114 @@ pub struct GetWarehouseResponse {
     pub tabular_delete_profile: TabularDeleteProfile,
     /// Whether the warehouse is protected from being deleted.
     pub protected: bool,
+    /// Named storage profiles in addition to `storage_profile`.
+    /// Storage profiles of a warehouse never overlap.
+    pub additional_storage_profiles: Vec<AdditionalStorageProfile>,
+}
+
+/// A named storage profile of a warehouse, i.e. an archive bucket or a bucket in another region.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdditionalStorageProfile {
+    /// Name of the storage profile, unique within the warehouse.
+    pub name: String,
+    pub storage_profile: StorageProfile,
+    pub storage_secret_id: Option<SecretIdent>,
+    /// New namespaces below any of these namespaces are placed on this storage profile.
+    pub namespace_prefixes: Vec<Vec<String>>,
+}
+
+impl GetWarehouseResponse {
+    /// All storage profiles of the warehouse, starting with the default storage profile.
+    pub fn storage_profiles(&self) -> impl Iterator<Item = &StorageProfile> {
+        std::iter::once(&self.storage_profile).chain(
+            self.additional_storage_profiles
+                .iter()
+                .map(|p| &p.storage_profile),
+        )
+    }
+
+    /// Storage profile and secret responsible for `location`.
+    /// Falls back to the default storage profile if no additional storage profile contains `location`.
+    #[must_use]
+    pub fn storage_for_location(
+        &self,
+        location: &Location,
+    ) -> (&StorageProfile, Option<SecretIdent>) {
+        resolve_storage_for_location(
+            &self.storage_profile,
+            self.storage_secret_id,
+            &self.additional_storage_profiles,
+            location,
+        )
+    }
+
+    /// Storage profile and secret on which a new namespace is placed.
+    ///
+    /// An explicitly requested storage profile takes precedence over the namespace prefixes of
+    /// the additional storage profiles. The most specific matching prefix wins.
+    ///
+    /// # Errors
+    /// Fails if the requested storage profile does not exist.
+    pub fn storage_for_namespace(
+        &self,
+        namespace: &NamespaceIdent,
+        requested_storage_profile: Option<&str>,
+    ) -> Result<(&StorageProfile, Option<SecretIdent>), ErrorModel> {
+        if let Some(name) = requested_storage_profile {
+            return self
+                .additional_storage_profiles
+                .iter()
+                .find(|p| p.name == name)
+                .map(|p| (&p.storage_profile, p.storage_secret_id))
+                .ok_or_else(|| {
+                    ErrorModel::bad_request(
+                        format!("Storage profile `{name}` does not exist in warehouse."),
+                        "StorageProfileNotFound",
+                        None,
+                    )
+                });
+        }
+
+        Ok(self
+            .additional_storage_profiles
+            .iter()
+            .flat_map(|p| p.namespace_prefixes.iter().map(move |prefix| (p, prefix)))
+            .filter(|(_, prefix)| namespace.starts_with(prefix))
+            .max_by_key(|(_, prefix)| prefix.len())
+            .map_or((&self.storage_profile, self.storage_secret_id), |(p, _)| {
+                (&p.storage_profile, p.storage_secret_id)
+            }))
+    }
+
+    /// Require that `location` is a sublocation of any storage profile of the warehouse.
+    ///
+    /// # Errors
+    /// Fails if no storage profile allows the location.
+    pub fn require_allowed_location(&self, location: &Location) -> Result<(), ErrorModel> {
+        if self
+            .additional_storage_profiles
+            .iter()
+            .any(|p| p.storage_profile.is_allowed_location(location))
+        {
+            return Ok(());
+        }
+        self.storage_profile.require_allowed_location(location)
+    }
+}
+
+/// Resolve the storage profile of `location` among the storage profiles of a warehouse.
+pub(crate) fn resolve_storage_for_location<'a>(
+    storage_profile: &'a StorageProfile,
+    storage_secret_id: Option<SecretIdent>,
+    additional_storage_profiles: &'a [AdditionalStorageProfile],
+    location: &Location,
+) -> (&'a StorageProfile, Option<SecretIdent>) {
+    additional_storage_profiles
+        .iter()
+        .find(|p| p.storage_profile.is_allowed_location(location))
+        .map_or((storage_profile, storage_secret_id), |p| {
+            (&p.storage_profile, p.storage_secret_id)
+        })
 }
 
 // --------------------------- GENERAL ERROR ---------------------------
@@ -417,9 +527,9 @@ where
         query: DeleteWarehouseQuery,
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
     ) -> ServiceResult<()> {
-        Self::delete_warehouse_impl(warehouse_id, query, transaction)
-            .await
-            .map_err(Into::into)
+        Self::delete_warehouse_impl(warehouse_id, query, transaction).await?;
+        cache::invalidate_on_commit(CacheInvalidation::Warehouse { warehouse_id }).await;
+        Ok(())
     }
 
     /// Rename a warehouse.
@@ -428,7 +538,9 @@ where
         new_name: &str,
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
     ) -> Result<(), CatalogRenameWarehouseError> {
-        Self::rename_warehouse_impl(warehouse_id, new_name, transaction).await
+        Self::rename_warehouse_impl(warehouse_id, new_name, transaction).await?;
+        cache::invalidate_on_commit(CacheInvalidation::Warehouse { warehouse_id }).await;
+        Ok(())
     }
 
     /// Return a list of all warehouse in a project
@@ -449,7 +561,14 @@ where
         warehouse_id: WarehouseId,
         state: Self::State,
     ) -> Result<Option<GetWarehouseResponse>, CatalogGetWarehouseByIdError> {
-        Self::get_warehouse_by_id_impl(warehouse_id, state).await
+        if let Some(warehouse) = cache::get_warehouse(warehouse_id).await {
+            return Ok(Some(warehouse));
+        }
+        let warehouse = Self::get_warehouse_by_id_impl(warehouse_id, state).await?;
+        if let Some(warehouse) = &warehouse {
+            cache::insert_warehouse(warehouse).await;
+        }
+        Ok(warehouse)
     }
 
     /// Wrapper around `get_warehouse` that returns a not-found error if the warehouse does not exist.
@@ -462,12 +581,40 @@ where
             .ok_or(WarehouseIdNotFound::new(warehouse_id).into())
     }
 
+    /// Get the warehouse metadata from the database, bypassing the cache, and lock the
+    /// warehouse until the end of the transaction. Returns a not-found error if the warehouse
+    /// does not exist or is not active.
+    async fn require_warehouse_by_id_for_update<'a>(
+        warehouse_id: WarehouseId,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<GetWarehouseResponse, CatalogGetWarehouseByIdError> {
+        Self::get_warehouse_by_id_for_update_impl(warehouse_id, transaction)
+            .await?
+            .filter(|w| w.status == WarehouseStatus::Active)
+            .ok_or(WarehouseIdNotFound::new(warehouse_id).into())
+    }
+
     async fn get_warehouse_by_name(
         warehouse_name: &str,
         project_id: &ProjectId,
         catalog_state: Self::State,
     ) -> Result<Option<GetWarehouseResponse>, CatalogGetWarehouseByNameError> {
-        Self::get_warehouse_by_name_impl(warehouse_name, project_id, catalog_state).await
+        if let Some(warehouse_id) =
+            cache::get_warehouse_id_by_name(project_id, warehouse_name).await
+        {
+            // The name index is not invalidated on rename, verify it is still current
+            if let Some(warehouse) = cache::get_warehouse(warehouse_id).await {
+                if warehouse.name == warehouse_name && &warehouse.project_id == project_id {
+                    return Ok(Some(warehouse));
+                }
+            }
+        }
+        let warehouse =
+            Self::get_warehouse_by_name_impl(warehouse_name, project_id, catalog_state).await?;
+        if let Some(warehouse) = &warehouse {
+            cache::insert_warehouse(warehouse).await;
+        }
+        Ok(warehouse)
     }
 
     /// Wrapper around `get_warehouse_by_name` that returns
@@ -488,7 +635,10 @@ where
         deletion_profile: &TabularDeleteProfile,
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
     ) -> Result<(), SetWarehouseDeletionProfileError> {
-        Self::set_warehouse_deletion_profile_impl(warehouse_id, deletion_profile, transaction).await
+        Self::set_warehouse_deletion_profile_impl(warehouse_id, deletion_profile, transaction)
+            .await?;
+        cache::invalidate_on_commit(CacheInvalidation::Warehouse { warehouse_id }).await;
+        Ok(())
     }
 
     async fn set_warehouse_status<'a>(
@@ -496,7 +646,9 @@ where
         status: WarehouseStatus,
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
     ) -> Result<(), SetWarehouseStatusError> {
-        Self::set_warehouse_status_impl(warehouse_id, status, transaction).await
+        Self::set_warehouse_status_impl(warehouse_id, status, transaction).await?;
+        cache::invalidate_on_commit(CacheInvalidation::Warehouse { warehouse_id }).await;
+        Ok(())
     }
 
     async fn update_storage_profile<'a>(
@@ -511,7 +663,9 @@ where
             storage_secret_id,
             transaction,
         )
-        .await
+        .await?;
+        cache::invalidate_on_commit(CacheInvalidation::Warehouse { warehouse_id }).await;
+        Ok(())
     }
 
     async fn set_warehouse_protected(
@@ -519,8 +673,142 @@ where
         protect: bool,
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
     ) -> std::result::Result<ProtectionResponse, SetWarehouseProtectedError> {
-        Self::set_warehouse_protected_impl(warehouse_id, protect, transaction).await
+        let response =
+            Self::set_warehouse_protected_impl(warehouse_id, protect, transaction).await?;
+        cache::invalidate_on_commit(CacheInvalidation::Warehouse { warehouse_id }).await;
+        Ok(response)
+    }
+
+    async fn set_additional_storage_profiles<'a>(
+        warehouse_id: WarehouseId,
+        additional_storage_profiles: Vec<AdditionalStorageProfile>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<(), UpdateWarehouseStorageProfileError> {
+        Self::set_additional_storage_profiles_impl(
+            warehouse_id,
+            additional_storage_profiles,
+            transaction,
+        )
+        .await?;
+        cache::invalidate_on_commit(CacheInvalidation::Warehouse { warehouse_id }).await;
+        Ok(())
+    }
+
+    async fn is_location_in_use<'a>(
+        warehouse_id: WarehouseId,
+        location: &Location,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<bool, CatalogBackendError> {
+        Self::is_location_in_use_impl(warehouse_id, location, transaction).await
     }
 }
 
 impl<T> CatalogWarehouseOps for T where T: CatalogStore {}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+    use crate::service::storage::{S3Flavor, S3Profile};
+
+    fn s3_profile(bucket: &str) -> StorageProfile {
+        S3Profile::builder()
+            .bucket(bucket.to_string())
+            .region("us-east-1".to_string())
+            .sts_enabled(false)
+            .flavor(S3Flavor::Aws)
+            .build()
+            .into()
+    }
+
+    fn warehouse() -> GetWarehouseResponse {
+        GetWarehouseResponse {
+            id: WarehouseId::new_random(),
+            name: "test".to_string(),
+            project_id: ProjectId::new_random(),
+            storage_profile: s3_profile("default"),
+            storage_secret_id: None,
+            status: WarehouseStatus::Active,
+            tabular_delete_profile: TabularDeleteProfile::Hard {},
+            protected: false,
+            additional_storage_profiles: vec![
+                AdditionalStorageProfile {
+                    name: "archive".to_string(),
+                    storage_profile: s3_profile("archive"),
+                    storage_secret_id: Some(SecretIdent::from(uuid::Uuid::now_v7())),
+                    namespace_prefixes: vec![vec!["archive".to_string()]],
+                },
+                AdditionalStorageProfile {
+                    name: "cold".to_string(),
+                    storage_profile: s3_profile("cold"),
+                    storage_secret_id: None,
+                    namespace_prefixes: vec![vec!["archive".to_string(), "cold".to_string()]],
+                },
+            ],
+        }
+    }
+
+    fn bucket_of(storage_profile: &StorageProfile) -> &str {
+        match storage_profile {
+            StorageProfile::S3(p) => &p.bucket,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_storage_for_namespace() {
+        let warehouse = warehouse();
+        let storage_for = |ns: &[&str], requested: Option<&str>| {
+            let ns = NamespaceIdent::from_strs(ns).unwrap();
+            warehouse
+                .storage_for_namespace(&ns, requested)
+                .map(|(p, _)| bucket_of(p).to_string())
+        };
+
+        assert_eq!(storage_for(&["sales"], None).unwrap(), "default");
+        assert_eq!(storage_for(&["archive"], None).unwrap(), "archive");
+        assert_eq!(storage_for(&["archive", "2020"], None).unwrap(), "archive");
+        // Most specific prefix wins
+        assert_eq!(
+            storage_for(&["archive", "cold", "x"], None).unwrap(),
+            "cold"
+        );
+        // Explicitly requested storage profile wins
+        assert_eq!(storage_for(&["sales"], Some("cold")).unwrap(), "cold");
+        assert_eq!(
+            storage_for(&["sales"], Some("missing")).unwrap_err().r#type,
+            "StorageProfileNotFound"
+        );
+    }
+
+    #[test]
+    fn test_storage_for_location() {
+        let warehouse = warehouse();
+        let storage_for = |location: &str| {
+            let location = Location::from_str(location).unwrap();
+            let (p, secret_id) = warehouse.storage_for_location(&location);
+            (bucket_of(p).to_string(), secret_id.is_some())
+        };
+
+        assert_eq!(
+            storage_for("s3://archive/ns/table"),
+            ("archive".to_string(), true)
+        );
+        assert_eq!(
+            storage_for("s3://cold/ns/table"),
+            ("cold".to_string(), false)
+        );
+        assert_eq!(
+            storage_for("s3://default/ns/table"),
+            ("default".to_string(), false)
+        );
+
+        assert!(warehouse
+            .require_allowed_location(&Location::from_str("s3://cold/ns/table").unwrap())
+            .is_ok());
+        assert!(warehouse
+            .require_allowed_location(&Location::from_str("s3://other/ns/table").unwrap())
+            .is_err());
+    }
+}
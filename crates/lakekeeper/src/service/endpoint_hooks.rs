@@ -10,8 +10,8 @@ use iceberg::{
     TableIdent,
 };
 use iceberg_ext::catalog::rest::{
-    CommitTransactionRequest, CommitViewRequest, CreateTableRequest, CreateViewRequest,
-    RegisterTableRequest, RenameTableRequest,
+    CommitTransactionRequest, CommitViewRequest, CreateNamespaceResponse, CreateTableRequest,
+    CreateViewRequest, RegisterTableRequest, RenameTableRequest, UpdateNamespacePropertiesResponse,
 };
 use lakekeeper_io::Location;
 
@@ -19,14 +19,24 @@ use crate::{
     api::{
         iceberg::{
             types::DropParams,
-            v1::{DataAccessMode, NamespaceParameters, TableParameters, ViewParameters},
+            v1::{
+                namespace::NamespaceDropFlags, DataAccessMode, NamespaceParameters,
+                TableParameters, ViewParameters,
+            },
+        },
+        management::v1::{
+            project::RenameProjectRequest,
+            warehouse::{RenameWarehouseRequest, UndropTabularsRequest},
+            DeleteWarehouseQuery,
         },
-        management::v1::warehouse::UndropTabularsRequest,
         RequestMetadata,
     },
     server::tables::CommitContext,
-    service::{TableId, TableInfo, ViewId, ViewOrTableInfo},
-    WarehouseId,
+    service::{
+        storage::StorageProfile, NamespaceId, TableId, TableInfo, TabularId, ViewId,
+        ViewOrTableInfo, WarehouseStatus,
+    },
+    ProjectId, WarehouseId,
 };
 
 #[derive(Clone)]
@@ -349,6 +359,311 @@ impl EndpointHookCollection {
         }))
         .await;
     }
+
+    pub(crate) async fn create_namespace(
+        &self,
+        warehouse_id: WarehouseId,
+        namespace_id: NamespaceId,
+        response: Arc<CreateNamespaceResponse>,
+        request_metadata: Arc<RequestMetadata>,
+    ) {
+        futures::future::join_all(self.0.iter().map(|hook| {
+            hook.create_namespace(
+                warehouse_id,
+                namespace_id,
+                response.clone(),
+                request_metadata.clone(),
+            )
+            .map_err(|e| {
+                tracing::warn!(
+                    "Hook '{}' encountered error on create_namespace: {e:?}",
+                    hook.to_string()
+                );
+            })
+        }))
+        .await;
+    }
+
+    pub(crate) async fn drop_namespace(
+        &self,
+        warehouse_id: WarehouseId,
+        parameters: NamespaceParameters,
+        flags: NamespaceDropFlags,
+        namespace_id: NamespaceId,
+        request_metadata: Arc<RequestMetadata>,
+    ) {
+        futures::future::join_all(self.0.iter().map(|hook| {
+            hook.drop_namespace(
+                warehouse_id,
+                parameters.clone(),
+                flags,
+                namespace_id,
+                request_metadata.clone(),
+            )
+            .map_err(|e| {
+                tracing::warn!(
+                    "Hook '{}' encountered error on drop_namespace: {e:?}",
+                    hook.to_string()
+                );
+            })
+        }))
+        .await;
+    }
+
+    pub(crate) async fn update_namespace_properties(
+        &self,
+        warehouse_id: WarehouseId,
+        parameters: NamespaceParameters,
+        namespace_id: NamespaceId,
+        response: Arc<UpdateNamespacePropertiesResponse>,
+        request_metadata: Arc<RequestMetadata>,
+    ) {
+        futures::future::join_all(self.0.iter().map(|hook| {
+            hook.update_namespace_properties(
+                warehouse_id,
+                parameters.clone(),
+                namespace_id,
+                response.clone(),
+                request_metadata.clone(),
+            )
+            .map_err(|e| {
+                tracing::warn!(
+                    "Hook '{}' encountered error on update_namespace_properties: {e:?}",
+                    hook.to_string()
+                );
+            })
+        }))
+        .await;
+    }
+
+    pub(crate) async fn set_namespace_protection(
+        &self,
+        warehouse_id: WarehouseId,
+        namespace_id: NamespaceId,
+        protected: bool,
+        request_metadata: Arc<RequestMetadata>,
+    ) {
+        futures::future::join_all(self.0.iter().map(|hook| {
+            hook.set_namespace_protection(
+                warehouse_id,
+                namespace_id,
+                protected,
+                request_metadata.clone(),
+            )
+            .map_err(|e| {
+                tracing::warn!(
+                    "Hook '{}' encountered error on set_namespace_protection: {e:?}",
+                    hook.to_string()
+                );
+            })
+        }))
+        .await;
+    }
+
+    pub(crate) async fn set_tabular_protection(
+        &self,
+        warehouse_id: WarehouseId,
+        tabular_id: TabularId,
+        protected: bool,
+        request_metadata: Arc<RequestMetadata>,
+    ) {
+        futures::future::join_all(self.0.iter().map(|hook| {
+            hook.set_tabular_protection(
+                warehouse_id,
+                tabular_id,
+                protected,
+                request_metadata.clone(),
+            )
+            .map_err(|e| {
+                tracing::warn!(
+                    "Hook '{}' encountered error on set_tabular_protection: {e:?}",
+                    hook.to_string()
+                );
+            })
+        }))
+        .await;
+    }
+
+    pub(crate) async fn create_warehouse(
+        &self,
+        warehouse_id: WarehouseId,
+        project_id: ProjectId,
+        warehouse_name: Arc<String>,
+        storage_profile: Arc<StorageProfile>,
+        request_metadata: Arc<RequestMetadata>,
+    ) {
+        futures::future::join_all(self.0.iter().map(|hook| {
+            hook.create_warehouse(
+                warehouse_id,
+                project_id.clone(),
+                warehouse_name.clone(),
+                storage_profile.clone(),
+                request_metadata.clone(),
+            )
+            .map_err(|e| {
+                tracing::warn!(
+                    "Hook '{}' encountered error on create_warehouse: {e:?}",
+                    hook.to_string()
+                );
+            })
+        }))
+        .await;
+    }
+
+    pub(crate) async fn rename_warehouse(
+        &self,
+        warehouse_id: WarehouseId,
+        request: Arc<RenameWarehouseRequest>,
+        request_metadata: Arc<RequestMetadata>,
+    ) {
+        futures::future::join_all(self.0.iter().map(|hook| {
+            hook.rename_warehouse(warehouse_id, request.clone(), request_metadata.clone())
+                .map_err(|e| {
+                    tracing::warn!(
+                        "Hook '{}' encountered error on rename_warehouse: {e:?}",
+                        hook.to_string()
+                    );
+                })
+        }))
+        .await;
+    }
+
+    pub(crate) async fn set_warehouse_status(
+        &self,
+        warehouse_id: WarehouseId,
+        status: WarehouseStatus,
+        request_metadata: Arc<RequestMetadata>,
+    ) {
+        futures::future::join_all(self.0.iter().map(|hook| {
+            hook.set_warehouse_status(warehouse_id, status, request_metadata.clone())
+                .map_err(|e| {
+                    tracing::warn!(
+                        "Hook '{}' encountered error on set_warehouse_status: {e:?}",
+                        hook.to_string()
+                    );
+                })
+        }))
+        .await;
+    }
+
+    pub(crate) async fn update_warehouse_storage(
+        &self,
+        warehouse_id: WarehouseId,
+        storage_profile: Arc<StorageProfile>,
+        request_metadata: Arc<RequestMetadata>,
+    ) {
+        futures::future::join_all(self.0.iter().map(|hook| {
+            hook.update_warehouse_storage(
+                warehouse_id,
+                storage_profile.clone(),
+                request_metadata.clone(),
+            )
+            .map_err(|e| {
+                tracing::warn!(
+                    "Hook '{}' encountered error on update_warehouse_storage: {e:?}",
+                    hook.to_string()
+                );
+            })
+        }))
+        .await;
+    }
+
+    pub(crate) async fn delete_warehouse(
+        &self,
+        warehouse_id: WarehouseId,
+        query: DeleteWarehouseQuery,
+        request_metadata: Arc<RequestMetadata>,
+    ) {
+        futures::future::join_all(self.0.iter().map(|hook| {
+            hook.delete_warehouse(warehouse_id, query, request_metadata.clone())
+                .map_err(|e| {
+                    tracing::warn!(
+                        "Hook '{}' encountered error on delete_warehouse: {e:?}",
+                        hook.to_string()
+                    );
+                })
+        }))
+        .await;
+    }
+
+    pub(crate) async fn set_warehouse_protection(
+        &self,
+        warehouse_id: WarehouseId,
+        protected: bool,
+        request_metadata: Arc<RequestMetadata>,
+    ) {
+        futures::future::join_all(self.0.iter().map(|hook| {
+            hook.set_warehouse_protection(warehouse_id, protected, request_metadata.clone())
+                .map_err(|e| {
+                    tracing::warn!(
+                        "Hook '{}' encountered error on set_warehouse_protection: {e:?}",
+                        hook.to_string()
+                    );
+                })
+        }))
+        .await;
+    }
+
+    pub(crate) async fn create_project(
+        &self,
+        project_id: ProjectId,
+        project_name: Arc<String>,
+        request_metadata: Arc<RequestMetadata>,
+    ) {
+        futures::future::join_all(self.0.iter().map(|hook| {
+            hook.create_project(
+                project_id.clone(),
+                project_name.clone(),
+                request_metadata.clone(),
+            )
+            .map_err(|e| {
+                tracing::warn!(
+                    "Hook '{}' encountered error on create_project: {e:?}",
+                    hook.to_string()
+                );
+            })
+        }))
+        .await;
+    }
+
+    pub(crate) async fn rename_project(
+        &self,
+        project_id: ProjectId,
+        request: Arc<RenameProjectRequest>,
+        request_metadata: Arc<RequestMetadata>,
+    ) {
+        futures::future::join_all(self.0.iter().map(|hook| {
+            hook.rename_project(
+                project_id.clone(),
+                request.clone(),
+                request_metadata.clone(),
+            )
+            .map_err(|e| {
+                tracing::warn!(
+                    "Hook '{}' encountered error on rename_project: {e:?}",
+                    hook.to_string()
+                );
+            })
+        }))
+        .await;
+    }
+
+    pub(crate) async fn delete_project(
+        &self,
+        project_id: ProjectId,
+        request_metadata: Arc<RequestMetadata>,
+    ) {
+        futures::future::join_all(self.0.iter().map(|hook| {
+            hook.delete_project(project_id.clone(), request_metadata.clone())
+                .map_err(|e| {
+                    tracing::warn!(
+                        "Hook '{}' encountered error on delete_project: {e:?}",
+                        hook.to_string()
+                    );
+                })
+        }))
+        .await;
+    }
 }
 
 /// `EndpointHook` is a trait that allows for custom hooks to be executed within the context of
@@ -482,4 +797,138 @@ pub trait EndpointHook: Send + Sync + Debug + Display {
     ) -> anyhow::Result<()> {
         Ok(())
     }
+
+    async fn create_namespace(
+        &self,
+        _warehouse_id: WarehouseId,
+        _namespace_id: NamespaceId,
+        _response: Arc<CreateNamespaceResponse>,
+        _request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn drop_namespace(
+        &self,
+        _warehouse_id: WarehouseId,
+        _parameters: NamespaceParameters,
+        _flags: NamespaceDropFlags,
+        _namespace_id: NamespaceId,
+        _request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn update_namespace_properties(
+        &self,
+        _warehouse_id: WarehouseId,
+        _parameters: NamespaceParameters,
+        _namespace_id: NamespaceId,
+        _response: Arc<UpdateNamespacePropertiesResponse>,
+        _request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn set_namespace_protection(
+        &self,
+        _warehouse_id: WarehouseId,
+        _namespace_id: NamespaceId,
+        _protected: bool,
+        _request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn set_tabular_protection(
+        &self,
+        _warehouse_id: WarehouseId,
+        _tabular_id: TabularId,
+        _protected: bool,
+        _request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn create_warehouse(
+        &self,
+        _warehouse_id: WarehouseId,
+        _project_id: ProjectId,
+        _warehouse_name: Arc<String>,
+        _storage_profile: Arc<StorageProfile>,
+        _request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn rename_warehouse(
+        &self,
+        _warehouse_id: WarehouseId,
+        _request: Arc<RenameWarehouseRequest>,
+        _request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn set_warehouse_status(
+        &self,
+        _warehouse_id: WarehouseId,
+        _status: WarehouseStatus,
+        _request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn update_warehouse_storage(
+        &self,
+        _warehouse_id: WarehouseId,
+        _storage_profile: Arc<StorageProfile>,
+        _request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn delete_warehouse(
+        &self,
+        _warehouse_id: WarehouseId,
+        _query: DeleteWarehouseQuery,
+        _request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn set_warehouse_protection(
+        &self,
+        _warehouse_id: WarehouseId,
+        _protected: bool,
+        _request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn create_project(
+        &self,
+        _project_id: ProjectId,
+        _project_name: Arc<String>,
+        _request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn rename_project(
+        &self,
+        _project_id: ProjectId,
+        _request: Arc<RenameProjectRequest>,
+        _request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn delete_project(
+        &self,
+        _project_id: ProjectId,
+        _request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
 }
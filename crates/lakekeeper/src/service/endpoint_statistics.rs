@@ -17,7 +17,29 @@ use http::StatusCode;
 use tracing::Instrument;
 use uuid::Uuid;
 
-use crate::{api::endpoints::Endpoint, request_metadata::RequestMetadata, ProjectId, WarehouseId};
+pub use self::{opentelemetry::OpenTelemetryStatisticsSink, prometheus::PrometheusStatisticsSink};
+use crate::{
+    api::endpoints::Endpoint, request_metadata::RequestMetadata, ProjectId, WarehouseId, CONFIG,
+};
+
+pub mod opentelemetry;
+pub mod prometheus;
+
+/// Sinks enabled in the configuration in addition to the sink of the catalog backend.
+#[must_use]
+pub fn get_additional_endpoint_statistics_sinks_from_config(
+) -> Vec<Arc<dyn EndpointStatisticsSink + 'static>> {
+    let mut sinks: Vec<Arc<dyn EndpointStatisticsSink>> = vec![];
+    if CONFIG.endpoint_statistics.prometheus_enabled {
+        tracing::info!("Exposing endpoint statistics as Prometheus metrics");
+        sinks.push(Arc::new(PrometheusStatisticsSink::new()));
+    }
+    if CONFIG.endpoint_statistics.opentelemetry_enabled {
+        tracing::info!("Recording endpoint statistics as OpenTelemetry metrics");
+        sinks.push(Arc::new(OpenTelemetryStatisticsSink::new()));
+    }
+    sinks
+}
 
 #[cfg(feature = "router")]
 /// Middleware for tracking endpoint statistics.
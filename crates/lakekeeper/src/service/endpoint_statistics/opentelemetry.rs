@@ -0,0 +1,71 @@
+use std::collections::HashMap;
+
+use ::opentelemetry::{metrics::Counter, KeyValue};
+
+use super::{EndpointIdentifier, EndpointStatisticsSink};
+use crate::ProjectId;
+
+/// Records endpoint statistics with the global `OpenTelemetry` meter provider.
+///
+/// The counter `lakekeeper.endpoint.calls` is labeled by `project_id`, `warehouse_id`,
+/// `route` and `status`. The sink must be created after the meter provider is installed,
+/// otherwise all measurements are dropped.
+#[derive(Debug)]
+pub struct OpenTelemetryStatisticsSink {
+    calls: Counter<u64>,
+}
+
+impl OpenTelemetryStatisticsSink {
+    #[must_use]
+    pub fn new() -> Self {
+        let calls = ::opentelemetry::global::meter("lakekeeper")
+            .u64_counter("lakekeeper.endpoint.calls")
+            .with_description(
+                "Number of calls per project, warehouse, endpoint and response status",
+            )
+            .build();
+        Self { calls }
+    }
+}
+
+impl Default for OpenTelemetryStatisticsSink {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl EndpointStatisticsSink for OpenTelemetryStatisticsSink {
+    async fn consume_endpoint_statistics(
+        &self,
+        stats: HashMap<ProjectId, HashMap<EndpointIdentifier, i64>>,
+    ) -> crate::api::Result<()> {
+        for (project_id, endpoints) in stats {
+            for (endpoint, count) in endpoints {
+                let Ok(count) = u64::try_from(count) else {
+                    continue;
+                };
+                self.calls.add(
+                    count,
+                    &[
+                        KeyValue::new("project_id", project_id.to_string()),
+                        KeyValue::new(
+                            "warehouse_id",
+                            endpoint
+                                .warehouse
+                                .map(|w| w.to_string())
+                                .unwrap_or_default(),
+                        ),
+                        KeyValue::new("route", endpoint.uri.as_http_route()),
+                        KeyValue::new("status", endpoint.status_code.as_u16().to_string()),
+                    ],
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn sink_id(&self) -> &'static str {
+        "opentelemetry"
+    }
+}
@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+
+use axum_prometheus::metrics;
+
+use super::{EndpointIdentifier, EndpointStatisticsSink};
+use crate::ProjectId;
+
+const METRIC_ENDPOINT_CALLS: &str = "lakekeeper_endpoint_calls_total";
+
+/// Exposes endpoint statistics as Prometheus counters on the metrics port.
+///
+/// Counters are labeled by `project_id`, `warehouse_id`, `route` and `status`.
+/// Calls that are not scoped to a warehouse have an empty `warehouse_id` label.
+#[derive(Debug)]
+pub struct PrometheusStatisticsSink;
+
+impl Default for PrometheusStatisticsSink {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PrometheusStatisticsSink {
+    #[must_use]
+    pub fn new() -> Self {
+        metrics::describe_counter!(
+            METRIC_ENDPOINT_CALLS,
+            "Number of calls per project, warehouse, endpoint and response status"
+        );
+        Self
+    }
+}
+
+#[async_trait::async_trait]
+impl EndpointStatisticsSink for PrometheusStatisticsSink {
+    async fn consume_endpoint_statistics(
+        &self,
+        stats: HashMap<ProjectId, HashMap<EndpointIdentifier, i64>>,
+    ) -> crate::api::Result<()> {
+        for (project_id, endpoints) in stats {
+            for (endpoint, count) in endpoints {
+                let Ok(count) = u64::try_from(count) else {
+                    continue;
+                };
+                metrics::counter!(
+                    METRIC_ENDPOINT_CALLS,
+                    "project_id" => project_id.to_string(),
+                    "warehouse_id" => endpoint.warehouse.map(|w| w.to_string()).unwrap_or_default(),
+                    "route" => endpoint.uri.as_http_route(),
+                    "status" => endpoint.status_code.as_u16().to_string(),
+                )
+                .increment(count);
+            }
+        }
+        Ok(())
+    }
+
+    fn sink_id(&self) -> &'static str {
+        "prometheus"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum_prometheus::metrics_exporter_prometheus::PrometheusBuilder;
+    use http::StatusCode;
+
+    use super::*;
+    use crate::{
+        api::endpoints::{CatalogV1Endpoint, Endpoint},
+        WarehouseId,
+    };
+
+    #[test]
+    fn test_calls_are_recorded_as_counter() {
+        let recorder = PrometheusBuilder::new().build_recorder();
+        let handle = recorder.handle();
+        let project_id = ProjectId::new_random();
+        let warehouse_id = WarehouseId::new_random();
+        let stats = HashMap::from([(
+            project_id.clone(),
+            HashMap::from([(
+                EndpointIdentifier {
+                    uri: Endpoint::CatalogV1(CatalogV1Endpoint::LoadTable),
+                    status_code: StatusCode::OK,
+                    warehouse: Some(warehouse_id),
+                    warehouse_name: None,
+                },
+                3,
+            )]),
+        )]);
+
+        metrics::with_local_recorder(&recorder, || {
+            futures::executor::block_on(
+                PrometheusStatisticsSink::default().consume_endpoint_statistics(stats),
+            )
+        })
+        .unwrap();
+
+        let rendered = handle.render();
+        let line = rendered
+            .lines()
+            .find(|l| l.starts_with(METRIC_ENDPOINT_CALLS))
+            .expect("counter should be rendered");
+        assert!(line.contains(&format!("project_id=\"{project_id}\"")));
+        assert!(line.contains(&format!("warehouse_id=\"{warehouse_id}\"")));
+        assert!(
+            line.contains("route=\"/catalog/v1/{prefix}/namespaces/{namespace}/tables/{table}\"")
+        );
+        assert!(line.contains("status=\"200\""));
+        assert!(line.ends_with(" 3"));
+    }
+}
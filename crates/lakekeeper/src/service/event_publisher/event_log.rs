@@ -0,0 +1,160 @@
+//! Persistence of `CloudEvents` in the catalog database.
+//!
+//! The [`EventLogSink`] stores every event that belongs to a warehouse, so that the history
+//! of a warehouse or tabular can be queried via the management API. Events are deleted
+//! once they are older than the configured retention period.
+use std::{fmt::Debug, time::Duration};
+
+use async_trait::async_trait;
+use cloudevents::{AttributesReader, Event};
+use uuid::Uuid;
+
+use super::CloudEventBackend;
+use crate::{
+    service::{CatalogStore, NamespaceId, NewEventLogEntry},
+    CancellationToken, WarehouseId, CONFIG,
+};
+
+pub struct EventLogSink<C: CatalogStore> {
+    catalog_state: C::State,
+}
+
+impl<C: CatalogStore> EventLogSink<C> {
+    #[must_use]
+    pub fn new(catalog_state: C::State) -> Self {
+        Self { catalog_state }
+    }
+}
+
+impl<C: CatalogStore> Debug for EventLogSink<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventLogSink").finish()
+    }
+}
+
+#[async_trait]
+impl<C: CatalogStore> CloudEventBackend for EventLogSink<C> {
+    async fn publish(&self, event: Event) -> anyhow::Result<()> {
+        let Some(entry) = event_log_entry(&event)? else {
+            // Events of projects or the server are not logged
+            return Ok(());
+        };
+        C::insert_event_log(vec![entry], self.catalog_state.clone())
+            .await
+            .map_err(|e| anyhow::anyhow!(e.error).context("Failed to store event in event log"))
+    }
+
+    async fn publish_batch(&self, events: Vec<Event>) {
+        let mut entries = Vec::with_capacity(events.len());
+        for event in &events {
+            match event_log_entry(event) {
+                Ok(Some(entry)) => entries.push(entry),
+                // Events of projects or the server are not logged
+                Ok(None) => {}
+                Err(e) => tracing::warn!(
+                    "Failed to store event with id: '{}' in event log due to: '{e}'.",
+                    event.id()
+                ),
+            }
+        }
+        let num_entries = entries.len();
+        if let Err(e) = C::insert_event_log(entries, self.catalog_state.clone()).await {
+            tracing::warn!(
+                "Failed to store {num_entries} events in event log due to: '{}'.",
+                e.error
+            );
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "event-log"
+    }
+}
+
+/// Returns `None` for events that don't belong to a warehouse.
+fn event_log_entry(event: &Event) -> anyhow::Result<Option<NewEventLogEntry>> {
+    let extension_uuid = |name: &str| -> anyhow::Result<Option<Uuid>> {
+        event
+            .extension(name)
+            .map(|value| {
+                Uuid::parse_str(&value.to_string())
+                    .map_err(|e| anyhow::anyhow!(e).context(format!("Invalid `{name}` extension")))
+            })
+            .transpose()
+    };
+
+    let Some(warehouse_id) = extension_uuid("warehouse-id")? else {
+        return Ok(None);
+    };
+    let event_id = Uuid::parse_str(event.id())
+        .map_err(|e| anyhow::anyhow!(e).context("Event id is not a UUID"))?;
+
+    Ok(Some(NewEventLogEntry {
+        event_id,
+        warehouse_id: WarehouseId::from(warehouse_id),
+        tabular_id: extension_uuid("tabular-id")?,
+        namespace_id: extension_uuid("namespace-id")?.map(NamespaceId::from),
+        event_type: event.ty().to_string(),
+        event_time: event.time().copied().unwrap_or_else(chrono::Utc::now),
+        event: serde_json::to_value(event)?,
+    }))
+}
+
+/// Periodically delete events older than the configured retention period.
+pub async fn prune_event_log_periodically<C: CatalogStore>(
+    catalog_state: C::State,
+    cancellation_token: CancellationToken,
+) {
+    const PRUNE_INTERVAL: Duration = Duration::from_secs(3600);
+
+    while !cancellation_token.is_cancelled() {
+        let older_than = chrono::Utc::now() - CONFIG.event_log.retention_seconds;
+        match C::prune_event_log(older_than, catalog_state.clone()).await {
+            Ok(0) => {}
+            Ok(n) => tracing::debug!("Pruned {n} events older than {older_than} from event log"),
+            Err(e) => tracing::warn!("Failed to prune event log: {}", e.error),
+        }
+
+        tokio::select! {
+            () = cancellation_token.cancelled() => {},
+            () = tokio::time::sleep(PRUNE_INTERVAL) => {},
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cloudevents::{EventBuilder, EventBuilderV10};
+
+    use super::*;
+
+    #[test]
+    fn test_event_log_entry() {
+        let warehouse_id = Uuid::now_v7();
+        let tabular_id = Uuid::now_v7();
+        let event = EventBuilderV10::new()
+            .id(Uuid::now_v7().to_string())
+            .source("test")
+            .ty("updateTable")
+            .time(chrono::Utc::now())
+            .extension("warehouse-id", warehouse_id.to_string())
+            .extension("tabular-id", tabular_id.to_string())
+            .build()
+            .unwrap();
+        let entry = event_log_entry(&event).unwrap().unwrap();
+        assert_eq!(*entry.warehouse_id, warehouse_id);
+        assert_eq!(entry.tabular_id, Some(tabular_id));
+        assert_eq!(entry.namespace_id, None);
+        assert_eq!(entry.event_type, "updateTable");
+        assert_eq!(Some(&entry.event_time), event.time());
+        assert_eq!(entry.event["id"], event.id());
+
+        let project_event = EventBuilderV10::new()
+            .id(Uuid::now_v7().to_string())
+            .source("test")
+            .ty("createProject")
+            .build()
+            .unwrap();
+        assert!(event_log_entry(&project_event).unwrap().is_none());
+    }
+}
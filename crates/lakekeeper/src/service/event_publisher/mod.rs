@@ -1,44 +1,60 @@
 use std::{
+    collections::HashMap,
     fmt::{Debug, Display},
     sync::Arc,
 };
 
 use anyhow::Context;
 use async_trait::async_trait;
-use cloudevents::Event;
+use cloudevents::{AttributesReader as _, Event};
 use iceberg::{
     spec::{TableMetadata, ViewMetadata},
     TableIdent,
 };
 use iceberg_ext::catalog::rest::{
-    CommitTransactionRequest, CommitViewRequest, CreateTableRequest, CreateViewRequest,
-    RegisterTableRequest, RenameTableRequest,
+    CommitTableRequest, CommitTransactionRequest, CommitViewRequest, CreateNamespaceResponse,
+    CreateTableRequest, CreateViewRequest, RegisterTableRequest, RenameTableRequest,
+    UpdateNamespacePropertiesResponse,
 };
 use lakekeeper_io::Location;
 use uuid::Uuid;
 
-use super::{TableId, ViewId, WarehouseId};
+use super::{NamespaceId, TableId, ViewId, WarehouseId};
 use crate::{
     api::{
         iceberg::{
             types::{DropParams, Prefix},
-            v1::{DataAccessMode, NamespaceParameters, TableParameters, ViewParameters},
+            v1::{
+                namespace::NamespaceDropFlags, DataAccessMode, NamespaceParameters,
+                TableParameters, ViewParameters,
+            },
+        },
+        management::v1::{
+            project::RenameProjectRequest,
+            warehouse::{RenameWarehouseRequest, UndropTabularsRequest},
+            DeleteWarehouseQuery,
         },
-        management::v1::warehouse::UndropTabularsRequest,
         RequestMetadata,
     },
     server::tables::{maybe_body_to_json, CommitContext},
     service::{
         endpoint_hooks::{EndpointHook, TableIdentToIdFn, ViewCommit},
-        TabularId, ViewOrTableInfo,
+        event_publisher::table_changes::TableChanges,
+        storage::StorageProfile,
+        TabularId, ViewOrTableInfo, WarehouseStatus,
     },
-    CONFIG,
+    ProjectId, CONFIG,
 };
 
+pub mod event_log;
 #[cfg(feature = "kafka")]
 pub mod kafka;
 #[cfg(feature = "nats")]
 pub mod nats;
+pub mod outbox;
+pub mod stream;
+pub mod table_changes;
+pub mod webhook;
 
 /// Builds the default cloud event backends from the configuration.
 ///
@@ -60,6 +76,11 @@ pub async fn get_default_cloud_event_backends_from_config(
             .push(Arc::new(kafka_publisher) as Arc<dyn CloudEventBackend + Sync + Send>);
     }
 
+    for webhook_publisher in webhook::build_webhook_publishers_from_config()? {
+        cloud_event_sinks
+            .push(Arc::new(webhook_publisher) as Arc<dyn CloudEventBackend + Sync + Send>);
+    }
+
     if let Some(true) = &CONFIG.log_cloudevents {
         let tracing_publisher = TracingPublisher;
         cloud_event_sinks
@@ -76,52 +97,110 @@ pub async fn get_default_cloud_event_backends_from_config(
     Ok(cloud_event_sinks)
 }
 
+/// Build one `updateTable` event per table changed by `request`.
+///
+/// # Errors
+/// If the actor of the request cannot be serialized.
+pub fn update_table_payloads(
+    warehouse_id: WarehouseId,
+    request: &CommitTransactionRequest,
+    commits: &[CommitContext],
+    table_ident_to_id_fn: &TableIdentToIdFn,
+    request_metadata: &RequestMetadata,
+) -> anyhow::Result<Vec<Payload>> {
+    let actor = serde_json::to_string(request_metadata.actor())
+        .map_err(|e| anyhow::anyhow!(e).context("Failed to serialize actor"))?;
+    let changes = request
+        .table_changes
+        .iter()
+        .filter_map(|commit_table_request| {
+            let id = commit_table_request.identifier.as_ref()?;
+            let table_id = table_ident_to_id_fn(id)?;
+            Some((commit_table_request, id, table_id))
+        })
+        .collect::<Vec<_>>();
+    let number_of_events = changes.len();
+    let trace_context = lakekeeper_io::trace_context::current();
+
+    Ok(changes
+        .into_iter()
+        .enumerate()
+        .map(
+            |(event_sequence_number, (commit_table_request, table_ident, table_id))| Payload {
+                id: Uuid::now_v7(),
+                typ: "updateTable".to_string(),
+                data: update_table_data(commit_table_request, table_ident, commits),
+                metadata: EventMetadata {
+                    tabular_id: TabularId::Table(table_id),
+                    warehouse_id,
+                    name: table_ident.name.clone(),
+                    namespace: table_ident.namespace.to_url_string(),
+                    prefix: String::new(),
+                    num_events: number_of_events,
+                    sequence_number: event_sequence_number,
+                    trace_id: request_metadata.request_id(),
+                    actor: actor.clone(),
+                }
+                .into(),
+                trace_context: trace_context.clone(),
+            },
+        )
+        .collect())
+}
+
+/// The request of the commit, enriched with the [`TableChanges`] of the commit if
+/// `cloud_events_table_changes` is enabled.
+fn update_table_data(
+    commit_table_request: &CommitTableRequest,
+    table_ident: &TableIdent,
+    commits: &[CommitContext],
+) -> serde_json::Value {
+    let mut data = maybe_body_to_json(commit_table_request);
+    if !CONFIG.cloud_events_table_changes {
+        return data;
+    }
+
+    let changes = commits
+        .iter()
+        .find(|commit| &commit.table_ident == table_ident)
+        .map(TableChanges::from_commit);
+    if let (serde_json::Value::Object(data), Some(changes)) = (&mut data, changes) {
+        data.insert("changes".to_string(), maybe_body_to_json(changes));
+    }
+    data
+}
+
 #[async_trait::async_trait]
 impl EndpointHook for CloudEventsPublisher {
     async fn commit_transaction(
         &self,
         warehouse_id: WarehouseId,
         request: Arc<CommitTransactionRequest>,
-        _commits: Arc<Vec<CommitContext>>,
+        commits: Arc<Vec<CommitContext>>,
         table_ident_to_id_fn: &TableIdentToIdFn,
         request_metadata: Arc<RequestMetadata>,
     ) -> anyhow::Result<()> {
-        let estimated = request.table_changes.len();
-        let mut events = Vec::with_capacity(estimated);
-        let mut event_table_ids: Vec<(TableIdent, TableId)> = Vec::with_capacity(estimated);
-        for commit_table_request in &request.table_changes {
-            if let Some(id) = &commit_table_request.identifier {
-                if let Some(uuid) = table_ident_to_id_fn(id) {
-                    events.push(maybe_body_to_json(commit_table_request));
-                    event_table_ids.push((id.clone(), uuid));
-                }
-            }
-        }
-        let number_of_events = events.len();
-        let mut futs = Vec::with_capacity(number_of_events);
-        for (event_sequence_number, (body, (table_ident, table_id))) in
-            events.into_iter().zip(event_table_ids).enumerate()
-        {
-            futs.push(
-                self.publish(
-                    Uuid::now_v7(),
-                    "updateTable",
-                    body,
-                    EventMetadata {
-                        tabular_id: TabularId::Table(table_id),
-                        warehouse_id,
-                        name: table_ident.name,
-                        namespace: table_ident.namespace.to_url_string(),
-                        prefix: String::new(),
-                        num_events: number_of_events,
-                        sequence_number: event_sequence_number,
-                        trace_id: request_metadata.request_id(),
-                        actor: serde_json::to_string(request_metadata.actor())
-                            .map_err(|e| anyhow::anyhow!(e).context("Failed to serialize actor"))?,
-                    },
-                ),
-            );
+        // Events are written to the outbox as part of the commit
+        if CONFIG.cloud_events_outbox.enabled {
+            return Ok(());
         }
+
+        let payloads = update_table_payloads(
+            warehouse_id,
+            &request,
+            &commits,
+            table_ident_to_id_fn,
+            &request_metadata,
+        )?;
+        let futs = payloads.into_iter().map(
+            |Payload {
+                 id,
+                 typ,
+                 data,
+                 metadata,
+                 ..
+             }| async move { self.publish(id, &typ, data, metadata).await },
+        );
         futures::future::try_join_all(futs)
             .await
             .context("Failed to publish `updateTable` event")?;
@@ -419,6 +498,272 @@ impl EndpointHook for CloudEventsPublisher {
             .context("Failed to publish `undropTabulars` event")?;
         Ok(())
     }
+
+    async fn create_namespace(
+        &self,
+        warehouse_id: WarehouseId,
+        namespace_id: NamespaceId,
+        response: Arc<CreateNamespaceResponse>,
+        request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        self.publish_lifecycle(
+            "createNamespace",
+            maybe_body_to_json(&*response),
+            LifecycleEventMetadata {
+                warehouse_id: Some(warehouse_id),
+                namespace_id: Some(namespace_id),
+                namespace: Some(response.namespace.to_url_string()),
+                ..LifecycleEventMetadata::from_request_metadata(&request_metadata)?
+            },
+        )
+        .await
+    }
+
+    async fn drop_namespace(
+        &self,
+        warehouse_id: WarehouseId,
+        parameters: NamespaceParameters,
+        flags: NamespaceDropFlags,
+        namespace_id: NamespaceId,
+        request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        self.publish_lifecycle(
+            "dropNamespace",
+            maybe_body_to_json(flags),
+            LifecycleEventMetadata {
+                warehouse_id: Some(warehouse_id),
+                namespace_id: Some(namespace_id),
+                namespace: Some(parameters.namespace.to_url_string()),
+                ..LifecycleEventMetadata::from_request_metadata(&request_metadata)?
+            },
+        )
+        .await
+    }
+
+    async fn update_namespace_properties(
+        &self,
+        warehouse_id: WarehouseId,
+        parameters: NamespaceParameters,
+        namespace_id: NamespaceId,
+        response: Arc<UpdateNamespacePropertiesResponse>,
+        request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        self.publish_lifecycle(
+            "updateNamespaceProperties",
+            maybe_body_to_json(&*response),
+            LifecycleEventMetadata {
+                warehouse_id: Some(warehouse_id),
+                namespace_id: Some(namespace_id),
+                namespace: Some(parameters.namespace.to_url_string()),
+                ..LifecycleEventMetadata::from_request_metadata(&request_metadata)?
+            },
+        )
+        .await
+    }
+
+    async fn set_namespace_protection(
+        &self,
+        warehouse_id: WarehouseId,
+        namespace_id: NamespaceId,
+        protected: bool,
+        request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        self.publish_lifecycle(
+            "setNamespaceProtection",
+            serde_json::json!({ "protected": protected }),
+            LifecycleEventMetadata {
+                warehouse_id: Some(warehouse_id),
+                namespace_id: Some(namespace_id),
+                ..LifecycleEventMetadata::from_request_metadata(&request_metadata)?
+            },
+        )
+        .await
+    }
+
+    async fn set_tabular_protection(
+        &self,
+        warehouse_id: WarehouseId,
+        tabular_id: TabularId,
+        protected: bool,
+        request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        let typ = match tabular_id {
+            TabularId::Table(_) => "setTableProtection",
+            TabularId::View(_) => "setViewProtection",
+        };
+        self.publish_lifecycle(
+            typ,
+            serde_json::json!({ "protected": protected }),
+            LifecycleEventMetadata {
+                warehouse_id: Some(warehouse_id),
+                tabular_id: Some(tabular_id),
+                ..LifecycleEventMetadata::from_request_metadata(&request_metadata)?
+            },
+        )
+        .await
+    }
+
+    async fn create_warehouse(
+        &self,
+        warehouse_id: WarehouseId,
+        project_id: ProjectId,
+        warehouse_name: Arc<String>,
+        storage_profile: Arc<StorageProfile>,
+        request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        self.publish_lifecycle(
+            "createWarehouse",
+            serde_json::json!({
+                "warehouse-name": warehouse_name,
+                "storage-profile": maybe_body_to_json(&*storage_profile),
+            }),
+            LifecycleEventMetadata {
+                project_id: Some(project_id),
+                warehouse_id: Some(warehouse_id),
+                ..LifecycleEventMetadata::from_request_metadata(&request_metadata)?
+            },
+        )
+        .await
+    }
+
+    async fn rename_warehouse(
+        &self,
+        warehouse_id: WarehouseId,
+        request: Arc<RenameWarehouseRequest>,
+        request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        self.publish_lifecycle(
+            "renameWarehouse",
+            serde_json::json!({ "new-name": request.new_name }),
+            LifecycleEventMetadata {
+                warehouse_id: Some(warehouse_id),
+                ..LifecycleEventMetadata::from_request_metadata(&request_metadata)?
+            },
+        )
+        .await
+    }
+
+    async fn set_warehouse_status(
+        &self,
+        warehouse_id: WarehouseId,
+        status: WarehouseStatus,
+        request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        let typ = match status {
+            WarehouseStatus::Active => "activateWarehouse",
+            WarehouseStatus::Inactive => "deactivateWarehouse",
+        };
+        self.publish_lifecycle(
+            typ,
+            serde_json::Value::Null,
+            LifecycleEventMetadata {
+                warehouse_id: Some(warehouse_id),
+                ..LifecycleEventMetadata::from_request_metadata(&request_metadata)?
+            },
+        )
+        .await
+    }
+
+    async fn update_warehouse_storage(
+        &self,
+        warehouse_id: WarehouseId,
+        storage_profile: Arc<StorageProfile>,
+        request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        self.publish_lifecycle(
+            "updateWarehouseStorage",
+            serde_json::json!({ "storage-profile": maybe_body_to_json(&*storage_profile) }),
+            LifecycleEventMetadata {
+                warehouse_id: Some(warehouse_id),
+                ..LifecycleEventMetadata::from_request_metadata(&request_metadata)?
+            },
+        )
+        .await
+    }
+
+    async fn delete_warehouse(
+        &self,
+        warehouse_id: WarehouseId,
+        query: DeleteWarehouseQuery,
+        request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        self.publish_lifecycle(
+            "deleteWarehouse",
+            serde_json::json!({ "force": query.force }),
+            LifecycleEventMetadata {
+                warehouse_id: Some(warehouse_id),
+                ..LifecycleEventMetadata::from_request_metadata(&request_metadata)?
+            },
+        )
+        .await
+    }
+
+    async fn set_warehouse_protection(
+        &self,
+        warehouse_id: WarehouseId,
+        protected: bool,
+        request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        self.publish_lifecycle(
+            "setWarehouseProtection",
+            serde_json::json!({ "protected": protected }),
+            LifecycleEventMetadata {
+                warehouse_id: Some(warehouse_id),
+                ..LifecycleEventMetadata::from_request_metadata(&request_metadata)?
+            },
+        )
+        .await
+    }
+
+    async fn create_project(
+        &self,
+        project_id: ProjectId,
+        project_name: Arc<String>,
+        request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        self.publish_lifecycle(
+            "createProject",
+            serde_json::json!({ "project-name": project_name }),
+            LifecycleEventMetadata {
+                project_id: Some(project_id),
+                ..LifecycleEventMetadata::from_request_metadata(&request_metadata)?
+            },
+        )
+        .await
+    }
+
+    async fn rename_project(
+        &self,
+        project_id: ProjectId,
+        request: Arc<RenameProjectRequest>,
+        request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        self.publish_lifecycle(
+            "renameProject",
+            serde_json::json!({ "new-name": request.new_name }),
+            LifecycleEventMetadata {
+                project_id: Some(project_id),
+                ..LifecycleEventMetadata::from_request_metadata(&request_metadata)?
+            },
+        )
+        .await
+    }
+
+    async fn delete_project(
+        &self,
+        project_id: ProjectId,
+        request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        self.publish_lifecycle(
+            "deleteProject",
+            serde_json::Value::Null,
+            LifecycleEventMetadata {
+                project_id: Some(project_id),
+                ..LifecycleEventMetadata::from_request_metadata(&request_metadata)?
+            },
+        )
+        .await
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -455,7 +800,7 @@ impl CloudEventsPublisher {
         id: Uuid,
         typ: &str,
         data: serde_json::Value,
-        metadata: EventMetadata,
+        metadata: impl Into<PayloadMetadata>,
     ) -> anyhow::Result<()> {
         self.tx
             .send_timeout(
@@ -463,7 +808,8 @@ impl CloudEventsPublisher {
                     id,
                     typ: typ.to_string(),
                     data,
-                    metadata,
+                    metadata: metadata.into(),
+                    trace_context: lakekeeper_io::trace_context::current(),
                 }),
                 self.timeout,
             )
@@ -474,6 +820,17 @@ impl CloudEventsPublisher {
             })?;
         Ok(())
     }
+
+    async fn publish_lifecycle(
+        &self,
+        typ: &str,
+        data: serde_json::Value,
+        metadata: LifecycleEventMetadata,
+    ) -> anyhow::Result<()> {
+        self.publish(Uuid::now_v7(), typ, data, metadata)
+            .await
+            .with_context(|| format!("Failed to publish `{typ}` event"))
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -489,12 +846,148 @@ pub struct EventMetadata {
     pub actor: String,
 }
 
+/// Metadata of events that concern namespaces, warehouses or projects, or the
+/// settings of a table or view rather than its content.
+///
+/// Only the fields that are set are emitted as extensions of the `CloudEvent`.
+#[derive(Debug, Clone)]
+pub struct LifecycleEventMetadata {
+    pub project_id: Option<ProjectId>,
+    pub warehouse_id: Option<WarehouseId>,
+    pub namespace_id: Option<NamespaceId>,
+    pub namespace: Option<String>,
+    pub tabular_id: Option<TabularId>,
+    pub trace_id: Uuid,
+    pub actor: String,
+}
+
+impl LifecycleEventMetadata {
+    /// Metadata with only `trace_id` and `actor` set.
+    ///
+    /// # Errors
+    /// If the actor of the request cannot be serialized.
+    pub fn from_request_metadata(request_metadata: &RequestMetadata) -> anyhow::Result<Self> {
+        Ok(Self {
+            project_id: None,
+            warehouse_id: None,
+            namespace_id: None,
+            namespace: None,
+            tabular_id: None,
+            trace_id: request_metadata.request_id(),
+            actor: serde_json::to_string(request_metadata.actor())
+                .map_err(|e| anyhow::anyhow!(e).context("Failed to serialize actor"))?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, derive_more::From)]
+pub enum PayloadMetadata {
+    Tabular(EventMetadata),
+    Lifecycle(LifecycleEventMetadata),
+}
+
 #[derive(Debug)]
 pub struct Payload {
     pub id: Uuid,
     pub typ: String,
     pub data: serde_json::Value,
-    pub metadata: EventMetadata,
+    pub metadata: PayloadMetadata,
+    /// W3C trace context (`traceparent`, `tracestate`) of the request that caused the event.
+    pub trace_context: HashMap<String, String>,
+}
+
+impl Payload {
+    /// # Errors
+    /// Returns an error if the `Event` cannot be built from the payload
+    pub fn into_event(self) -> anyhow::Result<Event> {
+        use cloudevents::{EventBuilder, EventBuilderV10};
+
+        let Payload {
+            id,
+            typ,
+            data,
+            metadata,
+            trace_context,
+        } = self;
+        let mut event_builder = EventBuilderV10::new()
+            .id(id.to_string())
+            .source(format!(
+                "uri:iceberg-catalog-service:{}",
+                hostname::get()
+                    .map(|os| os.to_string_lossy().to_string())
+                    .unwrap_or("hostname-unavailable".into())
+            ))
+            .ty(typ)
+            .data("application/json", data);
+        // Distributed tracing extension of the CloudEvents spec
+        for (name, value) in trace_context {
+            event_builder = event_builder.extension(&name, value);
+        }
+
+        let event = match metadata {
+            PayloadMetadata::Tabular(EventMetadata {
+                tabular_id,
+                warehouse_id,
+                name,
+                namespace,
+                prefix,
+                num_events,
+                sequence_number,
+                trace_id,
+                actor,
+            }) => {
+                // TODO: this could be more elegant with a proc macro to give us IntoIter for EventMetadata
+                event_builder
+                    .extension("tabular-type", tabular_id.typ_str())
+                    .extension("tabular-id", tabular_id.to_string())
+                    .extension("warehouse-id", warehouse_id.to_string())
+                    .extension("name", name.to_string())
+                    .extension("namespace", namespace.to_string())
+                    .extension("prefix", prefix.to_string())
+                    .extension("num-events", i64::try_from(num_events).unwrap_or(i64::MAX))
+                    .extension(
+                        "sequence-number",
+                        i64::try_from(sequence_number).unwrap_or(i64::MAX),
+                    )
+                    .extension("trace-id", trace_id.to_string())
+                    .extension("actor", actor)
+            }
+            PayloadMetadata::Lifecycle(LifecycleEventMetadata {
+                project_id,
+                warehouse_id,
+                namespace_id,
+                namespace,
+                tabular_id,
+                trace_id,
+                actor,
+            }) => {
+                if let Some(project_id) = project_id {
+                    event_builder = event_builder.extension("project-id", project_id.to_string());
+                }
+                if let Some(warehouse_id) = warehouse_id {
+                    event_builder =
+                        event_builder.extension("warehouse-id", warehouse_id.to_string());
+                }
+                if let Some(namespace_id) = namespace_id {
+                    event_builder =
+                        event_builder.extension("namespace-id", namespace_id.to_string());
+                }
+                if let Some(namespace) = namespace {
+                    event_builder = event_builder.extension("namespace", namespace);
+                }
+                if let Some(tabular_id) = tabular_id {
+                    event_builder = event_builder
+                        .extension("tabular-type", tabular_id.typ_str())
+                        .extension("tabular-id", tabular_id.to_string());
+                }
+                event_builder
+                    .extension("trace-id", trace_id.to_string())
+                    .extension("actor", actor)
+            }
+        }
+        .build()?;
+        Ok(event)
+    }
 }
 
 #[derive(Debug)]
@@ -510,63 +1003,42 @@ pub struct CloudEventsPublisherBackgroundTask {
     pub sinks: Vec<Arc<dyn CloudEventBackend + Sync + Send>>,
 }
 
+/// Number of events buffered per sink. Events are dropped if a sink falls further behind.
+const SINK_QUEUE_CAPACITY: usize = 1000;
+/// Maximum number of queued events passed to [`CloudEventBackend::publish_batch`] at once.
+const SINK_BATCH_SIZE: usize = 100;
+
 impl CloudEventsPublisherBackgroundTask {
+    /// Every sink is served from its own queue, so that a slow sink, i.e. a webhook
+    /// retrying a failed delivery, does not delay the other sinks.
+    ///
     /// # Errors
     /// Returns an error if the `Event` cannot be built from the data passed into this function
     pub async fn publish(mut self) -> anyhow::Result<()> {
-        while let Some(CloudEventsMessage::Event(Payload {
-            id,
-            typ,
-            data,
-            metadata,
-        })) = self.source.recv().await
-        {
-            use cloudevents::{EventBuilder, EventBuilderV10};
-
-            let event_builder = EventBuilderV10::new()
-                .id(id.to_string())
-                .source(format!(
-                    "uri:iceberg-catalog-service:{}",
-                    hostname::get()
-                        .map(|os| os.to_string_lossy().to_string())
-                        .unwrap_or("hostname-unavailable".into())
-                ))
-                .ty(typ)
-                .data("application/json", data);
-
-            let EventMetadata {
-                tabular_id,
-                warehouse_id,
-                name,
-                namespace,
-                prefix,
-                num_events,
-                sequence_number,
-                trace_id,
-                actor,
-            } = metadata;
-            // TODO: this could be more elegant with a proc macro to give us IntoIter for EventMetadata
-            let event = event_builder
-                .extension("tabular-type", tabular_id.typ_str())
-                .extension("tabular-id", tabular_id.to_string())
-                .extension("warehouse-id", warehouse_id.to_string())
-                .extension("name", name.to_string())
-                .extension("namespace", namespace.to_string())
-                .extension("prefix", prefix.to_string())
-                .extension("num-events", i64::try_from(num_events).unwrap_or(i64::MAX))
-                .extension(
-                    "sequence-number",
-                    i64::try_from(sequence_number).unwrap_or(i64::MAX),
-                )
-                // Implement distributed tracing: https://github.com/lakekeeper/lakekeeper/issues/63
-                .extension("trace-id", trace_id.to_string())
-                .extension("actor", actor)
-                .build()?;
-
-            let publish_futures = self.sinks.iter().map(|sink| {
-                let event = event.clone();
-                async move {
-                    if let Err(e) = sink.publish(event).await {
+        let mut workers = tokio::task::JoinSet::new();
+        let queues = self
+            .sinks
+            .iter()
+            .map(|sink| {
+                let (tx, mut rx) = tokio::sync::mpsc::channel::<Event>(SINK_QUEUE_CAPACITY);
+                let worker_sink = sink.clone();
+                workers.spawn(async move {
+                    let mut batch = Vec::with_capacity(SINK_BATCH_SIZE);
+                    while rx.recv_many(&mut batch, SINK_BATCH_SIZE).await > 0 {
+                        worker_sink.publish_batch(std::mem::take(&mut batch)).await;
+                    }
+                });
+                (tx, sink.clone())
+            })
+            .collect::<Vec<_>>();
+
+        let result = async {
+            while let Some(CloudEventsMessage::Event(payload)) = self.source.recv().await {
+                let id = payload.id;
+                let event = payload.into_event()?;
+
+                for (queue, sink) in &queues {
+                    if let Err(e) = queue.try_send(event.clone()) {
                         tracing::warn!(
                             "Failed to emit event with id: '{}' on sink: '{}' due to: '{}'.",
                             id,
@@ -574,21 +1046,40 @@ impl CloudEventsPublisherBackgroundTask {
                             e
                         );
                     }
-                    Ok::<_, anyhow::Error>(())
                 }
-            });
-
-            // Run all publish operations concurrently
-            futures::future::join_all(publish_futures).await;
+            }
+            Ok(())
         }
+        .await;
 
-        Ok(())
+        // Deliver the queued events before returning
+        drop(queues);
+        while workers.join_next().await.is_some() {}
+
+        result
     }
 }
 
 #[async_trait]
-pub trait CloudEventBackend: Debug {
+pub trait CloudEventBackend: Debug + Send + Sync {
     async fn publish(&self, event: Event) -> anyhow::Result<()>;
+
+    /// Publish events that queued up while the sink was busy and log failures.
+    /// Sinks that can write multiple events at once should override this.
+    async fn publish_batch(&self, events: Vec<Event>) {
+        for event in events {
+            let id = event.id().to_string();
+            if let Err(e) = self.publish(event).await {
+                tracing::warn!(
+                    "Failed to emit event with id: '{}' on sink: '{}' due to: '{}'.",
+                    id,
+                    self.name(),
+                    e
+                );
+            }
+        }
+    }
+
     fn name(&self) -> &str;
 }
 
@@ -608,3 +1099,255 @@ impl CloudEventBackend for TracingPublisher {
         "tracing-publisher"
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Mutex, time::Duration};
+
+    use super::*;
+
+    /// Sink that never completes a delivery.
+    #[derive(Debug)]
+    struct StuckSink;
+
+    #[async_trait]
+    impl CloudEventBackend for StuckSink {
+        async fn publish(&self, _event: Event) -> anyhow::Result<()> {
+            std::future::pending().await
+        }
+
+        fn name(&self) -> &'static str {
+            "stuck"
+        }
+    }
+
+    #[derive(Debug, Default)]
+    struct RecordingSink(Mutex<Vec<Event>>);
+
+    #[async_trait]
+    impl CloudEventBackend for RecordingSink {
+        async fn publish(&self, event: Event) -> anyhow::Result<()> {
+            self.0.lock().unwrap().push(event);
+            Ok(())
+        }
+
+        fn name(&self) -> &'static str {
+            "recording"
+        }
+    }
+
+    fn payload() -> Payload {
+        Payload {
+            id: Uuid::now_v7(),
+            typ: "createWarehouse".to_string(),
+            data: serde_json::json!({}),
+            metadata: LifecycleEventMetadata {
+                project_id: None,
+                warehouse_id: None,
+                namespace_id: None,
+                namespace: None,
+                tabular_id: None,
+                trace_id: Uuid::now_v7(),
+                actor: "\"anonymous\"".to_string(),
+            }
+            .into(),
+            trace_context: HashMap::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_slow_sink_does_not_block_other_sinks() {
+        let recording = Arc::new(RecordingSink::default());
+        let (tx, rx) = tokio::sync::mpsc::channel(10);
+        let task = CloudEventsPublisherBackgroundTask {
+            source: rx,
+            sinks: vec![Arc::new(StuckSink), recording.clone()],
+        };
+        tokio::spawn(task.publish());
+
+        for _ in 0..3 {
+            tx.send(CloudEventsMessage::Event(payload())).await.unwrap();
+        }
+
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while recording.0.lock().unwrap().len() < 3 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("Events should be delivered while another sink is stuck");
+    }
+
+    async fn published_event(rx: &mut tokio::sync::mpsc::Receiver<CloudEventsMessage>) -> Event {
+        match rx.recv().await {
+            Some(CloudEventsMessage::Event(payload)) => payload.into_event().unwrap(),
+            other => panic!("Expected an event, got {other:?}"),
+        }
+    }
+
+    fn extension(event: &Event, name: &str) -> Option<String> {
+        event.extension(name).map(ToString::to_string)
+    }
+
+    fn data(event: &Event) -> serde_json::Value {
+        match event.data() {
+            Some(cloudevents::Data::Json(value)) => value.clone(),
+            other => panic!("Expected JSON data, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_namespace_event() {
+        let (tx, mut rx) = tokio::sync::mpsc::channel(10);
+        let publisher = CloudEventsPublisher::new(tx);
+        let warehouse_id = WarehouseId::new_random();
+        let namespace_id = NamespaceId::new_random();
+        let namespace =
+            iceberg::NamespaceIdent::from_vec(vec!["a".to_string(), "b".to_string()]).unwrap();
+
+        publisher
+            .create_namespace(
+                warehouse_id,
+                namespace_id,
+                Arc::new(CreateNamespaceResponse {
+                    namespace: namespace.clone(),
+                    properties: None,
+                }),
+                Arc::new(RequestMetadata::new_unauthenticated()),
+            )
+            .await
+            .unwrap();
+
+        let event = published_event(&mut rx).await;
+        assert_eq!(event.ty(), "createNamespace");
+        assert_eq!(data(&event)["namespace"], serde_json::json!(["a", "b"]));
+        assert_eq!(
+            extension(&event, "warehouse-id"),
+            Some(warehouse_id.to_string())
+        );
+        assert_eq!(
+            extension(&event, "namespace-id"),
+            Some(namespace_id.to_string())
+        );
+        assert_eq!(
+            extension(&event, "namespace"),
+            Some(namespace.to_url_string())
+        );
+        assert_eq!(extension(&event, "project-id"), None);
+    }
+
+    #[tokio::test]
+    async fn test_warehouse_events() {
+        let (tx, mut rx) = tokio::sync::mpsc::channel(10);
+        let publisher = CloudEventsPublisher::new(tx);
+        let warehouse_id = WarehouseId::new_random();
+        let request_metadata = Arc::new(RequestMetadata::new_unauthenticated());
+
+        publisher
+            .delete_warehouse(
+                warehouse_id,
+                DeleteWarehouseQuery { force: true },
+                request_metadata.clone(),
+            )
+            .await
+            .unwrap();
+        let event = published_event(&mut rx).await;
+        assert_eq!(event.ty(), "deleteWarehouse");
+        assert_eq!(data(&event), serde_json::json!({ "force": true }));
+        assert_eq!(
+            extension(&event, "warehouse-id"),
+            Some(warehouse_id.to_string())
+        );
+
+        publisher
+            .set_warehouse_status(
+                warehouse_id,
+                WarehouseStatus::Inactive,
+                request_metadata.clone(),
+            )
+            .await
+            .unwrap();
+        let event = published_event(&mut rx).await;
+        assert_eq!(event.ty(), "deactivateWarehouse");
+        assert_eq!(
+            extension(&event, "warehouse-id"),
+            Some(warehouse_id.to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rename_project_event() {
+        let (tx, mut rx) = tokio::sync::mpsc::channel(10);
+        let publisher = CloudEventsPublisher::new(tx);
+        let project_id = ProjectId::new_random();
+
+        publisher
+            .rename_project(
+                project_id.clone(),
+                Arc::new(RenameProjectRequest {
+                    new_name: "renamed".to_string(),
+                }),
+                Arc::new(RequestMetadata::new_unauthenticated()),
+            )
+            .await
+            .unwrap();
+
+        let event = published_event(&mut rx).await;
+        assert_eq!(event.ty(), "renameProject");
+        assert_eq!(data(&event), serde_json::json!({ "new-name": "renamed" }));
+        assert_eq!(
+            extension(&event, "project-id"),
+            Some(project_id.to_string())
+        );
+        assert_eq!(extension(&event, "warehouse-id"), None);
+    }
+
+    #[tokio::test]
+    async fn test_protection_events() {
+        let (tx, mut rx) = tokio::sync::mpsc::channel(10);
+        let publisher = CloudEventsPublisher::new(tx);
+        let warehouse_id = WarehouseId::new_random();
+        let namespace_id = NamespaceId::new_random();
+        let view_id = ViewId::new_random();
+        let request_metadata = Arc::new(RequestMetadata::new_unauthenticated());
+
+        publisher
+            .set_namespace_protection(warehouse_id, namespace_id, true, request_metadata.clone())
+            .await
+            .unwrap();
+        let event = published_event(&mut rx).await;
+        assert_eq!(event.ty(), "setNamespaceProtection");
+        assert_eq!(data(&event), serde_json::json!({ "protected": true }));
+        assert_eq!(
+            extension(&event, "namespace-id"),
+            Some(namespace_id.to_string())
+        );
+
+        publisher
+            .set_tabular_protection(
+                warehouse_id,
+                TabularId::View(view_id),
+                false,
+                request_metadata.clone(),
+            )
+            .await
+            .unwrap();
+        let event = published_event(&mut rx).await;
+        assert_eq!(event.ty(), "setViewProtection");
+        assert_eq!(data(&event), serde_json::json!({ "protected": false }));
+        assert_eq!(extension(&event, "tabular-type"), Some("View".to_string()));
+        assert_eq!(extension(&event, "tabular-id"), Some(view_id.to_string()));
+
+        publisher
+            .set_warehouse_protection(warehouse_id, true, request_metadata)
+            .await
+            .unwrap();
+        let event = published_event(&mut rx).await;
+        assert_eq!(event.ty(), "setWarehouseProtection");
+        assert_eq!(data(&event), serde_json::json!({ "protected": true }));
+        assert_eq!(
+            extension(&event, "warehouse-id"),
+            Some(warehouse_id.to_string())
+        );
+    }
+}
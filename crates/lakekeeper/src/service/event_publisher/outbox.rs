@@ -0,0 +1,286 @@
+//! Relay for the transactional `CloudEvents` outbox.
+//!
+//! Events are written to the outbox in the same transaction as the change they describe.
+//! Only `updateTable` events are written to the outbox, all other events are published
+//! from memory by the [`CloudEventsPublisherBackgroundTask`](super::CloudEventsPublisherBackgroundTask).
+//! The relay delivers them to every [`CloudEventBackend`] in order and tracks a separate
+//! cursor per sink, so that an unavailable sink neither loses events nor blocks other sinks.
+//!
+//! One replica relays to a sink at a time. It claims the sink with a lease in a short
+//! transaction, publishes the batch without holding a transaction open and advances the
+//! cursor in a second short transaction.
+//! Delivery is at-least-once: events may be published again if the relay stops between
+//! publishing an event and storing the cursor, or if publishing a batch outlasts the lease.
+use std::{sync::Arc, time::Duration};
+
+use axum_prometheus::metrics;
+use cloudevents::Event;
+
+use super::CloudEventBackend;
+use crate::{
+    service::{CatalogStore, OutboxEntry, Transaction},
+    CancellationToken, CONFIG,
+};
+
+const METRIC_PENDING_EVENTS: &str = "lakekeeper_cloud_events_outbox_pending_events";
+const METRIC_LAG_SECONDS: &str = "lakekeeper_cloud_events_outbox_lag_seconds";
+const METRIC_DELIVERED_EVENTS: &str = "lakekeeper_cloud_events_outbox_delivered_events_total";
+const METRIC_DELIVERY_FAILURES: &str = "lakekeeper_cloud_events_outbox_delivery_failures_total";
+
+pub struct CloudEventsOutboxRelay<C: CatalogStore> {
+    pub catalog_state: C::State,
+    pub sinks: Vec<Arc<dyn CloudEventBackend + Sync + Send>>,
+}
+
+impl<C: CatalogStore> CloudEventsOutboxRelay<C> {
+    /// Relay events to all sinks until `cancellation_token` is cancelled.
+    ///
+    /// # Errors
+    /// Errors of individual sinks or the catalog are logged and retried, they do not
+    /// stop the relay.
+    pub async fn run(self, cancellation_token: CancellationToken) -> anyhow::Result<()> {
+        if self.sinks.is_empty() {
+            tracing::warn!(
+                "CloudEvents outbox is enabled but no sinks are configured. Events are kept in the outbox until a sink is configured."
+            );
+        }
+
+        let relays = self.sinks.iter().map(|sink| {
+            relay_sink::<C>(
+                self.catalog_state.clone(),
+                sink.clone(),
+                cancellation_token.clone(),
+            )
+        });
+        let sink_names = self
+            .sinks
+            .iter()
+            .map(|sink| sink.name().to_string())
+            .collect::<Vec<_>>();
+
+        futures::future::join(
+            futures::future::join_all(relays),
+            maintain_outbox::<C>(self.catalog_state.clone(), sink_names, cancellation_token),
+        )
+        .await;
+
+        tracing::info!("CloudEvents outbox relay stopped");
+        Ok(())
+    }
+}
+
+async fn relay_sink<C: CatalogStore>(
+    catalog_state: C::State,
+    sink: Arc<dyn CloudEventBackend + Sync + Send>,
+    cancellation_token: CancellationToken,
+) {
+    let config = &CONFIG.cloud_events_outbox;
+    let lease_holder = uuid::Uuid::now_v7();
+    let mut consecutive_failures = 0u32;
+
+    while !cancellation_token.is_cancelled() {
+        let wait = match relay_batch::<C>(&catalog_state, sink.as_ref(), lease_holder).await {
+            Ok(relayed) => {
+                consecutive_failures = 0;
+                if usize::try_from(config.batch_size).is_ok_and(|b| relayed >= b) {
+                    // More events might be waiting
+                    continue;
+                }
+                config.poll_interval
+            }
+            Err(e) => {
+                consecutive_failures = consecutive_failures.saturating_add(1);
+                metrics::counter!(METRIC_DELIVERY_FAILURES, "sink" => sink.name().to_string())
+                    .increment(1);
+                let backoff = retry_interval(consecutive_failures, config.max_retry_interval);
+                tracing::warn!(
+                    sink = sink.name(),
+                    attempt = consecutive_failures,
+                    "Failed to relay CloudEvents from outbox, retrying in {backoff:?}: {e:#}"
+                );
+                backoff
+            }
+        };
+
+        tokio::select! {
+            () = cancellation_token.cancelled() => {},
+            () = tokio::time::sleep(wait) => {},
+        }
+    }
+
+    // Let other replicas take over without waiting for the lease to expire
+    if let Err(e) =
+        C::release_cloud_event_outbox_lease(sink.name(), lease_holder, catalog_state).await
+    {
+        tracing::warn!(
+            sink = sink.name(),
+            "Failed to release CloudEvents outbox lease: {}",
+            e.error
+        );
+    }
+}
+
+/// Publish the next batch of events to `sink` and advance its cursor past all
+/// events that were published successfully.
+///
+/// Returns the number of published events.
+async fn relay_batch<C: CatalogStore>(
+    catalog_state: &C::State,
+    sink: &(dyn CloudEventBackend + Sync + Send),
+    lease_holder: uuid::Uuid,
+) -> anyhow::Result<usize> {
+    let config = &CONFIG.cloud_events_outbox;
+    let mut transaction = C::Transaction::begin_write(catalog_state.clone())
+        .await
+        .map_err(|e| anyhow::anyhow!(e.error).context("Failed to start transaction"))?;
+    let entries = C::claim_cloud_event_outbox_batch(
+        sink.name(),
+        lease_holder,
+        config.lease_duration,
+        config.batch_size,
+        transaction.transaction(),
+    )
+    .await
+    .map_err(|e| anyhow::anyhow!(e.error).context("Failed to read outbox"))?;
+    transaction
+        .commit()
+        .await
+        .map_err(|e| anyhow::anyhow!(e.error).context("Failed to commit outbox lease"))?;
+    let Some(entries) = entries else {
+        // Another replica relays to this sink
+        return Ok(0);
+    };
+
+    // Publish outside of any transaction, so that a slow sink neither keeps a
+    // connection busy nor holds back the xmin horizon of other relays.
+    let mut last_published = None;
+    let mut published = 0usize;
+    let mut publish_result = Ok(());
+    for entry in &entries {
+        match publish_entry(sink, entry).await {
+            Ok(()) => {
+                last_published = Some(entry.cursor);
+                published += 1;
+            }
+            Err(e) => {
+                publish_result = Err(e);
+                break;
+            }
+        }
+    }
+
+    if let Some(cursor) = last_published {
+        let mut transaction = C::Transaction::begin_write(catalog_state.clone())
+            .await
+            .map_err(|e| anyhow::anyhow!(e.error).context("Failed to start transaction"))?;
+        let advanced = C::set_cloud_event_outbox_cursor(
+            sink.name(),
+            lease_holder,
+            cursor,
+            transaction.transaction(),
+        )
+        .await
+        .map_err(|e| anyhow::anyhow!(e.error).context("Failed to store outbox cursor"))?;
+        transaction
+            .commit()
+            .await
+            .map_err(|e| anyhow::anyhow!(e.error).context("Failed to commit outbox cursor"))?;
+        if !advanced {
+            tracing::warn!(
+                sink = sink.name(),
+                "CloudEvents outbox lease expired while publishing, another replica took over. Events may be delivered twice. Consider increasing the lease duration."
+            );
+        }
+    }
+    metrics::counter!(METRIC_DELIVERED_EVENTS, "sink" => sink.name().to_string())
+        .increment(u64::try_from(published).unwrap_or(u64::MAX));
+
+    publish_result.map(|()| published)
+}
+
+async fn publish_entry(
+    sink: &(dyn CloudEventBackend + Sync + Send),
+    entry: &OutboxEntry,
+) -> anyhow::Result<()> {
+    let event = match serde_json::from_value::<Event>(entry.event.clone()) {
+        Ok(event) => event,
+        Err(e) => {
+            // Retrying won't help, don't block the outbox
+            tracing::error!(
+                sink = sink.name(),
+                "Skipping invalid CloudEvent in outbox at {:?}: {e}",
+                entry.cursor
+            );
+            return Ok(());
+        }
+    };
+    let id = event.id().to_string();
+    sink.publish(event).await.map_err(|e| {
+        e.context(format!(
+            "Failed to publish event with id '{id}' on sink '{}'",
+            sink.name()
+        ))
+    })
+}
+
+/// Periodically delete events delivered to all sinks and export the outbox lag.
+async fn maintain_outbox<C: CatalogStore>(
+    catalog_state: C::State,
+    sink_names: Vec<String>,
+    cancellation_token: CancellationToken,
+) {
+    const MAINTENANCE_INTERVAL: Duration = Duration::from_secs(10);
+
+    while !cancellation_token.is_cancelled() {
+        if !sink_names.is_empty() {
+            match C::prune_cloud_event_outbox(&sink_names, catalog_state.clone()).await {
+                Ok(0) => {}
+                Ok(n) => tracing::debug!("Pruned {n} delivered events from CloudEvents outbox"),
+                Err(e) => tracing::warn!("Failed to prune CloudEvents outbox: {}", e.error),
+            }
+        }
+
+        match C::get_cloud_event_outbox_lag(&sink_names, catalog_state.clone()).await {
+            Ok(lags) => {
+                for lag in lags {
+                    #[allow(clippy::cast_precision_loss)]
+                    let pending = lag.pending_events as f64;
+                    #[allow(clippy::cast_precision_loss)]
+                    let lag_seconds = lag
+                        .oldest_pending_event_age
+                        .map_or(0., |age| age.num_milliseconds() as f64 / 1000.);
+                    metrics::gauge!(METRIC_PENDING_EVENTS, "sink" => lag.sink_name.clone())
+                        .set(pending);
+                    metrics::gauge!(METRIC_LAG_SECONDS, "sink" => lag.sink_name).set(lag_seconds);
+                }
+            }
+            Err(e) => tracing::warn!("Failed to determine CloudEvents outbox lag: {}", e.error),
+        }
+
+        tokio::select! {
+            () = cancellation_token.cancelled() => {},
+            () = tokio::time::sleep(MAINTENANCE_INTERVAL) => {},
+        }
+    }
+}
+
+/// Exponential backoff starting at one second, capped at `max`.
+fn retry_interval(consecutive_failures: u32, max: Duration) -> Duration {
+    let exp = consecutive_failures.saturating_sub(1).min(16);
+    Duration::from_secs(1u64 << exp).min(max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_interval() {
+        let max = Duration::from_secs(60);
+        assert_eq!(retry_interval(1, max), Duration::from_secs(1));
+        assert_eq!(retry_interval(2, max), Duration::from_secs(2));
+        assert_eq!(retry_interval(6, max), Duration::from_secs(32));
+        assert_eq!(retry_interval(7, max), max);
+        assert_eq!(retry_interval(u32::MAX, max), max);
+    }
+}
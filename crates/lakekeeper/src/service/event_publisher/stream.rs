@@ -0,0 +1,182 @@
+//! In-memory fan-out of `CloudEvents` to clients of the event stream endpoint.
+//!
+//! The stream is not a sink of the publisher. Every server replica feeds its stream with
+//! the events logged by all replicas, see [`crate::service::CatalogStore::stream_logged_events`].
+//! A bounded history of recent events allows clients to resume the stream after a reconnect,
+//! also on a different replica.
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+use cloudevents::{AttributesReader, Event};
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use crate::CONFIG;
+
+#[derive(Debug, Clone)]
+pub struct CloudEventStream {
+    inner: Arc<CloudEventStreamInner>,
+}
+
+#[derive(Debug)]
+struct CloudEventStreamInner {
+    sender: broadcast::Sender<Arc<Event>>,
+    history: Mutex<VecDeque<Arc<Event>>>,
+    history_size: usize,
+}
+
+impl Default for CloudEventStream {
+    fn default() -> Self {
+        Self::new(
+            CONFIG.event_stream.channel_capacity,
+            CONFIG.event_stream.history_size,
+        )
+    }
+}
+
+impl CloudEventStream {
+    #[must_use]
+    pub fn new(channel_capacity: usize, history_size: usize) -> Self {
+        let (sender, _) = broadcast::channel(channel_capacity.max(1));
+        Self {
+            inner: Arc::new(CloudEventStreamInner {
+                sender,
+                history: Mutex::new(VecDeque::with_capacity(history_size)),
+                history_size,
+            }),
+        }
+    }
+
+    /// Subscribe to all events published from now on.
+    ///
+    /// If `last_event_id` is given, the events of the history that were published after it
+    /// are returned as well. They should be sent to the client before any event
+    /// of the receiver.
+    #[must_use]
+    pub fn subscribe(
+        &self,
+        last_event_id: Option<&str>,
+    ) -> (Vec<Arc<Event>>, broadcast::Receiver<Arc<Event>>) {
+        // Hold the lock while subscribing so that no event is missed or sent twice
+        let history = self
+            .inner
+            .history
+            .lock()
+            .expect("Event stream history lock poisoned");
+        let receiver = self.inner.sender.subscribe();
+        let Some(last_event_id) = last_event_id else {
+            return (vec![], receiver);
+        };
+
+        let replay = if let Some(position) = history.iter().position(|e| e.id() == last_event_id) {
+            history.iter().skip(position + 1).cloned().collect()
+        } else if let Ok(last_event_id) = Uuid::parse_str(last_event_id) {
+            // The event is no longer part of the history. Event ids are time-ordered UUIDv7,
+            // so replay everything that was created after it.
+            history
+                .iter()
+                .filter(|e| Uuid::parse_str(e.id()).is_ok_and(|id| id > last_event_id))
+                .cloned()
+                .collect()
+        } else {
+            vec![]
+        };
+        (replay, receiver)
+    }
+
+    /// Send `event` to all subscribers.
+    ///
+    /// Events that are part of the history already are ignored, so that events
+    /// received again after a reconnect of the feeding listener are not sent twice.
+    pub fn publish(&self, event: Event) {
+        let mut history = self
+            .inner
+            .history
+            .lock()
+            .expect("Event stream history lock poisoned");
+        if history.iter().any(|e| e.id() == event.id()) {
+            return;
+        }
+        let event = Arc::new(event);
+        if self.inner.history_size > 0 {
+            if history.len() >= self.inner.history_size {
+                history.pop_front();
+            }
+            history.push_back(event.clone());
+        }
+        // Sending only fails if nobody is subscribed
+        let _ = self.inner.sender.send(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cloudevents::{EventBuilder, EventBuilderV10};
+
+    use super::*;
+
+    fn event() -> Event {
+        EventBuilderV10::new()
+            .id(Uuid::now_v7().to_string())
+            .source("uri:test")
+            .ty("updateTable")
+            .build()
+            .unwrap()
+    }
+
+    fn ids(events: &[Arc<Event>]) -> Vec<String> {
+        events.iter().map(|e| e.id().to_string()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_resumes_after_last_event_id() {
+        let stream = CloudEventStream::new(16, 2);
+        let events = [event(), event(), event()];
+        for e in &events {
+            stream.publish(e.clone());
+        }
+
+        let (replay, _) = stream.subscribe(None);
+        assert!(replay.is_empty());
+
+        let (replay, _) = stream.subscribe(Some(events[1].id()));
+        assert_eq!(ids(&replay), vec![events[2].id().to_string()]);
+
+        // Evicted from history, resume by id order
+        let (replay, _) = stream.subscribe(Some(events[0].id()));
+        assert_eq!(
+            ids(&replay),
+            vec![events[1].id().to_string(), events[2].id().to_string()]
+        );
+
+        let (replay, _) = stream.subscribe(Some("not-a-uuid"));
+        assert!(replay.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_subscribers_receive_new_events() {
+        let stream = CloudEventStream::new(16, 0);
+        let (_, mut receiver) = stream.subscribe(None);
+        let e = event();
+        stream.publish(e.clone());
+        assert_eq!(receiver.recv().await.unwrap().id(), e.id());
+
+        let (replay, _) = stream.subscribe(Some(e.id()));
+        assert!(replay.is_empty(), "History is disabled");
+    }
+
+    #[tokio::test]
+    async fn test_events_in_history_are_not_sent_twice() {
+        let stream = CloudEventStream::new(16, 2);
+        let (_, mut receiver) = stream.subscribe(None);
+        let (first, second) = (event(), event());
+        stream.publish(first.clone());
+        stream.publish(first.clone());
+        stream.publish(second.clone());
+        assert_eq!(receiver.recv().await.unwrap().id(), first.id());
+        assert_eq!(receiver.recv().await.unwrap().id(), second.id());
+        assert!(receiver.try_recv().is_err());
+    }
+}
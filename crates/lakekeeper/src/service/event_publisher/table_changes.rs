@@ -0,0 +1,386 @@
+//! Typed summary of the changes of a table commit, added to `updateTable` events
+//! if `cloud_events_table_changes` is enabled.
+//!
+//! The summary is derived from [`calculate_diffs`], so that consumers don't have to diff
+//! Iceberg metadata themselves.
+use std::collections::BTreeMap;
+
+use iceberg::spec::{
+    NestedField, Operation, PartitionField, Schema, Snapshot, TableMetadata, Type,
+};
+use serde::Serialize;
+
+use crate::server::tables::{calculate_diffs, CommitContext};
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct TableChanges {
+    pub previous_metadata_location: Option<String>,
+    pub metadata_location: String,
+    /// Set if the current schema changed.
+    pub schema: Option<SchemaChanges>,
+    /// Set if the default partition spec changed.
+    pub partition_spec: Option<PartitionSpecChanges>,
+    pub added_snapshots: Vec<SnapshotChange>,
+    pub removed_snapshots: Vec<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SchemaChanges {
+    pub previous_schema_id: i32,
+    pub schema_id: i32,
+    pub added_columns: Vec<Column>,
+    pub dropped_columns: Vec<Column>,
+    pub renamed_columns: Vec<RenamedColumn>,
+    pub type_changed_columns: Vec<TypeChangedColumn>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Column {
+    pub field_id: i32,
+    /// Full name of the column, nested fields are separated by `.`.
+    pub name: String,
+    #[serde(rename = "type")]
+    pub typ: String,
+    pub required: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct RenamedColumn {
+    pub field_id: i32,
+    pub previous_name: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct TypeChangedColumn {
+    pub field_id: i32,
+    pub name: String,
+    pub previous_type: String,
+    #[serde(rename = "type")]
+    pub typ: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct PartitionSpecChanges {
+    pub previous_spec_id: i32,
+    pub spec_id: i32,
+    pub added_fields: Vec<PartitionField>,
+    pub removed_fields: Vec<PartitionField>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SnapshotChange {
+    pub snapshot_id: i64,
+    pub parent_snapshot_id: Option<i64>,
+    pub operation: Operation,
+    pub timestamp_ms: i64,
+    pub added_data_files: Option<u64>,
+    pub deleted_data_files: Option<u64>,
+    pub added_delete_files: Option<u64>,
+    pub removed_delete_files: Option<u64>,
+    pub added_records: Option<u64>,
+    pub deleted_records: Option<u64>,
+    pub total_records: Option<u64>,
+}
+
+impl TableChanges {
+    #[must_use]
+    pub fn from_commit(commit: &CommitContext) -> Self {
+        let diffs = calculate_diffs(
+            &commit.new_metadata,
+            &commit.previous_metadata,
+            commit.number_added_metadata_log_entries,
+            commit.number_expired_metadata_log_entries,
+        );
+
+        let schema = diffs.new_current_schema_id.map(|_| {
+            schema_changes(
+                commit.previous_metadata.current_schema(),
+                commit.new_metadata.current_schema(),
+            )
+        });
+        let partition_spec = diffs
+            .default_partition_spec_id
+            .map(|_| partition_spec_changes(&commit.previous_metadata, &commit.new_metadata));
+
+        let mut added_snapshots = diffs
+            .added_snapshots
+            .iter()
+            .filter_map(|id| commit.new_metadata.snapshot_by_id(*id))
+            .map(|snapshot| SnapshotChange::from_snapshot(snapshot))
+            .collect::<Vec<_>>();
+        added_snapshots.sort_by_key(|s| (s.timestamp_ms, s.snapshot_id));
+        let mut removed_snapshots = diffs.removed_snapshots;
+        removed_snapshots.sort_unstable();
+
+        Self {
+            previous_metadata_location: commit
+                .previous_metadata_location
+                .as_ref()
+                .map(ToString::to_string),
+            metadata_location: commit.new_metadata_location.to_string(),
+            schema,
+            partition_spec,
+            added_snapshots,
+            removed_snapshots,
+        }
+    }
+}
+
+impl SnapshotChange {
+    fn from_snapshot(snapshot: &Snapshot) -> Self {
+        let summary = snapshot.summary();
+        let count = |key: &str| {
+            summary
+                .additional_properties
+                .get(key)
+                .and_then(|v| v.parse().ok())
+        };
+        Self {
+            snapshot_id: snapshot.snapshot_id(),
+            parent_snapshot_id: snapshot.parent_snapshot_id(),
+            operation: summary.operation.clone(),
+            timestamp_ms: snapshot.timestamp_ms(),
+            added_data_files: count("added-data-files"),
+            deleted_data_files: count("deleted-data-files"),
+            added_delete_files: count("added-delete-files"),
+            removed_delete_files: count("removed-delete-files"),
+            added_records: count("added-records"),
+            deleted_records: count("deleted-records"),
+            total_records: count("total-records"),
+        }
+    }
+}
+
+fn schema_changes(previous: &Schema, new: &Schema) -> SchemaChanges {
+    let previous_columns = columns_by_id(previous);
+    let new_columns = columns_by_id(new);
+
+    let mut changes = SchemaChanges {
+        previous_schema_id: previous.schema_id(),
+        schema_id: new.schema_id(),
+        added_columns: vec![],
+        dropped_columns: vec![],
+        renamed_columns: vec![],
+        type_changed_columns: vec![],
+    };
+
+    for (field_id, (name, field)) in &new_columns {
+        let Some((previous_name, previous_field)) = previous_columns.get(field_id) else {
+            changes
+                .added_columns
+                .push(Column::new(*field_id, name, field));
+            continue;
+        };
+        if previous_name != name {
+            changes.renamed_columns.push(RenamedColumn {
+                field_id: *field_id,
+                previous_name: previous_name.clone(),
+                name: name.clone(),
+            });
+        }
+        // Changes of nested types are reported on their fields
+        if let (Type::Primitive(previous_type), Type::Primitive(new_type)) =
+            (&*previous_field.field_type, &*field.field_type)
+        {
+            if previous_type != new_type {
+                changes.type_changed_columns.push(TypeChangedColumn {
+                    field_id: *field_id,
+                    name: name.clone(),
+                    previous_type: previous_type.to_string(),
+                    typ: new_type.to_string(),
+                });
+            }
+        }
+    }
+
+    changes.dropped_columns = previous_columns
+        .iter()
+        .filter(|(field_id, _)| !new_columns.contains_key(field_id))
+        .map(|(field_id, (name, field))| Column::new(*field_id, name, field))
+        .collect();
+
+    changes
+}
+
+impl Column {
+    fn new(field_id: i32, name: &str, field: &NestedField) -> Self {
+        Self {
+            field_id,
+            name: name.to_string(),
+            typ: field.field_type.to_string(),
+            required: field.required,
+        }
+    }
+}
+
+/// All fields of `schema` including nested ones, by field id.
+fn columns_by_id(schema: &Schema) -> BTreeMap<i32, (String, &NestedField)> {
+    fn visit<'a>(
+        prefix: Option<&str>,
+        field: &'a NestedField,
+        columns: &mut BTreeMap<i32, (String, &'a NestedField)>,
+    ) {
+        let name = prefix.map_or_else(
+            || field.name.clone(),
+            |prefix| format!("{prefix}.{}", field.name),
+        );
+        match &*field.field_type {
+            Type::Primitive(_) => {}
+            Type::Struct(s) => {
+                for child in s.fields() {
+                    visit(Some(&name), child, columns);
+                }
+            }
+            Type::List(l) => visit(Some(&name), &l.element_field, columns),
+            Type::Map(m) => {
+                visit(Some(&name), &m.key_field, columns);
+                visit(Some(&name), &m.value_field, columns);
+            }
+        }
+        columns.insert(field.id, (name, field));
+    }
+
+    let mut columns = BTreeMap::new();
+    for field in schema.as_struct().fields() {
+        visit(None, field, &mut columns);
+    }
+    columns
+}
+
+fn partition_spec_changes(previous: &TableMetadata, new: &TableMetadata) -> PartitionSpecChanges {
+    let previous_spec = previous.default_partition_spec();
+    let new_spec = new.default_partition_spec();
+    let contains = |fields: &[PartitionField], field: &PartitionField| {
+        fields.iter().any(|f| f.field_id == field.field_id)
+    };
+
+    PartitionSpecChanges {
+        previous_spec_id: previous_spec.spec_id(),
+        spec_id: new_spec.spec_id(),
+        added_fields: new_spec
+            .fields()
+            .iter()
+            .filter(|f| !contains(previous_spec.fields(), f))
+            .cloned()
+            .collect(),
+        removed_fields: previous_spec
+            .fields()
+            .iter()
+            .filter(|f| !contains(new_spec.fields(), f))
+            .cloned()
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iceberg::spec::{ListType, NestedField, PrimitiveType, StructType};
+
+    use super::*;
+
+    fn schema(schema_id: i32, fields: Vec<NestedField>) -> Schema {
+        Schema::builder()
+            .with_schema_id(schema_id)
+            .with_fields(fields.into_iter().map(Into::into))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_schema_changes() {
+        let previous = schema(
+            0,
+            vec![
+                NestedField::required(1, "id", Type::Primitive(PrimitiveType::Int)),
+                NestedField::optional(2, "name", Type::Primitive(PrimitiveType::String)),
+                NestedField::optional(
+                    3,
+                    "address",
+                    Type::Struct(StructType::new(vec![NestedField::optional(
+                        4,
+                        "street",
+                        Type::Primitive(PrimitiveType::String),
+                    )
+                    .into()])),
+                ),
+            ],
+        );
+        let new = schema(
+            1,
+            vec![
+                NestedField::required(1, "id", Type::Primitive(PrimitiveType::Long)),
+                NestedField::optional(
+                    3,
+                    "location",
+                    Type::Struct(StructType::new(vec![NestedField::optional(
+                        4,
+                        "street",
+                        Type::Primitive(PrimitiveType::String),
+                    )
+                    .into()])),
+                ),
+                NestedField::optional(
+                    5,
+                    "tags",
+                    Type::List(ListType::new(
+                        NestedField::list_element(6, Type::Primitive(PrimitiveType::String), true)
+                            .into(),
+                    )),
+                ),
+            ],
+        );
+
+        let changes = schema_changes(&previous, &new);
+        assert_eq!(changes.previous_schema_id, 0);
+        assert_eq!(changes.schema_id, 1);
+        assert_eq!(
+            changes
+                .added_columns
+                .iter()
+                .map(|c| (c.field_id, c.name.as_str()))
+                .collect::<Vec<_>>(),
+            vec![(5, "tags"), (6, "tags.element")]
+        );
+        assert_eq!(
+            changes.dropped_columns,
+            vec![Column {
+                field_id: 2,
+                name: "name".to_string(),
+                typ: "string".to_string(),
+                required: false,
+            }]
+        );
+        assert_eq!(
+            changes.renamed_columns,
+            vec![
+                RenamedColumn {
+                    field_id: 3,
+                    previous_name: "address".to_string(),
+                    name: "location".to_string(),
+                },
+                RenamedColumn {
+                    field_id: 4,
+                    previous_name: "address.street".to_string(),
+                    name: "location.street".to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            changes.type_changed_columns,
+            vec![TypeChangedColumn {
+                field_id: 1,
+                name: "id".to_string(),
+                previous_type: "int".to_string(),
+                typ: "long".to_string(),
+            }]
+        );
+    }
+}
@@ -0,0 +1,528 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use cloudevents::{AttributesReader, Data, Event};
+use hmac::{Hmac, Mac};
+use http::{HeaderMap, HeaderName, HeaderValue, StatusCode};
+use serde::{Deserialize, Deserializer, Serialize};
+use sha2::Sha256;
+use url::Url;
+use veil::Redact;
+
+use super::CloudEventBackend;
+use crate::CONFIG;
+
+/// Header containing the unix timestamp (seconds) at which the request was signed.
+pub const WEBHOOK_TIMESTAMP_HEADER: &str = "x-lakekeeper-timestamp";
+/// Header containing `sha256=<hex>`, the HMAC-SHA256 of `{timestamp}.{body}`
+/// keyed with the secret of the webhook.
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "x-lakekeeper-signature-256";
+
+const INITIAL_RETRY_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Creates a webhook publisher for every webhook in the crates configuration.
+///
+/// # Errors
+/// - If the HTTP client cannot be created.
+pub fn build_webhook_publishers_from_config() -> anyhow::Result<Vec<WebhookBackend>> {
+    CONFIG
+        .webhooks
+        .iter()
+        .map(|(name, config)| {
+            let backend = WebhookBackend::try_new(name, config.clone())?;
+            tracing::info!(
+                "Publishing events to webhook `{name}` at {} in {} mode",
+                config.url,
+                config.mode
+            );
+            Ok(backend)
+        })
+        .collect()
+}
+
+/// How events are encoded in the HTTP request, see the `CloudEvents` HTTP protocol binding.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, strum_macros::Display,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum WebhookContentMode {
+    /// The whole event is sent as `application/cloudevents+json` body.
+    #[default]
+    Structured,
+    /// Attributes are sent as `ce-` headers, the body contains the event data only.
+    Binary,
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Redact)]
+pub struct WebhookConfig {
+    pub url: Url,
+    #[serde(default)]
+    pub mode: WebhookContentMode,
+    /// Key to sign request bodies with. Requests are not signed if not set.
+    #[redact]
+    #[serde(default)]
+    pub secret: Option<String>,
+    /// Only send events of these types, i.e. `updateTable`. All events are sent if not set.
+    /// Specify multiple types as a comma-separated list.
+    #[serde(default, deserialize_with = "deserialize_comma_separated")]
+    pub event_types: Option<Vec<String>>,
+    /// Only send events of these warehouses. All events are sent if not set.
+    /// Specify multiple warehouses as a comma-separated list.
+    #[serde(default, deserialize_with = "deserialize_comma_separated")]
+    pub warehouse_ids: Option<Vec<uuid::Uuid>>,
+    /// Maximum number of attempts to deliver an event. (default: 5)
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: u32,
+    /// Timeout of a single request. (default: 10s)
+    #[serde(
+        default = "default_timeout",
+        deserialize_with = "crate::config::seconds_to_std_duration",
+        serialize_with = "crate::config::serialize_std_duration_as_ms"
+    )]
+    pub timeout: Duration,
+}
+
+fn default_max_attempts() -> u32 {
+    5
+}
+
+fn default_timeout() -> Duration {
+    Duration::from_secs(10)
+}
+
+fn deserialize_comma_separated<'de, D, T>(deserializer: D) -> Result<Option<Vec<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    let buf = Option::<serde_json::Value>::deserialize(deserializer)?;
+    buf.map(|buf| {
+        let items = match buf {
+            serde_json::Value::String(s) => s
+                .split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+                .collect::<Vec<_>>(),
+            serde_json::Value::Array(items) => items
+                .into_iter()
+                .map(|v| match v {
+                    serde_json::Value::String(s) => Ok(s),
+                    _ => Err(serde::de::Error::custom("Expected a list of strings")),
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+            _ => return Err(serde::de::Error::custom("Expected a string")),
+        };
+        items
+            .iter()
+            .map(|s| T::from_str(s).map_err(serde::de::Error::custom))
+            .collect()
+    })
+    .transpose()
+}
+
+#[derive(Debug)]
+pub struct WebhookBackend {
+    name: String,
+    client: reqwest::Client,
+    config: WebhookConfig,
+}
+
+impl WebhookBackend {
+    /// # Errors
+    /// - If the HTTP client cannot be created.
+    pub fn try_new(name: &str, config: WebhookConfig) -> anyhow::Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(config.timeout)
+            .build()
+            .map_err(|e| anyhow::anyhow!(e).context("Failed to create webhook HTTP client"))?;
+        Ok(Self {
+            name: format!("webhook-{name}"),
+            client,
+            config,
+        })
+    }
+
+    fn is_subscribed(&self, event: &Event) -> bool {
+        let type_matches = self
+            .config
+            .event_types
+            .as_ref()
+            .is_none_or(|types| types.iter().any(|t| t == event.ty()));
+        let warehouse_matches = self.config.warehouse_ids.as_ref().is_none_or(|ids| {
+            event
+                .extension("warehouse-id")
+                .and_then(|id| uuid::Uuid::parse_str(&id.to_string()).ok())
+                .is_some_and(|id| ids.contains(&id))
+        });
+        type_matches && warehouse_matches
+    }
+
+    fn encode(&self, event: &Event) -> anyhow::Result<(HeaderMap, Vec<u8>)> {
+        match self.config.mode {
+            WebhookContentMode::Structured => {
+                let mut headers = HeaderMap::new();
+                headers.insert(
+                    http::header::CONTENT_TYPE,
+                    HeaderValue::from_static("application/cloudevents+json"),
+                );
+                Ok((headers, serde_json::to_vec(event)?))
+            }
+            WebhookContentMode::Binary => encode_binary(event),
+        }
+    }
+
+    fn sign(&self, headers: &mut HeaderMap, body: &[u8]) -> anyhow::Result<()> {
+        let Some(secret) = &self.config.secret else {
+            return Ok(());
+        };
+        let timestamp = chrono::Utc::now().timestamp().to_string();
+        headers.insert(WEBHOOK_TIMESTAMP_HEADER, HeaderValue::from_str(&timestamp)?);
+        headers.insert(
+            WEBHOOK_SIGNATURE_HEADER,
+            HeaderValue::from_str(&format!(
+                "sha256={}",
+                signature(secret.as_bytes(), &timestamp, body)
+            ))?,
+        );
+        Ok(())
+    }
+}
+
+/// Hex encoded HMAC-SHA256 of `{timestamp}.{body}`.
+fn signature(secret: &[u8], timestamp: &str, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC can take keys of any size");
+    mac.update(timestamp.as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    mac.finalize()
+        .into_bytes()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+fn encode_binary(event: &Event) -> anyhow::Result<(HeaderMap, Vec<u8>)> {
+    let mut headers = HeaderMap::new();
+    for (name, value) in event.iter() {
+        if name == "datacontenttype" {
+            headers.insert(
+                http::header::CONTENT_TYPE,
+                HeaderValue::from_str(&value.to_string())?,
+            );
+        } else {
+            headers.insert(
+                HeaderName::try_from(format!("ce-{name}"))?,
+                HeaderValue::from_str(&percent_encode_header(&value.to_string()))?,
+            );
+        }
+    }
+    let body = match event.data() {
+        Some(Data::Binary(bytes)) => bytes.clone(),
+        Some(Data::String(s)) => s.clone().into_bytes(),
+        Some(Data::Json(json)) => serde_json::to_vec(json)?,
+        None => vec![],
+    };
+    Ok((headers, body))
+}
+
+/// Percent-encode characters that are not allowed in `ce-` header values.
+fn percent_encode_header(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for b in value.bytes() {
+        if (0x21..=0x7E).contains(&b) && b != b'"' && b != b'%' {
+            encoded.push(char::from(b));
+        } else {
+            encoded.push_str(&format!("%{b:02X}"));
+        }
+    }
+    encoded
+}
+
+fn is_retryable(status: StatusCode) -> bool {
+    status.is_server_error()
+        || status == StatusCode::TOO_MANY_REQUESTS
+        || status == StatusCode::REQUEST_TIMEOUT
+}
+
+#[async_trait]
+impl CloudEventBackend for WebhookBackend {
+    async fn publish(&self, event: Event) -> anyhow::Result<()> {
+        if !self.is_subscribed(&event) {
+            return Ok(());
+        }
+
+        let (headers, body) = self.encode(&event)?;
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            // Sign every attempt, so that the timestamp is recent
+            let mut headers = headers.clone();
+            self.sign(&mut headers, &body)?;
+
+            let result = self
+                .client
+                .post(self.config.url.clone())
+                .headers(headers)
+                .body(body.clone())
+                .send()
+                .await;
+            let error = match result {
+                Ok(response) if response.status().is_success() => {
+                    tracing::debug!(
+                        "CloudEvents event {} sent to webhook {}",
+                        event.id(),
+                        self.name
+                    );
+                    return Ok(());
+                }
+                Ok(response) if !is_retryable(response.status()) => {
+                    return Err(anyhow::anyhow!(
+                        "Webhook {} rejected event {} with status {}",
+                        self.name,
+                        event.id(),
+                        response.status()
+                    ));
+                }
+                Ok(response) => anyhow::anyhow!("Webhook responded with {}", response.status()),
+                Err(e) => anyhow::anyhow!(e),
+            };
+
+            if attempt >= self.config.max_attempts {
+                return Err(error.context(format!(
+                    "Failed to send event {} to webhook {} after {attempt} attempts",
+                    event.id(),
+                    self.name
+                )));
+            }
+            let backoff = INITIAL_RETRY_INTERVAL * 2u32.pow(attempt.min(10) - 1);
+            let jitter = Duration::from_millis(fastrand::u64(..=100));
+            tracing::debug!(
+                "Failed to send event {} to webhook {} (attempt {attempt}), retrying in {backoff:?}: {error:#}",
+                event.id(),
+                self.name
+            );
+            tokio::time::sleep(backoff + jitter).await;
+        }
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use axum::{body::Bytes, extract::State, routing::post, Router};
+    use cloudevents::{EventBuilder, EventBuilderV10};
+
+    use super::*;
+
+    type Received = Arc<Mutex<Vec<(HeaderMap, Bytes)>>>;
+
+    /// Local stand-in for a webhook receiver.
+    /// Responds with `503` to the first `fail_first` requests.
+    async fn serve_receiver(fail_first: usize) -> (Url, Received) {
+        let received = Received::default();
+        let app = Router::new()
+            .route(
+                "/hook",
+                post(
+                    |State((received, fail_first)): State<(Received, usize)>,
+                     headers: HeaderMap,
+                     body: Bytes| async move {
+                        let mut received = received.lock().unwrap();
+                        received.push((headers, body));
+                        if received.len() <= fail_first {
+                            StatusCode::SERVICE_UNAVAILABLE
+                        } else {
+                            StatusCode::NO_CONTENT
+                        }
+                    },
+                ),
+            )
+            .with_state((received.clone(), fail_first));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (format!("http://{addr}/hook").parse().unwrap(), received)
+    }
+
+    fn config(url: Url) -> WebhookConfig {
+        WebhookConfig {
+            url,
+            mode: WebhookContentMode::Structured,
+            secret: None,
+            event_types: None,
+            warehouse_ids: None,
+            max_attempts: 3,
+            timeout: Duration::from_secs(5),
+        }
+    }
+
+    fn event(typ: &str, warehouse_id: uuid::Uuid) -> Event {
+        EventBuilderV10::new()
+            .id("42")
+            .source("uri:test")
+            .ty(typ)
+            .data("application/json", serde_json::json!({"foo": "bar"}))
+            .extension("warehouse-id", warehouse_id.to_string())
+            .extension("name", "my täble")
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_structured_mode_is_signed() {
+        let (url, received) = serve_receiver(0).await;
+        let backend = WebhookBackend::try_new(
+            "test",
+            WebhookConfig {
+                secret: Some("my-secret".to_string()),
+                ..config(url)
+            },
+        )
+        .unwrap();
+        assert_eq!(backend.name(), "webhook-test");
+
+        let event = event("updateTable", uuid::Uuid::now_v7());
+        backend.publish(event.clone()).await.unwrap();
+
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 1);
+        let (headers, body) = &received[0];
+        assert_eq!(
+            headers[http::header::CONTENT_TYPE],
+            "application/cloudevents+json"
+        );
+        assert_eq!(serde_json::from_slice::<Event>(body).unwrap(), event);
+        let timestamp = headers[WEBHOOK_TIMESTAMP_HEADER].to_str().unwrap();
+        assert_eq!(
+            headers[WEBHOOK_SIGNATURE_HEADER].to_str().unwrap(),
+            format!("sha256={}", signature(b"my-secret", timestamp, body))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_binary_mode() {
+        let (url, received) = serve_receiver(0).await;
+        let backend = WebhookBackend::try_new(
+            "test",
+            WebhookConfig {
+                mode: WebhookContentMode::Binary,
+                ..config(url)
+            },
+        )
+        .unwrap();
+
+        let warehouse_id = uuid::Uuid::now_v7();
+        backend
+            .publish(event("updateTable", warehouse_id))
+            .await
+            .unwrap();
+
+        let received = received.lock().unwrap();
+        let (headers, body) = &received[0];
+        assert_eq!(headers[http::header::CONTENT_TYPE], "application/json");
+        assert_eq!(headers["ce-id"], "42");
+        assert_eq!(headers["ce-type"], "updateTable");
+        assert_eq!(headers["ce-specversion"], "1.0");
+        assert_eq!(
+            headers["ce-warehouse-id"],
+            warehouse_id.to_string().as_str()
+        );
+        assert_eq!(headers["ce-name"], "my%20t%C3%A4ble");
+        assert!(!headers.contains_key(WEBHOOK_SIGNATURE_HEADER));
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(body).unwrap(),
+            serde_json::json!({"foo": "bar"})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retries_until_success() {
+        let (url, received) = serve_receiver(2).await;
+        let backend = WebhookBackend::try_new("test", config(url)).unwrap();
+
+        backend
+            .publish(event("updateTable", uuid::Uuid::now_v7()))
+            .await
+            .unwrap();
+        assert_eq!(received.lock().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_attempts() {
+        let (url, received) = serve_receiver(usize::MAX).await;
+        let backend = WebhookBackend::try_new(
+            "test",
+            WebhookConfig {
+                max_attempts: 2,
+                ..config(url)
+            },
+        )
+        .unwrap();
+
+        assert!(backend
+            .publish(event("updateTable", uuid::Uuid::now_v7()))
+            .await
+            .is_err());
+        assert_eq!(received.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_subscription_filters() {
+        let (url, received) = serve_receiver(0).await;
+        let warehouse_id = uuid::Uuid::now_v7();
+        let backend = WebhookBackend::try_new(
+            "test",
+            WebhookConfig {
+                event_types: Some(vec!["dropTable".to_string()]),
+                warehouse_ids: Some(vec![warehouse_id]),
+                ..config(url)
+            },
+        )
+        .unwrap();
+
+        backend
+            .publish(event("updateTable", warehouse_id))
+            .await
+            .unwrap();
+        backend
+            .publish(event("dropTable", uuid::Uuid::now_v7()))
+            .await
+            .unwrap();
+        assert!(received.lock().unwrap().is_empty());
+
+        backend
+            .publish(event("dropTable", warehouse_id))
+            .await
+            .unwrap();
+        assert_eq!(received.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_deserialize_config() {
+        let config: WebhookConfig = serde_json::from_value(serde_json::json!({
+            "url": "https://example.com/hook",
+            "mode": "binary",
+            "event_types": "updateTable, dropTable",
+            "warehouse_ids": "0195a3c6-7f21-7d3e-a8f2-5b1c9d0e4f17",
+            "timeout": "500ms",
+        }))
+        .unwrap();
+        assert_eq!(config.mode, WebhookContentMode::Binary);
+        assert_eq!(
+            config.event_types,
+            Some(vec!["updateTable".to_string(), "dropTable".to_string()])
+        );
+        assert_eq!(config.warehouse_ids.unwrap().len(), 1);
+        assert_eq!(config.timeout, Duration::from_millis(500));
+        assert_eq!(config.max_attempts, 5);
+        assert!(config.secret.is_none());
+    }
+}
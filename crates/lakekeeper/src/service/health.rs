@@ -54,7 +54,7 @@ pub struct Health {
 
 impl Health {
     #[must_use]
-    pub fn now(name: &'static str, status: HealthStatus) -> Self {
+    pub fn now(name: impl Into<String>, status: HealthStatus) -> Self {
         Self {
             name: name.into(),
             checked_at: chrono::Utc::now(),
@@ -155,6 +155,7 @@ define_id_type!(ViewId, true);
 define_id_type!(TableId, true);
 define_id_type!(NamespaceId, true);
 define_id_type!(RoleId, true);
+define_id_type!(AccessRequestId, true);
 
 impl TryFrom<Prefix> for WarehouseId {
     type Error = ErrorModel;
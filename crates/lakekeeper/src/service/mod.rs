@@ -9,6 +9,8 @@ pub mod health;
 pub mod secrets;
 pub mod storage;
 pub mod tasks;
+use std::sync::Arc;
+
 pub use authn::{Actor, UserId};
 pub use catalog_store::*;
 pub use endpoint_statistics::EndpointStatisticsTrackerTx;
@@ -22,7 +24,10 @@ use self::authz::Authorizer;
 pub use crate::api::{ErrorModel, IcebergErrorResponse};
 use crate::{
     api::{management::v1::server::LicenseStatus, ThreadSafe as ServiceState},
-    service::{contract_verification::ContractVerifiers, endpoint_hooks::EndpointHookCollection},
+    service::{
+        contract_verification::ContractVerifiers, endpoint_hooks::EndpointHookCollection,
+        event_publisher::stream::CloudEventStream,
+    },
 };
 
 mod identifier;
@@ -37,7 +42,10 @@ pub struct State<A: Authorizer + Clone, C: CatalogStore, S: SecretStore> {
     pub secrets: S,
     pub contract_verifiers: ContractVerifiers,
     pub hooks: EndpointHookCollection,
+    pub event_stream: CloudEventStream,
     pub registered_task_queues: RegisteredTaskQueues,
+    /// Names of the `CloudEvent` sinks a backfill can publish to.
+    pub cloud_event_backfill_sinks: Arc<[String]>,
     pub license_status: &'static LicenseStatus,
 }
 
@@ -1,7 +1,12 @@
 use async_trait::async_trait;
-use serde::{de::DeserializeOwned, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
-use crate::{api::Result, service::health::HealthExt};
+use crate::{
+    api::{ErrorModel, Result},
+    config::SecretReferencesConfig,
+    service::health::HealthExt,
+    ProjectId, CONFIG,
+};
 
 /// Interface for Handling Secrets.
 #[async_trait]
@@ -74,3 +79,397 @@ pub struct Secret<T> {
 
 // Prohibits us to store unwanted types in the storage.
 pub trait SecretInStorage {}
+
+/// Reference to a secret that is not stored by Lakekeeper, but read from a mounted file or
+/// an environment variable whenever it is used. Only the reference is stored in the secret store,
+/// so the referenced secret can be rotated without involving Lakekeeper.
+///
+/// References are scoped to the project they are used in, so that a project cannot
+/// read the secrets of another project.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(tag = "source", rename_all = "kebab-case")]
+pub enum SecretReference {
+    /// File `<project-id>/<name>` in the directory configured in `secret_references.directory`.
+    #[schema(title = "SecretReferenceFile")]
+    File { name: String },
+    /// Environment variable `<project-id>__<name>`, prefixed with `secret_references.env_prefix`.
+    /// `name` must not start with an underscore or contain two consecutive underscores.
+    #[schema(title = "SecretReferenceEnv")]
+    Env { name: String },
+}
+
+impl std::fmt::Display for SecretReference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::File { name } => write!(f, "file:{name}"),
+            Self::Env { name } => write!(f, "env:{name}"),
+        }
+    }
+}
+
+/// Value of the `type` field of secrets that are stored as a [`SecretReference`].
+pub(crate) const SECRET_REFERENCE_TYPE: &str = "reference";
+
+#[derive(thiserror::Error, Debug)]
+pub enum SecretReferenceError {
+    #[error("Secret references of source `{0}` are not enabled.")]
+    SourceDisabled(&'static str),
+    #[error("Invalid name of referenced secret `{0}`.")]
+    InvalidName(String),
+    #[error("Referenced secret `{reference}` could not be read: {reason}")]
+    Unreadable { reference: String, reason: String },
+    #[error("Referenced secret `{reference}` is not valid JSON: {source}")]
+    InvalidJson {
+        reference: String,
+        source: serde_json::Error,
+    },
+    #[error("Referenced secret `{0}` is a reference itself.")]
+    Nested(String),
+}
+
+impl SecretReferenceError {
+    /// Error for a reference that is supplied by the caller.
+    #[must_use]
+    pub fn into_bad_request(self) -> ErrorModel {
+        ErrorModel::bad_request(
+            self.to_string(),
+            "InvalidSecretReference",
+            Some(Box::new(self)),
+        )
+    }
+
+    /// Error for a reference that was accepted before, i.e. one that is read from the secret store.
+    #[must_use]
+    pub fn into_internal(self) -> ErrorModel {
+        ErrorModel::internal(
+            self.to_string(),
+            "SecretReferenceResolutionFailed",
+            Some(Box::new(self)),
+        )
+    }
+}
+
+impl SecretReference {
+    /// Read the referenced secret of `project_id`.
+    ///
+    /// # Errors
+    /// - The source of the reference is not enabled.
+    /// - The name could point outside of the secrets of the project.
+    /// - The secret cannot be read, is not valid JSON or is a reference itself.
+    pub async fn resolve(
+        &self,
+        project_id: &ProjectId,
+    ) -> Result<serde_json::Value, SecretReferenceError> {
+        self.resolve_with(&CONFIG.secret_references, project_id)
+            .await
+    }
+
+    async fn resolve_with(
+        &self,
+        config: &SecretReferencesConfig,
+        project_id: &ProjectId,
+    ) -> Result<serde_json::Value, SecretReferenceError> {
+        let content = match self {
+            Self::File { name } => {
+                let directory = config
+                    .directory
+                    .as_ref()
+                    .ok_or(SecretReferenceError::SourceDisabled("file"))?;
+                if name.is_empty() || name == "." || name == ".." || name.contains(['/', '\\']) {
+                    return Err(SecretReferenceError::InvalidName(name.clone()));
+                }
+                let path = directory.join(project_id.as_str()).join(name);
+                tokio::fs::read_to_string(&path).await.map_err(|e| {
+                    SecretReferenceError::Unreadable {
+                        reference: self.to_string(),
+                        reason: e.to_string(),
+                    }
+                })?
+            }
+            Self::Env { name } => {
+                let prefix = config
+                    .env_prefix
+                    .as_ref()
+                    .ok_or(SecretReferenceError::SourceDisabled("env"))?;
+                // Names must not start with or contain the separator `__`, so that
+                // a name can never address the variable of another project.
+                if name.is_empty()
+                    || name.starts_with('_')
+                    || name.contains("__")
+                    || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+                {
+                    return Err(SecretReferenceError::InvalidName(name.clone()));
+                }
+                std::env::var(format!("{prefix}{project_id}__{name}")).map_err(|e| {
+                    SecretReferenceError::Unreadable {
+                        reference: self.to_string(),
+                        reason: e.to_string(),
+                    }
+                })?
+            }
+        };
+
+        let value: serde_json::Value =
+            serde_json::from_str(&content).map_err(|source| SecretReferenceError::InvalidJson {
+                reference: self.to_string(),
+                source,
+            })?;
+        if is_secret_reference(&value) {
+            return Err(SecretReferenceError::Nested(self.to_string()));
+        }
+        Ok(value)
+    }
+}
+
+/// Secret as stored in a secret store, which may be a [`SecretReference`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(transparent)]
+pub(crate) struct StoredSecret(pub(crate) serde_json::Value);
+
+impl SecretInStorage for StoredSecret {}
+
+/// A [`SecretReference`] as stored in a secret store, together with the project
+/// it was created in. The project is set by the server, never by the caller.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct ScopedSecretReference {
+    project_id: ProjectId,
+    #[serde(flatten)]
+    reference: SecretReference,
+}
+
+impl StoredSecret {
+    /// Prepare `secret` of `project_id` for storage.
+    /// If it is a [`SecretReference`], it is scoped to `project_id`.
+    ///
+    /// # Errors
+    /// Fails if the secret cannot be serialized.
+    pub(crate) fn scoped<S: Serialize>(secret: &S, project_id: &ProjectId) -> Result<Self> {
+        let mut value = serde_json::to_value(secret).map_err(|e| {
+            ErrorModel::internal(
+                "Failed to serialize secret",
+                "SecretSerializationFailed",
+                Some(Box::new(e)),
+            )
+        })?;
+        if is_secret_reference(&value) {
+            if let Some(object) = value.as_object_mut() {
+                object.insert(
+                    "project-id".to_string(),
+                    serde_json::Value::String(project_id.to_string()),
+                );
+            }
+        }
+        Ok(Self(value))
+    }
+
+    /// Resolve the secret if it is a reference and deserialize it.
+    ///
+    /// # Errors
+    /// Fails if the reference cannot be resolved or the secret is not of type `S`.
+    pub(crate) async fn resolve<S: DeserializeOwned>(self) -> Result<S> {
+        let value = if is_secret_reference(&self.0) {
+            let ScopedSecretReference {
+                project_id,
+                reference,
+            } = serde_json::from_value(self.0).map_err(|e| {
+                ErrorModel::internal(
+                    "Stored secret reference is invalid",
+                    "SecretReadFailed",
+                    Some(Box::new(e)),
+                )
+            })?;
+            reference
+                .resolve(&project_id)
+                .await
+                .map_err(SecretReferenceError::into_internal)?
+        } else {
+            self.0
+        };
+
+        Ok(serde_json::from_value(value).map_err(|e| {
+            ErrorModel::internal(
+                "Failed to deserialize secret",
+                "SecretReadFailed",
+                Some(Box::new(e)),
+            )
+        })?)
+    }
+}
+
+fn is_secret_reference(value: &serde_json::Value) -> bool {
+    value.get("type").and_then(serde_json::Value::as_str) == Some(SECRET_REFERENCE_TYPE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secret_reference_serde() {
+        let reference: SecretReference =
+            serde_json::from_value(serde_json::json!({"source": "file", "name": "aws-prod"}))
+                .unwrap();
+        assert_eq!(
+            reference,
+            SecretReference::File {
+                name: "aws-prod".to_string()
+            }
+        );
+        assert!(is_secret_reference(&serde_json::json!({
+            "type": "reference",
+            "source": "env",
+            "name": "AWS_PROD"
+        })));
+        assert!(!is_secret_reference(&serde_json::json!({"type": "s3"})));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_file_reference() {
+        let project_id = ProjectId::new_random();
+        let directory = tempfile::tempdir().unwrap();
+        let project_directory = directory.path().join(project_id.as_str());
+        std::fs::create_dir(&project_directory).unwrap();
+        std::fs::write(
+            project_directory.join("aws-prod"),
+            r#"{"type": "s3", "credential-type": "access-key"}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            project_directory.join("nested"),
+            r#"{"type": "reference", "source": "file", "name": "aws-prod"}"#,
+        )
+        .unwrap();
+        let config = SecretReferencesConfig {
+            directory: Some(directory.path().to_path_buf()),
+            env_prefix: None,
+        };
+
+        let file = |name: &str| SecretReference::File {
+            name: name.to_string(),
+        };
+        let value = file("aws-prod")
+            .resolve_with(&config, &project_id)
+            .await
+            .unwrap();
+        assert_eq!(value["type"], "s3");
+
+        for name in ["", "..", "../aws-prod", "a/b"] {
+            assert!(matches!(
+                file(name).resolve_with(&config, &project_id).await,
+                Err(SecretReferenceError::InvalidName(_))
+            ));
+        }
+        assert!(matches!(
+            file("missing").resolve_with(&config, &project_id).await,
+            Err(SecretReferenceError::Unreadable { .. })
+        ));
+        assert!(matches!(
+            file("nested").resolve_with(&config, &project_id).await,
+            Err(SecretReferenceError::Nested(_))
+        ));
+        assert!(matches!(
+            SecretReference::Env {
+                name: "AWS_PROD".to_string()
+            }
+            .resolve_with(&config, &project_id)
+            .await,
+            Err(SecretReferenceError::SourceDisabled("env"))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_cross_project_reference_is_rejected() {
+        let project_id = ProjectId::try_new("project-a".to_string()).unwrap();
+        let other_project_id = ProjectId::try_new("project-b".to_string()).unwrap();
+        let directory = tempfile::tempdir().unwrap();
+        let other_project_directory = directory.path().join(other_project_id.as_str());
+        std::fs::create_dir(&other_project_directory).unwrap();
+        std::fs::write(
+            other_project_directory.join("aws-prod"),
+            r#"{"type": "s3"}"#,
+        )
+        .unwrap();
+        let config = SecretReferencesConfig {
+            directory: Some(directory.path().to_path_buf()),
+            env_prefix: Some("LAKEKEEPER_TEST_SECRET_REFERENCE_".to_string()),
+        };
+        std::env::set_var(
+            "LAKEKEEPER_TEST_SECRET_REFERENCE_project-b__AWS_PROD",
+            r#"{"type": "s3"}"#,
+        );
+
+        let file = |name: &str| SecretReference::File {
+            name: name.to_string(),
+        };
+        let env = |name: &str| SecretReference::Env {
+            name: name.to_string(),
+        };
+        file("aws-prod")
+            .resolve_with(&config, &other_project_id)
+            .await
+            .unwrap();
+        env("AWS_PROD")
+            .resolve_with(&config, &other_project_id)
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            file("aws-prod").resolve_with(&config, &project_id).await,
+            Err(SecretReferenceError::Unreadable { .. })
+        ));
+        assert!(matches!(
+            file("../project-b/aws-prod")
+                .resolve_with(&config, &project_id)
+                .await,
+            Err(SecretReferenceError::InvalidName(_))
+        ));
+        assert!(matches!(
+            env("AWS_PROD").resolve_with(&config, &project_id).await,
+            Err(SecretReferenceError::Unreadable { .. })
+        ));
+        for name in ["_AWS_PROD", "B__AWS_PROD"] {
+            assert!(matches!(
+                env(name).resolve_with(&config, &project_id).await,
+                Err(SecretReferenceError::InvalidName(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_stored_reference_is_scoped_to_project() {
+        let project_id = ProjectId::new_random();
+        let reference =
+            crate::service::storage::StorageCredential::Reference(SecretReference::File {
+                name: "aws-prod".to_string(),
+            });
+        let stored = StoredSecret::scoped(&reference, &project_id).unwrap();
+        let scoped = serde_json::from_value::<ScopedSecretReference>(stored.0).unwrap();
+        assert_eq!(scoped.project_id, project_id);
+        assert_eq!(
+            scoped.reference,
+            SecretReference::File {
+                name: "aws-prod".to_string()
+            }
+        );
+
+        // A project supplied by the caller is ignored
+        let reference: crate::service::storage::StorageCredential =
+            serde_json::from_value(serde_json::json!({
+                "type": "reference",
+                "source": "env",
+                "name": "AWS_PROD",
+                "project-id": "other-project"
+            }))
+            .unwrap();
+        let stored = StoredSecret::scoped(&reference, &project_id).unwrap();
+        assert_eq!(stored.0["project-id"], project_id.to_string());
+    }
+
+    #[tokio::test]
+    async fn test_stored_secret_resolve_inline() {
+        let secret = StoredSecret(serde_json::json!({"a": "b"}));
+        let value: std::collections::HashMap<String, String> = secret.resolve().await.unwrap();
+        assert_eq!(value["a"], "b");
+    }
+}
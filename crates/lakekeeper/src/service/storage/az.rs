@@ -15,6 +15,7 @@ use lakekeeper_io::{
     adls::{
         normalize_host, validate_account_name, validate_filesystem_name, AdlsLocation, AdlsStorage,
         AzureAuth, AzureClientCredentialsAuth, AzureSettings, AzureSharedAccessKeyAuth,
+        AzureWorkloadIdentityAuth,
     },
     InvalidLocationError, Location,
 };
@@ -28,12 +29,16 @@ use crate::{
         iceberg::{supported_endpoints, v1::tables::DataAccessMode},
         CatalogConfig, Result,
     },
-    service::storage::{
-        error::{
-            CredentialsError, IcebergFileIoError, InvalidProfileError, TableConfigError,
-            UpdateError, ValidationError,
+    service::{
+        storage::{
+            credential_cache::{CredentialCache, CredentialCacheKey},
+            error::{
+                CredentialsError, IcebergFileIoError, InvalidProfileError, TableConfigError,
+                UpdateError, ValidationError,
+            },
+            StoragePermissions, TableConfig,
         },
-        StoragePermissions, TableConfig,
+        SecretIdent,
     },
     WarehouseId, CONFIG,
 };
@@ -70,6 +75,9 @@ const MAX_SAS_TOKEN_VALIDITY_SECONDS_I64: i64 = 7 * 24 * 60 * 60;
 
 pub(crate) const ALTERNATIVE_PROTOCOLS: [&str; 1] = ["wasbs"];
 
+pub(super) static SAS_CACHE: LazyLock<CredentialCache<String>> =
+    LazyLock::new(|| CredentialCache::new("adls"));
+
 impl AdlsProfile {
     /// Check if an Azure variant is allowed.
     /// By default, only `abfss` is allowed.
@@ -230,6 +238,8 @@ impl AdlsProfile {
 
     /// Generate the table configuration for Azure Datalake Storage Gen2.
     ///
+    /// SAS tokens signed with a user delegation key are cached per warehouse.
+    ///
     /// # Errors
     /// Fails if sas token cannot be generated.
     pub async fn generate_table_config(
@@ -238,6 +248,8 @@ impl AdlsProfile {
         table_location: &Location,
         credential: &AzCredential,
         permissions: StoragePermissions,
+        warehouse_id: WarehouseId,
+        storage_secret_id: Option<SecretIdent>,
     ) -> Result<TableConfig, TableConfigError> {
         if !data_access.provide_credentials() {
             return Ok(TableConfig {
@@ -246,10 +258,22 @@ impl AdlsProfile {
             });
         }
 
+        let cache_key = |identity: Option<&String>| CredentialCacheKey {
+            warehouse_id,
+            storage_secret_id,
+            table_location: table_location.to_string(),
+            storage_permissions: permissions,
+            identity: identity.cloned(),
+        };
         let sas = match credential {
-            AzCredential::ClientCredentials { .. } => {
-                let client = self.blob_service_client(credential).await?;
-                self.sas_via_delegation_key(table_location, client, permissions)
+            AzCredential::ClientCredentials { client_id, .. }
+            | AzCredential::WorkloadIdentity { client_id, .. } => {
+                SAS_CACHE
+                    .get_or_try_insert_with(cache_key(Some(client_id)), async {
+                        let client = self.blob_service_client(credential).await?;
+                        self.sas_via_delegation_key(table_location, client, permissions)
+                            .await
+                    })
                     .await?
             }
             AzCredential::SharedAccessKey { key } => self.sas(
@@ -261,16 +285,20 @@ impl AdlsProfile {
                 azure_core::auth::Secret::new(key.to_string()),
             )?,
             AzCredential::AzureSystemIdentity {} => {
-                let client = self.blob_service_client(credential).await?;
-                self.sas_via_delegation_key(table_location, client, permissions)
-                    .await
-                    .map_err(|e| {
-                        tracing::debug!("Failed to get azure system identity token: {e}",);
-                        CredentialsError::ShortTermCredential {
-                            reason: "Failed to get azure system identity token".to_string(),
-                            source: Some(Box::new(e)),
-                        }
-                    })?
+                SAS_CACHE
+                    .get_or_try_insert_with(cache_key(None), async {
+                        let client = self.blob_service_client(credential).await?;
+                        self.sas_via_delegation_key(table_location, client, permissions)
+                            .await
+                            .map_err(|e| {
+                                tracing::debug!("Failed to get azure system identity token: {e}",);
+                                CredentialsError::ShortTermCredential {
+                                    reason: "Failed to get azure system identity token".to_string(),
+                                    source: Some(Box::new(e)),
+                                }
+                            })
+                    })
+                    .await?
             }
         };
 
@@ -293,7 +321,7 @@ impl AdlsProfile {
         path: &Location,
         client: BlobServiceClient,
         permissions: StoragePermissions,
-    ) -> Result<String, CredentialsError> {
+    ) -> Result<(String, std::time::SystemTime), CredentialsError> {
         // allow for some clock drift
         let start = time::OffsetDateTime::now_utc() - time::Duration::minutes(5);
         let max_validity_seconds = MAX_SAS_TOKEN_VALIDITY_SECONDS_I64;
@@ -324,7 +352,8 @@ impl AdlsProfile {
         let signed_expiry = delegation_key.user_deligation_key.signed_expiry;
         let key = delegation_key.user_deligation_key.clone();
 
-        self.sas(path, permissions, signed_expiry, key)
+        let sas = self.sas(path, permissions, signed_expiry, key)?;
+        Ok((sas, signed_expiry.into()))
     }
 
     fn sas(
@@ -456,6 +485,16 @@ pub enum AzCredential {
     #[serde(rename_all = "kebab-case")]
     #[schema(title = "AzCredentialManagedIdentity")]
     AzureSystemIdentity {},
+    /// Exchange the federated token of the host running Lakekeeper, i.e. the Kubernetes
+    /// projected service account token of Azure Workload Identity, for an Entra ID token
+    /// of the given app registration. The token is read from `AZURE_FEDERATED_TOKEN_FILE`.
+    /// Requires Azure system identities to be enabled.
+    #[serde(rename_all = "kebab-case")]
+    #[schema(title = "AzCredentialWorkloadIdentity")]
+    WorkloadIdentity {
+        client_id: String,
+        tenant_id: String,
+    },
 }
 
 impl From<StoragePermissions> for BlobSasPermissions {
@@ -529,32 +568,50 @@ impl TryFrom<AzCredential> for AzureAuth {
     type Error = CredentialsError;
 
     fn try_from(cred: AzCredential) -> Result<Self, Self::Error> {
-        if !CONFIG.enable_azure_system_credentials
-            && matches!(cred, AzCredential::AzureSystemIdentity {})
-        {
-            return Err(CredentialsError::Misconfiguration(
-                "Azure System identity credentials are disabled in this Lakekeeper deployment."
-                    .to_string(),
-            ));
-        }
-
-        Ok(match cred {
-            AzCredential::ClientCredentials {
-                client_id,
-                tenant_id,
-                client_secret,
-            } => AzureClientCredentialsAuth {
-                client_id,
-                tenant_id,
-                client_secret,
-            }
-            .into(),
-            AzCredential::SharedAccessKey { key } => AzureSharedAccessKeyAuth { key }.into(),
-            AzCredential::AzureSystemIdentity {} => AzureAuth::AzureSystemIdentity,
-        })
+        azure_auth_from_credential(cred, CONFIG.enable_azure_system_credentials)
     }
 }
 
+fn azure_auth_from_credential(
+    cred: AzCredential,
+    system_credentials_enabled: bool,
+) -> Result<AzureAuth, CredentialsError> {
+    if !system_credentials_enabled
+        && matches!(
+            cred,
+            AzCredential::AzureSystemIdentity {} | AzCredential::WorkloadIdentity { .. }
+        )
+    {
+        return Err(CredentialsError::Misconfiguration(
+            "Azure System identity credentials are disabled in this Lakekeeper deployment."
+                .to_string(),
+        ));
+    }
+
+    Ok(match cred {
+        AzCredential::ClientCredentials {
+            client_id,
+            tenant_id,
+            client_secret,
+        } => AzureClientCredentialsAuth {
+            client_id,
+            tenant_id,
+            client_secret,
+        }
+        .into(),
+        AzCredential::SharedAccessKey { key } => AzureSharedAccessKeyAuth { key }.into(),
+        AzCredential::AzureSystemIdentity {} => AzureAuth::AzureSystemIdentity,
+        AzCredential::WorkloadIdentity {
+            client_id,
+            tenant_id,
+        } => AzureWorkloadIdentityAuth {
+            client_id,
+            tenant_id,
+        }
+        .into(),
+    })
+}
+
 #[cfg(test)]
 pub(crate) mod test {
     use super::*;
@@ -563,6 +620,35 @@ pub(crate) mod test {
         NamespaceId, TabularId,
     };
 
+    #[test]
+    fn test_workload_identity_credential() {
+        let cred: AzCredential = serde_json::from_value(serde_json::json!({
+            "credential-type": "workload-identity",
+            "client-id": "my-client-id",
+            "tenant-id": "my-tenant-id",
+        }))
+        .unwrap();
+        assert_eq!(
+            cred,
+            AzCredential::WorkloadIdentity {
+                client_id: "my-client-id".to_string(),
+                tenant_id: "my-tenant-id".to_string(),
+            }
+        );
+
+        assert_eq!(
+            azure_auth_from_credential(cred.clone(), true).unwrap(),
+            AzureAuth::WorkloadIdentity(AzureWorkloadIdentityAuth {
+                client_id: "my-client-id".to_string(),
+                tenant_id: "my-tenant-id".to_string(),
+            })
+        );
+        assert!(matches!(
+            azure_auth_from_credential(cred, false),
+            Err(CredentialsError::Misconfiguration(_))
+        ));
+    }
+
     #[test]
     fn test_reduce_scheme_string() {
         // Test abfss protocol
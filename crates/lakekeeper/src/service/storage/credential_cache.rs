@@ -0,0 +1,263 @@
+//! In-memory cache of vended short-term storage credentials.
+//!
+//! Minting credentials (STS `AssumeRole`, Azure user delegation keys, GCS downscoping)
+//! requires a round trip to the cloud provider for every table load. Credentials are
+//! therefore cached per warehouse, storage secret, table location, permission level and
+//! identity until shortly before they expire. Entries of a warehouse are invalidated on all
+//! replicas whenever its storage profile or credential changes.
+use std::{
+    future::Future,
+    sync::Arc,
+    time::{Duration, Instant, SystemTime},
+};
+
+use axum_prometheus::metrics;
+
+use super::{error::CredentialsError, StoragePermissions};
+use crate::{service::SecretIdent, WarehouseId, CONFIG};
+
+const METRIC_CACHE_HITS: &str = "lakekeeper_vended_credential_cache_hits_total";
+const METRIC_CACHE_MISSES: &str = "lakekeeper_vended_credential_cache_misses_total";
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct CredentialCacheKey {
+    pub(crate) warehouse_id: WarehouseId,
+    /// Secret the credential is minted with. Namespace credential overrides replace the
+    /// secret and the role, so credentials are never shared across overrides.
+    pub(crate) storage_secret_id: Option<SecretIdent>,
+    pub(crate) table_location: String,
+    pub(crate) storage_permissions: StoragePermissions,
+    /// Identity the credential is issued for, i.e. the assumed role.
+    pub(crate) identity: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+struct CachedCredential<V> {
+    value: V,
+    expires_at: SystemTime,
+}
+
+struct CredentialExpiry;
+impl<V> moka::Expiry<CredentialCacheKey, CachedCredential<V>> for CredentialExpiry {
+    fn expire_after_create(
+        &self,
+        _key: &CredentialCacheKey,
+        value: &CachedCredential<V>,
+        _created_at: Instant,
+    ) -> Option<Duration> {
+        let remaining = value
+            .expires_at
+            .duration_since(SystemTime::now())
+            .unwrap_or_default();
+        Some(remaining.saturating_sub(Duration::from_secs(
+            CONFIG.cache.vended_credentials_expiry_margin_seconds,
+        )))
+    }
+}
+
+/// Cache of credentials of one storage type.
+pub(crate) struct CredentialCache<V> {
+    storage_type: &'static str,
+    cache: moka::future::Cache<CredentialCacheKey, CachedCredential<V>>,
+}
+
+impl<V> CredentialCache<V>
+where
+    V: Clone + Send + Sync + 'static,
+{
+    pub(crate) fn new(storage_type: &'static str) -> Self {
+        Self {
+            storage_type,
+            cache: moka::future::Cache::builder()
+                .max_capacity(CONFIG.cache.max_capacity)
+                .expire_after(CredentialExpiry)
+                .support_invalidation_closures()
+                .build(),
+        }
+    }
+
+    /// Return the cached credential for `key` or mint a new one with `init`.
+    /// `init` returns the credential together with the time it expires.
+    ///
+    /// Concurrent requests for the same key wait for a single mint.
+    pub(crate) async fn get_or_try_insert_with<F, E>(
+        &self,
+        key: CredentialCacheKey,
+        init: F,
+    ) -> Result<V, E>
+    where
+        F: Future<Output = Result<(V, SystemTime), E>>,
+        E: From<CredentialsError> + std::fmt::Display + Send + Sync + 'static,
+    {
+        if !CONFIG.cache.vended_credentials_enabled {
+            return init.await.map(|(value, _)| value);
+        }
+        self.get_or_mint(key, init).await
+    }
+
+    async fn get_or_mint<F, E>(&self, key: CredentialCacheKey, init: F) -> Result<V, E>
+    where
+        F: Future<Output = Result<(V, SystemTime), E>>,
+        E: From<CredentialsError> + std::fmt::Display + Send + Sync + 'static,
+    {
+        let entry = self
+            .cache
+            .entry(key)
+            .or_try_insert_with(async {
+                let (value, expires_at) = init.await?;
+                Ok::<_, E>(CachedCredential { value, expires_at })
+            })
+            .await
+            .map_err(|e| {
+                // Concurrent callers waiting for the same mint share the error
+                Arc::try_unwrap(e).unwrap_or_else(|e| {
+                    CredentialsError::ShortTermCredential {
+                        reason: e.to_string(),
+                        source: None,
+                    }
+                    .into()
+                })
+            })?;
+
+        let metric = if entry.is_fresh() {
+            METRIC_CACHE_MISSES
+        } else {
+            METRIC_CACHE_HITS
+        };
+        metrics::counter!(metric, "storage_type" => self.storage_type).increment(1);
+
+        Ok(entry.into_value().value)
+    }
+
+    fn invalidate_warehouse(&self, warehouse_id: WarehouseId) {
+        if let Err(e) = self
+            .cache
+            .invalidate_entries_if(move |key, _| key.warehouse_id == warehouse_id)
+        {
+            tracing::error!(
+                "Failed to invalidate cached {} credentials of warehouse {warehouse_id}: {e}",
+                self.storage_type
+            );
+        }
+    }
+
+    fn invalidate_all(&self) {
+        self.cache.invalidate_all();
+    }
+}
+
+/// Invalidate all cached credentials of a warehouse on this replica.
+/// Call after the change is committed, so that no credential minted with the previous
+/// configuration is cached afterwards.
+pub(crate) fn invalidate_warehouse(warehouse_id: WarehouseId) {
+    super::s3::STS_CREDENTIAL_CACHE.invalidate_warehouse(warehouse_id);
+    super::az::SAS_CACHE.invalidate_warehouse(warehouse_id);
+    super::gcs::DOWNSCOPED_TOKEN_CACHE.invalidate_warehouse(warehouse_id);
+}
+
+/// Invalidate all cached credentials.
+pub(crate) fn invalidate_all() {
+    super::s3::STS_CREDENTIAL_CACHE.invalidate_all();
+    super::az::SAS_CACHE.invalidate_all();
+    super::gcs::DOWNSCOPED_TOKEN_CACHE.invalidate_all();
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    fn key(
+        warehouse_id: WarehouseId,
+        storage_permissions: StoragePermissions,
+    ) -> CredentialCacheKey {
+        CredentialCacheKey {
+            warehouse_id,
+            storage_secret_id: None,
+            table_location: "s3://bucket/table".to_string(),
+            storage_permissions,
+            identity: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_credential_cache() {
+        let cache = CredentialCache::<String>::new("test");
+        let mints = AtomicUsize::new(0);
+        let mint = |valid_for: Duration| {
+            let n = mints.fetch_add(1, Ordering::SeqCst);
+            async move {
+                Ok::<_, CredentialsError>((
+                    format!("credential-{n}"),
+                    SystemTime::now() + valid_for,
+                ))
+            }
+        };
+        let warehouse_id = WarehouseId::new_random();
+        let valid = Duration::from_secs(3600);
+
+        let c1 = cache
+            .get_or_mint(key(warehouse_id, StoragePermissions::Read), mint(valid))
+            .await
+            .unwrap();
+        let c2 = cache
+            .get_or_mint(key(warehouse_id, StoragePermissions::Read), mint(valid))
+            .await
+            .unwrap();
+        assert_eq!(c1, c2);
+
+        // Different permission level
+        let c3 = cache
+            .get_or_mint(
+                key(warehouse_id, StoragePermissions::ReadWriteDelete),
+                mint(valid),
+            )
+            .await
+            .unwrap();
+        assert_ne!(c1, c3);
+
+        // Different secret, i.e. a rotated secret or a namespace credential override
+        let c_secret = cache
+            .get_or_mint(
+                CredentialCacheKey {
+                    storage_secret_id: Some(SecretIdent::from(uuid::Uuid::now_v7())),
+                    ..key(warehouse_id, StoragePermissions::Read)
+                },
+                mint(valid),
+            )
+            .await
+            .unwrap();
+        assert_ne!(c1, c_secret);
+
+        // Invalidation of the warehouse
+        cache.invalidate_warehouse(warehouse_id);
+        let c4 = cache
+            .get_or_mint(key(warehouse_id, StoragePermissions::Read), mint(valid))
+            .await
+            .unwrap();
+        assert_ne!(c1, c4);
+    }
+
+    #[tokio::test]
+    async fn test_credential_expiring_within_margin_is_not_cached() {
+        let cache = CredentialCache::<String>::new("test");
+        let warehouse_id = WarehouseId::new_random();
+        let expires_at = SystemTime::now() + Duration::from_secs(10);
+
+        let c1 = cache
+            .get_or_mint(key(warehouse_id, StoragePermissions::Read), async {
+                Ok::<_, CredentialsError>(("first".to_string(), expires_at))
+            })
+            .await
+            .unwrap();
+        let c2 = cache
+            .get_or_mint(key(warehouse_id, StoragePermissions::Read), async {
+                Ok::<_, CredentialsError>(("second".to_string(), expires_at))
+            })
+            .await
+            .unwrap();
+        assert_eq!(c1, "first");
+        assert_eq!(c2, "second");
+    }
+}
@@ -24,12 +24,17 @@ use crate::{
         iceberg::{supported_endpoints, v1::tables::DataAccessMode},
         CatalogConfig,
     },
-    service::storage::{
-        error::{
-            CredentialsError, IcebergFileIoError, InvalidProfileError, TableConfigError,
-            UpdateError, ValidationError,
+    config::GcpWorkloadIdentityConfig,
+    service::{
+        storage::{
+            credential_cache::{CredentialCache, CredentialCacheKey},
+            error::{
+                CredentialsError, IcebergFileIoError, InvalidProfileError, TableConfigError,
+                UpdateError, ValidationError,
+            },
+            StoragePermissions, TableConfig,
         },
-        StoragePermissions, TableConfig,
+        SecretIdent,
     },
     WarehouseId, CONFIG,
 };
@@ -45,6 +50,17 @@ static STS_URL: LazyLock<Url> = LazyLock::new(|| {
 });
 const GOOGLE_CLOUD_PLATFORM_SCOPE: &str = "https://www.googleapis.com/auth/cloud-platform";
 
+pub(super) static DOWNSCOPED_TOKEN_CACHE: LazyLock<CredentialCache<DownscopedToken>> =
+    LazyLock::new(|| CredentialCache::new("gcs"));
+
+#[derive(Clone, Redact)]
+pub(super) struct DownscopedToken {
+    #[redact(partial)]
+    access_token: String,
+    project_id: Option<String>,
+    expires_at: Option<std::time::SystemTime>,
+}
+
 #[derive(Debug, Eq, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
 #[serde(rename_all = "kebab-case")]
 pub struct GcsProfile {
@@ -58,8 +74,7 @@ pub struct GcsProfile {
 #[serde(tag = "credential-type", rename_all = "kebab-case")]
 /// GCS Credentials
 ///
-/// Currently only supports Service Account Key
-/// Example of a key:
+/// Example of a service account key:
 /// ```json
 ///     {
 ///       "type": "service_account",
@@ -88,6 +103,178 @@ pub enum GcsCredential {
     /// This can be a Compute Engine default service account or a user-assigned service account.
     #[schema(title = "GcsCredentialSystemIdentity")]
     GcpSystemIdentity {},
+
+    /// Workload Identity Federation
+    ///
+    /// The credential configuration of an external account (`"type": "external_account"`),
+    /// as generated by `gcloud iam workload-identity-pools create-cred-config`.
+    /// The OIDC or AWS token of the host running Lakekeeper is exchanged for a GCP token.
+    /// Requires GCP system identities to be enabled.
+    #[schema(title = "GcsCredentialWorkloadIdentityFederation")]
+    WorkloadIdentityFederation { config: GcsExternalAccountConfig },
+
+    /// HMAC Key
+    ///
+    /// Interoperability key for the S3 compatible XML API of GCS.
+    /// HMAC keys cannot be downscoped, so no credentials are vended to clients.
+    #[schema(title = "GcsCredentialHmacKey")]
+    HmacKey { key: GcsHmacKey },
+}
+
+#[derive(Redact, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct GcsExternalAccountConfig {
+    pub r#type: String,
+    pub audience: String,
+    pub subject_token_type: String,
+    pub token_url: String,
+    /// Where to obtain the subject token from, i.e. a `file`, `url` or `environment_id`.
+    #[redact]
+    #[schema(value_type = Object)]
+    pub credential_source: serde_json::Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub service_account_impersonation_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_info_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quota_project_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workforce_pool_user_project: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub universe_domain: Option<String>,
+}
+
+impl GcsExternalAccountConfig {
+    /// The subject token is read and sent by Lakekeeper, so the configuration must only use
+    /// token endpoints of GCP and subject token sources allowed by the operator.
+    fn validate(&self, restrictions: &GcpWorkloadIdentityConfig) -> Result<(), CredentialsError> {
+        let domain = restrictions.universe_domain.as_str();
+        if self.r#type != "external_account" {
+            return Err(CredentialsError::Misconfiguration(format!(
+                "Expected credential configuration of type `external_account`, got `{}`.",
+                self.r#type
+            )));
+        }
+        if let Some(universe_domain) = self
+            .universe_domain
+            .as_deref()
+            .filter(|universe_domain| *universe_domain != domain)
+        {
+            return Err(CredentialsError::Misconfiguration(format!(
+                "Universe domain `{universe_domain}` is not allowed. Expected `{domain}`."
+            )));
+        }
+
+        validate_google_endpoint("token_url", &self.token_url, &format!("sts.{domain}"))?;
+        if let Some(token_info_url) = &self.token_info_url {
+            validate_google_endpoint("token_info_url", token_info_url, &format!("sts.{domain}"))?;
+        }
+        if let Some(impersonation_url) = &self.service_account_impersonation_url {
+            validate_google_endpoint(
+                "service_account_impersonation_url",
+                impersonation_url,
+                &format!("iamcredentials.{domain}"),
+            )?;
+        }
+
+        self.validate_credential_source(restrictions)
+    }
+
+    fn validate_credential_source(
+        &self,
+        restrictions: &GcpWorkloadIdentityConfig,
+    ) -> Result<(), CredentialsError> {
+        let source = self.credential_source.as_object().ok_or_else(|| {
+            CredentialsError::Misconfiguration("`credential_source` must be an object.".to_string())
+        })?;
+        let is_allowed = |allowed: &Option<Vec<String>>, value: &serde_json::Value| {
+            value.as_str().is_some_and(|value| {
+                allowed
+                    .as_deref()
+                    .unwrap_or_default()
+                    .iter()
+                    .any(|a| a == value)
+            })
+        };
+
+        // AWS sources also contain urls of the metadata service, so check them first.
+        if source.contains_key("environment_id") {
+            if restrictions.allow_aws_credential_source {
+                return Ok(());
+            }
+            return Err(CredentialsError::Misconfiguration(
+                "AWS credential sources are disabled in this Lakekeeper deployment.".to_string(),
+            ));
+        }
+        if source.contains_key("executable") {
+            return Err(CredentialsError::Misconfiguration(
+                "Executable credential sources are not supported.".to_string(),
+            ));
+        }
+        match (source.get("file"), source.get("url")) {
+            (Some(file), None) if is_allowed(&restrictions.allowed_credential_files, file) => {
+                Ok(())
+            }
+            (None, Some(url)) if is_allowed(&restrictions.allowed_credential_urls, url) => {
+                Ok(())
+            }
+            (Some(_), None) => Err(CredentialsError::Misconfiguration(
+                "`credential_source.file` is not allowed in this Lakekeeper deployment."
+                    .to_string(),
+            )),
+            (None, Some(_)) => Err(CredentialsError::Misconfiguration(
+                "`credential_source.url` is not allowed in this Lakekeeper deployment."
+                    .to_string(),
+            )),
+            _ => Err(CredentialsError::Misconfiguration(
+                "`credential_source` must contain exactly one of `file`, `url` or `environment_id`."
+                    .to_string(),
+            )),
+        }
+    }
+
+    /// Validate the configuration and convert it for the token source of `google_cloud_auth`.
+    fn to_credentials_file(&self) -> Result<CredentialsFile, CredentialsError> {
+        self.validate(&CONFIG.gcp_workload_identity)?;
+        self.try_into()
+    }
+}
+
+fn validate_google_endpoint(field: &str, url: &str, host: &str) -> Result<(), CredentialsError> {
+    let parsed = Url::parse(url).map_err(|e| {
+        CredentialsError::Misconfiguration(format!("`{field}` is not a valid URL: {e}"))
+    })?;
+    if parsed.scheme() != "https"
+        || parsed.host_str() != Some(host)
+        || parsed.port().is_some()
+        || !parsed.username().is_empty()
+    {
+        return Err(CredentialsError::Misconfiguration(format!(
+            "`{field}` must be an https URL of `{host}`."
+        )));
+    }
+    Ok(())
+}
+
+impl TryFrom<&GcsExternalAccountConfig> for CredentialsFile {
+    type Error = CredentialsError;
+
+    fn try_from(config: &GcsExternalAccountConfig) -> Result<Self, Self::Error> {
+        serde_json::to_value(config)
+            .and_then(serde_json::from_value)
+            .map_err(|e| {
+                CredentialsError::Misconfiguration(format!(
+                    "Invalid external account configuration: {e}"
+                ))
+            })
+    }
+}
+
+#[derive(Redact, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct GcsHmacKey {
+    pub access_key_id: String,
+    #[redact(partial)]
+    pub secret: String,
 }
 
 #[derive(Redact, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
@@ -308,47 +495,108 @@ impl GcsProfile {
                 })?;
                 (TokenSource::Token(tsp.token_source()), tsp.project_id)
             }
+            GcsCredential::WorkloadIdentityFederation {
+                config: external_account,
+            } => {
+                if !CONFIG.enable_gcp_system_credentials {
+                    return Err(CredentialsError::Misconfiguration(
+                        "GCP System identity credentials are disabled in this Lakekeeper deployment."
+                            .to_string(),
+                    ));
+                }
+                let source = google_cloud_auth::project::create_token_source_from_credentials(
+                    &external_account.to_credentials_file()?,
+                    &config,
+                )
+                .await
+                .map_err(|e| {
+                    tracing::error!(
+                        "Failed to create gcp token source from external account: {:?}",
+                        e
+                    );
+                    CredentialsError::Misconfiguration(
+                        "Failed to create gcp token source from external account".to_string(),
+                    )
+                })?;
+                (
+                    TokenSource::GAuth(source.into()),
+                    external_account.quota_project_id.clone(),
+                )
+            }
+            GcsCredential::HmacKey { .. } => {
+                return Err(CredentialsError::Misconfiguration(
+                    "HMAC keys cannot be exchanged for gcp tokens".to_string(),
+                ))
+            }
         })
     }
 
     /// Generate the table configuration for GCS.
+    ///
+    /// Downscoped tokens are cached per warehouse.
     pub(crate) async fn generate_table_config(
         &self,
         data_access: DataAccessMode,
         cred: &GcsCredential,
         table_location: &Location,
         storage_permissions: StoragePermissions,
+        warehouse_id: WarehouseId,
+        storage_secret_id: Option<SecretIdent>,
     ) -> Result<TableConfig, TableConfigError> {
         let mut table_properties = TableProperties::default();
 
-        if !data_access.provide_credentials() {
+        // HMAC keys cannot be downscoped
+        if !data_access.provide_credentials() || matches!(cred, GcsCredential::HmacKey { .. }) {
             return Ok(TableConfig {
                 creds: table_properties.clone(),
                 config: table_properties,
             });
         }
 
-        let (source, project_id) = self.get_token_source(cred).await?;
-        let token = sts::downscope(
-            source,
-            &self.bucket,
-            table_location.clone(),
+        let cache_key = CredentialCacheKey {
+            warehouse_id,
+            storage_secret_id,
+            table_location: table_location.to_string(),
             storage_permissions,
-        )
-        .await?;
+            identity: None,
+        };
+        let token = DOWNSCOPED_TOKEN_CACHE
+            .get_or_try_insert_with(cache_key, async {
+                let (source, project_id) = self.get_token_source(cred).await?;
+                let token = sts::downscope(
+                    source,
+                    &self.bucket,
+                    table_location.clone(),
+                    storage_permissions,
+                )
+                .await?;
+                let expires_at = token.expires_in.map(|expiry| {
+                    std::time::SystemTime::now() + std::time::Duration::from_secs(expiry as u64)
+                });
+                let token = DownscopedToken {
+                    access_token: token.access_token,
+                    project_id,
+                    expires_at,
+                };
+                // Tokens without known expiry are not cached
+                Ok::<_, TableConfigError>((
+                    token,
+                    expires_at.unwrap_or_else(std::time::SystemTime::now),
+                ))
+            })
+            .await?;
 
         table_properties.insert(&gcs::Token(token.access_token));
-        if let Some(ref project_id) = project_id {
-            table_properties.insert(&gcs::ProjectId(project_id.clone()));
+        if let Some(project_id) = token.project_id {
+            table_properties.insert(&gcs::ProjectId(project_id));
         }
 
-        if let Some(expiry) = token.expires_in {
+        if let Some(expires_at) = token.expires_at {
             table_properties.insert(&gcs::TokenExpiresAt(
-                (std::time::SystemTime::now()
+                expires_at
                     .duration_since(std::time::UNIX_EPOCH)
-                    .unwrap()
+                    .unwrap_or_default()
                     .as_millis()
-                    + (expiry * 1000) as u128)
                     .to_string(),
             ));
         }
@@ -433,7 +681,11 @@ impl TryFrom<GcsCredential> for GcsAuth {
 
     fn try_from(credential: GcsCredential) -> Result<Self, Self::Error> {
         if !CONFIG.enable_gcp_system_credentials
-            && matches!(credential, GcsCredential::GcpSystemIdentity {})
+            && matches!(
+                credential,
+                GcsCredential::GcpSystemIdentity {}
+                    | GcsCredential::WorkloadIdentityFederation { .. }
+            )
         {
             return Err(CredentialsError::Misconfiguration(
                 "GCP System identity credentials are disabled in this Lakekeeper deployment."
@@ -446,12 +698,174 @@ impl TryFrom<GcsCredential> for GcsAuth {
                 GcsAuth::CredentialsFile { file: key.into() }
             }
             GcsCredential::GcpSystemIdentity {} => GcsAuth::GcpSystemIdentity {},
+            GcsCredential::WorkloadIdentityFederation { config } => GcsAuth::CredentialsFile {
+                file: config.to_credentials_file()?,
+            },
+            GcsCredential::HmacKey {
+                key:
+                    GcsHmacKey {
+                        access_key_id,
+                        secret,
+                    },
+            } => GcsAuth::HmacKey {
+                access_key_id,
+                secret,
+            },
         })
     }
 }
 
 #[cfg(test)]
 pub(crate) mod test {
+    use super::*;
+
+    #[test]
+    fn test_external_account_credentials_file() {
+        let cred: GcsCredential = serde_json::from_value(serde_json::json!({
+            "credential-type": "workload-identity-federation",
+            "config": {
+                "type": "external_account",
+                "audience": "//iam.googleapis.com/projects/123/locations/global/workloadIdentityPools/pool/providers/provider",
+                "subject_token_type": "urn:ietf:params:oauth:token-type:jwt",
+                "token_url": "https://sts.googleapis.com/v1/token",
+                "credential_source": {
+                    "file": "/var/run/secrets/tokens/gcp-token"
+                }
+            }
+        }))
+        .unwrap();
+        let GcsCredential::WorkloadIdentityFederation { config } = &cred else {
+            panic!("Expected workload identity federation credential");
+        };
+        let file = CredentialsFile::try_from(config).unwrap();
+        assert_eq!(file.tp, "external_account");
+        assert_eq!(
+            file.token_url_external.as_deref(),
+            Some("https://sts.googleapis.com/v1/token")
+        );
+        assert!(file.credential_source.is_some());
+
+        let cred: GcsCredential = serde_json::from_value(serde_json::json!({
+            "credential-type": "hmac-key",
+            "key": {
+                "access-key-id": "GOOG1Eexample",
+                "secret": "secret"
+            }
+        }))
+        .unwrap();
+        assert!(matches!(
+            GcsAuth::try_from(cred).unwrap(),
+            GcsAuth::HmacKey { .. }
+        ));
+    }
+
+    fn external_account(patch: serde_json::Value) -> GcsExternalAccountConfig {
+        let mut config = serde_json::json!({
+            "type": "external_account",
+            "audience": "//iam.googleapis.com/projects/123/locations/global/workloadIdentityPools/pool/providers/provider",
+            "subject_token_type": "urn:ietf:params:oauth:token-type:jwt",
+            "token_url": "https://sts.googleapis.com/v1/token",
+            "service_account_impersonation_url": "https://iamcredentials.googleapis.com/v1/projects/-/serviceAccounts/sa@example.iam.gserviceaccount.com:generateAccessToken",
+            "credential_source": {
+                "file": "/var/run/secrets/tokens/gcp-token"
+            }
+        });
+        config
+            .as_object_mut()
+            .unwrap()
+            .extend(patch.as_object().unwrap().clone());
+        serde_json::from_value(config).unwrap()
+    }
+
+    #[test]
+    fn test_external_account_validation() {
+        let restrictions = GcpWorkloadIdentityConfig {
+            allowed_credential_files: Some(vec!["/var/run/secrets/tokens/gcp-token".to_string()]),
+            ..Default::default()
+        };
+        external_account(serde_json::json!({}))
+            .validate(&restrictions)
+            .unwrap();
+
+        let rejected = [
+            serde_json::json!({"token_url": "https://attacker.example.com/v1/token"}),
+            serde_json::json!({"token_url": "http://sts.googleapis.com/v1/token"}),
+            serde_json::json!({"token_url": "https://sts.googleapis.com.example.com/v1/token"}),
+            serde_json::json!({"token_url": "https://sts.googleapis.com:8443/v1/token"}),
+            serde_json::json!({"token_info_url": "https://attacker.example.com/v1/introspect"}),
+            serde_json::json!({
+                "service_account_impersonation_url": "https://attacker.example.com/generateAccessToken"
+            }),
+            serde_json::json!({"universe_domain": "example.com"}),
+            serde_json::json!({"credential_source": {"file": "/proc/self/environ"}}),
+            serde_json::json!({
+                "credential_source": {"file": "/var/run/secrets/kubernetes.io/serviceaccount/token"}
+            }),
+            serde_json::json!({
+                "credential_source": {"url": "http://metadata.google.internal/computeMetadata/v1/token"}
+            }),
+            serde_json::json!({
+                "credential_source": {
+                    "environment_id": "aws1",
+                    "region_url": "http://169.254.169.254/latest/meta-data/placement/availability-zone",
+                    "regional_cred_verification_url": "https://sts.{region}.amazonaws.com?Action=GetCallerIdentity&Version=2011-06-15"
+                }
+            }),
+            serde_json::json!({"credential_source": {"executable": {"command": "/bin/sh"}}}),
+            serde_json::json!({
+                "credential_source": {
+                    "file": "/var/run/secrets/tokens/gcp-token",
+                    "url": "http://localhost/token"
+                }
+            }),
+        ];
+        for patch in rejected {
+            let result = external_account(patch.clone()).validate(&restrictions);
+            assert!(
+                matches!(result, Err(CredentialsError::Misconfiguration(_))),
+                "Expected {patch} to be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_external_account_validation_operator_allowlist() {
+        let restrictions = GcpWorkloadIdentityConfig {
+            universe_domain: "example-universe.com".to_string(),
+            allowed_credential_urls: Some(vec!["http://localhost:5000/token".to_string()]),
+            allow_aws_credential_source: true,
+            ..Default::default()
+        };
+        let allowed = serde_json::json!({
+            "token_url": "https://sts.example-universe.com/v1/token",
+            "service_account_impersonation_url": "https://iamcredentials.example-universe.com/v1/projects/-/serviceAccounts/sa@example.iam.gserviceaccount.com:generateAccessToken",
+            "universe_domain": "example-universe.com",
+            "credential_source": {"url": "http://localhost:5000/token"}
+        });
+        external_account(allowed).validate(&restrictions).unwrap();
+        external_account(serde_json::json!({
+            "token_url": "https://sts.example-universe.com/v1/token",
+            "service_account_impersonation_url": null,
+            "credential_source": {"environment_id": "aws1"}
+        }))
+        .validate(&restrictions)
+        .unwrap();
+
+        // Endpoints of the default universe are not allowed for a different universe.
+        assert!(external_account(serde_json::json!({
+            "credential_source": {"url": "http://localhost:5000/token"}
+        }))
+        .validate(&restrictions)
+        .is_err());
+        // Files are not allowed if only urls are configured.
+        assert!(external_account(serde_json::json!({
+            "token_url": "https://sts.example-universe.com/v1/token",
+            "service_account_impersonation_url": null,
+        }))
+        .validate(&restrictions)
+        .is_err());
+    }
+
     pub(crate) mod gcs_integration_tests {
         use crate::{
             api::RequestMetadata,
@@ -0,0 +1,354 @@
+//! Validation reports of the storage profiles of a warehouse and a periodic
+//! probe of all active warehouses for the `/health/storage` endpoint.
+use std::time::Instant;
+
+use futures::StreamExt as _;
+use serde::Serialize;
+use tokio::sync::{Mutex, RwLock};
+
+use super::{StorageAccessProbe, StorageCredential, StorageProfile};
+use crate::{
+    api::Result,
+    request_metadata::RequestMetadata,
+    service::{
+        health::{Health, HealthExt, HealthStatus},
+        CatalogStore, GetWarehouseResponse, SecretIdent, SecretStore, Transaction,
+    },
+    CONFIG,
+};
+
+/// Number of warehouses validated concurrently by the periodic probe.
+const PROBE_CONCURRENCY: usize = 4;
+
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct StorageValidationReport {
+    /// `true` if no check of any storage profile failed.
+    pub healthy: bool,
+    /// Reports of the default storage profile followed by all additional storage profiles.
+    pub storage_profiles: Vec<StorageProfileValidationReport>,
+}
+
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct StorageProfileValidationReport {
+    /// Name of the additional storage profile. Not set for the default storage profile.
+    pub storage_profile_name: Option<String>,
+    pub checks: Vec<StorageValidationCheck>,
+}
+
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct StorageValidationCheck {
+    pub check: StorageValidationCheckType,
+    pub status: StorageValidationStatus,
+    /// Reason if the check failed or was skipped.
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum StorageValidationCheckType {
+    /// Write, read, list and delete with the storage credential of the warehouse.
+    DirectAccess,
+    /// Write, read and delete with vended credentials.
+    /// Writes outside of the table location must be denied.
+    VendedCredentials,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum StorageValidationStatus {
+    Passed,
+    Failed,
+    Skipped,
+}
+
+impl StorageValidationCheck {
+    fn from_result(
+        check: StorageValidationCheckType,
+        result: std::result::Result<(), impl std::fmt::Display>,
+    ) -> Self {
+        match result {
+            Ok(()) => Self {
+                check,
+                status: StorageValidationStatus::Passed,
+                message: None,
+            },
+            Err(e) => Self {
+                check,
+                status: StorageValidationStatus::Failed,
+                message: Some(e.to_string()),
+            },
+        }
+    }
+
+    fn skipped(check: StorageValidationCheckType, reason: &str) -> Self {
+        Self {
+            check,
+            status: StorageValidationStatus::Skipped,
+            message: Some(reason.to_string()),
+        }
+    }
+}
+
+impl StorageProfileValidationReport {
+    fn from_probe(storage_profile_name: Option<String>, probe: StorageAccessProbe) -> Self {
+        let StorageAccessProbe {
+            direct_access,
+            vended_credentials,
+        } = probe;
+        let vended_credentials = match vended_credentials {
+            Some(result) => StorageValidationCheck::from_result(
+                StorageValidationCheckType::VendedCredentials,
+                result,
+            ),
+            None if direct_access.is_err() => StorageValidationCheck::skipped(
+                StorageValidationCheckType::VendedCredentials,
+                "Direct access failed.",
+            ),
+            None => StorageValidationCheck::skipped(
+                StorageValidationCheckType::VendedCredentials,
+                "Storage profile does not vend credentials.",
+            ),
+        };
+
+        Self {
+            storage_profile_name,
+            checks: vec![
+                StorageValidationCheck::from_result(
+                    StorageValidationCheckType::DirectAccess,
+                    direct_access,
+                ),
+                vended_credentials,
+            ],
+        }
+    }
+
+    fn is_healthy(&self) -> bool {
+        self.checks
+            .iter()
+            .all(|check| check.status != StorageValidationStatus::Failed)
+    }
+}
+
+/// Validate access to all storage profiles of a warehouse.
+/// Test files are written to random table locations and removed afterwards.
+pub async fn validate_warehouse_storage<S: SecretStore>(
+    warehouse: &GetWarehouseResponse,
+    secrets: &S,
+    request_metadata: &RequestMetadata,
+) -> StorageValidationReport {
+    let profiles = std::iter::once((
+        None,
+        &warehouse.storage_profile,
+        warehouse.storage_secret_id,
+    ))
+    .chain(warehouse.additional_storage_profiles.iter().map(|p| {
+        (
+            Some(p.name.clone()),
+            &p.storage_profile,
+            p.storage_secret_id,
+        )
+    }));
+
+    let mut storage_profiles = Vec::new();
+    for (name, profile, secret_id) in profiles {
+        storage_profiles.push(
+            validate_storage_profile(name, profile, secret_id, secrets, request_metadata).await,
+        );
+    }
+
+    StorageValidationReport {
+        healthy: storage_profiles
+            .iter()
+            .all(StorageProfileValidationReport::is_healthy),
+        storage_profiles,
+    }
+}
+
+async fn validate_storage_profile<S: SecretStore>(
+    name: Option<String>,
+    profile: &StorageProfile,
+    secret_id: Option<SecretIdent>,
+    secrets: &S,
+    request_metadata: &RequestMetadata,
+) -> StorageProfileValidationReport {
+    let credential = match secret_id {
+        Some(secret_id) => match secrets
+            .get_secret_by_id::<StorageCredential>(secret_id)
+            .await
+        {
+            Ok(secret) => Some(secret.secret),
+            Err(e) => {
+                return StorageProfileValidationReport {
+                    storage_profile_name: name,
+                    checks: vec![
+                        StorageValidationCheck::from_result(
+                            StorageValidationCheckType::DirectAccess,
+                            Err(format!(
+                                "Failed to load storage credential: {}",
+                                e.error.message
+                            )),
+                        ),
+                        StorageValidationCheck::skipped(
+                            StorageValidationCheckType::VendedCredentials,
+                            "Direct access failed.",
+                        ),
+                    ],
+                };
+            }
+        },
+        None => None,
+    };
+
+    let probe = Box::pin(profile.probe_access(credential.as_ref(), None, request_metadata)).await;
+    StorageProfileValidationReport::from_probe(name, probe)
+}
+
+/// Periodically validates the storage profiles of all active warehouses.
+/// Reports one health entry per warehouse, named `warehouse-<warehouse-id>`.
+/// Served on `/health/storage` and not part of the server health on `/health`.
+pub struct StorageHealthProbe<C: CatalogStore, S: SecretStore> {
+    catalog_state: C::State,
+    secrets: S,
+    health: RwLock<Vec<Health>>,
+    last_probe: Mutex<Option<Instant>>,
+}
+
+impl<C: CatalogStore, S: SecretStore> StorageHealthProbe<C, S> {
+    #[must_use]
+    pub fn new(catalog_state: C::State, secrets: S) -> Self {
+        Self {
+            catalog_state,
+            secrets,
+            health: RwLock::new(Vec::new()),
+            last_probe: Mutex::new(None),
+        }
+    }
+
+    async fn list_active_warehouses(&self) -> Result<Vec<GetWarehouseResponse>> {
+        let mut trx = C::Transaction::begin_read(self.catalog_state.clone()).await?;
+        let projects = C::list_projects(None, trx.transaction()).await?;
+        trx.commit().await?;
+
+        let mut warehouses = Vec::new();
+        for project in projects {
+            warehouses.extend(
+                C::list_warehouses(&project.project_id, None, self.catalog_state.clone()).await?,
+            );
+        }
+        Ok(warehouses)
+    }
+}
+
+#[async_trait::async_trait]
+impl<C: CatalogStore, S: SecretStore> HealthExt for StorageHealthProbe<C, S> {
+    async fn health(&self) -> Vec<Health> {
+        self.health.read().await.clone()
+    }
+
+    /// Called in the interval of the health checks.
+    /// Warehouses are only probed once per `storage_health_check.interval`.
+    async fn update_health(&self) {
+        {
+            let mut last_probe = self.last_probe.lock().await;
+            if last_probe.is_some_and(|t| t.elapsed() < CONFIG.storage_health_check.interval) {
+                return;
+            }
+            *last_probe = Some(Instant::now());
+        }
+
+        let warehouses = match self.list_active_warehouses().await {
+            Ok(warehouses) => warehouses,
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to list warehouses for storage health check: {}",
+                    e.error
+                );
+                return;
+            }
+        };
+
+        let request_metadata = RequestMetadata::new_lakekeeper_internal();
+        let health = futures::stream::iter(warehouses)
+            .map(|warehouse| {
+                let request_metadata = &request_metadata;
+                async move {
+                    let report =
+                        validate_warehouse_storage(&warehouse, &self.secrets, request_metadata)
+                            .await;
+                    let status = if report.healthy {
+                        HealthStatus::Healthy
+                    } else {
+                        tracing::warn!(
+                            warehouse_id = %warehouse.id,
+                            ?report,
+                            "Storage validation of warehouse failed"
+                        );
+                        HealthStatus::Unhealthy
+                    };
+                    Health::now(format!("warehouse-{}", warehouse.id), status)
+                }
+            })
+            .buffer_unordered(PROBE_CONCURRENCY)
+            .collect::<Vec<_>>()
+            .await;
+
+        *self.health.write().await = health;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::storage::ValidationError;
+
+    #[test]
+    fn test_report_from_probe() {
+        let report = StorageProfileValidationReport::from_probe(
+            Some("archive".to_string()),
+            StorageAccessProbe {
+                direct_access: Ok(()),
+                vended_credentials: Some(Err(ValidationError::from(
+                    lakekeeper_io::InvalidLocationError::new(
+                        "s3://bucket/foo".to_string(),
+                        "denied".to_string(),
+                    ),
+                ))),
+            },
+        );
+        assert!(!report.is_healthy());
+        assert_eq!(report.checks[0].status, StorageValidationStatus::Passed);
+        assert_eq!(report.checks[1].status, StorageValidationStatus::Failed);
+        assert!(report.checks[1]
+            .message
+            .as_ref()
+            .unwrap()
+            .contains("denied"));
+
+        let report = StorageProfileValidationReport::from_probe(
+            None,
+            StorageAccessProbe {
+                direct_access: Ok(()),
+                vended_credentials: None,
+            },
+        );
+        assert!(report.is_healthy());
+        assert_eq!(report.checks[1].status, StorageValidationStatus::Skipped);
+        assert_eq!(
+            serde_json::to_value(&report).unwrap(),
+            serde_json::json!({
+                "storage-profile-name": null,
+                "checks": [
+                    {"check": "direct-access", "status": "passed", "message": null},
+                    {
+                        "check": "vended-credentials",
+                        "status": "skipped",
+                        "message": "Storage profile does not vend credentials."
+                    }
+                ]
+            })
+        );
+    }
+}
@@ -1,17 +1,19 @@
 #![allow(clippy::match_wildcard_for_single_variants)]
 
 pub(crate) mod az;
+pub(crate) mod credential_cache;
 pub mod error;
 pub(crate) mod gcs;
+pub mod health;
 pub mod s3;
 
-use std::str::FromStr as _;
+use std::{borrow::Cow, str::FromStr as _};
 
 pub use az::{AdlsProfile, AzCredential};
 pub(crate) use error::ValidationError;
 use error::{CredentialsError, TableConfigError, UpdateError};
 use futures::StreamExt;
-pub use gcs::{GcsCredential, GcsProfile, GcsServiceKey};
+pub use gcs::{GcsCredential, GcsExternalAccountConfig, GcsHmacKey, GcsProfile, GcsServiceKey};
 use iceberg::io::FileIO;
 use iceberg_ext::{catalog::rest::ErrorModel, configs::table::TableProperties};
 use lakekeeper_io::{
@@ -21,7 +23,10 @@ use lakekeeper_io::{
 pub use s3::{S3Credential, S3Flavor, S3Profile};
 use serde::{Deserialize, Serialize};
 
-use super::{secrets::SecretInStorage, NamespaceId, TableId};
+use super::{
+    secrets::{SecretIdent, SecretInStorage, SecretReference, SecretReferenceError},
+    NamespaceId, TableId,
+};
 use crate::{
     api::{
         iceberg::v1::{tables::DataAccessMode, DataAccess},
@@ -34,9 +39,19 @@ use crate::{
         storage::error::{IcebergFileIoError, UnexpectedStorageType},
         TabularId,
     },
-    WarehouseId, CONFIG,
+    ProjectId, WarehouseId, CONFIG,
 };
 
+/// Outcome of the individual checks of [`StorageProfile::probe_access`].
+#[derive(Debug)]
+pub struct StorageAccessProbe {
+    /// Write, read, list and delete with the credential of the storage profile.
+    pub direct_access: Result<(), ValidationError>,
+    /// Write, read and delete with vended credentials, which must not allow writes outside
+    /// of the table location. `None` if the storage profile does not vend credentials.
+    pub vended_credentials: Option<Result<(), ValidationError>>,
+}
+
 /// Storage profile for a warehouse.
 #[derive(
     Debug, Clone, Eq, PartialEq, Serialize, Deserialize, derive_more::From, utoipa::ToSchema,
@@ -78,7 +93,7 @@ pub struct MemoryProfile {
     base_location: String,
 }
 
-#[derive(Debug, Clone, PartialEq, Copy)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
 pub enum StoragePermissions {
     Read,
     ReadWrite,
@@ -231,6 +246,7 @@ impl StorageProfile {
     }
 
     /// Generate the table config for the storage profile.
+    /// `storage_secret_id` is the id of `secret`. Vended credentials are cached per secret.
     ///
     /// # Errors
     /// Fails if the underlying storage profile's generation fails.
@@ -239,6 +255,7 @@ impl StorageProfile {
         &self,
         data_access: DataAccessMode,
         secret: Option<&StorageCredential>,
+        storage_secret_id: Option<SecretIdent>,
         table_location: &Location,
         storage_permissions: StoragePermissions,
         request_metadata: &RequestMetadata,
@@ -258,6 +275,7 @@ impl StorageProfile {
                         storage_permissions,
                         request_metadata,
                         warehouse_id,
+                        storage_secret_id,
                         tabular_id,
                     )
                     .await
@@ -272,6 +290,8 @@ impl StorageProfile {
                             .try_to_az()
                             .map_err(CredentialsError::from)?,
                         storage_permissions,
+                        warehouse_id,
+                        storage_secret_id,
                     )
                     .await
             }
@@ -288,6 +308,8 @@ impl StorageProfile {
                             })?,
                         table_location,
                         storage_permissions,
+                        warehouse_id,
+                        storage_secret_id,
                     )
                     .await
             }
@@ -334,7 +356,6 @@ impl StorageProfile {
     ///
     /// # Errors
     /// Fails if a file cannot be written and deleted.
-    #[allow(clippy::too_many_lines)]
     pub async fn validate_access(
         &self,
         credential: Option<&StorageCredential>,
@@ -346,15 +367,55 @@ impl StorageProfile {
             return Ok(());
         }
 
-        let io = self.file_io(credential).await?;
+        let StorageAccessProbe {
+            direct_access,
+            vended_credentials,
+        } = self
+            .probe_access(credential, location, request_metadata)
+            .await;
+        direct_access?;
+        vended_credentials.transpose()?;
+        tracing::debug!("Access validation finished");
+        Ok(())
+    }
 
-        let ns_id = NamespaceId::new_random();
-        let table_id = TableId::new_random();
-        let ns_location = self.default_namespace_location(ns_id)?;
-        let test_location = location.map_or_else(
-            || self.default_tabular_location(&ns_location, table_id.into()),
-            std::borrow::ToOwned::to_owned,
-        );
+    /// Probe physical access, reporting the outcome of each check individually.
+    /// In contrast to [`Self::validate_access`], this is not affected by
+    /// `skip_storage_validation`.
+    ///
+    /// If location is not provided, a dummy table location is used.
+    #[allow(clippy::too_many_lines)]
+    pub async fn probe_access(
+        &self,
+        credential: Option<&StorageCredential>,
+        location: Option<&Location>,
+        request_metadata: &RequestMetadata,
+    ) -> StorageAccessProbe {
+        let io = match self.file_io(credential).await {
+            Ok(io) => io,
+            // Vended credentials are not probed, as files could not be cleaned up
+            Err(e) => {
+                return StorageAccessProbe {
+                    direct_access: Err(e.into()),
+                    vended_credentials: None,
+                }
+            }
+        };
+
+        let test_location = match location {
+            Some(location) => location.clone(),
+            None => match self.default_namespace_location(NamespaceId::new_random()) {
+                Ok(ns_location) => {
+                    self.default_tabular_location(&ns_location, TableId::new_random().into())
+                }
+                Err(e) => {
+                    return StorageAccessProbe {
+                        direct_access: Err(e),
+                        vended_credentials: None,
+                    }
+                }
+            },
+        };
         tracing::debug!("Validating direct read/write access to {test_location}");
 
         // Test vended-credentials access
@@ -370,52 +431,50 @@ impl StorageProfile {
         let direct_validation = self.validate_read_write_lakekeeper(&io, &test_location);
         let vended_validation = async {
             if test_vended_credentials {
-                self.validate_vended_credentials_access(
-                    credential,
-                    &test_location,
-                    request_metadata,
+                Some(
+                    self.validate_vended_credentials_access(
+                        credential,
+                        &test_location,
+                        request_metadata,
+                    )
+                    .await,
                 )
-                .await?;
+            } else {
+                None
             }
-            Ok::<(), ValidationError>(())
         };
 
         let (direct_result, vended_result) = tokio::join!(direct_validation, vended_validation);
-        let validation_err = match (direct_result, vended_result) {
-            (Ok(()), Ok(())) => None,
-            (Err(e), Ok(()) | Err(_)) | (Ok(()), Err(e)) => Some(e),
-        };
         tracing::debug!("Cleanup started");
         if let Err(e) = io.remove_all(&test_location).await {
             tracing::warn!("Cleanup failed after validation: {e}");
         } else {
             tracing::debug!("Cleanup finished");
         }
-        if let Some(e) = validation_err {
-            return Err(e);
-        }
 
-        match is_empty(&io, &test_location).await {
-            Err(ValidationError::IoOperationFailed(io_error)) => {
-                tracing::info!("Error while checking location is empty: {io_error}");
-                Err(ValidationError::IoOperationFailed(io_error))
-            }
-            Ok(false) => Err(InvalidLocationError::new(
-                test_location.to_string(),
-                "Files are left after remove_all on test location".to_string(),
-            )
-            .into()),
-            Ok(true) => {
-                tracing::debug!("Location is empty");
-                Ok(Ok(()))
-            }
-            Err(other) => {
-                tracing::info!("Unrecoverable error: {other:?}");
-                Ok(Err(other))
-            }
-        }??;
-        tracing::debug!("Access validation finished");
-        Ok(())
+        let direct_access = match direct_result {
+            Ok(()) => match is_empty(&io, &test_location).await {
+                Ok(true) => {
+                    tracing::debug!("Location is empty");
+                    Ok(())
+                }
+                Ok(false) => Err(InvalidLocationError::new(
+                    test_location.to_string(),
+                    "Files are left after remove_all on test location".to_string(),
+                )
+                .into()),
+                Err(e) => {
+                    tracing::info!("Error while checking location is empty: {e}");
+                    Err(e)
+                }
+            },
+            Err(e) => Err(e),
+        };
+
+        StorageAccessProbe {
+            direct_access,
+            vended_credentials: vended_result,
+        }
     }
 
     /// Validate access with vended credentials
@@ -442,6 +501,7 @@ impl StorageProfile {
                 }
                 .into(),
                 credential,
+                None,
                 &sub_location,
                 StoragePermissions::ReadWriteDelete,
                 // The following arguments are used only for generating the remote signing configuration
@@ -914,6 +974,22 @@ pub enum StorageCredential {
     #[serde(rename = "gcs")]
     #[schema(title = "StorageCredentialGcs")]
     Gcs(GcsCredential),
+    /// Reference to a credential in a mounted file or an environment variable.
+    /// Only the reference is stored by Lakekeeper, the credential is read whenever it is used.
+    ///
+    /// Example payload:
+    ///
+    /// ```
+    /// use lakekeeper::service::storage::StorageCredential;
+    /// let cred: StorageCredential = serde_json::from_str(r#"{
+    ///     "type": "reference",
+    ///     "source": "file",
+    ///     "name": "aws-prod"
+    ///   }"#).unwrap();
+    /// ```
+    #[serde(rename = "reference")]
+    #[schema(title = "StorageCredentialReference")]
+    Reference(SecretReference),
 }
 
 impl SecretInStorage for StorageCredential {}
@@ -925,9 +1001,30 @@ impl StorageCredential {
             StorageCredential::S3(_) => "s3",
             StorageCredential::Az(_) => "adls",
             StorageCredential::Gcs(_) => "gcs",
+            StorageCredential::Reference(_) => "reference",
         }
     }
 
+    /// Resolve the credential if it is a [`StorageCredential::Reference`] of `project_id`.
+    ///
+    /// # Errors
+    /// Fails if the reference cannot be resolved or does not point to a storage credential.
+    pub async fn resolve_reference(
+        &self,
+        project_id: &ProjectId,
+    ) -> Result<Cow<'_, Self>, SecretReferenceError> {
+        let Self::Reference(reference) = self else {
+            return Ok(Cow::Borrowed(self));
+        };
+        let value = reference.resolve(project_id).await?;
+        serde_json::from_value(value)
+            .map(Cow::Owned)
+            .map_err(|source| SecretReferenceError::InvalidJson {
+                reference: reference.to_string(),
+                source,
+            })
+    }
+
     /// Try to convert the credential into an S3 credential.
     ///
     /// # Errors
@@ -971,6 +1068,27 @@ impl StorageCredential {
     }
 }
 
+/// Resolve a storage credential supplied by a caller if it is a [`StorageCredential::Reference`],
+/// so that it can be validated. The unresolved credential is stored.
+/// References can only point to secrets of `project_id`.
+///
+/// # Errors
+/// Fails if the reference cannot be resolved.
+pub async fn resolve_storage_credential<'a>(
+    credential: Option<&'a StorageCredential>,
+    project_id: &ProjectId,
+) -> Result<Option<Cow<'a, StorageCredential>>, ErrorModel> {
+    match credential {
+        Some(credential) => Ok(Some(
+            credential
+                .resolve_reference(project_id)
+                .await
+                .map_err(SecretReferenceError::into_bad_request)?,
+        )),
+        None => Ok(None),
+    }
+}
+
 pub fn join_location(
     prefix: &str,
     path: &str,
@@ -1416,6 +1534,7 @@ mod tests {
                 }
                 .into(),
                 Some(cred),
+                None,
                 &table_location1,
                 StoragePermissions::ReadWriteDelete,
                 &RequestMetadata::new_unauthenticated(),
@@ -1433,6 +1552,7 @@ mod tests {
                 }
                 .into(),
                 Some(cred),
+                None,
                 &table_location2,
                 StoragePermissions::ReadWriteDelete,
                 &RequestMetadata::new_unauthenticated(),
@@ -5,6 +5,7 @@ use std::{collections::HashMap, sync::LazyLock};
 use aws_config::SdkConfig;
 use aws_sdk_sts::{config::ProvideCredentials as _, types::Tag};
 use aws_smithy_runtime_api::client::identity::Identity;
+use axum_prometheus::metrics;
 use iceberg_ext::{
     catalog::rest::ErrorModel,
     configs::{
@@ -17,6 +18,7 @@ use lakekeeper_io::{
         validate_bucket_name, S3AccessKeyAuth, S3Auth, S3AwsSystemIdentityAuth, S3Location,
         S3Settings, S3Storage,
     },
+    trace_context::TraceContextInterceptor,
     InvalidLocationError, Location,
 };
 use serde::{Deserialize, Serialize};
@@ -35,18 +37,23 @@ use crate::{
     request_metadata::RequestMetadata,
     service::{
         storage::{
+            credential_cache::{CredentialCache, CredentialCacheKey},
             error::{
                 CredentialsError, IcebergFileIoError, InvalidProfileError, TableConfigError,
                 UpdateError, ValidationError,
             },
             StoragePermissions, TableConfig,
         },
-        TabularId,
+        Actor, RoleId, SecretIdent, TabularId, UserId,
     },
     WarehouseId, CONFIG,
 };
 
 static S3_HTTP_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(reqwest::Client::new);
+pub(super) static STS_CREDENTIAL_CACHE: LazyLock<CredentialCache<aws_sdk_sts::types::Credentials>> =
+    LazyLock::new(|| CredentialCache::new("s3"));
+
+const METRIC_STS_REQUEST_DURATION: &str = "lakekeeper_sts_request_duration_seconds";
 
 #[derive(
     Debug,
@@ -97,6 +104,19 @@ pub struct S3Profile {
     #[serde(default)]
     #[builder(default)]
     pub sts_session_tags: HashMap<String, String>,
+    /// Role ARNs to assume for sts vended-credentials of specific principals.
+    /// The first mapping that matches the principal of the request is used.
+    /// If no mapping matches, `sts_role_arn` is used.
+    #[serde(default)]
+    #[builder(default)]
+    pub sts_role_mappings: Vec<S3StsRoleMapping>,
+    /// Add session tags identifying the principal of the request to STS assume role
+    /// operations: `lakekeeper:user-id` and, if a role is assumed, `lakekeeper:role-id`.
+    /// Tagged sessions are visible in `CloudTrail` and can be used in ABAC policies.
+    /// Default: false
+    #[serde(default)]
+    #[builder(default)]
+    pub sts_principal_session_tags: bool,
     /// S3 flavor to use.
     /// Defaults to AWS
     #[serde(default)]
@@ -149,6 +169,145 @@ pub struct S3Profile {
     #[serde(default)]
     #[builder(default, setter(strip_option))]
     pub aws_kms_key_arn: Option<String>,
+    /// ARN of an S3 access point to access the bucket through.
+    /// Example: `arn:aws:s3:us-east-1:123456789012:accesspoint/my-access-point`
+    ///
+    /// Table locations keep using the bucket name. Lakekeeper sends its own requests to the
+    /// access point, scopes vended-credentials to it and sends the
+    /// `s3.access-points.<bucket>` property to clients.
+    #[serde(default)]
+    #[builder(default, setter(strip_option))]
+    pub access_point_arn: Option<String>,
+    /// Allow vending credentials for directory buckets (S3 Express One Zone).
+    /// Directory bucket sessions can not be restricted to a table location, so
+    /// vended-credentials grant access to the whole bucket.
+    /// Required if `sts-enabled` is true for a directory bucket. Default: false
+    #[serde(default)]
+    #[builder(default)]
+    pub allow_bucket_wide_vended_credentials: bool,
+}
+
+/// Role assumed for sts vended-credentials of a principal.
+#[derive(Debug, Eq, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct S3StsRoleMapping {
+    /// Principal the mapping applies to.
+    pub principal: S3StsPrincipal,
+    /// Role ARN to assume for the principal.
+    pub sts_role_arn: String,
+}
+
+#[derive(Debug, Eq, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case", tag = "type")]
+pub enum S3StsPrincipal {
+    /// Requests of a user, regardless of the role the user assumed.
+    #[schema(title = "S3StsPrincipalUser")]
+    #[serde(rename_all = "kebab-case")]
+    User {
+        #[schema(value_type = String)]
+        user_id: UserId,
+    },
+    /// Requests with an assumed Lakekeeper role.
+    #[schema(title = "S3StsPrincipalRole")]
+    #[serde(rename_all = "kebab-case")]
+    Role {
+        #[schema(value_type = uuid::Uuid)]
+        role_id: RoleId,
+    },
+}
+
+impl S3StsPrincipal {
+    fn matches(&self, actor: &Actor) -> bool {
+        match (self, actor) {
+            (
+                Self::User { user_id },
+                Actor::Principal(principal) | Actor::Role { principal, .. },
+            ) => user_id == principal,
+            (Self::Role { role_id }, Actor::Role { assumed_role, .. }) => role_id == assumed_role,
+            _ => false,
+        }
+    }
+}
+
+const PRINCIPAL_SESSION_TAG_USER_ID: &str = "lakekeeper:user-id";
+const PRINCIPAL_SESSION_TAG_ROLE_ID: &str = "lakekeeper:role-id";
+
+/// Replace characters not allowed in STS session tag values by `_`
+/// and truncate to the maximum length of 256 characters.
+fn session_tag_value(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c.is_whitespace() || "_.:/=+-@".contains(c) {
+                c
+            } else {
+                '_'
+            }
+        })
+        .take(256)
+        .collect()
+}
+
+/// Suffix of S3 directory bucket (S3 Express One Zone) names,
+/// for example `my-bucket--usw2-az1--x-s3`.
+const DIRECTORY_BUCKET_SUFFIX: &str = "--x-s3";
+
+/// Parsed S3 access point ARN of the form
+/// `arn:<partition>:s3:<region>:<account-id>:accesspoint/<name>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct S3AccessPointArn<'a> {
+    region: &'a str,
+    account_id: &'a str,
+    name: &'a str,
+}
+
+impl<'a> S3AccessPointArn<'a> {
+    fn parse(arn: &'a str) -> Result<Self, String> {
+        let invalid = || {
+            format!(
+                "Invalid access point ARN `{arn}`. Expected `arn:<partition>:s3:<region>:<account-id>:accesspoint/<name>`."
+            )
+        };
+        let mut parts = arn.splitn(6, ':');
+        let (
+            Some("arn"),
+            Some(partition),
+            Some("s3"),
+            Some(region),
+            Some(account_id),
+            Some(resource),
+        ) = (
+            parts.next(),
+            parts.next(),
+            parts.next(),
+            parts.next(),
+            parts.next(),
+            parts.next(),
+        )
+        else {
+            return Err(invalid());
+        };
+        let name = resource.strip_prefix("accesspoint/").ok_or_else(invalid)?;
+        if partition.is_empty() || account_id.is_empty() || name.is_empty() || name.contains('/') {
+            return Err(invalid());
+        }
+        if region.is_empty() {
+            return Err(format!(
+                "Multi-Region Access Points are not supported. Found `{arn}`."
+            ));
+        }
+        Ok(Self {
+            region,
+            account_id,
+            name,
+        })
+    }
+
+    /// First label of the access point's virtual host, i.e.
+    /// `<name>-<account-id>.s3-accesspoint.<region>.amazonaws.com`.
+    fn host_label(&self) -> String {
+        format!("{}-{}", self.name, self.account_id)
+    }
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default, ToSchema)]
@@ -229,6 +388,22 @@ impl S3Profile {
         self.allow_alternative_protocols.unwrap_or_default()
     }
 
+    /// Whether the bucket is an S3 directory bucket (S3 Express One Zone).
+    #[must_use]
+    pub fn is_directory_bucket(&self) -> bool {
+        self.bucket.ends_with(DIRECTORY_BUCKET_SUFFIX)
+    }
+
+    /// Host label clients use to address the access point of this profile,
+    /// i.e. `<name>-<account-id>` in `<name>-<account-id>.s3-accesspoint.<region>.amazonaws.com`.
+    #[must_use]
+    pub fn access_point_host_label(&self) -> Option<String> {
+        self.access_point_arn
+            .as_deref()
+            .and_then(|arn| S3AccessPointArn::parse(arn).ok())
+            .map(|arn| arn.host_label())
+    }
+
     /// Check if a s3 variant is allowed.
     /// By default, only `s3` is allowed.
     /// If `allow_variant_schemes` is set, `s3a` and `s3n` are also allowed.
@@ -305,11 +480,24 @@ impl S3Profile {
         self.normalize_assume_role_arn();
         self.normalize_sts_role_arn();
         self.normalize_kms_key_arn();
+        self.normalize_sts_role_mappings()?;
+        self.normalize_access_point_arn()?;
 
         if let Some(S3Credential::CloudflareR2(cloudflare_r2_credential)) = s3_credential {
             self.normalize_r2(cloudflare_r2_credential)?;
         }
 
+        if self.sts_enabled
+            && self.is_directory_bucket()
+            && !self.allow_bucket_wide_vended_credentials
+        {
+            return Err(InvalidProfileError {
+                source: None,
+                reason: "Vended-credentials for directory buckets grant access to the whole bucket. Set `allow-bucket-wide-vended-credentials` to enable STS for this bucket.".to_string(),
+                entity: "allow-bucket-wide-vended-credentials".to_string(),
+            }.into());
+        }
+
         if self.sts_enabled
             && matches!(self.flavor, S3Flavor::Aws)
             && self.sts_role_arn.is_none()
@@ -402,6 +590,7 @@ impl S3Profile {
         storage_permissions: StoragePermissions,
         request_metadata: &RequestMetadata,
         warehouse_id: WarehouseId,
+        storage_secret_id: Option<SecretIdent>,
         tabular_id: TabularId,
     ) -> Result<TableConfig, TableConfigError> {
         let (remote_signing, vended_credentials) = match data_access {
@@ -437,49 +626,78 @@ impl S3Profile {
             config.insert(&s3::Endpoint(endpoint.clone()));
         }
 
+        if let Some(access_point_arn) = &self.access_point_arn {
+            config.insert(&custom::CustomConfig {
+                key: format!("s3.access-points.{}", self.bucket),
+                value: access_point_arn.clone(),
+            });
+        }
+
         if vended_credentials {
             if self.sts_enabled || matches!(s3_credential, Some(S3Credential::CloudflareR2(..))) {
+                let actor = request_metadata.actor();
+                let sts_arn = self.sts_role_arn_for(actor).map(ToString::to_string);
+                if sts_arn.is_none()
+                    && S3Flavor::Aws == self.flavor
+                    && !matches!(s3_credential, Some(S3Credential::CloudflareR2(..)))
+                {
+                    return Err(TableConfigError::Misconfiguration(
+                        "Either `sts-role-arn` or `assume-role-arn` is required for Storage Profiles with AWS flavor if STS is enabled.".to_string(),
+                    ));
+                }
+                let session_tags = self.session_tags_for(actor);
+                // Credentials with principal session tags may only be shared by the same principal
+                let identity = if self.sts_principal_session_tags {
+                    Some(format!(
+                        "{}|{actor}",
+                        sts_arn.as_deref().unwrap_or_default()
+                    ))
+                } else {
+                    sts_arn.clone()
+                };
+                let cache_key = CredentialCacheKey {
+                    warehouse_id,
+                    storage_secret_id,
+                    table_location: table_location.to_string(),
+                    storage_permissions,
+                    identity,
+                };
                 let aws_sdk_sts::types::Credentials {
                     access_key_id,
                     secret_access_key,
                     session_token,
-                    expiration: _,
                     ..
-                } = match s3_credential.cloned() {
-                    Some(S3Credential::CloudflareR2(c)) => {
-                        self.get_cloudflare_r2_temporary_credentials(
-                            table_location,
-                            c,
-                            storage_permissions,
-                        )
-                        .await?
-                    }
-                    c @ (Some(
-                        S3Credential::AccessKey(..) | S3Credential::AwsSystemIdentity(..),
-                    )
-                    | None) => {
-                        let auth = c.map(S3Auth::try_from).transpose()?;
-                        let sts_arn = self
-                            .sts_role_arn
-                            .as_ref()
-                            .or(self.assume_role_arn.as_ref())
-                            .map(String::as_str);
-
-                        if sts_arn.is_none() && S3Flavor::Aws == self.flavor {
-                            return Err(TableConfigError::Misconfiguration(
-                                "Either `sts-role-arn` or `assume-role-arn` is required for Storage Profiles with AWS flavor if STS is enabled.".to_string(),
-                            ));
-                        }
-
-                        self.get_sts_token(
-                            table_location,
-                            auth.as_ref(),
-                            sts_arn,
-                            storage_permissions,
-                        )
-                        .await?
-                    }
-                };
+                } = STS_CREDENTIAL_CACHE
+                    .get_or_try_insert_with(cache_key, async {
+                        let credentials = match s3_credential.cloned() {
+                            Some(S3Credential::CloudflareR2(c)) => {
+                                self.get_cloudflare_r2_temporary_credentials(
+                                    table_location,
+                                    c,
+                                    storage_permissions,
+                                )
+                                .await?
+                            }
+                            c @ (Some(
+                                S3Credential::AccessKey(..) | S3Credential::AwsSystemIdentity(..),
+                            )
+                            | None) => {
+                                let auth = c.map(S3Auth::try_from).transpose()?;
+                                self.get_sts_token(
+                                    table_location,
+                                    auth.as_ref(),
+                                    sts_arn.as_deref(),
+                                    &session_tags,
+                                    storage_permissions,
+                                )
+                                .await?
+                            }
+                        };
+                        let expires_at = std::time::SystemTime::try_from(credentials.expiration)
+                            .unwrap_or_else(|_| std::time::SystemTime::now());
+                        Ok::<_, CredentialsError>((credentials, expires_at))
+                    })
+                    .await?;
 
                 config.insert(&s3::AccessKeyId(access_key_id.clone()));
                 config.insert(&s3::SecretAccessKey(secret_access_key.clone()));
@@ -612,17 +830,60 @@ impl S3Profile {
         table_location: &Location,
         s3_credential: Option<&S3Auth>,
         role_arn: Option<&str>,
+        session_tags: &HashMap<String, String>,
         storage_permissions: StoragePermissions,
     ) -> Result<aws_sdk_sts::types::Credentials, CredentialsError> {
         let policy = self.get_sts_policy_string(table_location, storage_permissions)?;
-        self.assume_role_with_sts(s3_credential, role_arn, Some(policy))
+        self.assume_role_with_sts(s3_credential, role_arn, session_tags, Some(policy))
             .await
     }
 
+    /// Role ARN to assume for sts vended-credentials of `actor`.
+    fn sts_role_arn_for(&self, actor: &Actor) -> Option<&str> {
+        self.sts_role_mappings
+            .iter()
+            .find(|mapping| mapping.principal.matches(actor))
+            .map(|mapping| mapping.sts_role_arn.as_str())
+            .or(self.sts_role_arn.as_deref())
+            .or(self.assume_role_arn.as_deref())
+    }
+
+    /// Session tags for sts vended-credentials of `actor`.
+    fn session_tags_for(&self, actor: &Actor) -> HashMap<String, String> {
+        let mut tags = self.sts_session_tags.clone();
+        if !self.sts_principal_session_tags {
+            return tags;
+        }
+        match actor {
+            Actor::Principal(user_id) => {
+                tags.insert(
+                    PRINCIPAL_SESSION_TAG_USER_ID.to_string(),
+                    session_tag_value(&user_id.to_string()),
+                );
+            }
+            Actor::Role {
+                principal,
+                assumed_role,
+            } => {
+                tags.insert(
+                    PRINCIPAL_SESSION_TAG_USER_ID.to_string(),
+                    session_tag_value(&principal.to_string()),
+                );
+                tags.insert(
+                    PRINCIPAL_SESSION_TAG_ROLE_ID.to_string(),
+                    assumed_role.to_string(),
+                );
+            }
+            Actor::Anonymous => {}
+        }
+        tags
+    }
+
     async fn assume_role_with_sts(
         &self,
         s3_credentials: Option<&S3Auth>,
         role_arn: Option<&str>,
+        session_tags: &HashMap<String, String>,
         policy: Option<String>,
     ) -> Result<aws_sdk_sts::types::Credentials, CredentialsError> {
         let external_id = s3_credentials
@@ -653,7 +914,10 @@ impl S3Profile {
         // the `assume-role-arn` role first.
         let sdk_config = self.get_aws_sdk_config(s3_credentials, None).await?;
 
-        let assume_role_builder = aws_sdk_sts::Client::new(&sdk_config)
+        let sts_config = aws_sdk_sts::config::Builder::from(&sdk_config)
+            .interceptor(TraceContextInterceptor)
+            .build();
+        let assume_role_builder = aws_sdk_sts::Client::from_conf(sts_config)
             .assume_role()
             .role_session_name("lakekeeper-sts")
             .duration_seconds(i32::try_from(self.sts_token_validity_seconds).unwrap_or(3600));
@@ -679,11 +943,10 @@ impl S3Profile {
             assume_role_builder
         };
 
-        let assume_role_builder = if self.sts_session_tags.is_empty() {
+        let assume_role_builder = if session_tags.is_empty() {
             assume_role_builder
         } else {
-            let tags: Vec<Tag> = self
-                .sts_session_tags
+            let tags: Vec<Tag> = session_tags
                 .iter()
                 .map(|(key, value)| {
                     Tag::builder().key(key).value(value).build().map_err(|e| {
@@ -697,7 +960,15 @@ impl S3Profile {
             assume_role_builder.set_tags(Some(tags))
         };
 
-        let v = assume_role_builder.send().await.map_err(|e| {
+        let started_at = std::time::Instant::now();
+        let result = assume_role_builder.send().await;
+        metrics::histogram!(
+            METRIC_STS_REQUEST_DURATION,
+            "status" => if result.is_ok() { "success" } else { "failure" },
+        )
+        .record(started_at.elapsed());
+
+        let v = result.map_err(|e| {
             let err_str = format!("{e:?}");
             tracing::warn!("Failed to assume role via STS: {err_str}");
             CredentialsError::ShortTermCredential {
@@ -798,14 +1069,31 @@ impl S3Profile {
                 reason: format!("Could not generate downscoped policy for temporary credentials as location is no valid S3 location: {e}").to_string(),
             }
         })?;
-        let bucket_arn = format!(
-            "arn:aws:s3:::{}",
-            table_location.bucket_name().trim_end_matches('/')
-        );
+        let bucket = table_location.bucket_name().trim_end_matches('/');
         let key = format!("{}/", table_location.key().join("/"));
 
-        let mut statements = format!(
-            r#"
+        let mut statements = if self.is_directory_bucket() {
+            if !self.allow_bucket_wide_vended_credentials {
+                return Err(CredentialsError::ShortTermCredential {
+                    source: None,
+                    reason: "Vended-credentials for directory buckets grant access to the whole bucket and require `allow-bucket-wide-vended-credentials` in the Storage Profile.".to_string(),
+                });
+            }
+            self.directory_bucket_policy_statements(bucket, storage_permissions)
+        } else {
+            // Objects of access points are addressed as `<access-point-arn>/object/<key>`
+            let (bucket_arn, object_arn_prefix) = match self.access_point_arn.as_ref() {
+                Some(access_point_arn) => (
+                    access_point_arn.clone(),
+                    format!("{access_point_arn}/object"),
+                ),
+                None => {
+                    let bucket_arn = format!("arn:aws:s3:::{bucket}");
+                    (bucket_arn.clone(), bucket_arn)
+                }
+            };
+            format!(
+                r#"
             {{
                 "Sid": "TableAccess",
                 "Effect": "Allow",
@@ -813,8 +1101,8 @@ impl S3Profile {
                     {}
                 ],
                 "Resource": [
-                    "{bucket_arn}/{key}",
-                    "{bucket_arn}/{key}*"
+                    "{object_arn_prefix}/{key}",
+                    "{object_arn_prefix}/{key}*"
                 ]
             }},
             {{
@@ -829,10 +1117,11 @@ impl S3Profile {
                 }}
             }}
         "#,
-            Self::permission_to_actions(storage_permissions),
-        )
-        .replace('\n', "")
-        .replace(' ', "");
+                Self::permission_to_actions(storage_permissions),
+            )
+            .replace('\n', "")
+            .replace(' ', "")
+        };
 
         if let Some(kms_key_arn) = self.aws_kms_key_arn.as_ref() {
             statements = format!(
@@ -862,6 +1151,26 @@ impl S3Profile {
         .replace(' ', ""))
     }
 
+    /// Directory buckets authorize object operations through sessions created with
+    /// `s3express:CreateSession`. Sessions are scoped to the whole bucket and only
+    /// distinguish between read-only and read-write access.
+    fn directory_bucket_policy_statements(
+        &self,
+        bucket: &str,
+        storage_permissions: StoragePermissions,
+    ) -> String {
+        let condition = match storage_permissions {
+            StoragePermissions::Read => {
+                r#","Condition":{"StringEquals":{"s3express:SessionMode":"ReadOnly"}}"#
+            }
+            StoragePermissions::ReadWrite | StoragePermissions::ReadWriteDelete => "",
+        };
+        format!(
+            r#"{{"Sid":"DirectoryBucketSession","Effect":"Allow","Action":"s3express:CreateSession","Resource":"arn:aws:s3express:{}:*:bucket/{bucket}"{condition}}}"#,
+            self.region
+        )
+    }
+
     fn normalize_key_prefix(&mut self) -> Result<(), ValidationError> {
         if let Some(key_prefix) = self.key_prefix.as_mut() {
             *key_prefix = key_prefix.trim().trim_matches('/').to_string();
@@ -951,6 +1260,76 @@ impl S3Profile {
         }
     }
 
+    fn normalize_sts_role_mappings(&mut self) -> Result<(), InvalidProfileError> {
+        for mapping in &mut self.sts_role_mappings {
+            mapping.sts_role_arn = mapping.sts_role_arn.trim().to_string();
+            if mapping.sts_role_arn.is_empty() {
+                return Err(InvalidProfileError {
+                    source: None,
+                    reason: "`sts-role-arn` of a role mapping must not be empty.".to_string(),
+                    entity: "sts-role-mappings".to_string(),
+                });
+            }
+        }
+
+        if self.sts_principal_session_tags {
+            if let Some(key) = [PRINCIPAL_SESSION_TAG_USER_ID, PRINCIPAL_SESSION_TAG_ROLE_ID]
+                .into_iter()
+                .find(|key| self.sts_session_tags.contains_key(*key))
+            {
+                return Err(InvalidProfileError {
+                    source: None,
+                    reason: format!(
+                        "Session tag `{key}` is reserved if `sts-principal-session-tags` is enabled."
+                    ),
+                    entity: "sts-session-tags".to_string(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn normalize_access_point_arn(&mut self) -> Result<(), InvalidProfileError> {
+        if let Some(access_point_arn) = self.access_point_arn.as_ref() {
+            if access_point_arn.trim().is_empty() {
+                self.access_point_arn = None;
+            } else {
+                self.access_point_arn = Some(access_point_arn.trim().to_string());
+            }
+        }
+
+        let Some(access_point_arn) = self.access_point_arn.as_deref() else {
+            return Ok(());
+        };
+        let err = |reason: String| InvalidProfileError {
+            source: None,
+            reason,
+            entity: "access-point-arn".to_string(),
+        };
+
+        if self.flavor != S3Flavor::Aws {
+            return Err(err(
+                "Access points are only supported for Storage Profiles with AWS flavor."
+                    .to_string(),
+            ));
+        }
+        if self.is_directory_bucket() {
+            return Err(err(
+                "Access points are not supported for directory buckets.".to_string(),
+            ));
+        }
+        let parsed = S3AccessPointArn::parse(access_point_arn).map_err(err)?;
+        if parsed.region != self.region {
+            return Err(err(format!(
+                "Region of the access point `{}` does not match the region of the Storage Profile `{}`.",
+                parsed.region, self.region
+            )));
+        }
+
+        Ok(())
+    }
+
     fn normalize_kms_key_arn(&mut self) {
         if let Some(aws_kms_key_arn) = self.aws_kms_key_arn.as_ref() {
             if aws_kms_key_arn.trim().is_empty() {
@@ -968,6 +1347,8 @@ impl S3Profile {
         // No assume role ARNs are supported, set them to None
         self.assume_role_arn = None;
         self.sts_role_arn = None;
+        self.sts_role_mappings.clear();
+        self.access_point_arn = None;
         self.sts_enabled = true;
         self.flavor = S3Flavor::S3Compat;
 
@@ -992,6 +1373,11 @@ fn storage_profile_to_s3_settings(profile: &S3Profile) -> S3Settings {
         path_style_access: profile.path_style_access,
         assume_role_arn: profile.assume_role_arn.clone(),
         aws_kms_key_arn: profile.aws_kms_key_arn.clone(),
+        access_points: profile
+            .access_point_arn
+            .iter()
+            .map(|arn| (profile.bucket.clone(), arn.clone()))
+            .collect(),
     }
 }
 
@@ -1259,12 +1645,16 @@ pub(crate) mod test {
             sts_role_arn: None,
             sts_enabled: false,
             sts_session_tags: HashMap::new(),
+            sts_role_mappings: vec![],
+            sts_principal_session_tags: false,
             flavor: S3Flavor::Aws,
             allow_alternative_protocols: Some(false),
             remote_signing_url_style: S3UrlStyleDetectionMode::Auto,
             sts_token_validity_seconds: 3600,
             push_s3_delete_disabled: false,
             aws_kms_key_arn: None,
+            access_point_arn: None,
+            allow_bucket_wide_vended_credentials: false,
         };
         let sp: StorageProfile = profile.clone().into();
 
@@ -1304,12 +1694,16 @@ pub(crate) mod test {
             sts_role_arn: None,
             sts_enabled: false,
             sts_session_tags: HashMap::new(),
+            sts_role_mappings: vec![],
+            sts_principal_session_tags: false,
             flavor: S3Flavor::Aws,
             allow_alternative_protocols: Some(false),
             remote_signing_url_style: S3UrlStyleDetectionMode::Auto,
             sts_token_validity_seconds: 3600,
             push_s3_delete_disabled: false,
             aws_kms_key_arn: None,
+            access_point_arn: None,
+            allow_bucket_wide_vended_credentials: false,
         };
 
         let namespace_location = Location::from_str("s3://test-bucket/foo/").unwrap();
@@ -1327,6 +1721,97 @@ pub(crate) mod test {
         assert_eq!(location.to_string(), expected);
     }
 
+    #[test]
+    fn test_sts_role_mappings() {
+        let user_id = UserId::new_unchecked("oidc", "mapped-user");
+        let role_id = RoleId::new_random();
+        let profile = S3Profile::builder()
+            .bucket("test-bucket".to_string())
+            .region("dummy".to_string())
+            .sts_enabled(true)
+            .flavor(S3Flavor::Aws)
+            .sts_role_arn("arn:aws:iam::123456789012:role/default".to_string())
+            .sts_role_mappings(vec![
+                S3StsRoleMapping {
+                    principal: S3StsPrincipal::Role { role_id },
+                    sts_role_arn: "arn:aws:iam::123456789012:role/role".to_string(),
+                },
+                S3StsRoleMapping {
+                    principal: S3StsPrincipal::User {
+                        user_id: user_id.clone(),
+                    },
+                    sts_role_arn: "arn:aws:iam::123456789012:role/user".to_string(),
+                },
+            ])
+            .build();
+
+        let other_user = UserId::new_unchecked("oidc", "other-user");
+        assert_eq!(
+            profile.sts_role_arn_for(&Actor::Principal(user_id.clone())),
+            Some("arn:aws:iam::123456789012:role/user")
+        );
+        // Role mapping comes first
+        assert_eq!(
+            profile.sts_role_arn_for(&Actor::Role {
+                principal: user_id,
+                assumed_role: role_id,
+            }),
+            Some("arn:aws:iam::123456789012:role/role")
+        );
+        assert_eq!(
+            profile.sts_role_arn_for(&Actor::Role {
+                principal: other_user.clone(),
+                assumed_role: RoleId::new_random(),
+            }),
+            Some("arn:aws:iam::123456789012:role/default")
+        );
+        assert_eq!(
+            profile.sts_role_arn_for(&Actor::Principal(other_user)),
+            Some("arn:aws:iam::123456789012:role/default")
+        );
+        assert_eq!(
+            profile.sts_role_arn_for(&Actor::Anonymous),
+            Some("arn:aws:iam::123456789012:role/default")
+        );
+    }
+
+    #[test]
+    fn test_principal_session_tags() {
+        let mut profile = S3Profile::builder()
+            .bucket("test-bucket".to_string())
+            .region("dummy".to_string())
+            .sts_enabled(true)
+            .flavor(S3Flavor::Aws)
+            .sts_session_tags(HashMap::from([("team".to_string(), "data".to_string())]))
+            .build();
+        let user_id = UserId::new_unchecked("oidc", "user~1");
+        let role_id = RoleId::new_random();
+        let actor = Actor::Role {
+            principal: user_id,
+            assumed_role: role_id,
+        };
+
+        // Disabled by default
+        assert_eq!(profile.session_tags_for(&actor), profile.sts_session_tags);
+
+        profile.sts_principal_session_tags = true;
+        let tags = profile.session_tags_for(&actor);
+        assert_eq!(tags.len(), 3);
+        assert_eq!(tags["team"], "data");
+        assert_eq!(tags[PRINCIPAL_SESSION_TAG_USER_ID], "oidc_user_1");
+        assert_eq!(tags[PRINCIPAL_SESSION_TAG_ROLE_ID], role_id.to_string());
+        assert_eq!(profile.session_tags_for(&Actor::Anonymous).len(), 1);
+
+        assert_eq!(session_tag_value(&"a".repeat(300)).len(), 256);
+
+        // Reserved tags may not be configured statically
+        profile.sts_session_tags.insert(
+            PRINCIPAL_SESSION_TAG_USER_ID.to_string(),
+            "static".to_string(),
+        );
+        assert!(profile.normalize_sts_role_mappings().is_err());
+    }
+
     pub(crate) mod minio_integration_tests {
         use std::{collections::HashMap, sync::LazyLock};
 
@@ -1359,6 +1844,8 @@ pub(crate) mod test {
                 path_style_access: Some(true),
                 sts_role_arn: None,
                 sts_session_tags: HashMap::new(),
+                sts_role_mappings: vec![],
+                sts_principal_session_tags: false,
                 flavor: S3Flavor::S3Compat,
                 sts_enabled: true,
                 allow_alternative_protocols: Some(false),
@@ -1367,6 +1854,8 @@ pub(crate) mod test {
                 sts_token_validity_seconds: 3600,
                 push_s3_delete_disabled: false,
                 aws_kms_key_arn: None,
+                access_point_arn: None,
+                allow_bucket_wide_vended_credentials: false,
             };
             let cred = S3Credential::AccessKey(S3AccessKeyCredential {
                 aws_access_key_id: TEST_ACCESS_KEY.clone(),
@@ -1418,6 +1907,8 @@ pub(crate) mod test {
                 path_style_access: Some(true),
                 sts_role_arn: Some(std::env::var("AWS_S3_STS_ROLE_ARN").unwrap()),
                 sts_session_tags: HashMap::new(),
+                sts_role_mappings: vec![],
+                sts_principal_session_tags: false,
                 flavor: S3Flavor::Aws,
                 sts_enabled: true,
                 allow_alternative_protocols: Some(false),
@@ -1426,6 +1917,8 @@ pub(crate) mod test {
                 sts_token_validity_seconds: 3600,
                 push_s3_delete_disabled: false,
                 aws_kms_key_arn: None,
+                access_point_arn: None,
+                allow_bucket_wide_vended_credentials: false,
             };
             let cred = S3Credential::AccessKey(S3AccessKeyCredential {
                 aws_access_key_id: std::env::var("AWS_S3_ACCESS_KEY_ID").unwrap(),
@@ -1513,6 +2006,8 @@ pub(crate) mod test {
                 path_style_access: Some(true),
                 sts_role_arn: None,
                 sts_session_tags: HashMap::new(),
+                sts_role_mappings: vec![],
+                sts_principal_session_tags: false,
                 flavor: S3Flavor::Aws,
                 sts_enabled: true,
                 allow_alternative_protocols: Some(false),
@@ -1521,6 +2016,8 @@ pub(crate) mod test {
                 sts_token_validity_seconds: 3600,
                 push_s3_delete_disabled: false,
                 aws_kms_key_arn: Some(std::env::var("AWS_S3_KMS_ARN").unwrap()),
+                access_point_arn: None,
+                allow_bucket_wide_vended_credentials: false,
             };
             let cred = S3Credential::AccessKey(S3AccessKeyCredential {
                 aws_access_key_id: std::env::var("AWS_S3_ACCESS_KEY_ID").unwrap(),
@@ -1578,12 +2075,16 @@ pub(crate) mod test {
                 flavor: S3Flavor::S3Compat,
                 sts_enabled: true,
                 sts_session_tags: HashMap::new(),
+                sts_role_mappings: vec![],
+                sts_principal_session_tags: false,
                 allow_alternative_protocols: Some(false),
                 remote_signing_url_style:
                     crate::service::storage::s3::S3UrlStyleDetectionMode::Auto,
                 sts_token_validity_seconds: 3600,
                 push_s3_delete_disabled: false,
                 aws_kms_key_arn: None,
+                access_point_arn: None,
+                allow_bucket_wide_vended_credentials: false,
             };
             let cred = S3Credential::CloudflareR2(S3CloudflareR2Credential {
                 access_key_id: std::env::var("LAKEKEEPER_TEST__R2_ACCESS_KEY_ID").unwrap(),
@@ -1639,6 +2140,140 @@ pub(crate) mod test {
             .unwrap();
         let _ = serde_json::from_str::<serde_json::Value>(&policy).unwrap();
     }
+
+    #[test]
+    fn test_access_point_policy_and_config() {
+        let access_point_arn = "arn:aws:s3:us-east-1:123456789012:accesspoint/my-ap";
+        let mut profile = S3Profile::builder()
+            .bucket("bucket-name".to_string())
+            .region("us-east-1".to_string())
+            .sts_enabled(true)
+            .sts_role_arn("arn:aws:iam::123456789012:role/sts".to_string())
+            .access_point_arn(format!(" {access_point_arn} "))
+            .build();
+        profile.normalize(None).unwrap();
+        assert_eq!(profile.access_point_arn.as_deref(), Some(access_point_arn));
+        assert_eq!(
+            profile.access_point_host_label().as_deref(),
+            Some("my-ap-123456789012")
+        );
+        assert_eq!(
+            storage_profile_to_s3_settings(&profile).access_points,
+            HashMap::from([("bucket-name".to_string(), access_point_arn.to_string())])
+        );
+
+        let policy = profile
+            .get_sts_policy_string(
+                &"s3://bucket-name/ns/table".parse().unwrap(),
+                StoragePermissions::Read,
+            )
+            .unwrap();
+        let policy = serde_json::from_str::<serde_json::Value>(&policy).unwrap();
+        assert_eq!(
+            policy["Statement"][0]["Resource"],
+            serde_json::json!([
+                format!("{access_point_arn}/object/ns/table/"),
+                format!("{access_point_arn}/object/ns/table/*")
+            ])
+        );
+        assert_eq!(policy["Statement"][1]["Resource"], access_point_arn);
+    }
+
+    #[test]
+    fn test_invalid_access_point_arn() {
+        for (arn, region, flavor) in [
+            ("my-ap", "us-east-1", S3Flavor::Aws),
+            (
+                "arn:aws:s3:us-east-1:123456789012:bucket/my-ap",
+                "us-east-1",
+                S3Flavor::Aws,
+            ),
+            (
+                "arn:aws:s3::123456789012:accesspoint/my-ap.mrap",
+                "us-east-1",
+                S3Flavor::Aws,
+            ),
+            (
+                "arn:aws:s3:us-east-1:123456789012:accesspoint/my-ap",
+                "eu-central-1",
+                S3Flavor::Aws,
+            ),
+            (
+                "arn:aws:s3:us-east-1:123456789012:accesspoint/my-ap",
+                "us-east-1",
+                S3Flavor::S3Compat,
+            ),
+        ] {
+            let mut profile = S3Profile::builder()
+                .bucket("bucket-name".to_string())
+                .region(region.to_string())
+                .flavor(flavor)
+                .sts_enabled(false)
+                .access_point_arn(arn.to_string())
+                .build();
+            assert!(profile.normalize(None).is_err(), "{arn} should be invalid");
+        }
+    }
+
+    #[test]
+    fn test_directory_bucket_policy() {
+        let profile = S3Profile::builder()
+            .bucket("my-bucket--usw2-az1--x-s3".to_string())
+            .region("us-west-2".to_string())
+            .sts_enabled(true)
+            .allow_bucket_wide_vended_credentials(true)
+            .build();
+        assert!(profile.is_directory_bucket());
+
+        for (permissions, session_mode) in [
+            (StoragePermissions::Read, Some("ReadOnly")),
+            (StoragePermissions::ReadWriteDelete, None),
+        ] {
+            let policy = profile
+                .get_sts_policy_string(
+                    &"s3://my-bucket--usw2-az1--x-s3/ns/table".parse().unwrap(),
+                    permissions,
+                )
+                .unwrap();
+            let policy = serde_json::from_str::<serde_json::Value>(&policy).unwrap();
+            let statement = &policy["Statement"][0];
+            assert_eq!(statement["Action"], "s3express:CreateSession");
+            assert_eq!(
+                statement["Resource"],
+                "arn:aws:s3express:us-west-2:*:bucket/my-bucket--usw2-az1--x-s3"
+            );
+            assert_eq!(
+                statement["Condition"]["StringEquals"]["s3express:SessionMode"].as_str(),
+                session_mode
+            );
+        }
+    }
+
+    #[test]
+    fn test_directory_bucket_requires_bucket_wide_vending_flag() {
+        let mut profile = S3Profile::builder()
+            .bucket("my-bucket--usw2-az1--x-s3".to_string())
+            .region("us-west-2".to_string())
+            .sts_enabled(true)
+            .sts_role_arn("arn:aws:iam::123456789012:role/role".to_string())
+            .build();
+        assert!(profile.normalize(None).is_err());
+        assert!(profile
+            .get_sts_policy_string(
+                &"s3://my-bucket--usw2-az1--x-s3/ns/table".parse().unwrap(),
+                StoragePermissions::Read,
+            )
+            .is_err());
+
+        profile.allow_bucket_wide_vended_credentials = true;
+        profile.normalize(None).unwrap();
+        profile
+            .get_sts_policy_string(
+                &"s3://my-bucket--usw2-az1--x-s3/ns/table".parse().unwrap(),
+                StoragePermissions::Read,
+            )
+            .unwrap();
+    }
 }
 
 #[cfg(test)]
@@ -1661,12 +2296,16 @@ mod is_overlapping_location_tests {
             sts_role_arn: None,
             sts_enabled: false,
             sts_session_tags: HashMap::new(),
+            sts_role_mappings: vec![],
+            sts_principal_session_tags: false,
             flavor: S3Flavor::Aws,
             allow_alternative_protocols: None,
             remote_signing_url_style: S3UrlStyleDetectionMode::Auto,
             sts_token_validity_seconds: 3600,
             push_s3_delete_disabled: true,
             aws_kms_key_arn: None,
+            access_point_arn: None,
+            allow_bucket_wide_vended_credentials: false,
         }
     }
 
@@ -0,0 +1,466 @@
+use std::{
+    sync::{Arc, LazyLock},
+    time::Duration,
+};
+
+use iceberg::{spec::Schema, TableIdent};
+use iceberg_ext::catalog::rest::{ErrorModel, IcebergErrorResponse};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use tracing::Instrument;
+use utoipa::{PartialSchema, ToSchema};
+use uuid::Uuid;
+
+use super::{
+    EntityId, QueueApiConfig, SpecializedTask, TaskConfig, TaskData, TaskExecutionDetails,
+};
+use crate::{
+    api::{iceberg::v1::tables::LoadTableFilters, Result},
+    service::{
+        event_publisher::{CloudEventBackend, EventMetadata, Payload},
+        tasks::TaskQueueName,
+        CatalogStore, CatalogTableOps, CatalogViewOps, LoadViewError, TabularId, Transaction,
+    },
+};
+
+const QN_STR: &str = "cloud_events_backfill";
+pub(crate) static QUEUE_NAME: LazyLock<TaskQueueName> = LazyLock::new(|| QN_STR.into());
+pub(crate) static API_CONFIG: LazyLock<QueueApiConfig> = LazyLock::new(|| QueueApiConfig {
+    queue_name: &QUEUE_NAME,
+    utoipa_type_name: CloudEventsBackfillQueueConfig::name(),
+    utoipa_schema: CloudEventsBackfillQueueConfig::schema(),
+});
+
+pub type CloudEventsBackfillTask = SpecializedTask<
+    CloudEventsBackfillQueueConfig,
+    CloudEventsBackfillPayload,
+    CloudEventsBackfillExecutionDetails,
+>;
+
+/// Emits one `snapshotTable` or `snapshotView` event for the table or view of the task.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CloudEventsBackfillPayload {
+    /// Name of the [`CloudEventBackend`] the event is published to.
+    pub(crate) sink: String,
+    /// Request id of the request that scheduled the backfill.
+    pub(crate) trace_id: Uuid,
+    pub(crate) actor: String,
+}
+
+impl TaskData for CloudEventsBackfillPayload {}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, ToSchema)]
+pub struct CloudEventsBackfillQueueConfig {}
+
+impl TaskConfig for CloudEventsBackfillQueueConfig {
+    fn queue_name() -> &'static TaskQueueName {
+        &QUEUE_NAME
+    }
+
+    fn max_time_since_last_heartbeat() -> chrono::Duration {
+        chrono::Duration::seconds(300)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CloudEventsBackfillExecutionDetails {}
+
+impl TaskExecutionDetails for CloudEventsBackfillExecutionDetails {}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct SnapshotData<'a> {
+    metadata_location: Option<String>,
+    schema: &'a Schema,
+}
+
+pub(crate) async fn cloud_events_backfill_worker<C: CatalogStore>(
+    catalog_state: C::State,
+    sinks: Vec<Arc<dyn CloudEventBackend + Sync + Send>>,
+    poll_interval: Duration,
+    cancellation_token: crate::CancellationToken,
+) {
+    loop {
+        let task = CloudEventsBackfillTask::poll_for_new_task::<C>(
+            catalog_state.clone(),
+            &poll_interval,
+            cancellation_token.clone(),
+        )
+        .await;
+
+        let Some(task) = task else {
+            tracing::info!("Graceful shutdown: exiting `{QN_STR}` worker");
+            return;
+        };
+
+        let span = tracing::debug_span!(
+            QN_STR,
+            sink = %task.data.sink,
+            warehouse_id = %task.task_metadata.warehouse_id,
+            entity_type = %task.task_metadata.entity_id.entity_type().to_string(),
+            attempt = %task.attempt(),
+            task_id = %task.task_id(),
+        );
+
+        instrumented_backfill::<C>(catalog_state.clone(), &sinks, &task)
+            .instrument(span.or_current())
+            .await;
+    }
+}
+
+async fn instrumented_backfill<C: CatalogStore>(
+    catalog_state: C::State,
+    sinks: &[Arc<dyn CloudEventBackend + Sync + Send>],
+    task: &CloudEventsBackfillTask,
+) {
+    let entity_id = task.task_metadata.entity_id;
+    match backfill::<C>(catalog_state.clone(), sinks, task).await {
+        Ok(true) => {
+            tracing::debug!(
+                "Task of `{QN_STR}` worker exited successfully. Published {entity_id}."
+            );
+            task.record_success::<C>(
+                catalog_state,
+                Some(&format!("Published snapshot to `{}`", task.data.sink)),
+            )
+            .await;
+        }
+        Ok(false) => {
+            tracing::debug!("{entity_id} not found for `{QN_STR}` task. Skipping.");
+            task.record_success::<C>(catalog_state, Some("Skipped, tabular not found"))
+                .await;
+        }
+        Err(err) => {
+            tracing::error!(
+                "Error in `{QN_STR}` worker. Publishing snapshot of {entity_id} failed. Error: {err}"
+            );
+            task.record_failure::<C>(
+                catalog_state,
+                &format!("Failed to publish snapshot of {entity_id}.\n{err}"),
+            )
+            .await;
+        }
+    }
+}
+
+/// Publishes the snapshot event of the task's tabular.
+/// Returns `false` if the tabular does not exist anymore.
+async fn backfill<C: CatalogStore>(
+    catalog_state: C::State,
+    sinks: &[Arc<dyn CloudEventBackend + Sync + Send>],
+    task: &CloudEventsBackfillTask,
+) -> Result<bool> {
+    let Some(sink) = sinks.iter().find(|sink| sink.name() == task.data.sink) else {
+        let existing_sinks = sinks.iter().map(|sink| sink.name()).sorted().join(", ");
+        return Err(ErrorModel::bad_request(
+            format!(
+                "CloudEvent backend `{}` not found! Existing backends: [{existing_sinks}]",
+                task.data.sink
+            ),
+            "CloudEventBackendNotFound",
+            None,
+        )
+        .into());
+    };
+
+    let warehouse_id = task.task_metadata.warehouse_id;
+    let mut trx = C::Transaction::begin_read(catalog_state)
+        .await
+        .map_err(|e| {
+            e.append_detail(format!("Failed to start transaction for `{QN_STR}` Queue."))
+        })?;
+
+    let (typ, data) = match task.task_metadata.entity_id {
+        EntityId::Table(table_id) => {
+            let table = C::load_tables(
+                warehouse_id,
+                [table_id],
+                false,
+                &LoadTableFilters::default(),
+                trx.transaction(),
+            )
+            .await
+            .map_err(|e| {
+                IcebergErrorResponse::from(e.append_detail(format!(
+                    "Failed to load table with id `{table_id}` for `{QN_STR}` task."
+                )))
+            })?
+            .into_iter()
+            .next();
+            let Some(table) = table else {
+                return Ok(false);
+            };
+            (
+                "snapshotTable",
+                snapshot_data(
+                    table.metadata_location.map(|l| l.to_string()),
+                    table.table_metadata.current_schema(),
+                )?,
+            )
+        }
+        EntityId::View(view_id) => {
+            let view = match C::load_view(warehouse_id, view_id, false, trx.transaction()).await {
+                Err(LoadViewError::TabularNotFound(..)) => return Ok(false),
+                Err(e) => {
+                    return Err(e
+                        .append_detail(format!(
+                            "Failed to load view with id `{view_id}` for `{QN_STR}` task."
+                        ))
+                        .into())
+                }
+                Ok(view) => view,
+            };
+            (
+                "snapshotView",
+                snapshot_data(
+                    Some(view.metadata_location.to_string()),
+                    view.metadata.current_schema(),
+                )?,
+            )
+        }
+    };
+    trx.commit().await?;
+
+    let tabular_ident = TableIdent::from_strs(&task.task_metadata.entity_name).map_err(|e| {
+        ErrorModel::internal(
+            format!(
+                "Invalid entity name `{}` of `{QN_STR}` task.",
+                task.task_metadata.entity_name.join(".")
+            ),
+            "InvalidEntityName",
+            Some(Box::new(e)),
+        )
+    })?;
+    let tabular_id = match task.task_metadata.entity_id {
+        EntityId::Table(table_id) => TabularId::Table(table_id),
+        EntityId::View(view_id) => TabularId::View(view_id),
+    };
+    let event = Payload {
+        id: Uuid::now_v7(),
+        typ: typ.to_string(),
+        data,
+        metadata: EventMetadata {
+            tabular_id,
+            warehouse_id,
+            name: tabular_ident.name,
+            namespace: tabular_ident.namespace.to_url_string(),
+            prefix: String::new(),
+            num_events: 1,
+            sequence_number: 0,
+            trace_id: task.data.trace_id,
+            actor: task.data.actor.clone(),
+        }
+        .into(),
+        trace_context: lakekeeper_io::trace_context::current(),
+    }
+    .into_event()
+    .map_err(|e| {
+        ErrorModel::internal(
+            format!("Failed to build snapshot event of `{QN_STR}` task."),
+            "CloudEventBuildError",
+            Some(e.into()),
+        )
+    })?;
+
+    sink.publish(event).await.map_err(|e| {
+        ErrorModel::internal(
+            format!("Failed to publish event to `{}`.", sink.name()),
+            "CloudEventPublishError",
+            Some(e.into()),
+        )
+    })?;
+
+    Ok(true)
+}
+
+fn snapshot_data(metadata_location: Option<String>, schema: &Schema) -> Result<serde_json::Value> {
+    serde_json::to_value(SnapshotData {
+        metadata_location,
+        schema,
+    })
+    .map_err(|e| {
+        ErrorModel::internal(
+            format!("Failed to serialize snapshot event of `{QN_STR}` task."),
+            "SerializationError",
+            Some(Box::new(e)),
+        )
+        .into()
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Mutex;
+
+    use async_trait::async_trait;
+    use cloudevents::{AttributesReader as _, Data, Event};
+    use sqlx::PgPool;
+
+    use super::*;
+    use crate::{
+        implementations::postgres::{
+            tabular::table::tests::initialize_table, warehouse::test::initialize_warehouse,
+            CatalogState, PostgresBackend, PostgresTransaction,
+        },
+        service::{storage::MemoryProfile, tasks::TaskMetadata, TableId},
+        WarehouseId,
+    };
+
+    #[derive(Debug, Default)]
+    struct RecordingSink(Mutex<Vec<Event>>);
+
+    #[async_trait]
+    impl CloudEventBackend for RecordingSink {
+        async fn publish(&self, event: Event) -> anyhow::Result<()> {
+            self.0.lock().unwrap().push(event);
+            Ok(())
+        }
+
+        fn name(&self) -> &'static str {
+            "recording"
+        }
+    }
+
+    async fn schedule_and_pick(
+        catalog_state: CatalogState,
+        warehouse_id: WarehouseId,
+        table_id: TableId,
+        sink: &str,
+    ) -> CloudEventsBackfillTask {
+        let mut trx = PostgresTransaction::begin_write(catalog_state.clone())
+            .await
+            .unwrap();
+        CloudEventsBackfillTask::schedule_task::<PostgresBackend>(
+            TaskMetadata {
+                warehouse_id,
+                entity_id: EntityId::Table(table_id),
+                parent_task_id: None,
+                schedule_for: None,
+                entity_name: vec!["ns".to_string(), "tab".to_string()],
+            },
+            CloudEventsBackfillPayload {
+                sink: sink.to_string(),
+                trace_id: Uuid::now_v7(),
+                actor: "\"anonymous\"".to_string(),
+            },
+            trx.transaction(),
+        )
+        .await
+        .unwrap();
+        trx.commit().await.unwrap();
+
+        CloudEventsBackfillTask::pick_new_task::<PostgresBackend>(catalog_state)
+            .await
+            .unwrap()
+            .unwrap()
+    }
+
+    #[sqlx::test]
+    async fn test_backfill_publishes_snapshot_to_sink(pool: PgPool) {
+        let catalog_state = CatalogState::from_pools(pool.clone(), pool);
+        let warehouse_id = initialize_warehouse(
+            catalog_state.clone(),
+            Some(MemoryProfile::default().into()),
+            None,
+            None,
+            true,
+        )
+        .await;
+        let table = initialize_table(
+            warehouse_id,
+            catalog_state.clone(),
+            false,
+            None,
+            None,
+            Some("tab".to_string()),
+        )
+        .await;
+        let task = schedule_and_pick(
+            catalog_state.clone(),
+            warehouse_id,
+            table.table_id,
+            "recording",
+        )
+        .await;
+
+        let sink = Arc::new(RecordingSink::default());
+        let sinks: Vec<Arc<dyn CloudEventBackend + Sync + Send>> = vec![sink.clone()];
+        let published = backfill::<PostgresBackend>(catalog_state, &sinks, &task)
+            .await
+            .unwrap();
+        assert!(published);
+
+        let events = sink.0.lock().unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].ty(), "snapshotTable");
+        let Some(Data::Json(data)) = events[0].data() else {
+            panic!("Expected JSON data, got {:?}", events[0].data());
+        };
+        assert!(data.get("metadata-location").is_some());
+        assert!(data.get("schema").is_some());
+    }
+
+    #[sqlx::test]
+    async fn test_backfill_of_missing_table_is_skipped(pool: PgPool) {
+        let catalog_state = CatalogState::from_pools(pool.clone(), pool);
+        let warehouse_id = initialize_warehouse(
+            catalog_state.clone(),
+            Some(MemoryProfile::default().into()),
+            None,
+            None,
+            true,
+        )
+        .await;
+        let task = schedule_and_pick(
+            catalog_state.clone(),
+            warehouse_id,
+            TableId::new_random(),
+            "recording",
+        )
+        .await;
+
+        let sink = Arc::new(RecordingSink::default());
+        let sinks: Vec<Arc<dyn CloudEventBackend + Sync + Send>> = vec![sink.clone()];
+        let published = backfill::<PostgresBackend>(catalog_state, &sinks, &task)
+            .await
+            .unwrap();
+        assert!(!published);
+        assert!(sink.0.lock().unwrap().is_empty());
+    }
+
+    #[sqlx::test]
+    async fn test_backfill_to_unknown_sink_fails(pool: PgPool) {
+        let catalog_state = CatalogState::from_pools(pool.clone(), pool);
+        let warehouse_id = initialize_warehouse(
+            catalog_state.clone(),
+            Some(MemoryProfile::default().into()),
+            None,
+            None,
+            true,
+        )
+        .await;
+        let table = initialize_table(
+            warehouse_id,
+            catalog_state.clone(),
+            false,
+            None,
+            None,
+            Some("tab".to_string()),
+        )
+        .await;
+        let task = schedule_and_pick(
+            catalog_state.clone(),
+            warehouse_id,
+            table.table_id,
+            "unknown",
+        )
+        .await;
+
+        let sinks: Vec<Arc<dyn CloudEventBackend + Sync + Send>> =
+            vec![Arc::new(RecordingSink::default())];
+        let err = backfill::<PostgresBackend>(catalog_state, &sinks, &task)
+            .await
+            .unwrap_err();
+        assert_eq!(err.error.r#type, "CloudEventBackendNotFound");
+    }
+}
@@ -12,12 +12,17 @@ use crate::service::{
     CatalogStore, CatalogTaskOps, TableId, TableNamed, TabularId, ViewId, ViewNamed,
 };
 
+mod task_queue_metrics;
 mod task_queues_runner;
 mod task_registry;
+mod task_wakeup;
+pub use task_queue_metrics::export_task_queue_metrics_periodically;
 pub use task_queues_runner::{TaskQueueWorkerFn, TaskQueuesRunner};
 pub use task_registry::{
     QueueApiConfig, QueueRegistration, RegisteredTaskQueues, TaskQueueRegistry, ValidatorFn,
 };
+pub use task_wakeup::wake_task_queue;
+pub mod cloud_events_backfill_queue;
 pub mod tabular_expiration_queue;
 pub mod tabular_purge_queue;
 
@@ -29,6 +34,7 @@ const DEFAULT_MAX_RETRIES: i32 = 5;
 #[allow(clippy::declare_interior_mutable_const)]
 pub static BUILT_IN_API_CONFIGS: LazyLock<Vec<QueueApiConfig>> = LazyLock::new(|| {
     vec![
+        cloud_events_backfill_queue::API_CONFIG.clone(),
         tabular_expiration_queue::API_CONFIG.clone(),
         tabular_purge_queue::API_CONFIG.clone(),
     ]
@@ -561,13 +567,20 @@ impl<Q: TaskConfig, D: TaskData, E: TaskExecutionDetails> SpecializedTask<Q, D,
     }
 
     /// Continuously poll for a new task in the queue until a task is found.
+    /// Polling is skipped ahead if the queue is woken up via [`wake_task_queue`].
     /// Returns None if cancellation is requested.
     pub async fn poll_for_new_task<C: CatalogStore>(
         catalog_state: C::State,
         poll_interval: &Duration,
         cancellation_token: tokio_util::sync::CancellationToken,
     ) -> Option<Self> {
+        let wakeup = task_wakeup::task_queue_wakeup(Q::queue_name());
         loop {
+            // Register before picking, so that wakeups while picking are not missed
+            let woken_up = wakeup.notified();
+            tokio::pin!(woken_up);
+            woken_up.as_mut().enable();
+
             tokio::select! {
                 () = cancellation_token.cancelled() => {
                     tracing::info!("Graceful shutdown requested for queue `{}`", Q::queue_name());
@@ -599,6 +612,7 @@ impl<Q: TaskConfig, D: TaskData, E: TaskExecutionDetails> SpecializedTask<Q, D,
                                 return None;
                             }
                             () = tokio::time::sleep(*poll_interval + Duration::from_millis(jitter)) => continue,
+                            () = woken_up.as_mut() => continue,
                         }
                     };
 
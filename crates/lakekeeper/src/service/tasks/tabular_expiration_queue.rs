@@ -292,6 +292,7 @@ mod test {
                 cat,
                 sec,
                 auth,
+                vec![],
                 Duration::from_millis(100),
             )
             .await;
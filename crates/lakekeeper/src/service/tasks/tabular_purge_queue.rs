@@ -1,4 +1,4 @@
-use std::{str::FromStr, sync::LazyLock, time::Duration};
+use std::{borrow::Cow, str::FromStr, sync::LazyLock, time::Duration};
 
 use iceberg_ext::catalog::rest::{ErrorModel, IcebergErrorResponse};
 use lakekeeper_io::Location;
@@ -9,8 +9,8 @@ use utoipa::{PartialSchema, ToSchema};
 use super::{QueueApiConfig, SpecializedTask, TaskConfig, TaskData, TaskExecutionDetails};
 use crate::{
     api::Result,
-    server::{io::remove_all, maybe_get_secret},
-    service::{tasks::TaskQueueName, CatalogStore, CatalogWarehouseOps, SecretStore},
+    server::{io::remove_all, maybe_get_secret, tabular_storage},
+    service::{tasks::TaskQueueName, CatalogStore, CatalogWarehouseOps, NamespaceId, SecretStore},
 };
 
 const QN_STR: &str = "tabular_purge";
@@ -27,12 +27,17 @@ pub type TabularPurgeTask =
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TabularPurgePayload {
     pub(crate) tabular_location: String,
+    /// Namespace of the tabular, used to apply the namespace's storage credential override.
+    /// If unset or the namespace no longer exists, the warehouse credential is used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) namespace_id: Option<NamespaceId>,
 }
 
 impl TabularPurgePayload {
     pub fn new(tabular_location: impl Into<String>) -> Self {
         Self {
             tabular_location: tabular_location.into(),
+            namespace_id: None,
         }
     }
 }
@@ -133,7 +138,7 @@ where
 {
     let tabular_location_str = &task.data.tabular_location;
     let warehouse_id = task.task_metadata.warehouse_id;
-    let warehouse = C::require_warehouse_by_id(warehouse_id, catalog_state)
+    let warehouse = C::require_warehouse_by_id(warehouse_id, catalog_state.clone())
         .await
         .map_err(|e| {
             e.append_detail(format!(
@@ -149,7 +154,20 @@ where
         )
     })?;
 
-    let secret = maybe_get_secret(warehouse.storage_secret_id, secret_state)
+    let (storage_profile, storage_secret_id) = if let Some(namespace_id) = task.data.namespace_id {
+        tabular_storage::<C>(&warehouse, namespace_id, &tabular_location, catalog_state)
+            .await
+            .map_err(|e| {
+                e.append_detail(format!(
+                    "Failed to get storage of namespace {namespace_id} for Tabular Purge task."
+                ))
+            })?
+    } else {
+        let (storage_profile, storage_secret_id) =
+            warehouse.storage_for_location(&tabular_location);
+        (Cow::Borrowed(storage_profile), storage_secret_id)
+    };
+    let secret = maybe_get_secret(storage_secret_id, secret_state)
         .await
         .map_err(|e| {
             e.append_detail(format!(
@@ -157,8 +175,7 @@ where
             ))
         })?;
 
-    let file_io = warehouse
-        .storage_profile
+    let file_io = storage_profile
         .file_io(secret.as_ref())
         .await
         .map_err(|e| {
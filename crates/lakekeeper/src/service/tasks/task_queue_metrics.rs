@@ -0,0 +1,63 @@
+use std::time::Duration;
+
+use axum_prometheus::metrics;
+
+use super::RegisteredTaskQueues;
+use crate::{
+    service::{CatalogStore, CatalogTaskOps},
+    CancellationToken,
+};
+
+const METRIC_SCHEDULED_TASKS: &str = "lakekeeper_task_queue_scheduled_tasks";
+const METRIC_RUNNING_TASKS: &str = "lakekeeper_task_queue_running_tasks";
+const METRIC_OLDEST_DUE_TASK_AGE: &str = "lakekeeper_task_queue_oldest_due_task_age_seconds";
+
+/// Periodically export the depth and age of all task queues as Prometheus gauges.
+///
+/// Gauges of all `registered_task_queues` are reset on every export, so that queues
+/// without scheduled or running tasks report `0` instead of their last value.
+pub async fn export_task_queue_metrics_periodically<C: CatalogStore>(
+    catalog_state: C::State,
+    registered_task_queues: RegisteredTaskQueues,
+    cancellation_token: CancellationToken,
+) {
+    const EXPORT_INTERVAL: Duration = Duration::from_secs(15);
+
+    while !cancellation_token.is_cancelled() {
+        match C::get_task_queue_stats(catalog_state.clone()).await {
+            Ok(stats) => {
+                for queue_name in registered_task_queues.queue_names().await {
+                    set_gauges(queue_name.to_string(), 0., 0., 0.);
+                }
+                for stats in stats {
+                    #[allow(clippy::cast_precision_loss)]
+                    let scheduled = stats.scheduled_tasks as f64;
+                    #[allow(clippy::cast_precision_loss)]
+                    let running = stats.running_tasks as f64;
+                    #[allow(clippy::cast_precision_loss)]
+                    let oldest_due_task_age = stats
+                        .oldest_due_task_age
+                        .map_or(0., |age| age.num_milliseconds() as f64 / 1000.);
+                    set_gauges(
+                        stats.queue_name.into_string(),
+                        scheduled,
+                        running,
+                        oldest_due_task_age,
+                    );
+                }
+            }
+            Err(e) => tracing::warn!("Failed to determine task queue stats: {}", e.error),
+        }
+
+        tokio::select! {
+            () = cancellation_token.cancelled() => {},
+            () = tokio::time::sleep(EXPORT_INTERVAL) => {},
+        }
+    }
+}
+
+fn set_gauges(queue_name: String, scheduled: f64, running: f64, oldest_due_task_age: f64) {
+    metrics::gauge!(METRIC_SCHEDULED_TASKS, "queue" => queue_name.clone()).set(scheduled);
+    metrics::gauge!(METRIC_RUNNING_TASKS, "queue" => queue_name.clone()).set(running);
+    metrics::gauge!(METRIC_OLDEST_DUE_TASK_AGE, "queue" => queue_name).set(oldest_due_task_age);
+}
@@ -5,6 +5,7 @@ use tokio::sync::RwLock;
 use crate::{
     service::{
         authz::Authorizer,
+        event_publisher::CloudEventBackend,
         tasks::{
             task_queues_runner::QueueWorkerConfig, TaskConfig, TaskQueueName, TaskQueueWorkerFn,
             TaskQueuesRunner,
@@ -173,14 +174,18 @@ impl TaskQueueRegistry {
         self
     }
 
+    /// Registers the built-in task queues.
+    ///
+    /// `cloud_event_sinks` are the backends the `cloud_events_backfill` queue can publish to.
     pub async fn register_built_in_queues<C: CatalogStore, S: SecretStore, A: Authorizer>(
         &self,
         catalog_state: C::State,
         secret_store: S,
         authorizer: A,
+        cloud_event_sinks: Vec<Arc<dyn CloudEventBackend + Sync + Send>>,
         poll_interval: Duration,
     ) -> &Self {
-        use super::{tabular_expiration_queue, tabular_purge_queue};
+        use super::{cloud_events_backfill_queue, tabular_expiration_queue, tabular_purge_queue};
 
         let catalog_state_clone = catalog_state.clone();
         self.register_queue::<tabular_expiration_queue::TabularExpirationQueueConfig>(
@@ -206,10 +211,11 @@ impl TaskQueueRegistry {
         )
         .await;
 
+        let catalog_state_clone = catalog_state.clone();
         self.register_queue::<tabular_purge_queue::PurgeQueueConfig>(QueueRegistration {
             queue_name: &tabular_purge_queue::QUEUE_NAME,
             worker_fn: Arc::new(move |cancellation_token| {
-                let catalog_state_clone = catalog_state.clone();
+                let catalog_state_clone = catalog_state_clone.clone();
                 let secret_store = secret_store.clone();
                 Box::pin(async move {
                     tabular_purge_queue::tabular_purge_worker::<C, S>(
@@ -225,6 +231,27 @@ impl TaskQueueRegistry {
         })
         .await;
 
+        self.register_queue::<cloud_events_backfill_queue::CloudEventsBackfillQueueConfig>(
+            QueueRegistration {
+                queue_name: &cloud_events_backfill_queue::QUEUE_NAME,
+                worker_fn: Arc::new(move |cancellation_token| {
+                    let catalog_state_clone = catalog_state.clone();
+                    let cloud_event_sinks = cloud_event_sinks.clone();
+                    Box::pin(async move {
+                        cloud_events_backfill_queue::cloud_events_backfill_worker::<C>(
+                            catalog_state_clone,
+                            cloud_event_sinks,
+                            poll_interval,
+                            cancellation_token,
+                        )
+                        .await;
+                    })
+                }),
+                num_workers: CONFIG.task_cloud_events_backfill_workers,
+            },
+        )
+        .await;
+
         self
     }
 
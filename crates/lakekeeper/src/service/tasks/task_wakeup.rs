@@ -0,0 +1,35 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, LazyLock, Mutex},
+};
+
+use tokio::sync::Notify;
+
+/// Wakeups for idle workers, by queue name.
+static TASK_QUEUE_WAKEUPS: LazyLock<Mutex<HashMap<String, Arc<Notify>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+pub(super) fn task_queue_wakeup(queue_name: &str) -> Arc<Notify> {
+    TASK_QUEUE_WAKEUPS
+        .lock()
+        .expect("Task queue wakeups lock poisoned")
+        .entry(queue_name.to_string())
+        .or_default()
+        .clone()
+}
+
+/// Wake all idle workers of `queue_name` so that they poll for new tasks immediately
+/// instead of waiting for the poll interval to pass.
+///
+/// Workers that are currently busy are not affected, they poll once their task is done.
+pub fn wake_task_queue(queue_name: &str) {
+    let wakeup = TASK_QUEUE_WAKEUPS
+        .lock()
+        .expect("Task queue wakeups lock poisoned")
+        .get(queue_name)
+        .cloned();
+    if let Some(wakeup) = wakeup {
+        tracing::debug!("Waking up idle workers of task queue `{queue_name}`");
+        wakeup.notify_waiters();
+    }
+}
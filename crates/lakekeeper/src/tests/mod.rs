@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use crate::{
     api::{
         management::v1::{
@@ -14,6 +16,7 @@ use crate::{
     service::{
         contract_verification::ContractVerifiers,
         endpoint_hooks::EndpointHookCollection,
+        event_publisher::stream::CloudEventStream,
         storage::{StorageCredential, StorageProfile},
         UserId,
     },
@@ -68,6 +71,7 @@ pub async fn spawn_build_in_queues<T: Authorizer>(
             ctx.v1_state.catalog.clone(),
             ctx.v1_state.secrets.clone(),
             ctx.v1_state.authz.clone(),
+            vec![],
             poll_interval.unwrap_or(CONFIG.task_poll_interval),
         )
         .await;
@@ -203,6 +207,7 @@ pub(crate) async fn get_api_context<T: Authorizer>(
             catalog_state.clone(),
             secret_store.clone(),
             auth.clone(),
+            vec![],
             CONFIG.task_poll_interval,
         )
         .await;
@@ -214,7 +219,9 @@ pub(crate) async fn get_api_context<T: Authorizer>(
             secrets: secret_store,
             contract_verifiers: ContractVerifiers::new(vec![]),
             hooks: EndpointHookCollection::new(vec![]),
+            event_stream: CloudEventStream::default(),
             registered_task_queues,
+            cloud_event_backfill_sinks: Arc::from([]),
             license_status: &APACHE_LICENSE_STATUS,
         },
     }
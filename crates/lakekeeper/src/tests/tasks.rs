@@ -115,6 +115,7 @@ mod test {
                     .unwrap(),
                 ),
                 max_seconds_since_last_heartbeat: None,
+                max_concurrent_tasks: None,
             },
             transaction.transaction(),
         )